@@ -0,0 +1,42 @@
+use anyhow::Result;
+use std::time::Duration;
+use tokio::time::sleep;
+use trui::*;
+
+#[path = "./shared/logging.rs"]
+mod logging;
+
+#[derive(Default)]
+struct AppState {
+    lines: Option<Vec<String>>,
+}
+
+/// Simulates loading the initial data, e.g. a config or a file, which takes a while.
+async fn load_lines() -> Vec<String> {
+    sleep(Duration::from_secs(2)).await;
+    (1..=10).map(|i| format!("Line {i}")).collect()
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _guard = crate::logging::setup_logging(tracing::Level::DEBUG)?;
+
+    App::new(AppState::default(), |state| {
+        let content = match &state.lines {
+            None => "Loading...".to_string(),
+            Some(lines) => lines.join("\n"),
+        };
+        // The placeholder is rendered right away, the lines are swapped in when they're loaded
+        content.border(BorderKind::Rounded).on_mount_async(
+            load_lines,
+            |state: &mut AppState, result| match result {
+                FutureState::Ready(lines) => state.lines = Some(lines),
+                FutureState::Error(err) => state.lines = Some(vec![format!("Failed: {err}")]),
+                FutureState::Pending => (),
+            },
+        )
+    })
+    .await
+    .run()
+    .await
+}
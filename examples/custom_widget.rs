@@ -0,0 +1,134 @@
+use anyhow::Result;
+use trui::{
+    geometry::{to_ratatui_rect, Size},
+    widget::{
+        BoxConstraints, ChangeFlags, Event, EventCx, LayoutCx, LifeCycle, LifeCycleCx, PaintCx,
+        Widget,
+    },
+    *,
+};
+
+#[path = "./shared/logging.rs"]
+mod logging;
+
+/// A horizontal bar that is filled according to `ratio`, implemented completely outside of trui.
+pub struct GaugeWidget {
+    ratio: f64,
+    style: Style,
+}
+
+impl GaugeWidget {
+    fn set_ratio(&mut self, ratio: f64) -> ChangeFlags {
+        if self.ratio != ratio {
+            self.ratio = ratio;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn set_style(&mut self, style: Style) -> ChangeFlags {
+        if self.style != style {
+            self.style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+impl Widget for GaugeWidget {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let style = self.style.patch(cx.override_style());
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        let filled = (rect.width as f64 * self.ratio.clamp(0.0, 1.0)).round() as u16;
+
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let symbol = if x - rect.x < filled { "█" } else { "░" };
+                buf.get_mut(x, y).set_symbol(symbol).set_style(style);
+            }
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // Take all the available width, but only a single row
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            20.0
+        };
+        bc.constrain(Size::new(width, 1.0))
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
+
+    fn lifecycle(&mut self, _cx: &mut LifeCycleCx, _event: &LifeCycle) {}
+}
+
+pub struct Gauge {
+    ratio: f64,
+    style: Style,
+}
+
+pub fn gauge(ratio: f64, style: Style) -> Gauge {
+    Gauge { ratio, style }
+}
+
+impl ViewMarker for Gauge {}
+
+impl<T, A> View<T, A> for Gauge {
+    type State = ();
+
+    type Element = GaugeWidget;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, element) = cx.with_new_id(|_| GaugeWidget {
+            ratio: self.ratio,
+            style: self.style,
+        });
+        (id, (), element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut Id,
+        _state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        element.set_ratio(self.ratio) | element.set_style(self.style)
+    }
+
+    fn message(
+        &self,
+        _id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut T,
+    ) -> MessageResult<A> {
+        MessageResult::Stale(message)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let _guard = crate::logging::setup_logging(tracing::Level::DEBUG)?;
+
+    App::new(0.3, |ratio: &mut f64| {
+        v_stack((
+            format!("Click the gauge to fill it: {:.0}%", *ratio * 100.0),
+            gauge(*ratio, Style::default().fg(Color::Green))
+                .border(BorderKind::Rounded)
+                .on_click(|ratio: &mut f64| {
+                    *ratio = if *ratio >= 1.0 { 0.0 } else { *ratio + 0.1 };
+                }),
+        ))
+    })
+    .await
+    .run()
+    .await
+}
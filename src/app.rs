@@ -1,60 +1,92 @@
-#[cfg(not(any(test, doctest, feature = "doctests")))]
-use std::io::{stdout, Write};
-
-use futures::StreamExt;
-
 use crate::{
-    geometry::{Point, Size},
-    view::{Cx, View},
+    backend::poll_as_app,
+    geometry::{Point, Rect, Size},
+    view::{layers, Cx, Layers, View, ViewSequence},
     widget::{
-        BoxConstraints, CxState, Event, EventCx, LayoutCx, LifeCycle, LifeCycleCx, Message,
-        PaintCx, Pod, PodFlags, ViewContext, WidgetState,
+        normalize_key, BoxConstraints, CxState, Event, EventCx, FocusState, Key, KeyEvent,
+        KeyStroke, LayoutCx, LifeCycle, LifeCycleCx, Message, MouseKind, PaintCx, Pod, PodFlags,
+        RawMouseEvent, ViewContext, WidgetState,
     },
-    AppConfig,
+    AppConfig, CrosstermInput, DefaultBackend, RenderTiming, TerminalBackend,
 };
 use anyhow::Result;
-
-#[cfg(not(any(test, doctest, feature = "doctests")))]
-use crossterm::{
-    cursor,
-    event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture},
-    execute, queue,
-    terminal::{
-        disable_raw_mode, enable_raw_mode, BeginSynchronizedUpdate, EndSynchronizedUpdate,
-        EnterAlternateScreen, LeaveAlternateScreen,
-    },
-};
-
-use crossterm::event::{Event as CxEvent, KeyCode, KeyEvent};
+use ratatui::{buffer::Buffer, style::Modifier};
+use unicode_width::UnicodeWidthStr;
 
 use std::{
+    any::Any,
     collections::HashSet,
-    sync::Arc,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
 use xilem_core::{AsyncWake, Id, IdPath, MessageResult};
 
-pub struct App<T: Send + 'static, V: View<T> + 'static> {
-    pub(crate) config: AppConfig,
+/// The root of a trui application, which renders the views returned by the app logic to a terminal.
+///
+/// It renders to stdout via crossterm by default, another [`TerminalBackend`] can be configured via
+/// [`AppConfig::with_backend`] and [`App::new_with_config`].
+///
+/// The app logic only runs again when the app state may have changed, i.e. after an event handler,
+/// a key binding or a resolved future was called, or when the terminal was resized. Events which
+/// aren't handled by any view, like mouse moves, only repaint the widgets that requested it.
+/// State that is changed outside of the app, e.g. by another thread, has to be passed to the app
+/// e.g. via a [`stream`](crate::stream) to be rendered.
+pub struct App<T: Send + 'static, V: View<T> + 'static, B: TerminalBackend = DefaultBackend> {
+    pub(crate) config: AppConfig<B>,
     req_chan: tokio::sync::mpsc::Sender<AppMessage>,
-    render_response_chan: tokio::sync::mpsc::Receiver<RenderResponse<V, V::State>>,
+    /// `Ok(None)` if the app logic didn't have to run, because nothing changed since the last render
+    render_response_chan: tokio::sync::mpsc::Receiver<RenderResult<V, V::State>>,
     return_chan: tokio::sync::mpsc::Sender<(V, V::State, HashSet<Id>)>,
     event_chan: tokio::sync::mpsc::Receiver<Event>,
 
     #[cfg(any(test, doctest, feature = "doctests"))]
     event_tx: tokio::sync::mpsc::Sender<Event>,
 
+    /// Whether the terminal is switched to raw mode while the app runs, which is necessary when the
+    /// input events are read from the terminal
+    raw_mode: bool,
     size: Size,
     request_render_notifier: Arc<tokio::sync::Notify>,
+    /// The background tasks spawned by the app, which are stopped when it quits
+    tasks: Vec<tokio::task::JoinHandle<()>>,
+    /// The task which runs the app logic, it's awaited to report why it stopped unexpectedly
+    app_task: Option<tokio::task::JoinHandle<()>>,
     cursor_pos: Option<Point>,
+    /// Whether the terminal window has the focus, as far as the terminal reports it
+    terminal_focused: bool,
+    /// The screen is painted inverted until then, after the visual bell was rung
+    flash_until: Option<Instant>,
+    /// The views are only rendered if the terminal has at least this size, see [`App::min_size`]
+    min_size: Option<(u16, u16)>,
+    min_size_message: Option<String>,
+    /// Whether the terminal was smaller than the `min_size` when it was rendered the last time
+    too_small: bool,
     events: Vec<Message>,
+    key_bindings: KeyBindings<T>,
+    /// Quits the app when it's pressed, see [`App::quit_key`]
+    quit_key: KeyStroke,
     root_state: WidgetState,
     root_pod: Option<Pod>,
+    focus: FocusState,
     cx: Cx,
     id: Option<Id>,
 }
 
+/// The handlers of the global key bindings of an [`App`], which are shared with the [`AppTask`].
+type KeyBindings<T> = Arc<Mutex<Vec<(KeyStroke, Box<dyn FnMut(&mut T) + Send>)>>>;
+
+/// How long the screen is inverted when the visual bell is rung.
+const FLASH_DURATION: Duration = Duration::from_millis(100);
+
+/// Quits the app by default, see [`App::quit_key`].
+const DEFAULT_QUIT_KEY: KeyEvent = KeyEvent::new(Key::Char('c'), crate::widget::Modifiers::CONTROL);
+
+/// Suspends the app, if it's enabled via [`AppConfig::with_suspend`].
+#[cfg(unix)]
+const SUSPEND_KEY: KeyEvent = KeyEvent::new(Key::Char('z'), crate::widget::Modifiers::CONTROL);
+
 /// The standard delay for waiting for async futures.
 const RENDER_DELAY: Duration = Duration::from_millis(5);
 
@@ -65,7 +97,7 @@ const RENDER_DELAY: Duration = Duration::from_millis(5);
 /// The App can send [AppMessage] to inform the the AppTask about an user interaction.
 struct AppTask<T, V: View<T>, F: FnMut(&mut T) -> V> {
     req_chan: tokio::sync::mpsc::Receiver<AppMessage>,
-    response_chan: tokio::sync::mpsc::Sender<RenderResponse<V, V::State>>,
+    response_chan: tokio::sync::mpsc::Sender<RenderResult<V, V::State>>,
     return_chan: tokio::sync::mpsc::Receiver<(V, V::State, HashSet<Id>)>,
     event_chan: tokio::sync::mpsc::Sender<Event>,
 
@@ -75,6 +107,9 @@ struct AppTask<T, V: View<T>, F: FnMut(&mut T) -> V> {
     state: Option<V::State>,
     pending_async: HashSet<Id>,
     ui_state: UiState,
+    key_bindings: KeyBindings<T>,
+    /// Whether the app state may have changed since the app logic ran the last time
+    needs_rebuild: bool,
 }
 
 // TODO maybe rename this, so that it is clear that these events are sent to the AppTask (AppTask name is also for debate IMO)
@@ -82,6 +117,11 @@ struct AppTask<T, V: View<T>, F: FnMut(&mut T) -> V> {
 pub(crate) enum AppMessage {
     Events(Vec<Message>),
     Wake(IdPath),
+    /// The indices of the global key bindings whose keys were pressed
+    KeyBindings(Vec<usize>),
+    /// The app logic has to run on the next render, even if no message changed the app state,
+    /// e.g. because the terminal was resized
+    Invalidate,
     // Parameter indicates whether it should be delayed for async
     Render(bool),
 }
@@ -93,6 +133,44 @@ struct RenderResponse<V, S> {
     state: Option<S>,
 }
 
+type RenderResult<V, S> = std::result::Result<Option<RenderResponse<V, S>>, AppLogicPanic>;
+
+/// The error returned by [`App::run`], when the app logic panicked.
+///
+/// It can be found with [`anyhow::Error::downcast_ref`], e.g. to report the panic differently.
+/// The terminal is restored before `run` returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppLogicPanic {
+    message: String,
+}
+
+impl AppLogicPanic {
+    fn new(payload: &(dyn Any + Send)) -> Self {
+        let message = match (
+            payload.downcast_ref::<&str>(),
+            payload.downcast_ref::<String>(),
+        ) {
+            (Some(message), _) => message.to_string(),
+            (_, Some(message)) => message.clone(),
+            _ => String::new(),
+        };
+        AppLogicPanic { message }
+    }
+
+    /// The message passed to `panic!`, which is empty if the panic had a payload of another type.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for AppLogicPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the app logic panicked: {}", self.message)
+    }
+}
+
+impl std::error::Error for AppLogicPanic {}
+
 /// The state of the  [`AppTask`].
 ///
 /// While the [`App`] follows a strict order of UIEvents -> Render -> Paint (this is simplified)
@@ -112,9 +190,39 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
     pub async fn new(data: T, app_logic: impl FnMut(&mut T) -> V + Send + 'static) -> Self {
         App::new_with_config(AppConfig::default(), data, app_logic).await
     }
+}
 
+impl<T: Send + 'static, VT: ViewSequence<T, ()> + 'static> App<T, Layers<T, (), VT>> {
+    /// Like [`App::new`], but the app logic returns an ordered list of root views, which are
+    /// stacked on top of each other as [`layers`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # async {
+    /// App::new_layered(false, |show_help: &mut bool| {
+    ///     (
+    ///         "The main UI, press F1 for help",
+    ///         show_help.then(|| "The help".border(BorderKind::Rounded)),
+    ///     )
+    /// })
+    /// .await
+    /// .key_binding(Key::F(1), |show_help| *show_help = !*show_help)
+    /// .run()
+    /// .await
+    /// # };
+    /// ```
+    pub async fn new_layered(
+        data: T,
+        mut app_logic: impl FnMut(&mut T) -> VT + Send + 'static,
+    ) -> Self {
+        App::new(data, move |data: &mut T| layers(app_logic(data))).await
+    }
+}
+
+impl<T: Send + 'static, V: View<T> + 'static, B: TerminalBackend> App<T, V, B> {
     pub async fn new_with_config(
-        config: AppConfig,
+        mut config: AppConfig<B>,
         data: T,
         app_logic: impl FnMut(&mut T) -> V + Send + 'static,
     ) -> Self {
@@ -137,11 +245,12 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
         // context. Consider crossbeam and flume channels as alternatives.
         let message_tx_clone = message_tx.clone();
         let (wake_tx, mut wake_rx) = tokio::sync::mpsc::channel(10);
-        tokio::task::spawn(async move {
+        let mut tasks = Vec::new();
+        tasks.push(tokio::task::spawn(async move {
             while let Some(id_path) = wake_rx.recv().await {
                 let _ = message_tx_clone.send(AppMessage::Wake(id_path)).await;
             }
-        });
+        }));
 
         let request_render_notifier = Arc::new(tokio::sync::Notify::new());
 
@@ -149,7 +258,7 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
         let event_tx_clone = event_tx.clone();
 
         // Until we have a solid way to sync with the screen refresh rate, do an update every 1/60 secs when it is requested
-        tokio::task::spawn(async move {
+        tasks.push(tokio::task::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / 60.0));
             interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
@@ -160,28 +269,18 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
                     break;
                 }
             }
-        });
+        }));
 
         // spawn io event proxy task
+        let raw_mode =
+            cfg!(not(any(test, doctest, feature = "doctests"))) && config.input.is_none();
+        let mut input = config
+            .input
+            .take()
+            .unwrap_or_else(|| Box::new(CrosstermInput::new()));
         let event_tx_clone = event_tx.clone();
-        tokio::task::spawn(async move {
-            // let mut interval = tokio::time::interval(Duration::from_millis(100));
-            let mut reader = crossterm::event::EventStream::new();
-            while let Some(event) = reader.next().await {
-                let event = match event {
-                    // TODO quit app at least for now, until proper key handling is implemented, then this thread might need a signal to quit itself
-                    Ok(CxEvent::Key(KeyEvent {
-                        code: KeyCode::Esc, ..
-                    })) => Event::Quit,
-                    Ok(CxEvent::Key(key_event)) => Event::Key(key_event),
-                    Ok(CxEvent::Mouse(mouse_event)) => Event::Mouse(mouse_event.into()),
-                    Ok(CxEvent::FocusGained) => Event::FocusGained,
-                    Ok(CxEvent::FocusLost) => Event::FocusLost,
-                    // CxEvent::Paste(_) => todo!(),
-                    Ok(CxEvent::Resize(width, height)) => Event::Resize { width, height },
-                    _ => continue, // TODO handle other kinds of events and errors
-                };
-
+        tasks.push(tokio::task::spawn(async move {
+            while let Some(event) = input.next_event().await {
                 let quit = matches!(event, Event::Quit);
 
                 let _ = event_tx_clone.send(event).await;
@@ -190,14 +289,33 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
                     break;
                 }
             }
-        });
+        }));
+
+        // In raw mode `Ctrl-Z` doesn't send `SIGTSTP`, but it may still be sent from elsewhere
+        #[cfg(unix)]
+        if config.suspend {
+            let event_tx_clone = event_tx.clone();
+            tasks.push(tokio::task::spawn(async move {
+                let kind = tokio::signal::unix::SignalKind::from_raw(libc::SIGTSTP);
+                let Ok(mut signal) = tokio::signal::unix::signal(kind) else {
+                    return;
+                };
+                while signal.recv().await.is_some() {
+                    if event_tx_clone.send(Event::Key(SUSPEND_KEY)).await.is_err() {
+                        break;
+                    }
+                }
+            }));
+        }
 
         // Send this event here, so that the app renders directly when it is run.
         let _ = event_tx.send(Event::Start).await;
 
+        let key_bindings: KeyBindings<T> = Arc::default();
+        let key_bindings_clone = Arc::clone(&key_bindings);
         let event_tx_clone = event_tx.clone();
         // spawn app task
-        tokio::task::spawn(async move {
+        let app_task = tokio::task::spawn(async move {
             let mut app_task = AppTask {
                 req_chan: message_rx,
                 response_chan: response_tx,
@@ -209,11 +327,18 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
                 state: None,
                 pending_async: HashSet::new(),
                 ui_state: UiState::Start,
+                key_bindings: key_bindings_clone,
+                needs_rebuild: true,
             };
             app_task.run().await;
         });
 
-        let cx = Cx::new(wake_tx, config.runtime_handle());
+        let cx = Cx::new(
+            wake_tx,
+            config.runtime_handle(),
+            config.theme.clone(),
+            config.widget_bounds(),
+        );
 
         App {
             config,
@@ -225,15 +350,167 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
             #[cfg(any(test, doctest, feature = "doctests"))]
             event_tx: event_tx.clone(),
 
+            raw_mode,
             size: Size::default(),
             cursor_pos: None,
+            terminal_focused: true,
+            flash_until: None,
+            min_size: None,
+            min_size_message: None,
+            too_small: false,
             root_pod: None,
+            focus: FocusState::default(),
             cx,
             id: None,
             root_state: WidgetState::new(),
             events: Vec::new(),
+            key_bindings,
+            quit_key: normalize_key(&DEFAULT_QUIT_KEY),
             request_render_notifier,
+            tasks,
+            app_task: Some(app_task),
+        }
+    }
+
+    /// Calls `handler` with the app state when `key` is pressed, regardless of which widget is focused.
+    ///
+    /// Global key bindings take precedence: a bound key isn't sent to the focused widget (and its
+    /// ancestors), only unbound keys are, and if they aren't handled there, `Tab`/`BackTab` move the
+    /// focus. `Shift` is ignored for characters, an uppercase character has to be used instead.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # use trui::{Key, KeyEvent, Modifiers};
+    /// # async {
+    /// App::new(false, |show_help| if *show_help { "Help" } else { "Press F1 for help" })
+    ///     .await
+    ///     .key_binding(Key::F(1), |show_help| *show_help = !*show_help)
+    ///     .key_binding(
+    ///         KeyEvent::new(Key::Char('h'), Modifiers::CONTROL),
+    ///         |show_help| *show_help = true,
+    ///     )
+    ///     .run()
+    ///     .await
+    /// # };
+    /// ```
+    pub fn key_binding(
+        self,
+        key: impl Into<KeyEvent>,
+        handler: impl FnMut(&mut T) + Send + 'static,
+    ) -> Self {
+        self.key_bindings
+            .lock()
+            .unwrap()
+            .push((normalize_key(&key.into()), Box::new(handler)));
+        self
+    }
+
+    /// Quits the app when `key` is pressed, instead of `Ctrl+C`.
+    ///
+    /// Like a [key binding](App::key_binding) it takes precedence over the focused widget,
+    /// all other keys, e.g. `Esc` to close a dialog, are sent to the widgets.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # use trui::Key;
+    /// # async {
+    /// App::new((), |()| "Press q to quit")
+    ///     .await
+    ///     .quit_key(Key::Char('q'))
+    ///     .run()
+    ///     .await
+    /// # };
+    /// ```
+    pub fn quit_key(mut self, key: impl Into<KeyEvent>) -> Self {
+        self.quit_key = normalize_key(&key.into());
+        self
+    }
+
+    /// Shows a message centered on the otherwise empty screen instead of the views, while the
+    /// terminal is smaller than `width` x `height` cells, as the layout would be unusable.
+    ///
+    /// The views are rendered again as soon as the terminal is resized to a sufficient size.
+    /// The message is "Terminal too small (need 80x24)" by default, see
+    /// [`min_size_message`](App::min_size_message).
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # async {
+    /// App::new((), |()| "A complex dashboard")
+    ///     .await
+    ///     .min_size(80, 24)
+    ///     .run()
+    ///     .await
+    /// # };
+    /// ```
+    pub fn min_size(mut self, width: u16, height: u16) -> Self {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    /// The message which is shown while the terminal is smaller than the [`min_size`](App::min_size).
+    pub fn min_size_message(mut self, message: impl Into<String>) -> Self {
+        self.min_size_message = Some(message.into());
+        self
+    }
+
+    /// Whether the terminal is smaller than the [`min_size`](App::min_size) in any direction.
+    fn is_too_small(&self, (width, height): (u16, u16)) -> bool {
+        self.min_size
+            .is_some_and(|(min_width, min_height)| width < min_width || height < min_height)
+    }
+
+    /// Paints the message of [`App::min_size`] centered on the otherwise empty screen.
+    fn render_too_small(&mut self, area: ratatui::layout::Rect) -> Result<()> {
+        let message = match (&self.min_size_message, self.min_size) {
+            (Some(message), _) => message.clone(),
+            (None, Some((width, height))) => {
+                format!("Terminal too small (need {width}x{height})")
+            }
+            (None, None) => String::new(),
+        };
+        let style = self.config.theme.text.default;
+        let buffer = self.config.terminal.current_buffer_mut();
+        buffer.reset();
+        if area.height > 0 {
+            let width = (message.width() as u16).min(area.width);
+            let x = area.x + (area.width - width) / 2;
+            let y = area.y + area.height / 2;
+            buffer.set_stringn(x, y, &message, width as usize, style);
+        }
+        self.config.terminal.hide_cursor()?;
+        if let Some(frame_stats) = &mut self.config.frame_stats {
+            frame_stats.report(self.config.terminal.current_buffer_mut());
         }
+        self.config.terminal.flush()?;
+        self.config.terminal.swap_buffers();
+        self.config.terminal.backend_mut().flush()?;
+        Ok(())
+    }
+
+    /// Replaces presses of the [`quit_key`](App::quit_key) with [`Event::Quit`].
+    fn map_quit_key(&self, events: &mut [Event]) {
+        for event in events {
+            if matches!(event, Event::Key(key) if normalize_key(key) == self.quit_key) {
+                *event = Event::Quit;
+            }
+        }
+    }
+
+    /// The index of the global key binding of `key`.
+    fn key_binding_index(&self, key: &KeyEvent) -> Option<usize> {
+        let key = normalize_key(key);
+        let key_bindings = self.key_bindings.lock().unwrap();
+        key_bindings.iter().position(|(bound, _)| *bound == key)
+    }
+
+    /// Returns the id of the topmost widget under `point` (in terminal cells),
+    /// or `None` if the point is outside the widget tree (or nothing has been rendered yet).
+    pub fn hit_test(&self, point: Point) -> Option<Id> {
+        self.root_pod.as_ref()?.hit_test(point)
     }
 
     async fn send_events(&mut self) {
@@ -247,12 +524,7 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
     /// Returns whether a rerender should be scheduled
     #[tracing::instrument(skip(self))]
     async fn render(&mut self, time_since_last_render: Duration) -> Result<bool> {
-        if self.build_widget_tree(false).await {
-            self.build_widget_tree(true).await;
-        }
-        let root_pod = self.root_pod.as_mut().unwrap();
-        let cx_state = &mut CxState::new(&mut self.events, time_since_last_render);
-
+        let start = Instant::now();
         // TODO via event (Event::Resize)?
         self.config.terminal.autoresize()?;
 
@@ -262,8 +534,55 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
             width: width as f64,
             height: height as f64,
         };
+        // The app logic may depend on the size, so it has to be updated before the app logic runs
+        if self.config.terminal_size.get() != term_size {
+            self.config.terminal_size.set(term_size);
+            let _ = self.req_chan.send(AppMessage::Invalidate).await;
+        }
+
+        if self.build_widget_tree(false).await? {
+            self.build_widget_tree(true).await?;
+        }
+        let built = Instant::now();
+
+        self.too_small = self.is_too_small((width, height));
+        if self.too_small {
+            self.render_too_small(term_rect)?;
+            // Forces a layout and a full paint, once the terminal is large enough again
+            self.size = Size::ZERO;
+            return Ok(false);
+        }
+
+        // The bell may have been rung by an event handler, i.e. while the app logic ran
+        let bell = self.config.bell.take();
+        if bell.audible {
+            self.config.terminal.backend_mut().bell()?;
+        }
+        if bell.visual {
+            self.flash_until = Some(built + FLASH_DURATION);
+        }
+        // Clearing the terminal also resets the previous frame, so the next flush writes all cells
+        let full_redraw = self.config.full_redraw.take();
+        if full_redraw {
+            self.config.terminal.clear()?;
+            if let Some(frame_stats) = &mut self.config.frame_stats {
+                frame_stats.reset();
+            }
+        }
+
+        // The screen is painted once more after the flash, to show it without the inverted colors again
+        let flashing = self.flash_until.is_some();
+        let flash_ended = self.flash_until.is_some_and(|until| built >= until);
+        if flash_ended {
+            self.flash_until = None;
+        }
+
+        let root_pod = self.root_pod.as_mut().unwrap();
+        let cx_state = &mut CxState::new(&mut self.events, &mut self.focus, time_since_last_render);
 
-        if root_pod.state.flags.contains(PodFlags::REQUEST_ANIMATION) {
+        // While paused, the animation request stays pending until the terminal has the focus again
+        let paused = self.config.pause_when_unfocused && !self.terminal_focused;
+        if root_pod.state.flags.contains(PodFlags::REQUEST_ANIMATION) && !paused {
             root_pod.lifecycle(
                 &mut LifeCycleCx {
                     cx_state,
@@ -295,9 +614,10 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
             .flags
             .contains(PodFlags::VIEW_CONTEXT_CHANGED)
         {
+            cx_state.update_hot_path(root_pod, self.cursor_pos);
             let view_context = ViewContext {
                 window_origin: Point::ORIGIN,
-                // clip: Rect::from_origin_size(Point::ORIGIN, root_pod.state.size),
+                clip: Rect::from_origin_size(Point::ORIGIN, self.size),
                 mouse_position: self.cursor_pos,
             };
             let mut lifecycle_cx = LifeCycleCx {
@@ -310,42 +630,108 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
             );
         }
 
-        if root_pod.state.flags.intersects(PodFlags::REQUEST_PAINT) || needs_layout_recomputation {
+        root_pod.update_focus(&mut LifeCycleCx {
+            cx_state,
+            widget_state: &mut self.root_state,
+        });
+
+        let laid_out = Instant::now();
+        let mut painted = laid_out;
+
+        if root_pod.state.flags.intersects(PodFlags::REQUEST_PAINT)
+            || needs_layout_recomputation
+            || flashing
+            || full_redraw
+        {
             let _paint_span = tracing::debug_span!("paint");
             let mut paint_cx = PaintCx {
                 widget_state: &mut self.root_state,
                 cx_state,
-                terminal: &mut self.config.terminal,
+                buffer: self.config.terminal.current_buffer_mut(),
                 override_style: ratatui::style::Style::default(),
             };
 
             root_pod.paint(&mut paint_cx);
+            cx_state.paint_overlays(self.config.terminal.current_buffer_mut());
+            if flashing && !flash_ended {
+                invert(self.config.terminal.current_buffer_mut());
+            }
+            painted = Instant::now();
 
-            #[cfg(not(any(test, doctest, feature = "doctests")))]
-            queue!(stdout(), BeginSynchronizedUpdate)?;
+            self.config
+                .terminal
+                .backend_mut()
+                .begin_synchronized_update()?;
+
+            if let Some(frame_stats) = &mut self.config.frame_stats {
+                frame_stats.report(self.config.terminal.current_buffer_mut());
+            }
 
             self.config.terminal.flush()?;
 
-            #[cfg(not(any(test, doctest, feature = "doctests")))]
-            execute!(stdout(), EndSynchronizedUpdate)?;
+            match cx_state.cursor_position() {
+                Some((x, y)) => {
+                    self.config.terminal.set_cursor(x, y)?;
+                    self.config.terminal.show_cursor()?;
+                }
+                None => self.config.terminal.hide_cursor()?,
+            }
+
+            self.config
+                .terminal
+                .backend_mut()
+                .end_synchronized_update()?;
 
             self.config.terminal.swap_buffers();
 
-            #[cfg(not(any(test, doctest, feature = "doctests")))]
             self.config.terminal.backend_mut().flush()?;
         }
 
-        // currently only an animation update can request a rerender
-        Ok(root_pod.state.flags.contains(PodFlags::REQUEST_ANIMATION))
+        if self.config.render_timings.is_recording() {
+            let flushed = Instant::now();
+            let timing = RenderTiming {
+                build: built - start,
+                layout: laid_out - built,
+                paint: painted - laid_out,
+                flush: flushed - painted,
+            };
+            self.config.render_timings.record(start, timing);
+        }
+
+        // The app logic may depend on the measured bounds, which are only known after layout
+        if self.config.widget_bounds.take_changed() {
+            let _ = self.req_chan.send(AppMessage::Invalidate).await;
+            self.request_render_notifier.notify_one();
+        }
+
+        // Messages sent during layout or lifecycle passes have to be handled by the app logic
+        // in another render pass
+        if !self.events.is_empty() {
+            self.request_render_notifier.notify_one();
+        }
+
+        // currently only an animation update or the visual bell can request a rerender
+        Ok(!paused
+            && (root_pod.state.flags.contains(PodFlags::REQUEST_ANIMATION)
+                || self.flash_until.is_some()))
     }
 
-    /// Run one pass of app logic.
+    /// Run one pass of app logic, which is skipped if the app state hasn't changed since the last pass.
     ///
     /// Return value is whether there are any pending async futures.
-    async fn build_widget_tree(&mut self, delay: bool) -> bool {
+    /// Fails if the app logic task has stopped, e.g. because the app logic panicked.
+    async fn build_widget_tree(&mut self, delay: bool) -> Result<bool> {
         self.cx.pending_async.clear();
         let _ = self.req_chan.send(AppMessage::Render(delay)).await;
-        if let Some(response) = self.render_response_chan.recv().await {
+        let response = match self.render_response_chan.recv().await {
+            Some(Ok(response)) => response,
+            Some(Err(panic)) => {
+                tracing::error!("{panic}");
+                return Err(panic.into());
+            }
+            None => return Err(self.app_task_error().await),
+        };
+        if let Some(response) = response {
             let state = if let Some(widget) = self.root_pod.as_mut() {
                 let mut state = response.state.unwrap();
                 let changes = response.view.rebuild(
@@ -371,54 +757,187 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
             let pending = std::mem::take(&mut self.cx.pending_async);
             let has_pending = !pending.is_empty();
             let _ = self.return_chan.send((response.view, state, pending)).await;
-            has_pending
+            Ok(has_pending)
         } else {
-            false
+            Ok(false)
         }
     }
 
-    pub async fn run(mut self) -> Result<()> {
-        #[cfg(not(any(test, doctest, feature = "doctests")))]
-        self.init_terminal()?;
+    /// Why the app logic task has stopped, which only happens unexpectedly, as it runs until the app is dropped.
+    async fn app_task_error(&mut self) -> anyhow::Error {
+        let err = match self.app_task.take() {
+            Some(task) => match task.await {
+                Err(err) if err.is_panic() => AppLogicPanic::new(&*err.into_panic()).into(),
+                Err(err) => anyhow::anyhow!("the app logic task failed: {err}"),
+                Ok(()) => anyhow::anyhow!("the app logic task has stopped"),
+            },
+            None => anyhow::anyhow!("the app logic task has stopped"),
+        };
+        tracing::error!("{err}");
+        err
+    }
 
-        self.config.terminal.clear()?;
+    /// Dispatches a batch of input events to the widget tree and sends the resulting messages
+    /// to the app logic, which handles them with the next render.
+    async fn handle_events(
+        &mut self,
+        mut events: Vec<Event>,
+        time_since_last_render_request: Duration,
+    ) {
+        if let Some(Event::Mouse(mouse)) = events
+            .iter()
+            .rev()
+            .find(|event| matches!(event, Event::Mouse(_)))
+        {
+            self.cursor_pos = Some(Point::new(mouse.column as f64, mouse.row as f64));
+        }
 
-        let main_loop_tracing_span = tracing::debug_span!("main loop");
-        let mut time_of_last_render = Instant::now();
-        let mut time_since_last_render_request = Duration::ZERO;
-        while let Some(event) = self.event_chan.recv().await {
-            let mut events = vec![event];
-            // batch events
-            while let Ok(event) = self.event_chan.try_recv() {
-                events.push(event);
-            }
+        if let Some(focused) = events.iter().rev().find_map(|event| match event {
+            Event::FocusGained => Some(true),
+            Event::FocusLost => Some(false),
+            _ => None,
+        }) {
+            self.terminal_focused = focused;
+        }
 
-            let quit = events.iter().any(|e| matches!(e, Event::Quit));
+        // The widgets aren't visible while the terminal is too small, so they must not be
+        // interacted with via their stale layout
+        if self.too_small {
+            events.retain(|event| matches!(event, Event::Quit | Event::Resize { .. }));
+        }
 
-            if let Some(Event::Mouse(mouse)) = events
-                .iter()
-                .rev()
-                .find(|event| matches!(event, Event::Mouse(_)))
-            {
-                self.cursor_pos = Some(Point::new(mouse.column as f64, mouse.row as f64));
-            }
+        // Global key bindings take precedence over the focused widget
+        let mut bound_keys = Vec::new();
+        events.retain(|event| match event {
+            Event::Key(key) => match self.key_binding_index(key) {
+                Some(index) => {
+                    bound_keys.push(index);
+                    false
+                }
+                None => true,
+            },
+            _ => true,
+        });
+        if !bound_keys.is_empty() {
+            let _ = self
+                .req_chan
+                .send(AppMessage::KeyBindings(bound_keys))
+                .await;
+        }
 
-            if let Some(root_pod) = self.root_pod.as_mut() {
-                let cx_state = &mut CxState::new(&mut self.events, time_since_last_render_request);
+        if let Some(root_pod) = self.root_pod.as_mut() {
+            let cx_state = &mut CxState::new(
+                &mut self.events,
+                &mut self.focus,
+                time_since_last_render_request,
+            );
 
+            for event in events {
+                if let Event::Mouse(mouse) = &event {
+                    let pos = Point::new(mouse.column as f64, mouse.row as f64);
+                    cx_state.update_hot_path(root_pod, Some(pos));
+                }
                 let mut cx = EventCx {
                     is_handled: false,
                     widget_state: &mut self.root_state,
                     cx_state,
                 };
-                for event in events {
-                    // TODO filter out some events like Event::Wake?
-                    root_pod.event(&mut cx, &event);
+                // TODO filter out some events like Event::Wake?
+                root_pod.event(&mut cx, &event);
+                let is_handled = cx.is_handled;
+
+                let mut cx = LifeCycleCx {
+                    widget_state: &mut self.root_state,
+                    cx_state,
+                };
+                // Focus traversal, if the focused widget doesn't handle Tab itself
+                match event {
+                    Event::Key(KeyEvent { code: Key::Tab, .. }) if !is_handled => {
+                        root_pod.focus_next(&mut cx, true)
+                    }
+                    Event::Key(KeyEvent {
+                        code: Key::BackTab, ..
+                    }) if !is_handled => root_pod.focus_next(&mut cx, false),
+                    _ => root_pod.update_focus(&mut cx),
                 }
             }
-            self.send_events().await;
+        }
+        self.send_events().await;
+    }
+
+    /// Runs the app logic, lays out the widget tree to the current terminal size and paints a single frame,
+    /// without entering the event loop.
+    ///
+    /// Unlike [`run`](App::run) the terminal isn't set up (e.g. the alternate screen isn't entered),
+    /// so this is useful to print a view to a headless backend, e.g. in snapshot tests,
+    /// or into the scrollback of the terminal. It can be called again to render another frame.
+    pub async fn render_once(&mut self) -> Result<()> {
+        self.render(Duration::ZERO).await?;
+        Ok(())
+    }
+
+    pub async fn run(self) -> Result<()> {
+        // Only panics of the app itself restore the terminal right away, see `TerminalGuard`
+        poll_as_app(self.run_event_loop()).await
+    }
 
-            let rerender_requested = self.render(time_since_last_render_request).await?;
+    async fn run_event_loop(mut self) -> Result<()> {
+        let mouse_capture = self.config.mouse_capture;
+        self.config.terminal.setup(self.raw_mode, mouse_capture)?;
+
+        self.config.terminal.clear()?;
+
+        let main_loop_tracing_span = tracing::debug_span!("main loop");
+        let mut time_of_last_render = Instant::now();
+        let mut time_since_last_render_request = Duration::ZERO;
+        loop {
+            let event = match self.config.idle_tick {
+                Some(interval) => {
+                    match tokio::time::timeout(interval, self.event_chan.recv()).await {
+                        Ok(event) => event,
+                        // Nothing happened within the interval, the app logic runs again
+                        Err(_) => {
+                            let _ = self.req_chan.send(AppMessage::Invalidate).await;
+                            Some(Event::Wake)
+                        }
+                    }
+                }
+                None => self.event_chan.recv().await,
+            };
+            let Some(event) = event else {
+                break;
+            };
+            let mut events = vec![event];
+            // batch events
+            while let Ok(event) = self.event_chan.try_recv() {
+                events.push(event);
+            }
+            coalesce_mouse_moves(&mut events);
+            self.map_quit_key(&mut events);
+
+            #[cfg(unix)]
+            if self.config.suspend {
+                let event_count = events.len();
+                events.retain(|event| !matches!(event, Event::Key(key) if *key == SUSPEND_KEY));
+                if events.len() != event_count {
+                    if let Err(err) = self.suspend() {
+                        self.stop_tasks().await;
+                        return Err(err);
+                    }
+                }
+            }
+
+            let quit = events.iter().any(|e| matches!(e, Event::Quit));
+            self.handle_events(events, time_since_last_render_request)
+                .await;
+
+            let rerender_requested = match self.render(time_since_last_render_request).await {
+                Ok(rerender_requested) => rerender_requested,
+                Err(err) => {
+                    self.stop_tasks().await;
+                    return Err(err);
+                }
+            };
             // TODO this is a workaround (I consider this at least as that) for getting animations right
             // There's likely a cleaner solution
             if rerender_requested {
@@ -435,33 +954,43 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
         }
         drop(main_loop_tracing_span);
 
+        self.stop_tasks().await;
+
         Ok(())
     }
 
-    #[cfg(not(any(test, doctest, feature = "doctests")))]
-    fn init_terminal(&self) -> Result<()> {
-        enable_raw_mode()?;
-        execute!(
-            stdout(),
-            EnterAlternateScreen,
-            EnableFocusChange,
-            EnableMouseCapture,
-            cursor::Hide
-        )?;
+    /// Restores the terminal, stops the process until it's continued and sets up the terminal again.
+    #[cfg(unix)]
+    fn suspend(&mut self) -> Result<()> {
+        self.config.terminal.restore()?;
+
+        // `SIGTSTP` is handled by the app while suspending is enabled, so `SIGSTOP` (which can't be
+        // handled) stops the process instead.
+        // SAFETY: `raise` has no preconditions, the process continues here after `SIGCONT`
+        unsafe { libc::raise(libc::SIGSTOP) };
+
+        let mouse_capture = self.config.mouse_capture;
+        self.config.terminal.setup(self.raw_mode, mouse_capture)?;
+        // Other programs may have written to the terminal in the meantime
+        self.config.full_redraw.request();
         Ok(())
     }
 
-    #[cfg(not(any(test, doctest, feature = "doctests")))]
-    fn restore_terminal(&self) -> Result<()> {
-        execute!(
-            stdout(),
-            cursor::Show,
-            LeaveAlternateScreen,
-            DisableFocusChange,
-            DisableMouseCapture
-        )?;
-        disable_raw_mode()?;
-        Ok(())
+    /// Stops the background tasks and waits until they are finished, so that they don't outlive the app,
+    /// e.g. the input task which may be blocked waiting for the next event.
+    async fn stop_tasks(&mut self) {
+        self.tasks.extend(self.app_task.take());
+        for task in &self.tasks {
+            task.abort();
+        }
+        for task in self.tasks.drain(..) {
+            // Tasks which already finished or were cancelled are fine
+            if let Err(err) = task.await {
+                if err.is_panic() {
+                    tracing::error!("A background task of the app panicked: {err}");
+                }
+            }
+        }
     }
 
     #[cfg(any(test, doctest, feature = "doctests"))]
@@ -470,12 +999,123 @@ impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
     }
 }
 
-/// Restore the terminal no matter how the app exits
-impl<T: Send + 'static, V: View<T> + 'static> Drop for App<T, V> {
+/// Helpers to drive an app in tests without its event loop
+#[cfg(test)]
+impl<T: Send + 'static, V: View<T> + 'static> App<T, V> {
+    /// Dispatches `events` like the event loop does and renders the next frame
+    pub(crate) async fn dispatch(&mut self, events: impl IntoIterator<Item = Event>) {
+        self.handle_events(events.into_iter().collect(), Duration::ZERO)
+            .await;
+        self.render_once().await.unwrap();
+    }
+
+    /// Renders a frame `elapsed` after the previous one and returns whether another frame is
+    /// requested, e.g. by an animation
+    pub(crate) async fn render_frame(&mut self, elapsed: Duration) -> bool {
+        self.render(elapsed).await.unwrap()
+    }
+
+    /// The row `y` of the last rendered frame, without trailing whitespace
+    pub(crate) fn screen_line(&self, y: u16) -> String {
+        let buffer = self.config.terminal.backend().buffer();
+        let line: String = (0..buffer.area.width)
+            .map(|x| buffer.get(x, y).symbol())
+            .collect();
+        line.trim_end().to_string()
+    }
+
+    /// Renders frames until `condition` holds, e.g. until the result of a future is shown.
+    ///
+    /// Spawned tasks get the chance to run between the frames, the test fails if `condition`
+    /// doesn't hold within a second.
+    pub(crate) async fn render_until(&mut self, mut condition: impl FnMut(&Self) -> bool) {
+        let rendered = tokio::time::timeout(Duration::from_secs(1), async {
+            while !condition(self) {
+                tokio::task::yield_now().await;
+                self.render_once().await.unwrap();
+            }
+        });
+        rendered
+            .await
+            .expect("the condition didn't hold within a second");
+    }
+}
+
+/// Holds back the futures of a test until it's opened, to control when they complete.
+#[cfg(test)]
+#[derive(Clone)]
+pub(crate) struct TestGate {
+    open: Arc<tokio::sync::watch::Sender<bool>>,
+    dropped: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+#[cfg(test)]
+impl TestGate {
+    pub(crate) fn new() -> Self {
+        TestGate {
+            open: Arc::new(tokio::sync::watch::channel(false).0),
+            dropped: Arc::default(),
+        }
+    }
+
+    /// A future which completes with `output` once the gate is opened.
+    pub(crate) fn pass<O>(&self, output: O) -> impl std::future::Future<Output = O> {
+        /// Counts the future as dropped, unless it's defused because the future completed
+        struct DropCounter(Option<Arc<std::sync::atomic::AtomicUsize>>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                if let Some(dropped) = self.0.take() {
+                    dropped.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                }
+            }
+        }
+
+        let mut open = self.open.subscribe();
+        let mut counter = DropCounter(Some(self.dropped.clone()));
+        async move {
+            let _ = open.wait_for(|open| *open).await;
+            counter.0 = None;
+            output
+        }
+    }
+
+    pub(crate) fn open(&self) {
+        self.open.send_replace(true);
+    }
+
+    /// The number of futures which were dropped before they completed, e.g. because they were aborted.
+    pub(crate) fn dropped(&self) -> usize {
+        self.dropped.load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn test_key(key: Key) -> Event {
+    Event::Key(KeyEvent::from(key))
+}
+
+#[cfg(test)]
+pub(crate) fn test_mouse(kind: MouseKind, column: i16, row: i16) -> Event {
+    Event::Mouse(RawMouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: crate::widget::Modifiers::NONE,
+    })
+}
+
+/// Stops the tasks no matter how the app exits, the terminal is restored afterwards when the
+/// [`TerminalGuard`](crate::backend::TerminalGuard) of the config is dropped
+impl<T: Send + 'static, V: View<T> + 'static, B: TerminalBackend> Drop for App<T, V, B> {
     fn drop(&mut self) {
-        #[cfg(not(any(test, doctest, feature = "doctests")))]
-        self.restore_terminal()
-            .unwrap_or_else(|e| eprint!("Restoring the terminal failed: {e}"));
+        // `run` stops the tasks, but it may have returned early with an error or never been called
+        for task in &self.tasks {
+            task.abort();
+        }
+        if let Some(app_task) = &self.app_task {
+            app_task.abort();
+        }
     }
 }
 
@@ -493,14 +1133,27 @@ impl<T, V: View<T>, F: FnMut(&mut T) -> V> AppTask<T, V, F> {
                     AppMessage::Events(events) => {
                         for event in events {
                             let id_path = &event.id_path[1..];
-                            self.view.as_ref().unwrap().message(
+                            let result = self.view.as_ref().unwrap().message(
                                 id_path,
                                 self.state.as_mut().unwrap(),
                                 event.body,
                                 &mut self.data,
                             );
+                            // Handlers may change the app state even if they return `Nop`,
+                            // only messages which didn't reach a handler are known to not change it
+                            if !matches!(result, MessageResult::Stale(_)) {
+                                self.needs_rebuild = true;
+                            }
                         }
                     }
+                    AppMessage::KeyBindings(indices) => {
+                        let mut key_bindings = self.key_bindings.lock().unwrap();
+                        for index in indices {
+                            (key_bindings[index].1)(&mut self.data);
+                        }
+                        self.needs_rebuild = true;
+                    }
+                    AppMessage::Invalidate => self.needs_rebuild = true,
                     AppMessage::Wake(id_path) => {
                         let needs_rebuild;
                         {
@@ -515,6 +1168,7 @@ impl<T, V: View<T>, F: FnMut(&mut T) -> V> AppTask<T, V, F> {
                         }
 
                         if needs_rebuild {
+                            self.needs_rebuild = true;
                             // request re-render from UI thread
                             if self.ui_state == UiState::Start {
                                 self.ui_state = UiState::WokeUI;
@@ -532,6 +1186,15 @@ impl<T, V: View<T>, F: FnMut(&mut T) -> V> AppTask<T, V, F> {
                             }
                         }
                     }
+                    // A delayed render still waits for pending futures
+                    AppMessage::Render(delay)
+                        if !self.needs_rebuild && (!delay || self.pending_async.is_empty()) =>
+                    {
+                        tracing::debug!("Skip render, the app state hasn't changed");
+                        if self.response_chan.send(Ok(None)).await.is_err() {
+                            tracing::error!("error sending render response");
+                        }
+                    }
                     AppMessage::Render(delay) => {
                         if !delay || self.pending_async.is_empty() {
                             tracing::debug!("Render without delay");
@@ -558,13 +1221,24 @@ impl<T, V: View<T>, F: FnMut(&mut T) -> V> AppTask<T, V, F> {
     }
 
     async fn render(&mut self) {
-        let view = (self.app_logic)(&mut self.data);
+        self.needs_rebuild = false;
+        // The panic is reported by `App::run`, which restores the terminal before it returns
+        let view = match catch_unwind(AssertUnwindSafe(|| (self.app_logic)(&mut self.data))) {
+            Ok(view) => view,
+            Err(payload) => {
+                let _ = self
+                    .response_chan
+                    .send(Err(AppLogicPanic::new(&*payload)))
+                    .await;
+                return;
+            }
+        };
         let response = RenderResponse {
             prev: self.view.take(),
             view,
             state: self.state.take(),
         };
-        if self.response_chan.send(response).await.is_err() {
+        if self.response_chan.send(Ok(Some(response))).await.is_err() {
             tracing::error!("error sending render response");
         }
         if let Some((view, state, pending)) = self.return_chan.recv().await {
@@ -575,3 +1249,540 @@ impl<T, V: View<T>, F: FnMut(&mut T) -> V> AppTask<T, V, F> {
         self.ui_state = UiState::Start;
     }
 }
+
+/// Swaps the foreground and background colors of all cells, for the visual bell.
+fn invert(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        cell.modifier.toggle(Modifier::REVERSED);
+    }
+}
+
+/// Drops mouse moves which are directly followed by another one, as only the last position matters.
+///
+/// Other events like clicks are kept in order, including a move right before them, so that
+/// e.g. the hot state is up to date when they're handled.
+fn coalesce_mouse_moves(events: &mut Vec<Event>) {
+    let is_move = |event: &Event| {
+        matches!(
+            event,
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Moved,
+                ..
+            })
+        )
+    };
+    // `dedup_by` keeps the first of consecutive duplicates, so it's applied in reverse order
+    events.reverse();
+    events.dedup_by(|a, b| is_move(a) && is_move(b));
+    events.reverse();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        widget::{Modifiers, MouseButton},
+        BellKind, FutureState,
+    };
+
+    use super::*;
+
+    fn mouse(kind: MouseKind, column: i16) -> Event {
+        Event::Mouse(RawMouseEvent {
+            kind,
+            column,
+            row: 0,
+            modifiers: Modifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn only_the_last_of_consecutive_mouse_moves_is_kept() {
+        let down = MouseKind::Down(MouseButton::Left);
+        let mut events = vec![
+            mouse(MouseKind::Moved, 1),
+            mouse(MouseKind::Moved, 2),
+            mouse(MouseKind::Moved, 3),
+            mouse(down, 8),
+            mouse(MouseKind::Moved, 4),
+            Event::Key(KeyEvent::from(Key::Enter)),
+            mouse(MouseKind::Moved, 5),
+            mouse(MouseKind::Moved, 6),
+        ];
+        coalesce_mouse_moves(&mut events);
+        let columns: Vec<_> = events
+            .iter()
+            .map(|event| match event {
+                Event::Mouse(mouse) => mouse.column,
+                _ => -1,
+            })
+            .collect();
+        assert_eq!(columns, vec![3, 8, 4, -1, 6]);
+    }
+
+    #[tokio::test]
+    async fn render_once_paints_a_single_frame() {
+        let mut app = App::new((), |_: &mut ()| "Hello").await;
+        app.render_once().await.unwrap();
+        let buffer = app.config.terminal.backend().buffer();
+        let line: String = (0..5).map(|x| buffer.get(x, 0).symbol()).collect();
+        assert_eq!(line, "Hello");
+    }
+
+    #[tokio::test]
+    async fn a_message_is_shown_while_the_terminal_is_too_small() {
+        let mut app = App::new((), |_: &mut ()| "Hello")
+            .await
+            .min_size(100, 10)
+            .min_size_message("Too small");
+        app.render_once().await.unwrap();
+        let buffer = app.config.terminal.backend().buffer();
+        let line: String = (35..44).map(|x| buffer.get(x, 20).symbol()).collect();
+        assert_eq!(line, "Too small");
+        assert_eq!(buffer.get(0, 0).symbol(), " ");
+
+        app.config.terminal.backend_mut().resize(100, 40);
+        app.render_once().await.unwrap();
+        let buffer = app.config.terminal.backend().buffer();
+        let line: String = (0..5).map(|x| buffer.get(x, 0).symbol()).collect();
+        assert_eq!(line, "Hello");
+        assert_eq!(buffer.get(45, 20).symbol(), " ");
+    }
+
+    #[tokio::test]
+    async fn input_is_ignored_while_the_terminal_is_too_small() {
+        let mut app = App::new(String::new(), |text: &mut String| {
+            crate::text_input(text.clone(), |text: &mut String, new: String| *text = new)
+        })
+        .await
+        .min_size(80, 40);
+        let key = |c| Event::Key(KeyEvent::from(Key::Char(c)));
+        app.render_once().await.unwrap();
+        // focuses the input
+        app.handle_events(vec![Event::Key(KeyEvent::from(Key::Tab))], Duration::ZERO)
+            .await;
+        app.render_once().await.unwrap();
+
+        app.config.terminal.backend_mut().resize(60, 40);
+        app.render_once().await.unwrap();
+        app.handle_events(vec![key('a')], Duration::ZERO).await;
+        app.render_once().await.unwrap();
+
+        app.config.terminal.backend_mut().resize(80, 40);
+        app.render_once().await.unwrap();
+        app.handle_events(vec![key('b')], Duration::ZERO).await;
+        app.render_once().await.unwrap();
+        let buffer = app.config.terminal.backend().buffer();
+        let line: String = (0..2).map(|x| buffer.get(x, 0).symbol()).collect();
+        assert_eq!(line, "b ");
+    }
+
+    #[tokio::test]
+    async fn tab_and_back_tab_move_the_keyboard_focus_between_inputs() {
+        let mut app = App::new([String::new(), String::new()], |texts: &mut [String; 2]| {
+            crate::v_stack((
+                crate::text_input(texts[0].clone(), |texts: &mut [String; 2], new| {
+                    texts[0] = new
+                }),
+                crate::text_input(texts[1].clone(), |texts: &mut [String; 2], new| {
+                    texts[1] = new
+                }),
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        // nothing is focused yet
+        app.dispatch([test_key(Key::Char('x'))]).await;
+        app.dispatch([test_key(Key::Tab), test_key(Key::Char('a'))])
+            .await;
+        app.dispatch([test_key(Key::Tab), test_key(Key::Char('b'))])
+            .await;
+        // wraps around to the first input
+        app.dispatch([test_key(Key::Tab), test_key(Key::Char('c'))])
+            .await;
+        app.dispatch([test_key(Key::BackTab), test_key(Key::Char('d'))])
+            .await;
+        assert_eq!(app.screen_line(0), "ac");
+        assert_eq!(app.screen_line(1), "bd");
+    }
+
+    /// The text of an input and a log of the handlers that were called
+    type Log = (String, Vec<&'static str>);
+
+    async fn logging_input_app() -> App<Log, impl View<Log> + 'static> {
+        App::new((String::new(), Vec::new()), |(text, log): &mut Log| {
+            crate::v_stack((
+                format!("{log:?}"),
+                crate::text_input(text.clone(), |(text, log): &mut Log, new| {
+                    *text = new;
+                    log.push("input");
+                }),
+            ))
+        })
+        .await
+        .key_binding(Key::F(1), |(_, log): &mut Log| log.push("F1"))
+        .key_binding(Key::Char('x'), |(_, log): &mut Log| log.push("x"))
+    }
+
+    #[tokio::test]
+    async fn key_bindings_run_before_the_widgets_handle_events() {
+        let mut app = logging_input_app().await;
+        app.render_once().await.unwrap();
+        app.dispatch([test_key(Key::Tab)]).await;
+        // the input gets `a` before `F1` is pressed
+        app.dispatch([test_key(Key::Char('a')), test_key(Key::F(1))])
+            .await;
+        assert_eq!(app.screen_line(0), r#"["F1", "input"]"#);
+    }
+
+    #[tokio::test]
+    async fn bound_keys_dont_reach_the_focused_widget() {
+        let mut app = logging_input_app().await;
+        app.render_once().await.unwrap();
+        app.dispatch([test_key(Key::Tab), test_key(Key::Char('x'))])
+            .await;
+        assert_eq!(app.screen_line(0), r#"["x"]"#);
+        assert_eq!(app.screen_line(1), "");
+        // `Shift` is ignored for characters, so an uppercase character is another key
+        let shift_x = KeyEvent::new(Key::Char('X'), Modifiers::SHIFT);
+        app.dispatch([Event::Key(shift_x)]).await;
+        assert_eq!(app.screen_line(0), r#"["x", "input"]"#);
+    }
+
+    #[tokio::test]
+    async fn unbound_keys_still_reach_the_focused_widget() {
+        let mut app = logging_input_app().await;
+        app.render_once().await.unwrap();
+        app.dispatch([
+            test_key(Key::Tab),
+            test_key(Key::Char('a')),
+            test_key(Key::Char('b')),
+        ])
+        .await;
+        assert_eq!(app.screen_line(0), r#"["input", "input"]"#);
+        assert_eq!(app.screen_line(1), "ab");
+    }
+
+    /// Counts how often an app logic ran
+    #[derive(Clone, Default)]
+    struct RunCounter(Arc<std::sync::atomic::AtomicUsize>);
+
+    impl RunCounter {
+        fn count(&self) {
+            self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+
+        fn runs(&self) -> usize {
+            self.0.load(std::sync::atomic::Ordering::SeqCst)
+        }
+    }
+
+    #[tokio::test]
+    async fn the_app_logic_only_runs_when_the_app_state_may_have_changed() {
+        let counter = RunCounter::default();
+        let app_counter = counter.clone();
+        let mut app = App::new(0, move |count: &mut i32| {
+            app_counter.count();
+            crate::v_stack((
+                format!("count {count}"),
+                crate::ViewExt::on_click("+", |count: &mut i32| *count += 1),
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        assert_eq!(counter.runs(), 1);
+
+        // no handler is called for these events
+        app.dispatch([mouse(MouseKind::Moved, 3), test_key(Key::Char('a'))])
+            .await;
+        app.render_once().await.unwrap();
+        assert_eq!(counter.runs(), 1);
+
+        let down = MouseKind::Down(MouseButton::Left);
+        let up = MouseKind::Up(MouseButton::Left);
+        app.dispatch([test_mouse(down, 0, 1), test_mouse(up, 0, 1)])
+            .await;
+        assert_eq!(counter.runs(), 2);
+        assert_eq!(app.screen_line(0), "count 1");
+    }
+
+    #[tokio::test]
+    async fn the_app_logic_runs_after_a_resize_or_an_invalidation() {
+        let counter = RunCounter::default();
+        let app_counter = counter.clone();
+        let mut app = App::new((), move |_: &mut ()| {
+            app_counter.count();
+            "Hello"
+        })
+        .await;
+        app.render_once().await.unwrap();
+        app.render_once().await.unwrap();
+        assert_eq!(counter.runs(), 1);
+
+        app.config.terminal.backend_mut().resize(60, 20);
+        app.render_once().await.unwrap();
+        assert_eq!(counter.runs(), 2);
+
+        let _ = app.req_chan.send(AppMessage::Invalidate).await;
+        app.render_once().await.unwrap();
+        assert_eq!(counter.runs(), 3);
+        app.render_once().await.unwrap();
+        assert_eq!(counter.runs(), 3);
+    }
+
+    #[tokio::test]
+    async fn the_app_logic_runs_after_a_future_woke_the_app() {
+        let counter = RunCounter::default();
+        let app_counter = counter.clone();
+        let gate = TestGate::new();
+        let app_gate = gate.clone();
+        let mut app = App::new(None, move |data: &mut Option<String>| {
+            app_counter.count();
+            let gate = app_gate.clone();
+            crate::ViewExt::on_mount_async(
+                data.clone().unwrap_or_else(|| "Loading".into()),
+                move || gate.pass("Loaded".to_string()),
+                |data: &mut Option<String>, state| {
+                    if let FutureState::Ready(loaded) = state {
+                        *data = Some(loaded);
+                    }
+                },
+            )
+        })
+        .await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "Loading");
+
+        // only the wake of the resolved future changes the app state
+        let runs = counter.runs();
+        gate.open();
+        app.render_until(|app| app.screen_line(0) == "Loaded").await;
+        assert!(counter.runs() > runs);
+
+        let runs = counter.runs();
+        app.dispatch([mouse(MouseKind::Moved, 3)]).await;
+        assert_eq!(counter.runs(), runs);
+    }
+
+    /// An app config which collects the changed cells of each flushed frame
+    fn changed_cells_config() -> (AppConfig, Arc<Mutex<Vec<usize>>>) {
+        let changed_cells = Arc::new(Mutex::new(Vec::new()));
+        let reported = changed_cells.clone();
+        let config = AppConfig::new().with_frame_stats(move |stats| {
+            reported.lock().unwrap().push(stats.changed_cells);
+        });
+        (config, changed_cells)
+    }
+
+    #[tokio::test]
+    async fn a_full_redraw_reports_all_cells_as_changed() {
+        let (config, changed_cells) = changed_cells_config();
+        let full_redraw = config.full_redraw();
+        let mut app = App::new_with_config(config, 0, |count: &mut i32| {
+            crate::ViewExt::on_click(format!("Hello {count}"), |count: &mut i32| *count += 1)
+        })
+        .await;
+        app.render_once().await.unwrap();
+        full_redraw.request();
+        app.render_once().await.unwrap();
+        let down = MouseKind::Down(crate::widget::MouseButton::Left);
+        let up = MouseKind::Up(crate::widget::MouseButton::Left);
+        app.dispatch([mouse(down, 0), mouse(up, 0)]).await;
+        assert_eq!(*changed_cells.lock().unwrap(), vec![6, 6, 1]);
+    }
+
+    #[tokio::test]
+    async fn frames_shown_while_the_terminal_is_too_small_are_reported() {
+        let (config, changed_cells) = changed_cells_config();
+        let mut app = App::new_with_config(config, (), |_: &mut ()| "Hello")
+            .await
+            .min_size(80, 40)
+            .min_size_message("Small");
+        app.render_once().await.unwrap();
+        app.config.terminal.backend_mut().resize(60, 40);
+        app.render_once().await.unwrap();
+        // the terminal is cleared after a resize, so all cells are written again
+        app.config.terminal.backend_mut().resize(80, 40);
+        app.render_once().await.unwrap();
+        assert_eq!(*changed_cells.lock().unwrap(), vec![5, 5, 5]);
+    }
+
+    #[tokio::test]
+    async fn the_first_frame_is_rendered_before_the_initial_data_is_loaded() {
+        let mut app = App::new(None, |data: &mut Option<String>| {
+            crate::ViewExt::on_mount_async(
+                data.clone().unwrap_or_else(|| "Loading".into()),
+                std::future::pending::<String>,
+                |data: &mut Option<String>, state| {
+                    if let FutureState::Ready(loaded) = state {
+                        *data = Some(loaded);
+                    }
+                },
+            )
+        })
+        .await;
+        app.render_once().await.unwrap();
+        let buffer = app.config.terminal.backend().buffer();
+        let line: String = (0..7).map(|x| buffer.get(x, 0).symbol()).collect();
+        assert_eq!(line, "Loading");
+    }
+
+    #[tokio::test]
+    async fn the_visual_bell_inverts_the_screen() {
+        let config = AppConfig::new();
+        let bell = config.bell();
+        let mut app = App::new_with_config(config, (), |_: &mut ()| "Hello").await;
+        bell.ring(BellKind::Visual);
+        app.render_once().await.unwrap();
+        let buffer = app.config.terminal.backend().buffer();
+        assert!(buffer.get(0, 0).modifier.contains(Modifier::REVERSED));
+    }
+
+    #[tokio::test]
+    async fn measured_bounds_are_available_after_render() {
+        let config = AppConfig::new();
+        let bounds = config.widget_bounds();
+        let mut app = App::new_with_config(config, (), |_: &mut ()| {
+            crate::ViewExt::measure("Hello", "greeting")
+        })
+        .await;
+        assert_eq!(bounds.get("greeting"), None);
+        app.render_once().await.unwrap();
+        assert_eq!(bounds.get("greeting"), Some(Rect::new(0.0, 0.0, 5.0, 1.0)));
+    }
+
+    #[tokio::test]
+    async fn the_quit_key_quits_the_app() {
+        let app = App::new((), |_: &mut ()| "Hello")
+            .await
+            .quit_key(Key::Char('q'));
+        let event_tx = app.event_tx();
+        // `Esc` isn't the quit key, it's sent to the widgets
+        for key in [Key::Esc, Key::Char('q')] {
+            event_tx.send(test_key(key)).await.unwrap();
+        }
+        tokio::time::timeout(Duration::from_secs(1), app.run())
+            .await
+            .expect("the app didn't quit")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn animations_are_paused_while_the_terminal_is_unfocused() {
+        let config = AppConfig::new().with_pause_when_unfocused(true);
+        let mut app = App::new_with_config(config, (), |_: &mut ()| {
+            crate::ToMarqueeText::marquee("abcdef").speed(1.0)
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(4, 1);
+        assert!(app.render_frame(Duration::ZERO).await);
+        app.dispatch([Event::FocusLost]).await;
+        assert!(!app.render_frame(Duration::from_secs(1)).await);
+        assert_eq!(app.screen_line(0), "abcd");
+
+        // continues where it was paused
+        app.dispatch([Event::FocusGained]).await;
+        assert!(app.render_frame(Duration::from_secs(1)).await);
+        assert_eq!(app.screen_line(0), "bcde");
+    }
+
+    /// How often the app logic runs, while the app runs for 3.5 seconds without any input
+    async fn app_logic_runs_without_input(idle_tick: Option<Duration>) -> usize {
+        use futures::StreamExt;
+
+        let quit = futures::stream::once(tokio::time::sleep(Duration::from_millis(3500)));
+        let mut config = AppConfig::new().with_input(Box::new(quit.map(|()| Event::Quit).boxed()));
+        if let Some(interval) = idle_tick {
+            config = config.with_idle_tick(interval);
+        }
+        let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let app_runs = Arc::clone(&runs);
+        let app = App::new_with_config(config, (), move |_: &mut ()| {
+            app_runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            "Hello"
+        })
+        .await;
+        app.run().await.unwrap();
+        runs.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_app_logic_runs_again_after_each_idle_tick() {
+        // at the start and after 1, 2 and 3 seconds
+        let runs = app_logic_runs_without_input(Some(Duration::from_secs(1))).await;
+        assert_eq!(runs, 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_app_logic_only_runs_at_the_start_without_idle_ticks() {
+        assert_eq!(app_logic_runs_without_input(None).await, 1);
+    }
+
+    /// Never sends an event, the pending future is counted by the gate when it's dropped
+    struct BlockedInput(TestGate);
+
+    impl crate::InputSource for BlockedInput {
+        fn next_event(&mut self) -> futures::future::BoxFuture<'_, Option<Event>> {
+            Box::pin(self.0.pass(None))
+        }
+    }
+
+    /// Sets the flag when the app state is dropped
+    struct DropFlag(Arc<std::sync::atomic::AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn the_background_tasks_are_stopped_when_the_app_quits() {
+        let gate = TestGate::new();
+        let config = AppConfig::new().with_input(Box::new(BlockedInput(gate.clone())));
+        let state_dropped = Arc::default();
+        let app = App::new_with_config(
+            config,
+            DropFlag(Arc::clone(&state_dropped)),
+            |_: &mut DropFlag| "Hello",
+        )
+        .await;
+        // Lets the input task start waiting for the first event
+        tokio::task::yield_now().await;
+        app.event_tx().send(Event::Quit).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), app.run())
+            .await
+            .expect("the app didn't quit")
+            .unwrap();
+        // The input task, which is waiting for the next event, is aborted and the app logic task,
+        // which owns the app state, is finished
+        assert_eq!(gate.dropped(), 1);
+        assert!(state_dropped.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn only_the_quit_key_is_replaced_with_quit() {
+        let app = App::new((), |_: &mut ()| "Hello").await;
+        let ctrl_c = KeyEvent::new(Key::Char('c'), Modifiers::CONTROL);
+        let mut events = vec![
+            test_key(Key::Esc),
+            Event::Key(ctrl_c),
+            test_key(Key::Char('c')),
+        ];
+        app.map_quit_key(&mut events);
+        assert!(matches!(
+            events[..],
+            [Event::Key(_), Event::Quit, Event::Key(_)]
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_fails_when_the_app_logic_panics() {
+        let app = App::new((), |_: &mut ()| -> &'static str {
+            panic!("broken app logic")
+        })
+        .await;
+        let err = app.run().await.unwrap_err();
+        let panic = err.downcast_ref::<AppLogicPanic>().unwrap();
+        assert_eq!(panic.message(), "broken app logic");
+    }
+}
@@ -1,51 +1,563 @@
-#[cfg(not(any(test, doctest, feature = "doctests")))]
-use std::io::stdout;
+use std::{
+    collections::{HashMap, VecDeque},
+    io::Write,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
-#[cfg(not(any(test, doctest, feature = "doctests")))]
-use ratatui::backend::CrosstermBackend;
+use ratatui::{backend::CrosstermBackend, buffer::Buffer, Terminal};
 
-#[cfg(any(test, doctest, feature = "doctests"))]
-use ratatui::backend::TestBackend;
+use crate::{
+    backend::TerminalGuard,
+    geometry::{Rect, Size},
+    DefaultBackend, InputSource, TerminalBackend, Theme,
+};
 
-use ratatui::Terminal;
+/// Configuration to create a customized [`App`](crate::App) instance
+pub struct AppConfig<B: TerminalBackend = DefaultBackend> {
+    /// The terminal backend use to render the output to, it's restored when the config is dropped
+    pub(crate) terminal: TerminalGuard<B>,
 
-#[cfg(not(any(test, doctest, feature = "doctests")))]
-use std::io::Stdout;
+    runtime: RuntimeOrHandle,
 
-/// Configuration to create a customized [`App`](crate::App) instance
-pub struct AppConfig {
-    /// The terminal used when run from unit or integration tests
-    #[cfg(any(test, doctest, feature = "doctests"))]
-    pub(crate) terminal: Terminal<TestBackend>,
+    pub(crate) theme: Theme,
 
-    /// The terminal backend use to render the output to
-    #[cfg(not(any(test, doctest, feature = "doctests")))]
-    pub(crate) terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// The source of input events, [`CrosstermInput`](crate::CrosstermInput) if it's `None`
+    pub(crate) input: Option<Box<dyn InputSource>>,
 
-    runtime: RuntimeOrHandle,
+    pub(crate) terminal_size: TerminalSize,
+
+    pub(crate) widget_bounds: WidgetBounds,
+
+    pub(crate) bell: Bell,
+
+    pub(crate) full_redraw: FullRedraw,
+
+    /// Whether mouse events are captured, which disables the text selection of the terminal
+    pub(crate) mouse_capture: bool,
+
+    /// Whether animations are paused while the terminal doesn't have the focus
+    pub(crate) pause_when_unfocused: bool,
+
+    /// Whether `Ctrl-Z` suspends the app, see [`with_suspend`](AppConfig::with_suspend)
+    #[cfg(unix)]
+    pub(crate) suspend: bool,
+
+    /// The app logic runs again after this duration without events, see [`with_idle_tick`](AppConfig::with_idle_tick)
+    pub(crate) idle_tick: Option<Duration>,
+
+    /// Reports the changed cells of each frame, see [`with_frame_stats`](AppConfig::with_frame_stats)
+    pub(crate) frame_stats: Option<FrameStatsHook>,
+
+    /// Records the duration of each render pass, see [`render_timings`](AppConfig::render_timings)
+    pub(crate) render_timings: RenderTimings,
+}
+
+/// A shared handle to the current size of the terminal (in cells), which can be moved into the app logic
+/// to make layout decisions up front, e.g. to choose a compact layout on narrow terminals.
+///
+/// The size is updated by the [`App`](crate::App) before the app logic runs, so it's already
+/// available in the first run of the app logic and the app logic runs again when the terminal is resized.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// let config = AppConfig::new();
+/// let size = config.terminal_size();
+/// App::new_with_config(config, (), move |_| {
+///     if size.get().width < 60.0 {
+///         "compact"
+///     } else {
+///         "a wide layout with more details"
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TerminalSize(Arc<Mutex<Size>>);
+
+impl TerminalSize {
+    /// The current size, which is zero until the app has started.
+    pub fn get(&self) -> Size {
+        *self.0.lock().unwrap()
+    }
+
+    pub(crate) fn set(&self, size: Size) {
+        *self.0.lock().unwrap() = size;
+    }
+}
+
+/// A shared handle to the bounds of the views tagged with [`ViewExt::measure`](crate::ViewExt::measure),
+/// which can be moved into the app logic to make decisions based on the layout, e.g. how many rows fit on a page.
+///
+/// The bounds (in terminal cells) of a view are available after it was laid out the first time,
+/// i.e. not yet in the run of the app logic which created the view. Whenever they change, the app logic
+/// runs again, so it shouldn't change the layout in a way that changes the measured bounds every time.
+/// A key should be used by only one view at a time, otherwise the bounds of the view laid out last are returned.
+/// It's removed when its view is removed.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// let config = AppConfig::new();
+/// let bounds = config.widget_bounds();
+/// App::new_with_config(config, (), move |_| {
+///     let rows = bounds.get("list").map_or(1, |rect| rect.height() as usize);
+///     v_stack((
+///         "Items",
+///         v_stack((0..rows).map(|row| format!("item {row}")).collect::<Vec<_>>())
+///             .fill_max_size(1.0)
+///             .measure("list"),
+///     ))
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WidgetBounds(Arc<Mutex<MeasuredBounds>>);
+
+#[derive(Debug, Default)]
+struct MeasuredBounds {
+    bounds: HashMap<String, Rect>,
+    /// Whether the bounds changed since the app logic ran
+    changed: bool,
+}
+
+impl WidgetBounds {
+    /// The bounds of the view tagged with `key` in the terminal, as they were laid out last.
+    pub fn get(&self, key: &str) -> Option<Rect> {
+        self.0.lock().unwrap().bounds.get(key).copied()
+    }
+
+    pub(crate) fn set(&self, key: &str, rect: Rect) {
+        let mut measured = self.0.lock().unwrap();
+        if measured.bounds.insert(key.to_string(), rect) != Some(rect) {
+            measured.changed = true;
+        }
+    }
+
+    pub(crate) fn remove(&self, key: &str) {
+        self.0.lock().unwrap().bounds.remove(key);
+    }
+
+    /// Whether the bounds changed since the last call.
+    pub(crate) fn take_changed(&self) -> bool {
+        std::mem::take(&mut self.0.lock().unwrap().changed)
+    }
+}
+
+/// Whether the bell is audible or the screen flashes instead, see [`Bell`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BellKind {
+    /// Writes the BEL character, the terminal decides how it's signaled, backends without a terminal ignore it
+    Audible,
+    /// Shows the screen with inverted colors for a moment
+    Visual,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct PendingBell {
+    pub(crate) audible: bool,
+    pub(crate) visual: bool,
+}
+
+/// A shared handle to ring the bell of the terminal, e.g. as feedback for invalid input.
+///
+/// It's usually stored in the app state, so that event handlers can ring it,
+/// the bell is signaled when the app renders the next time.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// let config = AppConfig::new();
+/// let bell = config.bell();
+/// App::new_with_config(config, bell, |_| {
+///     "delete".on_click(|bell: &mut Bell| bell.ring(BellKind::Visual))
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Bell(Arc<Mutex<PendingBell>>);
+
+impl Bell {
+    /// Signals the bell in the next render pass, ringing it more than once before doesn't repeat it.
+    pub fn ring(&self, kind: BellKind) {
+        let mut pending = self.0.lock().unwrap();
+        match kind {
+            BellKind::Audible => pending.audible = true,
+            BellKind::Visual => pending.visual = true,
+        }
+    }
+
+    pub(crate) fn take(&self) -> PendingBell {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+/// A shared handle to redraw the whole terminal, e.g. after another program wrote to it,
+/// or after the app was suspended and resumed.
+///
+/// Usually only the cells which changed since the last frame are written to the terminal,
+/// a full redraw clears the terminal and paints and writes all cells (and the cursor) again
+/// in the next render pass.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # use trui::{Key, KeyEvent, Modifiers};
+/// # async {
+/// let config = AppConfig::new();
+/// let full_redraw = config.full_redraw();
+/// App::new_with_config(config, (), |_| "Hello")
+///     .await
+///     .key_binding(
+///         KeyEvent::new(Key::Char('l'), Modifiers::CONTROL),
+///         move |_| full_redraw.request(),
+///     )
+///     .run()
+///     .await
+/// # };
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FullRedraw(Arc<AtomicBool>);
+
+impl FullRedraw {
+    /// Redraws the whole terminal in the next render pass.
+    pub fn request(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn take(&self) -> bool {
+        self.0.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// How long the phases of a render pass took, see [`AppConfig::render_timings`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderTiming {
+    /// Running the app logic and rebuilding the widget tree
+    pub build: Duration,
+    /// Animating and laying out the widgets and updating the hot and focus state
+    pub layout: Duration,
+    /// Painting the widgets into the buffer of the terminal
+    pub paint: Duration,
+    /// Writing the changed cells to the terminal
+    pub flush: Duration,
+}
+
+impl RenderTiming {
+    /// The duration of the whole render pass
+    pub fn total(&self) -> Duration {
+        self.build + self.layout + self.paint + self.flush
+    }
+}
+
+/// The amount of render passes the averages of [`RenderTimings`] are computed of
+const RENDER_TIMINGS_WINDOW: usize = 32;
+
+/// A shared handle to the durations of the last render passes, which can be moved into the app logic,
+/// e.g. to show a frame rate counter, see [`AppConfig::render_timings`].
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// let config = AppConfig::new();
+/// let timings = config.render_timings();
+/// App::new_with_config(config, (), move |_| {
+///     let average = timings.average();
+///     format!(
+///         "{:.0} fps, layout: {:?}, paint: {:?}",
+///         timings.frames_per_second(),
+///         average.layout,
+///         average.paint
+///     )
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RenderTimings {
+    /// Whether the render passes are recorded, which is the case once a handle was requested
+    recording: Arc<AtomicBool>,
+    window: Arc<Mutex<RenderTimingsWindow>>,
+}
+
+#[derive(Debug, Default)]
+struct RenderTimingsWindow {
+    /// When each of the last render passes started and how long it took
+    passes: VecDeque<(Instant, RenderTiming)>,
+    /// The sum of the timings in `passes`, so that the average is cheap to compute
+    sum: RenderTiming,
+}
+
+impl RenderTimings {
+    /// The timing of the last render pass, which is zero until the app has rendered.
+    ///
+    /// The app logic runs within a render pass, so it sees the timing of the pass before.
+    pub fn last(&self) -> RenderTiming {
+        let window = self.window.lock().unwrap();
+        window.passes.back().map(|(_, t)| *t).unwrap_or_default()
+    }
+
+    /// The average timing of the last 32 render passes.
+    pub fn average(&self) -> RenderTiming {
+        let window = self.window.lock().unwrap();
+        let n = window.passes.len().max(1) as u32;
+        let sum = window.sum;
+        RenderTiming {
+            build: sum.build / n,
+            layout: sum.layout / n,
+            paint: sum.paint / n,
+            flush: sum.flush / n,
+        }
+    }
+
+    /// The rate of render passes within the last 32 passes, it's lower while the app is idle,
+    /// as the app only renders when something changed.
+    pub fn frames_per_second(&self) -> f64 {
+        let window = self.window.lock().unwrap();
+        frames_per_second(&window.passes, Instant::now())
+    }
+
+    pub(crate) fn is_recording(&self) -> bool {
+        self.recording.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record(&self, start: Instant, timing: RenderTiming) {
+        let mut window = self.window.lock().unwrap();
+        if window.passes.len() == RENDER_TIMINGS_WINDOW {
+            let (_, oldest) = window.passes.pop_front().unwrap();
+            window.sum.build -= oldest.build;
+            window.sum.layout -= oldest.layout;
+            window.sum.paint -= oldest.paint;
+            window.sum.flush -= oldest.flush;
+        }
+        window.sum.build += timing.build;
+        window.sum.layout += timing.layout;
+        window.sum.paint += timing.paint;
+        window.sum.flush += timing.flush;
+        window.passes.push_back((start, timing));
+    }
+}
+
+/// The amount of `passes` per second, from the start of the oldest pass until `now`.
+fn frames_per_second(passes: &VecDeque<(Instant, RenderTiming)>, now: Instant) -> f64 {
+    match passes.front() {
+        Some((first, _)) => {
+            let elapsed = now.saturating_duration_since(*first).as_secs_f64();
+            if elapsed > 0.0 {
+                passes.len() as f64 / elapsed
+            } else {
+                0.0
+            }
+        }
+        None => 0.0,
+    }
+}
+
+/// Statistics about a frame which was flushed to the terminal, see
+/// [`AppConfig::with_frame_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameStats {
+    /// The amount of cells which differ from the previous frame and were written to the terminal
+    pub changed_cells: usize,
+    /// The amount of cells of the terminal
+    pub total_cells: usize,
+}
+
+pub(crate) struct FrameStatsHook {
+    hook: Box<dyn FnMut(FrameStats) + Send>,
+    /// A copy of the previously flushed frame, ratatui doesn't expose its own copy
+    prev_frame: Option<Buffer>,
+}
+
+impl FrameStatsHook {
+    /// Calls the hook with the stats of `frame`, which is about to be flushed.
+    pub(crate) fn report(&mut self, frame: &Buffer) {
+        let stats = FrameStats {
+            changed_cells: changed_cells(self.prev_frame.as_ref(), frame),
+            total_cells: frame.content.len(),
+        };
+        match &mut self.prev_frame {
+            Some(prev_frame) if prev_frame.area == frame.area => prev_frame.clone_from(frame),
+            prev_frame => *prev_frame = Some(frame.clone()),
+        }
+        (self.hook)(stats);
+    }
+
+    /// Forgets the previous frame after the terminal was cleared, as the next frame is written completely.
+    pub(crate) fn reset(&mut self) {
+        self.prev_frame = None;
+    }
+}
+
+/// The amount of cells ratatui writes to the terminal when `frame` follows `prev_frame`.
+///
+/// After a resize ratatui clears the terminal, so only the non-empty cells are written.
+fn changed_cells(prev_frame: Option<&Buffer>, frame: &Buffer) -> usize {
+    match prev_frame {
+        Some(prev_frame) if prev_frame.area == frame.area => prev_frame.diff(frame).len(),
+        _ => Buffer::empty(frame.area).diff(frame).len(),
+    }
 }
 
 impl AppConfig {
     pub fn new() -> Self {
         Self::default()
     }
+}
 
-    /// Provide a custom backend to render the output to
-    #[cfg(not(any(test, doctest, feature = "doctests")))]
-    pub fn with_backend(mut self, backend: CrosstermBackend<Stdout>) -> Self {
-        self.terminal = Terminal::new(backend).unwrap();
+impl<B: TerminalBackend> AppConfig<B> {
+    /// Provide a custom backend to render the output to, e.g. a
+    /// [`CrosstermBackend`](ratatui::backend::CrosstermBackend) writing to a PTY or a network stream
+    pub fn with_backend<NB: TerminalBackend>(self, backend: NB) -> AppConfig<NB> {
+        AppConfig {
+            terminal: TerminalGuard::new(Terminal::new(backend).unwrap()),
+            runtime: self.runtime,
+            theme: self.theme,
+            input: self.input,
+            terminal_size: self.terminal_size,
+            widget_bounds: self.widget_bounds,
+            bell: self.bell,
+            full_redraw: self.full_redraw,
+            mouse_capture: self.mouse_capture,
+            pause_when_unfocused: self.pause_when_unfocused,
+            #[cfg(unix)]
+            suspend: self.suspend,
+            idle_tick: self.idle_tick,
+            frame_stats: self.frame_stats,
+            render_timings: self.render_timings,
+        }
+    }
+
+    /// Render to `writer` instead of stdout, e.g. to a file or a `Vec<u8>` to capture the ANSI output
+    ///
+    /// The terminal is not switched to raw mode for a custom writer, unless the input events are read
+    /// from the terminal, i.e. no other source was provided via [`with_input`](AppConfig::with_input).
+    pub fn with_writer<W: Write>(self, writer: W) -> AppConfig<CrosstermBackend<W>> {
+        self.with_backend(CrosstermBackend::new(writer))
+    }
+
+    /// Provide a custom theme, which is used by views that don't get an explicit style
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
         self
     }
 
-    /// Provide a custom backend to render the output to
-    #[cfg(any(test, doctest, feature = "doctests"))]
-    pub fn with_backend(mut self, backend: TestBackend) -> Self {
-        self.terminal = Terminal::new(backend).unwrap();
+    /// Provide a custom source of input events instead of reading them from the terminal
+    pub fn with_input(mut self, input: Box<dyn InputSource>) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    /// Whether the mouse is captured, which is the default.
+    ///
+    /// Capturing the mouse disables the native text selection of the terminal, so apps that don't need
+    /// the mouse can disable it to let users copy their output. Without mouse capture the app doesn't
+    /// receive any mouse events, so e.g. hover styles and click handlers are just never triggered,
+    /// while everything that's reachable via the keyboard keeps working.
+    pub fn with_mouse_capture(mut self, mouse_capture: bool) -> Self {
+        self.mouse_capture = mouse_capture;
+        self
+    }
+
+    /// Pauses animations (and thereby the continuous rendering of frames) while the terminal window
+    /// doesn't have the focus, they continue where they were paused when it gains the focus again.
+    ///
+    /// This is off by default, as not all terminals report focus changes.
+    pub fn with_pause_when_unfocused(mut self, pause: bool) -> Self {
+        self.pause_when_unfocused = pause;
+        self
+    }
+
+    /// Suspends the app with `Ctrl-Z` (or a `SIGTSTP` signal) like other programs in the shell, it's off by default.
+    ///
+    /// The terminal is restored while the app is suspended, e.g. the alternate screen is left,
+    /// and set up and redrawn completely when it's continued (e.g. with `fg`).
+    /// While it's enabled `Ctrl-Z` isn't sent to the widgets or key bindings.
+    #[cfg(unix)]
+    pub fn with_suspend(mut self, suspend: bool) -> Self {
+        self.suspend = suspend;
         self
     }
 
+    /// Runs the app logic (and renders) again, whenever no event arrived within `interval`,
+    /// e.g. for a clock or to show the contents of a file, which are read in the app logic.
+    ///
+    /// This is off by default, so that an idle app doesn't do any work.
+    /// For updates of only a part of the view, [`stream`](crate::stream) or an animation are
+    /// usually a better fit.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # use std::time::{Duration, SystemTime};
+    /// let config = AppConfig::new().with_idle_tick(Duration::from_secs(1));
+    /// App::new_with_config(config, (), move |_| {
+    ///     let elapsed = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+    ///     format!("{} seconds since the epoch", elapsed.as_secs())
+    /// });
+    /// ```
+    pub fn with_idle_tick(mut self, interval: Duration) -> Self {
+        self.idle_tick = Some(interval);
+        self
+    }
+
+    /// Calls `hook` with the amount of changed cells of every frame that is flushed to the terminal,
+    /// which helps to find views that are repainted more often than necessary.
+    ///
+    /// Frames are only flushed when a widget requested a repaint (or the layout changed), a high ratio
+    /// of changed to total cells on every frame usually hints at a continuously running animation.
+    /// Without a hook nothing is tracked, with a hook a copy of the last frame is kept for the diff.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// let config = AppConfig::new().with_frame_stats(|stats| {
+    ///     if stats.changed_cells * 2 > stats.total_cells {
+    ///         tracing::debug!("{} of {} cells changed", stats.changed_cells, stats.total_cells);
+    ///     }
+    /// });
+    /// App::new_with_config(config, (), |_| "Hello");
+    /// ```
+    pub fn with_frame_stats(mut self, hook: impl FnMut(FrameStats) + Send + 'static) -> Self {
+        self.frame_stats = Some(FrameStatsHook {
+            hook: Box::new(hook),
+            prev_frame: None,
+        });
+        self
+    }
+
+    /// Starts to record how long each render pass takes and returns a handle to the timings,
+    /// see [`RenderTimings`].
+    ///
+    /// Recording is off by default, when it's enabled it adds a few clock reads and a short lock
+    /// to each render pass. Every call returns a handle to the same timings.
+    pub fn render_timings(&self) -> RenderTimings {
+        self.render_timings.recording.store(true, Ordering::Relaxed);
+        self.render_timings.clone()
+    }
+
+    /// A handle to the current size of the terminal, see [`TerminalSize`]
+    pub fn terminal_size(&self) -> TerminalSize {
+        self.terminal_size.clone()
+    }
+
+    /// A handle to the bounds of the views tagged with [`ViewExt::measure`](crate::ViewExt::measure),
+    /// see [`WidgetBounds`]
+    pub fn widget_bounds(&self) -> WidgetBounds {
+        self.widget_bounds.clone()
+    }
+
+    /// A handle to ring the bell of the terminal, see [`Bell`]
+    pub fn bell(&self) -> Bell {
+        self.bell.clone()
+    }
+
+    /// A handle to redraw the whole terminal, see [`FullRedraw`]
+    pub fn full_redraw(&self) -> FullRedraw {
+        self.full_redraw.clone()
+    }
+
     #[cfg(any(test, doctest, feature = "doctests"))]
-    pub(crate) fn terminal_mut(&mut self) -> &mut Terminal<TestBackend> {
+    pub(crate) fn terminal_mut(&mut self) -> &mut Terminal<B> {
         &mut self.terminal
     }
 
@@ -67,19 +579,35 @@ impl AppConfig {
 impl Default for AppConfig {
     fn default() -> Self {
         #[cfg(not(any(test, doctest, feature = "doctests")))]
-        let backend = CrosstermBackend::new(stdout()); // TODO handle errors...
+        let backend = CrosstermBackend::new(std::io::stdout()); // TODO handle errors...
 
         #[cfg(any(test, doctest, feature = "doctests"))]
-        let backend = TestBackend::new(80, 40);
+        let backend = ratatui::backend::TestBackend::new(80, 40);
 
-        let terminal = Terminal::new(backend).unwrap();
+        let terminal = TerminalGuard::new(Terminal::new(backend).unwrap());
 
         let runtime = match tokio::runtime::Handle::try_current() {
             Ok(handle) => RuntimeOrHandle::Handle(handle),
             Err(_) => RuntimeOrHandle::Runtime(tokio::runtime::Runtime::new().unwrap()),
         };
 
-        Self { terminal, runtime }
+        Self {
+            terminal,
+            runtime,
+            theme: Theme::default(),
+            input: None,
+            terminal_size: TerminalSize::default(),
+            widget_bounds: WidgetBounds::default(),
+            bell: Bell::default(),
+            full_redraw: FullRedraw::default(),
+            mouse_capture: true,
+            pause_when_unfocused: false,
+            #[cfg(unix)]
+            suspend: false,
+            idle_tick: None,
+            frame_stats: None,
+            render_timings: RenderTimings::default(),
+        }
     }
 }
 
@@ -87,3 +615,61 @@ enum RuntimeOrHandle {
     Runtime(tokio::runtime::Runtime),
     Handle(tokio::runtime::Handle),
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{layout::Rect, style::Style};
+
+    use super::*;
+
+    #[test]
+    fn changed_cells_are_counted_against_the_previous_frame() {
+        let area = Rect::new(0, 0, 10, 2);
+        let mut frame = Buffer::empty(area);
+        frame.set_string(0, 0, "abc", Style::default());
+        assert_eq!(changed_cells(None, &frame), 3);
+
+        let mut next_frame = frame.clone();
+        next_frame.set_string(2, 0, "d", Style::default());
+        assert_eq!(changed_cells(Some(&frame), &next_frame), 1);
+        assert_eq!(changed_cells(Some(&frame), &frame), 0);
+
+        // a resized frame is drawn onto the cleared terminal
+        let mut resized = Buffer::empty(Rect::new(0, 0, 5, 2));
+        resized.set_string(0, 1, "ab", Style::default());
+        assert_eq!(changed_cells(Some(&frame), &resized), 2);
+    }
+
+    #[test]
+    fn render_timings_are_averaged_over_the_last_passes() {
+        let timings = RenderTimings::default();
+        assert_eq!(timings.average(), RenderTiming::default());
+
+        let start = Instant::now();
+        let timing = |ms| RenderTiming {
+            build: Duration::from_millis(ms),
+            paint: Duration::from_millis(2 * ms),
+            ..Default::default()
+        };
+        for i in 0..RENDER_TIMINGS_WINDOW as u64 {
+            timings.record(start, timing(if i == 0 { 100 } else { 4 }));
+        }
+        timings.record(start, timing(4));
+        // the first pass has left the window
+        assert_eq!(timings.average(), timing(4));
+        assert_eq!(timings.last().total(), Duration::from_millis(12));
+
+        let passes: VecDeque<_> = (0..10).map(|_| (start, timing(1))).collect();
+        let fps = frames_per_second(&passes, start + Duration::from_millis(500));
+        assert_eq!(fps, 20.0);
+    }
+
+    #[test]
+    fn render_timings_are_only_recorded_once_a_handle_was_requested() {
+        let config = AppConfig::new();
+        assert!(!config.render_timings.is_recording());
+        let timings = config.render_timings();
+        assert!(timings.is_recording());
+        assert!(config.render_timings.is_recording());
+    }
+}
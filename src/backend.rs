@@ -0,0 +1,361 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    io::{self, Write},
+    ops::{Deref, DerefMut},
+    panic,
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Once,
+    },
+};
+
+use crossterm::{
+    cursor,
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture,
+    },
+    execute, queue,
+    terminal::{
+        disable_raw_mode, enable_raw_mode, BeginSynchronizedUpdate, EndSynchronizedUpdate,
+        EnterAlternateScreen, LeaveAlternateScreen,
+    },
+};
+use ratatui::{
+    backend::{Backend, CrosstermBackend, TestBackend},
+    Terminal,
+};
+
+/// The backend an [`App`](crate::App) renders to when no other backend is configured.
+#[cfg(not(any(test, doctest, feature = "doctests")))]
+pub type DefaultBackend = CrosstermBackend<std::io::Stdout>;
+
+/// The backend an [`App`](crate::App) renders to when run from unit or integration tests.
+#[cfg(any(test, doctest, feature = "doctests"))]
+pub type DefaultBackend = TestBackend;
+
+/// A ratatui [`Backend`] which can be used as output of an [`App`](crate::App).
+///
+/// Besides drawing, the app sets up the terminal when it's run, restores it when it's dropped
+/// and wraps every frame in a synchronized update to avoid tearing.
+/// All of these are no-ops by default, so e.g. a backend rendering to a network stream only needs
+/// an empty `impl TerminalBackend for MyBackend {}`.
+///
+/// The backend is configured via [`AppConfig::with_backend`](crate::AppConfig::with_backend).
+pub trait TerminalBackend: Backend {
+    /// Prepares the terminal before the first frame is drawn, e.g. by entering the alternate screen.
+    fn init(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Undoes [`init`](TerminalBackend::init) when the app exits.
+    fn restore(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Reports mouse events to the app, it's called after [`init`](TerminalBackend::init) unless mouse
+    /// capture was disabled via [`AppConfig::with_mouse_capture`](crate::AppConfig::with_mouse_capture).
+    fn enable_mouse_capture(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Undoes [`enable_mouse_capture`](TerminalBackend::enable_mouse_capture) when the app exits.
+    fn disable_mouse_capture(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Rings the audible bell of the terminal, see [`Bell`](crate::Bell).
+    fn bell(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called before a frame is flushed, so that the terminal can present it at once.
+    fn begin_synchronized_update(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called after a frame (including the cursor) was flushed.
+    fn end_synchronized_update(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// All escape sequences are written to the writer of the backend, so that e.g. the output of an app
+/// rendering to a `Vec<u8>` via [`AppConfig::with_writer`](crate::AppConfig::with_writer) can be
+/// replayed as is.
+impl<W: Write> TerminalBackend for CrosstermBackend<W> {
+    fn init(&mut self) -> io::Result<()> {
+        // Pasted text is reported as a whole, instead of as single key presses
+        execute!(
+            self,
+            EnterAlternateScreen,
+            EnableFocusChange,
+            EnableBracketedPaste,
+            cursor::Hide
+        )
+    }
+
+    fn restore(&mut self) -> io::Result<()> {
+        execute!(
+            self,
+            cursor::Show,
+            LeaveAlternateScreen,
+            DisableFocusChange,
+            DisableBracketedPaste
+        )
+    }
+
+    fn enable_mouse_capture(&mut self) -> io::Result<()> {
+        execute!(self, EnableMouseCapture)
+    }
+
+    fn disable_mouse_capture(&mut self) -> io::Result<()> {
+        execute!(self, DisableMouseCapture)
+    }
+
+    fn bell(&mut self) -> io::Result<()> {
+        self.write_all(b"\x07")?;
+        Write::flush(self)
+    }
+
+    fn begin_synchronized_update(&mut self) -> io::Result<()> {
+        queue!(self, BeginSynchronizedUpdate)
+    }
+
+    fn end_synchronized_update(&mut self) -> io::Result<()> {
+        execute!(self, EndSynchronizedUpdate)
+    }
+}
+
+impl TerminalBackend for TestBackend {}
+
+/// Whether a [`TerminalGuard`] keeps the terminal in raw mode, i.e. whether the panic hook has to restore it
+static TERMINAL_GUARDED: AtomicBool = AtomicBool::new(false);
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+thread_local! {
+    /// Whether the app itself is polled on this thread, see [`poll_as_app`].
+    static POLLING_APP: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Owns the terminal of an app and restores it when it's dropped, also when the app unwinds
+/// because of a panic.
+///
+/// [`setup`](TerminalGuard::setup) switches to raw mode and initializes the backend, everything
+/// which was set up is undone by [`restore`](TerminalGuard::restore) or when the guard is dropped.
+///
+/// A panic hook is installed as well, which restores the terminal before the previously installed
+/// hook prints the panic message, which would otherwise end up in the alternate screen.
+/// It only does so for panics of the app itself (see [`poll_as_app`]), panics of spawned tasks,
+/// e.g. of a future of an [`async_handler`](crate::async_handler), are caught by the runtime and
+/// the app keeps running.
+pub(crate) struct TerminalGuard<B: TerminalBackend> {
+    terminal: Terminal<B>,
+    /// What has to be undone, `None` while the terminal isn't set up
+    setup: Option<Setup>,
+}
+
+#[derive(Clone, Copy)]
+struct Setup {
+    raw_mode: bool,
+    mouse_capture: bool,
+}
+
+impl<B: TerminalBackend> TerminalGuard<B> {
+    pub(crate) fn new(terminal: Terminal<B>) -> Self {
+        TerminalGuard {
+            terminal,
+            setup: None,
+        }
+    }
+
+    /// Sets up the terminal before the first frame is drawn, see [`TerminalBackend::init`].
+    pub(crate) fn setup(&mut self, raw_mode: bool, mouse_capture: bool) -> io::Result<()> {
+        if raw_mode {
+            enable_raw_mode()?;
+            install_panic_hook();
+            TERMINAL_GUARDED.store(true, Ordering::SeqCst);
+        }
+        // Whatever succeeds is undone, even if a later step fails
+        self.setup = Some(Setup {
+            raw_mode,
+            mouse_capture: false,
+        });
+        self.terminal.backend_mut().init()?;
+        if mouse_capture {
+            self.terminal.backend_mut().enable_mouse_capture()?;
+            self.setup = Some(Setup {
+                raw_mode,
+                mouse_capture,
+            });
+        }
+        Ok(())
+    }
+
+    /// Undoes [`setup`](TerminalGuard::setup), it's a no-op if the terminal isn't set up.
+    pub(crate) fn restore(&mut self) -> io::Result<()> {
+        let Some(setup) = self.setup.take() else {
+            return Ok(());
+        };
+        let backend = self.terminal.backend_mut();
+        let mouse_capture_disabled = if setup.mouse_capture {
+            backend.disable_mouse_capture()
+        } else {
+            Ok(())
+        };
+        let restored = mouse_capture_disabled.and_then(|_| backend.restore());
+        // The raw mode is disabled in any case, as the terminal is unusable otherwise
+        if setup.raw_mode {
+            TERMINAL_GUARDED.store(false, Ordering::SeqCst);
+            disable_raw_mode()?;
+        }
+        restored
+    }
+}
+
+impl<B: TerminalBackend> Deref for TerminalGuard<B> {
+    type Target = Terminal<B>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.terminal
+    }
+}
+
+impl<B: TerminalBackend> DerefMut for TerminalGuard<B> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.terminal
+    }
+}
+
+impl<B: TerminalBackend> Drop for TerminalGuard<B> {
+    fn drop(&mut self) {
+        self.restore()
+            .unwrap_or_else(|e| eprint!("Restoring the terminal failed: {e}"));
+    }
+}
+
+/// Whether the panic hook has to restore the terminal, i.e. whether the panic ends the app and the
+/// terminal is still `guarded`, which is reset, so that the terminal is only restored once.
+fn restores_on_panic(guarded: &AtomicBool) -> bool {
+    POLLING_APP.with(Cell::get) && guarded.swap(false, Ordering::SeqCst)
+}
+
+fn install_panic_hook() {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if restores_on_panic(&TERMINAL_GUARDED) {
+                // The output of the app may go elsewhere, but the raw mode means that the input
+                // is read from the terminal, so restoring stdout is the best guess
+                let mut stdout = CrosstermBackend::new(io::stdout());
+                let _ = disable_raw_mode()
+                    .and_then(|_| stdout.disable_mouse_capture())
+                    .and_then(|_| stdout.restore());
+            }
+            previous_hook(info);
+        }));
+    });
+}
+
+/// Polls `future` (the event loop of the app) with a marker, so that the panic hook only restores
+/// the terminal for panics which end the app, not for panics of tasks which are polled by the
+/// runtime on the same thread in between.
+pub(crate) async fn poll_as_app<F: Future>(future: F) -> F::Output {
+    /// Resets the marker when the poll returns or unwinds, after the panic hook ran
+    struct Polling(bool);
+
+    impl Drop for Polling {
+        fn drop(&mut self) {
+            POLLING_APP.with(|polling| polling.set(self.0));
+        }
+    }
+
+    let mut future = pin!(future);
+    std::future::poll_fn(|cx| {
+        let _polling = Polling(POLLING_APP.with(|polling| polling.replace(true)));
+        future.as_mut().poll(cx)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_panics_of_the_app_itself_restore_the_terminal() {
+        // Local state instead of the one of the installed hook, which is shared by all tests
+        let guarded = Arc::new(AtomicBool::new(true));
+        let task_guarded = guarded.clone();
+        poll_as_app(async {
+            // Spawned tasks are polled by the runtime, outside of the poll of the app
+            let task = tokio::spawn(async move { restores_on_panic(&task_guarded) });
+            assert!(!task.await.unwrap());
+            assert!(restores_on_panic(&guarded));
+            // only once
+            assert!(!restores_on_panic(&guarded));
+        })
+        .await;
+
+        guarded.store(true, Ordering::SeqCst);
+        assert!(!restores_on_panic(&guarded));
+    }
+
+    /// A writer whose output can be inspected after the backend was dropped
+    #[derive(Clone, Default)]
+    struct SharedWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn escape_sequences(commands: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> Vec<u8> {
+        let mut sequences = Vec::new();
+        commands(&mut sequences).unwrap();
+        sequences
+    }
+
+    #[test]
+    fn dropping_the_guard_restores_what_was_set_up() {
+        let writer = SharedWriter::default();
+        let terminal = Terminal::new(CrosstermBackend::new(writer.clone())).unwrap();
+        let mut guard = TerminalGuard::new(terminal);
+        guard.setup(false, true).unwrap();
+        writer.0.lock().unwrap().clear();
+        drop(guard);
+
+        let expected = escape_sequences(|out| {
+            execute!(
+                out,
+                DisableMouseCapture,
+                cursor::Show,
+                LeaveAlternateScreen,
+                DisableFocusChange,
+                DisableBracketedPaste
+            )
+        });
+        assert_eq!(*writer.0.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn a_terminal_which_was_not_set_up_is_not_restored() {
+        let writer = SharedWriter::default();
+        let terminal = Terminal::new(CrosstermBackend::new(writer.clone())).unwrap();
+        let mut guard = TerminalGuard::new(terminal);
+        guard.setup(false, false).unwrap();
+        guard.restore().unwrap();
+        writer.0.lock().unwrap().clear();
+        drop(guard);
+        assert!(writer.0.lock().unwrap().is_empty());
+    }
+}
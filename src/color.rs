@@ -0,0 +1,282 @@
+//! Conversions of [`Color`]s from and to their textual and numeric representations,
+//! e.g. to build a [`Theme`](crate::Theme) programmatically or from the colors of a config file,
+//! and [`Gradient`]s between colors.
+
+use std::str::FromStr;
+
+use ratatui::style::Color;
+
+use crate::rgb;
+
+/// The error of [`from_hex`], contains the invalid input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseColorError(pub String);
+
+impl std::fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid color {:?}, expected a hex color like \"#FF5370\" or a color name",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+/// Parses a hex color like `"#FF5370"` or `"#F57"` (the `#` is optional), or the name of a
+/// terminal color like `"red"` or `"light-blue"`.
+///
+/// Names are case insensitive and are the ones of the [`Color`] variants, which use the colors of the
+/// terminal's palette. Use [`from_ansi256`] for palette indices.
+///
+/// # Examples
+/// ```
+/// # use trui::{color, Color};
+/// assert_eq!(color::from_hex("#FF5370"), Ok(Color::Rgb(0xFF, 0x53, 0x70)));
+/// assert_eq!(color::from_hex("f57"), Ok(Color::Rgb(0xFF, 0x55, 0x77)));
+/// assert_eq!(color::from_hex("Light-Blue"), Ok(Color::LightBlue));
+/// assert!(color::from_hex("#FF98").is_err());
+/// ```
+pub fn from_hex(color: &str) -> Result<Color, ParseColorError> {
+    let error = || ParseColorError(color.to_string());
+    let trimmed = color.trim();
+    let digits = trimmed.strip_prefix('#').unwrap_or(trimmed);
+    if digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        let value = u32::from_str_radix(digits, 16).map_err(|_| error())?;
+        match digits.len() {
+            // each digit is doubled, e.g. `F57` is `FF5577`
+            3 => {
+                let (r, g, b) = ((value >> 8) & 0xF, (value >> 4) & 0xF, value & 0xF);
+                return Ok(rgb(((r * 0x11) << 16) | ((g * 0x11) << 8) | (b * 0x11)));
+            }
+            6 => return Ok(rgb(value)),
+            _ => return Err(error()),
+        }
+    }
+    // Names don't start with a `#`
+    if digits.len() != trimmed.len() {
+        return Err(error());
+    }
+    match Color::from_str(trimmed) {
+        // ratatui also parses palette indices, which are ambiguous with short hex colors
+        Ok(Color::Indexed(_)) | Err(_) => Err(error()),
+        Ok(color) => Ok(color),
+    }
+}
+
+/// The color with the `index` in the 256 color palette of the terminal.
+///
+/// The first 16 colors are the ones of the named [`Color`] variants, the terminal usually allows
+/// to configure them, followed by a 6x6x6 color cube and 24 shades of gray.
+pub const fn from_ansi256(index: u8) -> Color {
+    Color::Indexed(index)
+}
+
+/// The hex representation of `color` like `"#FF5370"`.
+///
+/// Named and indexed colors are converted with the default palette of xterm, as the actual
+/// palette of the terminal isn't known. Returns `None` for [`Color::Reset`].
+///
+/// # Examples
+/// ```
+/// # use trui::{color, Color};
+/// assert_eq!(color::to_hex(Color::Rgb(0xFF, 0x53, 0x70)).as_deref(), Some("#FF5370"));
+/// assert_eq!(color::to_hex(color::from_ansi256(196)).as_deref(), Some("#FF0000"));
+/// assert_eq!(color::to_hex(Color::Reset), None);
+/// ```
+pub fn to_hex(color: Color) -> Option<String> {
+    let (r, g, b) = to_rgb(color)?;
+    Some(format!("#{r:02X}{g:02X}{b:02X}"))
+}
+
+/// The direction in which a [`Gradient`] goes from its start to its end color.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GradientDirection {
+    /// From left to right
+    #[default]
+    Horizontal,
+    /// From top to bottom
+    Vertical,
+    /// From the top left to the bottom right corner
+    Diagonal,
+}
+
+/// A linear gradient between two colors, e.g. for the background of a panel
+/// (see [`ViewExt::background_gradient`](crate::ViewExt::background_gradient)) or for the cells
+/// of a progress bar.
+///
+/// [`Color::Rgb`] colors are interpolated per channel, other colors switch in the middle.
+///
+/// # Examples
+/// ```
+/// # use trui::{color::Gradient, Color};
+/// let gradient = Gradient::new(Color::Rgb(0, 0, 0), Color::Rgb(200, 100, 0));
+/// let colors: Vec<_> = gradient.colors(3).collect();
+/// assert_eq!(colors, [Color::Rgb(0, 0, 0), Color::Rgb(100, 50, 0), Color::Rgb(200, 100, 0)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gradient {
+    pub from: Color,
+    pub to: Color,
+}
+
+impl Gradient {
+    pub const fn new(from: Color, to: Color) -> Self {
+        Gradient { from, to }
+    }
+
+    /// The color at `ratio`, from `0.0` (the start color) to `1.0` (the end color).
+    pub fn at(&self, ratio: f64) -> Color {
+        lerp(self.from, self.to, ratio.clamp(0.0, 1.0))
+    }
+
+    /// `len` colors with even steps, which start and end with the colors of the gradient,
+    /// e.g. one for every cell of a bar.
+    pub fn colors(&self, len: usize) -> impl Iterator<Item = Color> + '_ {
+        (0..len).map(move |i| self.at(step_ratio(i, len)))
+    }
+
+    /// The color of the cell at `x`/`y` within an area of `width` x `height` cells,
+    /// when the gradient goes through the area in `direction`.
+    pub fn color_at(
+        &self,
+        direction: GradientDirection,
+        (x, y): (u16, u16),
+        (width, height): (u16, u16),
+    ) -> Color {
+        let horizontal = step_ratio(x as usize, width as usize);
+        let vertical = step_ratio(y as usize, height as usize);
+        self.at(match direction {
+            GradientDirection::Horizontal => horizontal,
+            GradientDirection::Vertical => vertical,
+            GradientDirection::Diagonal if width <= 1 => vertical,
+            GradientDirection::Diagonal if height <= 1 => horizontal,
+            GradientDirection::Diagonal => (horizontal + vertical) / 2.0,
+        })
+    }
+}
+
+/// The ratio of the step `i` of `len` steps, the first one is `0.0` and the last one is `1.0`.
+fn step_ratio(i: usize, len: usize) -> f64 {
+    if len <= 1 {
+        0.0
+    } else {
+        i as f64 / (len - 1) as f64
+    }
+}
+
+/// Interpolates the channels of RGB colors, other colors switch in the middle.
+pub(crate) fn lerp(from: Color, to: Color, ratio: f64) -> Color {
+    match (from, to) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * ratio).round() as u8;
+            Color::Rgb(lerp(r1, r2), lerp(g1, g2), lerp(b1, b2))
+        }
+        _ if ratio < 0.5 => from,
+        _ => to,
+    }
+}
+
+/// The RGB value of `color` in the default palette of xterm.
+fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let index = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(index) => index,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+    const SYSTEM_COLORS: [u32; 16] = [
+        0x000000, 0xCD0000, 0x00CD00, 0xCDCD00, 0x0000EE, 0xCD00CD, 0x00CDCD, 0xE5E5E5, 0x7F7F7F,
+        0xFF0000, 0x00FF00, 0xFFFF00, 0x5C5CFF, 0xFF00FF, 0x00FFFF, 0xFFFFFF,
+    ];
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    Some(match index {
+        0..=15 => {
+            let value = SYSTEM_COLORS[index as usize];
+            ((value >> 16) as u8, (value >> 8) as u8, value as u8)
+        }
+        16..=231 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[(i / 36) as usize],
+                CUBE_LEVELS[(i / 6 % 6) as usize],
+                CUBE_LEVELS[(i % 6) as usize],
+            )
+        }
+        232..=255 => {
+            let gray = 8 + (index - 232) * 10;
+            (gray, gray, gray)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_colors_and_names_are_parsed() {
+        assert_eq!(from_hex(" #263238 "), Ok(Color::Rgb(0x26, 0x32, 0x38)));
+        assert_eq!(from_hex("#abc"), Ok(Color::Rgb(0xAA, 0xBB, 0xCC)));
+        assert_eq!(from_hex("DarkGray"), Ok(Color::DarkGray));
+        assert_eq!(from_hex("42"), Err(ParseColorError("42".to_string())));
+        assert!(from_hex("#red").is_err());
+        assert!(from_hex("#+12345").is_err());
+        assert!(from_hex("#FF53701").is_err());
+        assert!(from_hex("").is_err());
+    }
+
+    #[test]
+    fn palette_colors_are_converted_with_the_xterm_palette() {
+        assert_eq!(to_hex(Color::LightBlue).as_deref(), Some("#5C5CFF"));
+        assert_eq!(to_hex(from_ansi256(12)), to_hex(Color::LightBlue));
+        assert_eq!(to_hex(from_ansi256(16)).as_deref(), Some("#000000"));
+        assert_eq!(to_hex(from_ansi256(110)).as_deref(), Some("#87AFD7"));
+        assert_eq!(to_hex(from_ansi256(255)).as_deref(), Some("#EEEEEE"));
+    }
+
+    #[test]
+    fn rgb_colors_are_interpolated_per_channel() {
+        let from = Color::Rgb(0, 100, 255);
+        let to = Color::Rgb(255, 100, 0);
+        assert_eq!(lerp(from, to, 0.0), from);
+        assert_eq!(lerp(from, to, 0.5), Color::Rgb(128, 100, 128));
+        assert_eq!(lerp(from, to, 1.0), to);
+        assert_eq!(lerp(Color::Red, to, 0.4), Color::Red);
+        assert_eq!(lerp(Color::Red, to, 0.6), to);
+    }
+
+    #[test]
+    fn gradients_go_through_the_area_in_their_direction() {
+        let gradient = Gradient::new(Color::Rgb(0, 0, 0), Color::Rgb(100, 0, 0));
+        let area = (5, 3);
+        let red = |color| match color {
+            Color::Rgb(r, _, _) => r,
+            _ => unreachable!(),
+        };
+        let at = |direction, x, y| red(gradient.color_at(direction, (x, y), area));
+        assert_eq!(at(GradientDirection::Horizontal, 1, 2), 25);
+        assert_eq!(at(GradientDirection::Vertical, 1, 2), 100);
+        assert_eq!(at(GradientDirection::Diagonal, 0, 0), 0);
+        assert_eq!(at(GradientDirection::Diagonal, 2, 1), 50);
+        assert_eq!(at(GradientDirection::Diagonal, 4, 2), 100);
+        // a single cell only has the start color
+        assert_eq!(gradient.colors(1).collect::<Vec<_>>(), [gradient.from]);
+    }
+}
@@ -19,8 +19,8 @@ pub fn to_ratatui_rect(rect: Rect) -> ratatui::layout::Rect {
     ratatui::layout::Rect {
         x: rect.x0.round().clamp(0.0, u16::MAX as f64) as u16,
         y: rect.y0.round().clamp(0.0, u16::MAX as f64) as u16,
-        width: rect.x1.round().clamp(0.0, u16::MAX as f64) as u16,
-        height: rect.y1.round().clamp(0.0, u16::MAX as f64) as u16,
+        width: rect.width().round().clamp(0.0, u16::MAX as f64) as u16,
+        height: rect.height().round().clamp(0.0, u16::MAX as f64) as u16,
     }
 }
 
@@ -220,3 +220,14 @@ impl Dim2 for BoxConstraints {
         self.min().height..self.max().height
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rects_keep_their_size_when_converted_to_ratatui_rects() {
+        let rect = to_ratatui_rect(Rect::new(2.0, 3.0, 7.0, 5.0));
+        assert_eq!(rect, ratatui::layout::Rect::new(2, 3, 5, 2));
+    }
+}
@@ -0,0 +1,131 @@
+use crossterm::event::{Event as CxEvent, EventStream};
+use futures::{future::BoxFuture, FutureExt, Stream, StreamExt};
+
+use crate::widget::Event;
+
+/// A source of input [`Event`]s for an [`App`](crate::App).
+///
+/// The default is [`CrosstermInput`], which reads from the terminal. Other sources, e.g. keystrokes
+/// forwarded by an SSH server or scripted events in a test harness, can be provided via
+/// [`AppConfig::with_input`](crate::AppConfig::with_input).
+pub trait InputSource: Send {
+    /// Waits for the next event, `None` means there is no more input.
+    ///
+    /// The app stops reading after [`Event::Quit`] or `None`.
+    fn next_event(&mut self) -> BoxFuture<'_, Option<Event>>;
+}
+
+/// Any stream of events can be used as input, e.g. a channel fed by a test harness.
+impl<S: Stream<Item = Event> + Send + Unpin> InputSource for S {
+    fn next_event(&mut self) -> BoxFuture<'_, Option<Event>> {
+        self.next().boxed()
+    }
+}
+
+/// Reads the input events of the terminal via crossterm.
+///
+/// The events are awaited asynchronously via crossterm's `EventStream`, instead of polling
+/// the terminal in an interval, so input, resizes and quitting the app are handled as soon as
+/// they happen and nothing wakes up while the app is idle.
+pub struct CrosstermInput {
+    reader: EventStream,
+}
+
+impl CrosstermInput {
+    pub fn new() -> Self {
+        CrosstermInput {
+            reader: EventStream::new(),
+        }
+    }
+}
+
+impl Default for CrosstermInput {
+    fn default() -> Self {
+        CrosstermInput::new()
+    }
+}
+
+impl InputSource for CrosstermInput {
+    fn next_event(&mut self) -> BoxFuture<'_, Option<Event>> {
+        async move {
+            while let Some(event) = self.reader.next().await {
+                let event = match event {
+                    Ok(CxEvent::Key(key_event)) => match key_event.try_into() {
+                        Ok(key_event) => Event::Key(key_event),
+                        // Media and modifier keys aren't reported without enhanced keyboard reporting
+                        Err(_) => continue,
+                    },
+                    Ok(CxEvent::Mouse(mouse_event)) => Event::Mouse(mouse_event.into()),
+                    Ok(CxEvent::FocusGained) => Event::FocusGained,
+                    Ok(CxEvent::FocusLost) => Event::FocusLost,
+                    Ok(CxEvent::Paste(text)) => Event::Paste(text),
+                    Ok(CxEvent::Resize(width, height)) => Event::Resize { width, height },
+                    _ => continue, // TODO handle other kinds of events and errors
+                };
+                return Some(event);
+            }
+            None
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{text_input, App, AppConfig, Key, KeyEvent};
+
+    /// Plays back scripted events one at a time, so that each of them is handled in a frame of its own
+    struct ScriptedInput(std::vec::IntoIter<Event>);
+
+    impl InputSource for ScriptedInput {
+        fn next_event(&mut self) -> BoxFuture<'_, Option<Event>> {
+            async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                self.0.next()
+            }
+            .boxed()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn an_app_is_driven_by_a_custom_input_source() {
+        let key = |key| Event::Key(KeyEvent::from(key));
+        let input = ScriptedInput(
+            vec![
+                // focuses the input
+                key(Key::Tab),
+                key(Key::Char('a')),
+                Event::Paste("bc".to_string()),
+                Event::Resize {
+                    width: 40,
+                    height: 10,
+                },
+                Event::Quit,
+            ]
+            .into_iter(),
+        );
+        let config = AppConfig::new().with_input(Box::new(input));
+        // The text, every time the app logic runs
+        let texts = Arc::new(Mutex::new(Vec::new()));
+        let app_texts = texts.clone();
+        let app = App::new_with_config(config, String::new(), move |text: &mut String| {
+            app_texts.lock().unwrap().push(text.clone());
+            text_input(text.clone(), |text: &mut String, new: String| *text = new)
+        })
+        .await;
+        tokio::time::timeout(Duration::from_secs(10), app.run())
+            .await
+            .expect("the app didn't quit")
+            .unwrap();
+
+        let mut texts = texts.lock().unwrap();
+        texts.dedup();
+        assert_eq!(*texts, ["", "a", "abc"]);
+    }
+}
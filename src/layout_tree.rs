@@ -0,0 +1,189 @@
+use xilem_core::Id;
+
+use crate::{
+    geometry::{Point, Rect, Size},
+    view::{Cx, View},
+    widget::{
+        BoxConstraints, CxState, FocusState, LayoutCx, LifeCycle, LifeCycleCx, Pod, PodFlags,
+        WidgetState,
+    },
+    Theme, WidgetBounds,
+};
+
+/// The geometry of a widget tree after the layout pass, see [`compute_layout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutTree {
+    root: LayoutNode,
+}
+
+/// A widget within a [`LayoutTree`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutNode {
+    /// The id of the widget, as returned by [`Pod::id`] and [`App::hit_test`](crate::App::hit_test)
+    pub id: Id,
+    /// The area of the widget in window coordinates (terminal cells)
+    pub rect: Rect,
+    /// The children of the widget in paint order
+    pub children: Vec<LayoutNode>,
+}
+
+impl LayoutTree {
+    /// The root widget, which is the widget of the view passed to [`compute_layout`].
+    pub fn root(&self) -> &LayoutNode {
+        &self.root
+    }
+
+    /// The area of the widget with `id` in window coordinates.
+    pub fn rect(&self, id: Id) -> Option<Rect> {
+        self.iter().find(|node| node.id == id).map(|node| node.rect)
+    }
+
+    /// All widgets in depth-first (paint) order, starting with the root.
+    pub fn iter(&self) -> impl Iterator<Item = &LayoutNode> {
+        let mut stack = vec![&self.root];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+impl LayoutNode {
+    fn new(pod: &Pod, parent_origin: Point) -> Self {
+        let origin = parent_origin + pod.state.origin.to_vec2();
+        LayoutNode {
+            id: pod.id(),
+            rect: Rect::from_origin_size(origin, pod.state.size),
+            children: pod
+                .widget
+                .children()
+                .iter()
+                .map(|child| LayoutNode::new(child, origin))
+                .collect(),
+        }
+    }
+}
+
+/// Builds the view returned by `app_logic` and lays it out within `size`, without a terminal,
+/// to inspect the resulting geometry, e.g. in tests of layout logic.
+///
+/// Like the first render pass of an [`App`](crate::App) the widgets are animated once before the
+/// layout pass (animations are at their start), but nothing is painted and no events are dispatched.
+/// The result is deterministic for the same view and size.
+///
+/// # Examples
+/// ```
+/// use trui::{geometry::Size, *};
+///
+/// let size = Size::new(80.0, 1.0);
+/// let layout = compute_layout(&mut (), |_| weighted_h_stack(("a".weight(4.0), "b")), size);
+/// let widths: Vec<f64> = layout.root().children.iter().map(|c| c.rect.width()).collect();
+/// assert_eq!(widths, vec![64.0, 16.0]);
+/// ```
+pub fn compute_layout<T, V: View<T>>(
+    data: &mut T,
+    app_logic: impl FnOnce(&mut T) -> V,
+    size: Size,
+) -> LayoutTree {
+    // Views may spawn futures while they are built, which just never resolve here
+    let runtime = match tokio::runtime::Handle::try_current() {
+        Ok(_) => None,
+        Err(_) => Some(
+            tokio::runtime::Builder::new_current_thread()
+                .build()
+                .unwrap(),
+        ),
+    };
+    let handle = match &runtime {
+        Some(runtime) => runtime.handle().clone(),
+        None => tokio::runtime::Handle::current(),
+    };
+    let (wake_tx, _wake_rx) = tokio::sync::mpsc::channel(1);
+    let mut cx = Cx::new(wake_tx, handle, Theme::default(), WidgetBounds::default());
+
+    let view = app_logic(data);
+    let (_, _state, element) = view.build(&mut cx);
+    let mut root_pod = Pod::new(element);
+
+    let mut root_state = WidgetState::new();
+    let mut events = Vec::new();
+    let mut focus = FocusState::default();
+    let cx_state = &mut CxState::new(&mut events, &mut focus, Default::default());
+
+    if root_pod.state.flags.contains(PodFlags::REQUEST_ANIMATION) {
+        root_pod.lifecycle(
+            &mut LifeCycleCx {
+                cx_state,
+                widget_state: &mut root_state,
+            },
+            &LifeCycle::Animate,
+        );
+    }
+    let mut layout_cx = LayoutCx {
+        widget_state: &mut root_state,
+        cx_state,
+    };
+    root_pod.layout(&mut layout_cx, &BoxConstraints::tight(size).loosen());
+    root_pod.set_origin(&mut layout_cx, Point::ORIGIN);
+
+    LayoutTree {
+        root: LayoutNode::new(&root_pod, Point::ORIGIN),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{h_stack, weighted_h_stack, ViewExt};
+
+    #[test]
+    fn weighted_children_are_laid_out_side_by_side() {
+        let size = Size::new(80.0, 1.0);
+        let layout = compute_layout(
+            &mut (),
+            |_| weighted_h_stack(("left".weight(4.0), h_stack(("a", "b")))),
+            size,
+        );
+        let root = layout.root();
+        assert_eq!(root.rect, Rect::from_origin_size(Point::ORIGIN, size));
+        assert_eq!(root.children[0].rect, Rect::new(0.0, 0.0, 64.0, 1.0));
+        assert_eq!(root.children[1].rect, Rect::new(64.0, 0.0, 80.0, 1.0));
+
+        // nested children are in window coordinates
+        let b = &root.children[1].children[1];
+        assert_eq!(b.rect, Rect::new(65.0, 0.0, 66.0, 1.0));
+        assert_eq!(layout.rect(b.id), Some(b.rect));
+        assert_eq!(layout.iter().count(), 6);
+    }
+
+    #[test]
+    fn nested_fill_max_size_is_relative_to_the_parent() {
+        let layout = compute_layout(
+            &mut (),
+            |_| "half of half".fill_max_width(0.5).fill_max_width(0.5),
+            Size::new(80.0, 1.0),
+        );
+        let text = &layout.root().children[0].children[0];
+        assert_eq!(text.rect.width(), 20.0);
+    }
+
+    #[test]
+    fn border_is_drawn_inside_the_margin_and_around_the_padding() {
+        let layout = compute_layout(
+            &mut (),
+            |_| "text".margin(1).border(()).margin(2),
+            Size::new(80.0, 10.0),
+        );
+        let margin = layout.root();
+        let border = &margin.children[0];
+        let text = &border.children[0].children[0];
+        assert_eq!(margin.rect, Rect::new(0.0, 0.0, 12.0, 9.0));
+        assert_eq!(border.rect, Rect::new(2.0, 2.0, 10.0, 7.0));
+        assert_eq!(text.rect, Rect::new(4.0, 4.0, 8.0, 5.0));
+
+        // the margin doesn't exceed the available space
+        let layout = compute_layout(&mut (), |_| "text".margin(2), Size::new(3.0, 3.0));
+        assert_eq!(layout.root().rect.size(), Size::new(3.0, 3.0));
+    }
+}
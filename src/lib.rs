@@ -1,15 +1,28 @@
 mod app;
 mod app_config;
+mod backend;
+pub mod color;
 pub mod geometry;
+mod input;
+mod layout_tree;
+pub mod prelude;
+mod theme;
 mod view;
-mod widget;
+pub mod widget;
 
 // wildcards at least temporarily for convenience...
-pub use app::App;
-pub use app_config::AppConfig;
+pub use app::{App, AppLogicPanic};
+pub use app_config::{
+    AppConfig, Bell, BellKind, FrameStats, FullRedraw, RenderTiming, RenderTimings, TerminalSize,
+    WidgetBounds,
+};
+pub use backend::{DefaultBackend, TerminalBackend};
+pub use input::{CrosstermInput, InputSource};
+pub use layout_tree::{compute_layout, LayoutNode, LayoutTree};
 pub use ratatui::style::{Color, Modifier, Style};
+pub use theme::*;
 pub use view::*;
-pub use widget::CatchMouseButton;
+pub use widget::{CatchMouseButton, Key, KeyEvent, KeyKind, Modifiers, MouseButton, MouseKind};
 
 #[cfg(test)]
 mod test_helper;
@@ -0,0 +1,59 @@
+//! The types and traits which are commonly needed to build an app, `use trui::prelude::*;` gets started.
+//!
+//! Lower level types, which are needed to implement custom views and widgets (e.g. [`Cx`](crate::Cx)
+//! or [`Widget`](crate::widget::Widget)), are not part of the prelude, they're imported from the
+//! crate root instead.
+//!
+//! # Examples
+//! ```
+//! use trui::prelude::*;
+//!
+//! # App::new(0, |count: &mut i32| {
+//! v_stack((
+//!     format!("count: {count}").fg(Color::Yellow),
+//!     "increment"
+//!         .border(BorderKind::Rounded)
+//!         .on_click(|count: &mut i32| *count += 1),
+//! ))
+//! # });
+//! ```
+
+// The app
+pub use crate::{App, AppConfig, Theme};
+
+// Styles
+pub use crate::{rgb, BorderKind, Borders, Color, Modifier, Style, StyleExt, Styleable};
+
+// Core view traits and views
+pub use crate::{memoize, static_view, AnyView, IntoBoxedView, View, ViewExt, ViewSequence};
+
+// Text
+pub use crate::{
+    ansi_text, ControlChars, Text, ToMarqueeText, ToTruncatedText, ToWrappedText, TruncateMode,
+};
+
+// Layout
+pub use crate::{
+    h_flow, h_stack, layers, responsive, scaffold, v_flow, v_stack, virtual_list, weighted,
+    weighted_h_stack, weighted_v_stack, Alignment, ScrollAxes, ScrollController,
+};
+
+// Input and data
+pub use crate::{
+    bar_chart, canvas, canvas_with, image, sparkline, table, text_area, text_input, toggle, Bar,
+    Column, ColumnWidth, Validation,
+};
+
+// Dialogs, menus and notifications
+pub use crate::{
+    command_palette, confirm, Command, KeySequence, Menu, MenuEntry, MenuItem, Notifications,
+};
+
+// Event handlers, async and local state
+pub use crate::{
+    async_handler, callback, callback_result, defer, defer_view, memoized_future, stream,
+    use_state, EventHandler, FutureState, StreamMessage, WithState,
+};
+
+// Animations
+pub use crate::{lerp, low_pass, transition, Animatable, Easing, Tweenable};
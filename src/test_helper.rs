@@ -67,8 +67,8 @@ pub async fn render_view<T: Send + 'static>(
 
 /// This widget provides access to the terminal output of its children
 ///
-/// After its children were painted it calls the flush() and clones the
-/// terminal's buffer and sends it using the passed [Sender<Buffer>].
+/// After its children were painted it clones the current buffer of the terminal
+/// and sends it using the passed [Sender<Buffer>].
 ///
 /// This is handy for snapshot tests.
 pub struct DebugView<V, T, A> {
@@ -145,9 +145,7 @@ impl Widget for DebugWidget {
     fn paint(&mut self, cx: &mut crate::widget::PaintCx) {
         self.content.paint(cx);
 
-        cx.terminal.flush().unwrap();
-
-        let buffer = cx.terminal.backend().buffer().to_owned();
+        let buffer = cx.buffer_mut().clone();
         let chan_tx = self.debug_chan_tx.clone();
         tokio::task::spawn_local(async move {
             chan_tx.send(buffer).await.unwrap();
@@ -165,6 +163,10 @@ impl Widget for DebugWidget {
     fn lifecycle(&mut self, cx: &mut crate::widget::LifeCycleCx, event: &crate::widget::LifeCycle) {
         self.content.lifecycle(cx, event);
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
 }
 
 /// Utility for visual snapshot test debugging
@@ -0,0 +1,428 @@
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::color;
+
+/// A color from its hex RGB value, e.g. `rgb(0xFF5370)`.
+pub const fn rgb(hex: u32) -> Color {
+    Color::Rgb((hex >> 16) as u8, (hex >> 8) as u8, hex as u8)
+}
+
+/// A color from a hex string like `"#FF5370"` or a color name, see [`color::from_hex`].
+///
+/// # Panics
+///
+/// If `hex` isn't a valid color, use [`color::from_hex`] to handle invalid colors
+/// (e.g. of a config file) gracefully.
+pub fn hex(hex: &str) -> Color {
+    color::from_hex(hex).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Builder-style helpers to construct a [`Style`], e.g. in theming code.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// let selected = Style::colors(Color::White, Color::Blue);
+/// let error = Style::default().fg_hex("#FF5370").bg(rgb(0x263238));
+/// assert_eq!(error, Style::default().fg(hex("FF5370")).bg(Color::Rgb(0x26, 0x32, 0x38)));
+/// ```
+pub trait StyleExt: Sized {
+    /// A style with the foreground color `fg` and the background color `bg`.
+    fn colors(fg: Color, bg: Color) -> Self;
+
+    /// Sets the foreground color from a hex string, see [`hex`].
+    fn fg_hex(self, color: &str) -> Self;
+
+    /// Sets the background color from a hex string, see [`hex`].
+    fn bg_hex(self, color: &str) -> Self;
+}
+
+impl StyleExt for Style {
+    fn colors(fg: Color, bg: Color) -> Self {
+        Style::new().fg(fg).bg(bg)
+    }
+
+    fn fg_hex(self, color: &str) -> Self {
+        self.fg(hex(color))
+    }
+
+    fn bg_hex(self, color: &str) -> Self {
+        self.bg(hex(color))
+    }
+}
+
+/// The visual theme of an app, which is used by views that don't get an explicit style.
+///
+/// It can be set via [`AppConfig::with_theme`](crate::AppConfig::with_theme) and is accessible
+/// for views via [`Cx::theme`](crate::Cx::theme).
+///
+/// Prefer the semantic colors over the fields of the palette [`Colors`], they map to:
+///
+/// | Semantic color                                | [`Colors`] field       |
+/// |-----------------------------------------------|------------------------|
+/// | [`surface`](Theme::surface)                   | `background`           |
+/// | [`on_surface`](Theme::on_surface)             | `foreground`           |
+/// | [`surface_variant`](Theme::surface_variant)   | `second_background`    |
+/// | [`primary`](Theme::primary)                   | `accent`               |
+/// | [`on_primary`](Theme::on_primary)             | `selection_foreground` |
+/// | [`selection`](Theme::selection)               | `selection_background` |
+/// | [`error`](Theme::error)                       | `error`                |
+/// | [`disabled`](Theme::disabled)                 | `disabled`             |
+/// | [`outline`](Theme::outline)                   | `border`               |
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub colors: Colors,
+    pub text: TextStyles,
+    pub border: BorderStyles,
+    pub toggle: ToggleStyles,
+}
+
+impl Theme {
+    /// Derives the styles of all widgets from the palette `colors`.
+    pub fn with_colors(colors: Colors) -> Self {
+        Theme {
+            text: TextStyles::with_colors(&colors),
+            border: BorderStyles::with_colors(&colors),
+            toggle: ToggleStyles::with_colors(&colors),
+            colors,
+        }
+    }
+
+    /// The background of the app, [`Colors::background`].
+    pub fn surface(&self) -> Color {
+        self.colors.background
+    }
+
+    /// Text and icons on the [`surface`](Theme::surface), [`Colors::foreground`].
+    pub fn on_surface(&self) -> Color {
+        self.colors.foreground
+    }
+
+    /// The background of elements which stand out from the surface, e.g. inputs, menus or dialogs,
+    /// [`Colors::second_background`].
+    pub fn surface_variant(&self) -> Color {
+        self.colors.second_background
+    }
+
+    /// The color of prominent elements, e.g. the focus or highlighted text, [`Colors::accent`].
+    pub fn primary(&self) -> Color {
+        self.colors.accent
+    }
+
+    /// Text and icons on the [`primary`](Theme::primary) color, [`Colors::selection_foreground`].
+    pub fn on_primary(&self) -> Color {
+        self.colors.selection_foreground
+    }
+
+    /// The background of selected elements, [`Colors::selection_background`].
+    pub fn selection(&self) -> Color {
+        self.colors.selection_background
+    }
+
+    /// Errors, [`Colors::error`].
+    pub fn error(&self) -> Color {
+        self.colors.error
+    }
+
+    /// Disabled elements, [`Colors::disabled`].
+    pub fn disabled(&self) -> Color {
+        self.colors.disabled
+    }
+
+    /// Borders and dividers, [`Colors::border`].
+    pub fn outline(&self) -> Color {
+        self.colors.border
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::with_colors(Colors::material_oceanic())
+    }
+}
+
+/// The color palette of a [`Theme`], the names follow the ones of the Material Theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Colors {
+    pub background: Color,
+    pub foreground: Color,
+    pub text: Color,
+    pub selection_background: Color,
+    pub selection_foreground: Color,
+    pub buttons: Color,
+    pub second_background: Color,
+    pub disabled: Color,
+    pub contrast: Color,
+    pub active: Color,
+    pub border: Color,
+    pub highlight: Color,
+    pub tree: Color,
+    pub notifications: Color,
+    pub accent: Color,
+    pub excluded_files: Color,
+    pub green: Color,
+    pub yellow: Color,
+    pub blue: Color,
+    pub red: Color,
+    pub purple: Color,
+    pub orange: Color,
+    pub cyan: Color,
+    pub gray: Color,
+    pub white_black: Color,
+    pub error: Color,
+    pub comments: Color,
+    pub variables: Color,
+    pub links: Color,
+    pub functions: Color,
+    pub keywords: Color,
+    pub tags: Color,
+    pub strings: Color,
+    pub operators: Color,
+    pub attributes: Color,
+    pub numbers: Color,
+    pub parameters: Color,
+}
+
+/// An error of [`Colors::from_hex_map`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColorsError {
+    /// There's no color with this name in [`Colors`]
+    UnknownField(String),
+    /// The value of `field` isn't a hex color
+    InvalidColor { field: String, value: String },
+}
+
+impl std::fmt::Display for ColorsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorsError::UnknownField(field) => write!(f, "unknown theme color `{field}`"),
+            ColorsError::InvalidColor { field, value } => write!(
+                f,
+                "invalid value {value:?} of theme color `{field}`, expected a hex color like \"#FF5370\""
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ColorsError {}
+
+impl Colors {
+    /// A palette from hex colors (e.g. `"#FF5370"`) by field name, e.g. of a theme which is loaded
+    /// from a file. Colors which aren't in `colors` are the ones of [`Colors::material_oceanic`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// let colors = Colors::from_hex_map([("accent", "#FF9800"), ("background", "#212121")]).unwrap();
+    /// assert_eq!(colors.accent, rgb(0xFF9800));
+    ///
+    /// let error = Colors::from_hex_map([("accent", "#FF98")]).unwrap_err();
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "invalid value \"#FF98\" of theme color `accent`, expected a hex color like \"#FF5370\""
+    /// );
+    /// ```
+    pub fn from_hex_map<'a>(
+        colors: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Result<Self, ColorsError> {
+        let mut palette = Colors::material_oceanic();
+        for (field, value) in colors {
+            let color = palette
+                .field_mut(field)
+                .ok_or_else(|| ColorsError::UnknownField(field.to_string()))?;
+            *color = color::from_hex(value).map_err(|_| ColorsError::InvalidColor {
+                field: field.to_string(),
+                value: value.to_string(),
+            })?;
+        }
+        Ok(palette)
+    }
+
+    fn field_mut(&mut self, field: &str) -> Option<&mut Color> {
+        Some(match field {
+            "background" => &mut self.background,
+            "foreground" => &mut self.foreground,
+            "text" => &mut self.text,
+            "selection_background" => &mut self.selection_background,
+            "selection_foreground" => &mut self.selection_foreground,
+            "buttons" => &mut self.buttons,
+            "second_background" => &mut self.second_background,
+            "disabled" => &mut self.disabled,
+            "contrast" => &mut self.contrast,
+            "active" => &mut self.active,
+            "border" => &mut self.border,
+            "highlight" => &mut self.highlight,
+            "tree" => &mut self.tree,
+            "notifications" => &mut self.notifications,
+            "accent" => &mut self.accent,
+            "excluded_files" => &mut self.excluded_files,
+            "green" => &mut self.green,
+            "yellow" => &mut self.yellow,
+            "blue" => &mut self.blue,
+            "red" => &mut self.red,
+            "purple" => &mut self.purple,
+            "orange" => &mut self.orange,
+            "cyan" => &mut self.cyan,
+            "gray" => &mut self.gray,
+            "white_black" => &mut self.white_black,
+            "error" => &mut self.error,
+            "comments" => &mut self.comments,
+            "variables" => &mut self.variables,
+            "links" => &mut self.links,
+            "functions" => &mut self.functions,
+            "keywords" => &mut self.keywords,
+            "tags" => &mut self.tags,
+            "strings" => &mut self.strings,
+            "operators" => &mut self.operators,
+            "attributes" => &mut self.attributes,
+            "numbers" => &mut self.numbers,
+            "parameters" => &mut self.parameters,
+            _ => return None,
+        })
+    }
+
+    /// The palette of the "Material Oceanic" theme.
+    pub fn material_oceanic() -> Self {
+        Colors {
+            background: rgb(0x263238),
+            foreground: rgb(0xB0BEC5),
+            text: rgb(0x607D8B),
+            selection_background: rgb(0x546E7A),
+            selection_foreground: rgb(0xFFFFFF),
+            buttons: rgb(0x2E3C43),
+            second_background: rgb(0x32424A),
+            disabled: rgb(0x415967),
+            contrast: rgb(0x1E272C),
+            active: rgb(0x314549),
+            border: rgb(0x2A373E),
+            highlight: rgb(0x425B67),
+            tree: rgb(0x546E7A),
+            notifications: rgb(0x1E272C),
+            accent: rgb(0x009688),
+            excluded_files: rgb(0x2E3C43),
+            green: rgb(0xC3E88D),
+            yellow: rgb(0xFFCB6B),
+            blue: rgb(0x82AAFF),
+            red: rgb(0xF07178),
+            purple: rgb(0xC792EA),
+            orange: rgb(0xF78C6C),
+            cyan: rgb(0x89DDFF),
+            gray: rgb(0x546E7A),
+            white_black: rgb(0xEEFFFF),
+            error: rgb(0xFF5370),
+            comments: rgb(0x546E7A),
+            variables: rgb(0xEEFFFF),
+            links: rgb(0x80CBC4),
+            functions: rgb(0x82AAFF),
+            keywords: rgb(0xC792EA),
+            tags: rgb(0xF07178),
+            strings: rgb(0xC3E88D),
+            operators: rgb(0x89DDFF),
+            attributes: rgb(0xFFCB6B),
+            numbers: rgb(0xF78C6C),
+            parameters: rgb(0xF78C6C),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextStyles {
+    pub default: Style,
+    pub hover: Style,
+    pub selected: Style,
+    pub disabled: Style,
+    /// Highlighted ranges of text, e.g. search results, see [`Text::highlight`](crate::Text::highlight)
+    pub highlight: Style,
+}
+
+impl TextStyles {
+    pub fn with_colors(colors: &Colors) -> Self {
+        let default = Style::default().fg(colors.foreground);
+        TextStyles {
+            default,
+            hover: Style::default().fg(colors.white_black),
+            selected: Style::colors(colors.selection_foreground, colors.selection_background),
+            disabled: Style::default().fg(colors.disabled),
+            highlight: Style::default().bg(colors.highlight),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BorderStyles {
+    pub default: Style,
+    pub hover: Style,
+    pub focus: Style,
+}
+
+impl BorderStyles {
+    pub fn with_colors(colors: &Colors) -> Self {
+        BorderStyles {
+            default: Style::default().fg(colors.border),
+            hover: Style::default().fg(colors.highlight),
+            focus: Style::default().fg(colors.accent),
+        }
+    }
+}
+
+/// The styles of a [`toggle`](crate::toggle), `hover` is patched on top of `on`/`off` while the pointer is over it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToggleStyles {
+    pub on: Style,
+    pub off: Style,
+    pub hover: Style,
+}
+
+impl ToggleStyles {
+    pub fn with_colors(colors: &Colors) -> Self {
+        ToggleStyles {
+            on: Style::colors(colors.selection_foreground, colors.accent),
+            off: Style::colors(colors.foreground, colors.active),
+            hover: Style::default()
+                .fg(colors.white_black)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_colors() {
+        assert_eq!(rgb(0x263238), Color::Rgb(0x26, 0x32, 0x38));
+        assert_eq!(hex("#263238"), rgb(0x263238));
+        assert_eq!(hex("ff5370"), Color::Rgb(0xFF, 0x53, 0x70));
+        assert_eq!(
+            Style::default().fg_hex("#FFFFFF"),
+            Style::default().fg(rgb(0xFFFFFF))
+        );
+    }
+
+    #[test]
+    fn invalid_hex_map_colors_are_errors() {
+        let error = Colors::from_hex_map([("background", "#000000"), ("accent", "#00968")]);
+        assert_eq!(
+            error,
+            Err(ColorsError::InvalidColor {
+                field: "accent".to_string(),
+                value: "#00968".to_string()
+            })
+        );
+        assert!(error.unwrap_err().to_string().contains("`accent`"));
+        assert_eq!(
+            Colors::from_hex_map([("acent", "#009688")]),
+            Err(ColorsError::UnknownField("acent".to_string()))
+        );
+        let colors = Colors::from_hex_map([("numbers", "#000000")]).unwrap();
+        assert_eq!(colors.numbers, Color::Rgb(0, 0, 0));
+        assert_eq!(colors.accent, Colors::material_oceanic().accent);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid color")]
+    fn panics_on_invalid_hex_colors() {
+        hex("#+12345");
+    }
+}
@@ -1,38 +1,146 @@
 mod animatables;
+mod background_gradient;
+mod bar_chart;
+mod blink;
 mod border;
+mod canvas;
+mod command_palette;
 mod common;
+mod confirm;
 mod core;
 mod defer;
+mod enabled;
 mod events;
 mod fill_max_size;
+mod flow;
+mod image;
+mod key_sequence;
+mod layers;
 mod linear_layout;
 mod margin;
+mod measure;
+mod memoized_future;
+mod menu_bar;
+mod modal;
+mod notifications;
+mod on_mount;
+mod raw_widget;
+mod responsive;
+mod scaffold;
+mod scroll;
+mod sparkline;
+mod style_override;
+mod table;
 mod text;
+mod text_area;
+mod text_input;
+mod toggle;
+mod tooltip;
 mod use_state;
+mod virtual_list;
 mod weighted_linear_layout;
 
-use std::marker::PhantomData;
+use std::{borrow::Cow, future::Future, marker::PhantomData, time::Duration};
 
+use crate::{
+    color::{Gradient, GradientDirection},
+    geometry::Point,
+    CatchMouseButton, MouseButton, Theme,
+};
 use ratatui::style::{Color, Style};
-pub use xilem_core::{Id, IdPath, VecSplice};
+pub use xilem_core::{Id, IdPath, MessageResult, VecSplice};
 
-// TODO do this via a prelude instead (and possibly not wildcard export)
+// The commonly used items are also exported via `crate::prelude`
 pub use self::core::*;
 pub use animatables::*;
+pub use background_gradient::*;
+pub use bar_chart::*;
+pub use blink::*;
 pub use border::*;
+pub use canvas::*;
+pub use command_palette::*;
 pub use common::*;
+pub use confirm::*;
 pub use defer::*;
+pub use enabled::*;
 pub use events::*;
 pub use fill_max_size::*;
+pub use flow::*;
+pub use image::*;
+pub use key_sequence::*;
+pub use layers::*;
 pub use linear_layout::*;
 pub use margin::*;
+pub use measure::*;
+pub use memoized_future::*;
+pub use menu_bar::*;
+pub use modal::*;
+pub use notifications::*;
+pub use on_mount::*;
+pub use raw_widget::*;
+pub use responsive::*;
+pub use scaffold::*;
+pub use scroll::*;
+pub use sparkline::*;
+pub use style_override::*;
+pub use table::*;
 pub use text::*;
+pub use text_area::*;
+pub use text_input::*;
+pub use toggle::*;
+pub use tooltip::*;
 pub use use_state::*;
+pub use virtual_list::*;
 pub use weighted_linear_layout::*;
 
 // TODO this could maybe also be added directly to `View` (possibly copying the macro expanded version of it)
 /// A trait that makes it possible to use core views such as [`Adapt`] in the continuation/builder style.
 pub trait ViewExt<T, A>: View<T, A> + Sized {
+    /// Embeds this view, which works with the state `T` and produces actions `A`, into a parent with
+    /// the state `ParentT` and the actions `ParentA`.
+    ///
+    /// `f` is called with every message for this view, it passes the child state to the thunk,
+    /// which handles the message and returns the result of the child, e.g. the action `A` returned by
+    /// an event handler as `MessageResult::Action`. `f` translates it into the result of the parent,
+    /// so reusable components can report what happened with their own action type, and the parent
+    /// decides what it means.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// enum CounterAction {
+    ///     Increment,
+    ///     Reset,
+    /// }
+    ///
+    /// /// A reusable component, which doesn't know anything about the app state
+    /// fn counter(count: i32) -> impl View<i32, CounterAction> {
+    ///     h_stack((
+    ///         format!("{count} "),
+    ///         "[+]".on_click(|_: &mut i32| CounterAction::Increment),
+    ///         "[Reset]".on_click(|_: &mut i32| CounterAction::Reset),
+    ///     ))
+    /// }
+    ///
+    /// struct AppState {
+    ///     count: i32,
+    ///     resets: usize,
+    /// }
+    ///
+    /// # App::new(AppState { count: 0, resets: 0 }, move |state: &mut AppState| {
+    /// counter(state.count).adapt(|state: &mut AppState, thunk| {
+    ///     match thunk.call(&mut state.count) {
+    ///         MessageResult::Action(CounterAction::Increment) => state.count += 1,
+    ///         MessageResult::Action(CounterAction::Reset) => {
+    ///             state.count = 0;
+    ///             state.resets += 1;
+    ///         }
+    ///         other => return other.map(|_| ()),
+    ///     }
+    ///     MessageResult::Action(())
+    /// })
+    /// # });
+    /// ```
     fn adapt<ParentT, ParentA, F>(self, f: F) -> Adapt<ParentT, ParentA, T, A, Self, F>
     where
         F: Fn(&mut ParentT, AdaptThunk<T, A, Self>) -> xilem_core::MessageResult<ParentA>
@@ -49,6 +157,20 @@ pub trait ViewExt<T, A>: View<T, A> + Sized {
         AdaptState::new(f, self)
     }
 
+    /// Adds `amount` empty cells around this view (or only at the sides of the [`Position`]).
+    ///
+    /// Wrappers like the margin and the [`border`](ViewExt::border) apply from the inside out, so the
+    /// order of the calls decides whether the margin is outside of the border or inside of it
+    /// (i.e. a padding of the content).
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// // the text is inset by the padding and the border, which is drawn inside of the margin
+    /// "Padded".margin(1).border(BorderKind::Rounded).margin(2)
+    /// # });
+    /// ```
     fn margin<S: Into<MarginStyle>>(self, style: S) -> Margin<Self, T, A> {
         let style = style.into();
         Margin {
@@ -59,6 +181,9 @@ pub trait ViewExt<T, A>: View<T, A> + Sized {
         }
     }
 
+    /// Draws a border around this view, every drawn side takes a single cell and the view is inset
+    /// by it. See [`margin`](ViewExt::margin) for how both compose.
+    ///
     /// # Examples
     /// ```
     /// # use trui::*;
@@ -77,10 +202,44 @@ pub trait ViewExt<T, A>: View<T, A> + Sized {
             borders: style.borders,
             kind: style.kind,
             style: style.style,
+            side_styles: style.side_styles,
+            focus_indicator: false,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Draws a border around this view, which uses the hover and focus styles of the
+    /// [`Theme`](crate::Theme) while the pointer is over it or while a widget inside it is focused.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(String::new(), move |name| {
+    /// text_input(name.clone(), |name: &mut String, new_name: String| *name = new_name)
+    ///     .focus_border()
+    /// # });
+    /// ```
+    fn focus_border(self) -> Border<Self, T, A> {
+        Border {
+            content: self,
+            borders: Borders::ALL,
+            kind: BorderKind::default(),
+            style: Style::default(),
+            side_styles: SideStyles::default(),
+            focus_indicator: true,
             phantom: PhantomData,
         }
     }
 
+    /// Sizes this view to `percent` (`0.0..=1.0`) of the maximum width and height its parent allows,
+    /// e.g. a view which fills half the width within another one which fills half the width,
+    /// is a quarter of the width of the terminal.
+    ///
+    /// The percentage is of the space of the parent, even if the parent requires a larger size
+    /// (e.g. a weighted layout), then the view is laid out at the top left of that space.
+    /// It has no effect in directions in which the space is unbounded, e.g. in the content of a
+    /// [`scroll`](ViewExt::scroll) view.
+    ///
     /// # Examples
     /// ```
     /// # use trui::*;
@@ -103,6 +262,7 @@ pub trait ViewExt<T, A>: View<T, A> + Sized {
         }
     }
 
+    /// Like [`fill_max_size`](ViewExt::fill_max_size), but only for the width.
     fn fill_max_width<P: Animatable<f64>>(self, percent: P) -> FillMaxSize<Self, P, T, A> {
         FillMaxSize {
             content: self,
@@ -112,6 +272,7 @@ pub trait ViewExt<T, A>: View<T, A> + Sized {
         }
     }
 
+    /// Like [`fill_max_size`](ViewExt::fill_max_size), but only for the height.
     fn fill_max_height<P: Animatable<f64>>(self, percent: P) -> FillMaxSize<Self, P, T, A> {
         FillMaxSize {
             content: self,
@@ -121,21 +282,429 @@ pub trait ViewExt<T, A>: View<T, A> + Sized {
         }
     }
 
+    /// Calls `event_handler` when this view is clicked with the left mouse button, or when it's
+    /// [focusable](OnClick::focusable), focused (via `Tab`) and `Enter` or `Space` is pressed.
+    ///
+    /// See [`on_right_click`](ViewExt::on_right_click), [`on_middle_click`](ViewExt::on_middle_click)
+    /// and [`on_button_click`](ViewExt::on_button_click) for the other mouse buttons.
     fn on_click<EH: EventHandler<T, A>>(self, event_handler: EH) -> OnClick<Self, EH> {
         OnClick {
             view: self,
+            buttons: CatchMouseButton::LEFT,
+            stop_propagation: false,
+            focusable: false,
+            event_handler,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Like [`on_click`](ViewExt::on_click), but `event_handler` gets the position of the click
+    /// within this view, with `(0, 0)` at its top-left corner, e.g. to place something on a canvas.
+    ///
+    /// The position is clamped to the bounds of the view, an activation via the keyboard is
+    /// reported as a click in its middle.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::{*, geometry::Point};
+    /// # App::new(Vec::new(), move |marks: &mut Vec<Point>| {
+    /// canvas_with(|width, height| Grid::new(width, height))
+    ///     .fill_max_size(1.0)
+    ///     .on_click_at(|marks: &mut Vec<Point>, position: Point| marks.push(position))
+    /// # });
+    /// ```
+    fn on_click_at<EH: EventHandler<T, A, Point>>(
+        self,
+        event_handler: EH,
+    ) -> OnClick<Self, EH, Point> {
+        OnClick {
+            view: self,
+            buttons: CatchMouseButton::LEFT,
+            stop_propagation: false,
+            focusable: false,
+            event_handler,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Calls `event_handler` when this view is clicked with the right mouse button,
+    /// e.g. to open a context menu.
+    fn on_right_click<EH: EventHandler<T, A>>(self, event_handler: EH) -> OnClick<Self, EH> {
+        self.on_click(event_handler)
+            .buttons(CatchMouseButton::RIGHT)
+    }
+
+    /// Calls `event_handler` when this view is clicked with the middle mouse button.
+    fn on_middle_click<EH: EventHandler<T, A>>(self, event_handler: EH) -> OnClick<Self, EH> {
+        self.on_click(event_handler)
+            .buttons(CatchMouseButton::MIDDLE)
+    }
+
+    /// Like [`on_click`](ViewExt::on_click), but clicks with any mouse button call
+    /// `event_handler`, which gets the clicked button, to bind different actions to them.
+    ///
+    /// An activation via the keyboard is reported as a left click.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(0, move |count: &mut i32| {
+    /// format!("Count: {count}").on_button_click(|count: &mut i32, button| match button {
+    ///     MouseButton::Left => *count += 1,
+    ///     MouseButton::Right => *count -= 1,
+    ///     MouseButton::Middle => *count = 0,
+    /// })
+    /// # });
+    /// ```
+    fn on_button_click<EH: EventHandler<T, A, MouseButton>>(
+        self,
+        event_handler: EH,
+    ) -> OnClick<Self, EH, MouseButton> {
+        OnClick {
+            view: self,
+            buttons: CatchMouseButton::all(),
+            stop_propagation: false,
+            focusable: false,
             event_handler,
+            phantom: PhantomData,
         }
     }
 
+    /// Runs the future returned by `future_fn` when the view is clicked, see [`async_handler`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// async fn fetch_weather() -> String {
+    ///     "Sunny".to_string()
+    /// }
+    ///
+    /// # App::new(None, move |weather: &mut Option<FutureState<String>>| {
+    /// v_stack((
+    ///     "[Refresh]".on_click_async(
+    ///         |_: &mut Option<FutureState<String>>| fetch_weather(),
+    ///         |weather: &mut Option<FutureState<String>>, state| *weather = Some(state),
+    ///     ),
+    ///     match weather {
+    ///         None => "Click refresh".to_string(),
+    ///         Some(FutureState::Pending) => "Loading...".to_string(),
+    ///         Some(FutureState::Ready(weather)) => weather.clone(),
+    ///         Some(FutureState::Error(err)) => format!("Failed: {err}"),
+    ///     },
+    /// ))
+    /// # });
+    /// ```
+    fn on_click_async<FO, F, FF, SF>(
+        self,
+        future_fn: FF,
+        state_fn: SF,
+    ) -> OnClick<Self, AsyncEventHandler<T, A, FO, F, FF, SF>>
+    where
+        FO: Send + 'static,
+        F: Future<Output = FO> + Send + 'static,
+        FF: Fn(&mut T) -> F + Send + Sync,
+        SF: Fn(&mut T, FutureState<FO>) + Send + Sync,
+    {
+        self.on_click(async_handler(future_fn, state_fn))
+    }
+
+    /// Runs the future returned by `future_fn` once, when this view is added to the tree,
+    /// and calls `state_fn` with its result, e.g. to load the initial data of the app.
+    ///
+    /// This view is rendered right away, so it can show a placeholder until the data is stored in the
+    /// app state. The future is aborted when the view is removed from the tree before it's finished.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// async fn load_config() -> String {
+    ///     "dark".to_string()
+    /// }
+    ///
+    /// # App::new(None, move |theme: &mut Option<String>| {
+    /// match theme {
+    ///     None => "Loading...".to_string(),
+    ///     Some(theme) => format!("Theme: {theme}"),
+    /// }
+    /// .on_mount_async(load_config, |theme: &mut Option<String>, state| {
+    ///     if let FutureState::Ready(config) = state {
+    ///         *theme = Some(config);
+    ///     }
+    /// })
+    /// # });
+    /// ```
+    fn on_mount_async<FO, F, FF, SF>(
+        self,
+        future_fn: FF,
+        state_fn: SF,
+    ) -> OnMountAsync<Self, T, A, FF, SF>
+    where
+        FO: Send + 'static,
+        F: Future<Output = FO> + Send + 'static,
+        FF: Fn() -> F + Send + Sync,
+        SF: Fn(&mut T, FutureState<FO>) + Send + Sync,
+    {
+        OnMountAsync::new(self, future_fn, state_fn)
+    }
+
     fn weight<W: Animatable<f64>>(self, weight: W) -> WeightedLayoutElement<Self, W, T, A> {
         WeightedLayoutElement {
             content: self,
             weight,
+            reserve_min_size: false,
             phantom: PhantomData,
         }
     }
 
+    /// Shows the toasts of `notifications` stacked in the bottom right corner on top of this view.
+    ///
+    /// Toasts disappear when they time out or when they are clicked.
+    /// This is usually applied to the root view, so that toasts are shown above everything else.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::Duration;
+    /// # use trui::*;
+    /// # App::new(Notifications::new(), move |notifications| {
+    /// let toasts = notifications.clone();
+    /// "Save"
+    ///     .on_click(|notifications: &mut Notifications| {
+    ///         notifications.notify("Saved", Duration::from_secs(3));
+    ///     })
+    ///     .fill_max_size(1.0)
+    ///     .notifications(&toasts)
+    /// # });
+    /// ```
+    fn notifications(self, notifications: &Notifications) -> NotificationOverlay<Self, T, A> {
+        NotificationOverlay::new(self, notifications)
+    }
+
+    /// Shows `dialog` centered on top of this view, e.g. a [`confirm`] dialog.
+    ///
+    /// While a dialog is shown, only it receives mouse and key events and it traps the keyboard focus,
+    /// i.e. this view loses the focus and `Tab` doesn't move it back. `None` closes the dialog.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(false, move |show_help: &mut bool| {
+    /// let help = show_help.then(|| "Press Tab to move the focus".border(BorderKind::Rounded));
+    /// "[Help]"
+    ///     .on_click(|show_help: &mut bool| *show_help = !*show_help)
+    ///     .fill_max_size(1.0)
+    ///     .modal(help)
+    /// # });
+    /// ```
+    fn modal<D: View<T, A>>(self, dialog: Option<D>) -> Modal<Self, D, T, A> {
+        Modal::new(self, dialog)
+    }
+
+    /// Disables this view when `enabled` is `false`: it's painted with the disabled color of the theme,
+    /// it doesn't receive mouse, key and paste events, so its event handlers aren't called, and it
+    /// can't be hovered or focused (it loses the focus when it's disabled while focused).
+    ///
+    /// A disabled view disables all views inside it, regardless of their own `enabled` state.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// struct AppState {
+    ///     name: String,
+    ///     saved: Vec<String>,
+    /// }
+    ///
+    /// # App::new(AppState { name: String::new(), saved: vec![] }, move |state: &mut AppState| {
+    /// v_stack((
+    ///     text_input(state.name.clone(), |state: &mut AppState, name: String| {
+    ///         state.name = name
+    ///     }),
+    ///     "[Save]"
+    ///         .on_click(|state: &mut AppState| state.saved.push(state.name.clone()))
+    ///         .enabled(!state.name.is_empty()),
+    /// ))
+    /// # });
+    /// ```
+    fn enabled(self, enabled: bool) -> Enabled<Self> {
+        Enabled::new(self, enabled)
+    }
+
+    /// Tags this view with `key`, so that its bounds can be read in the app logic after it was laid out,
+    /// see [`WidgetBounds`](crate::WidgetBounds).
+    fn measure(self, key: impl Into<Cow<'static, str>>) -> Measure<Self> {
+        Measure::new(self, key.into())
+    }
+
+    /// Changes the theme of this view and its descendants, e.g. to change the hover or selected
+    /// style of a single view, while the rest of its styles is still the one of the app theme.
+    ///
+    /// `override_theme` is called with the theme of the parent, so the override takes precedence
+    /// over the app theme and over overrides of ancestors, but explicit styles of views
+    /// (e.g. [`Styleable::fg`] or [`Table::selected_style`]) still take precedence over it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// v_stack((
+    ///     "[Delete]"
+    ///         .border(BorderKind::Rounded)
+    ///         .on_click(|_: &mut ()| {})
+    ///         .style_override(|theme: &mut Theme| {
+    ///             theme.border.hover = theme.border.hover.fg(theme.error());
+    ///             theme.text.selected = theme.text.selected.bg(theme.error());
+    ///         }),
+    ///     "[Cancel]".border(BorderKind::Rounded).on_click(|_: &mut ()| {}),
+    /// ))
+    /// # });
+    /// ```
+    fn style_override<F: Fn(&mut Theme) + Send + Sync>(
+        self,
+        override_theme: F,
+    ) -> StyleOverride<Self, F> {
+        StyleOverride::new(self, override_theme)
+    }
+
+    /// Shows the part of this view that fits, which can be scrolled along `axes` when it's larger.
+    ///
+    /// The content isn't limited along the scrolled axes, each axis is only scrollable when the
+    /// content overflows it. The mouse wheel scrolls vertically (with `Shift` horizontally), while
+    /// the scroll view or one of its descendants is focused the arrow keys scroll, unless the
+    /// focused widget handles them itself. `PageUp`/`PageDown` scroll by a page (keeping one line
+    /// of the previous one) and `Home`/`End` jump to the start/end of the content.
+    /// While the content overflows, the scroll view itself can be focused with `Tab`, so that
+    /// content without focusable views can be scrolled with the keyboard as well.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |_| {
+    /// let lines: Vec<_> = (0..100)
+    ///     .map(|i| format!("{i}: a very long log line, which is wider than the terminal"))
+    ///     .collect();
+    /// lines.join("\n").scroll(ScrollAxes::BOTH).border(BorderKind::Rounded)
+    /// # });
+    /// ```
+    fn scroll(self, axes: ScrollAxes) -> Scroll<Self> {
+        Scroll::new(self, axes)
+    }
+
+    /// Shows `text` in a small label next to the mouse cursor, after it rested on this view for
+    /// a [`delay`](Tooltip::delay) of 500ms.
+    ///
+    /// The tooltip follows the mouse cursor while it's moved within this view, it's placed below
+    /// the cursor (or above it at the bottom of the terminal) and it's hidden when the cursor leaves
+    /// this view or a mouse button is pressed. It's painted on top of all other views.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # use std::time::Duration;
+    /// # App::new(0, move |count: &mut i32| {
+    /// format!("[+] {count}")
+    ///     .on_click(|count: &mut i32| *count += 1)
+    ///     .tooltip("Increments the counter")
+    ///     .delay(Duration::from_millis(300))
+    /// # });
+    /// ```
+    fn tooltip(self, text: impl Into<Cow<'static, str>>) -> Tooltip<Self> {
+        Tooltip::new(self, text.into())
+    }
+
+    /// Shows a menu bar with `menus` in the row above this view, `on_action` is called with the id
+    /// of the chosen [`MenuItem`].
+    ///
+    /// A menu is opened by clicking its title or with `Alt` and the first letter of its title
+    /// (the latter only while a widget inside this view has the focus, since key events are sent
+    /// along the focus path). The dropdown of an open menu is shown on top of this view and it traps
+    /// the mouse and key events: `Left`/`Right` switch the menu, `Up`/`Down` highlight an item,
+    /// `Enter` or the accelerator key of an item chooses it and `Esc` or a click outside closes the menu.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(Vec::<String>::new(), move |log| {
+    /// let menus = vec![
+    ///     Menu::new("File")
+    ///         .item(MenuItem::new("open", "Open").accelerator('o'))
+    ///         .item(MenuItem::new("save", "Save").enabled(!log.is_empty()))
+    ///         .separator()
+    ///         .item(MenuItem::new("quit", "Quit").accelerator('q')),
+    ///     Menu::new("Help").item(MenuItem::new("about", "About")),
+    /// ];
+    /// log.join("\n")
+    ///     .fill_max_size(1.0)
+    ///     .menu_bar(menus, |log: &mut Vec<String>, action: String| log.push(action))
+    /// # });
+    /// ```
+    fn menu_bar<EH: EventHandler<T, A, String>>(
+        self,
+        menus: Vec<Menu>,
+        on_action: EH,
+    ) -> MenuBar<Self, EH> {
+        MenuBar::new(self, menus, on_action)
+    }
+
+    /// Hides this view in the second half of every `period`, e.g. to draw attention to it.
+    ///
+    /// Instead of hiding it, the view can also be shown with an "off" style via [`Blink::off_style`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::Duration;
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// v_stack((
+    ///     "Recording".blink(Duration::from_secs(1)),
+    ///     "Unsaved changes"
+    ///         .blink(Duration::from_millis(500))
+    ///         .off_style(Style::default().fg(Color::Red)),
+    /// ))
+    /// # });
+    /// ```
+    fn blink(self, period: Duration) -> Blink<Self> {
+        Blink::new(self, period)
+    }
+
+    /// Interpolates the foreground color of this view from `from` to `to` and back within `period`.
+    ///
+    /// [`Pulse::background`] pulses the background color instead. Only [`Color::Rgb`] colors are
+    /// interpolated smoothly, other colors switch halfway.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::time::Duration;
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// "Alert"
+    ///     .pulse(Color::Rgb(80, 0, 0), Color::Rgb(255, 80, 80), Duration::from_secs(2))
+    ///     .background()
+    /// # });
+    /// ```
+    fn pulse(self, from: Color, to: Color, period: Duration) -> Pulse<Self> {
+        Pulse::new(self, from, to, period)
+    }
+
+    /// Fills the background of this view with a gradient from `from` to `to` in `direction`,
+    /// see [`Gradient`](crate::color::Gradient).
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::{*, color::GradientDirection};
+    /// # App::new((), move |_| {
+    /// "Status: ok"
+    ///     .fill_max_width(1.0)
+    ///     .background_gradient(rgb(0x263238), rgb(0x546E7A), GradientDirection::Horizontal)
+    /// # });
+    /// ```
+    fn background_gradient(
+        self,
+        from: Color,
+        to: Color,
+        direction: GradientDirection,
+    ) -> BackgroundGradient<Self> {
+        BackgroundGradient::new(self, Gradient::new(from, to), direction)
+    }
+
     fn on_mouse<EH: EventHandler<T, A, crate::widget::MouseEvent>>(
         self,
         event_handler: EH,
@@ -143,10 +712,88 @@ pub trait ViewExt<T, A>: View<T, A> + Sized {
         OnMouse {
             view: self,
             catch_event: crate::CatchMouseButton::empty(),
+            stop_propagation: false,
+            event_handler,
+        }
+    }
+
+    /// Calls `event_handler` with `true` when the terminal window gains the focus and with `false`
+    /// when it loses it, e.g. to pause animations in the background.
+    ///
+    /// This is not related to the keyboard focus of widgets. Not all terminals report focus changes.
+    /// See also [`AppConfig::with_pause_when_unfocused`](crate::AppConfig::with_pause_when_unfocused).
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(true, move |focused| {
+    /// (if *focused { "focused" } else { "in the background" })
+    ///     .on_terminal_focus(|focused: &mut bool, gained: bool| *focused = gained)
+    /// # });
+    /// ```
+    fn on_terminal_focus<EH: EventHandler<T, A, bool>>(
+        self,
+        event_handler: EH,
+    ) -> OnTerminalFocus<Self, EH> {
+        OnTerminalFocus {
+            view: self,
+            event_handler,
+        }
+    }
+
+    /// Calls `event_handler` with the origin of this view in window coordinates, when it's laid out
+    /// for the first time and whenever it moves afterwards, e.g. to place an overlay next to it.
+    ///
+    /// It's only called when the origin has actually changed, not with every frame.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::{*, geometry::Point};
+    /// # App::new(Point::ZERO, move |anchor: &mut Point| {
+    /// "Anchor".on_origin_change(|anchor: &mut Point, origin: Point| *anchor = origin)
+    /// # });
+    /// ```
+    fn on_origin_change<EH: EventHandler<T, A, Point>>(
+        self,
+        event_handler: EH,
+    ) -> OnOriginChange<Self, EH> {
+        OnOriginChange {
+            view: self,
             event_handler,
         }
     }
 
+    /// Calls `event_handler` with the id of a [`KeySequence`] when its keys were pressed one after
+    /// another, e.g. `g g` to scroll to the top like in vim.
+    ///
+    /// Keys are only received while this view or one of its descendants is focused and if the focused
+    /// widget doesn't handle them itself. The keys of an incomplete sequence are dropped after a
+    /// [`timeout`](KeySequences::timeout) or when a key doesn't continue any sequence. If a complete
+    /// sequence is the start of a longer one (like `d` and `d d`), it waits for the next key or the timeout.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(Vec::<String>::new(), move |log| {
+    /// log.join("\n")
+    ///     .on_key_sequences(
+    ///         [
+    ///             KeySequence::chars("top", "gg"),
+    ///             KeySequence::chars("bottom", "G"),
+    ///             KeySequence::chars("delete_line", "dd"),
+    ///         ],
+    ///         |log: &mut Vec<String>, id: String| log.push(id),
+    ///     )
+    /// # });
+    /// ```
+    fn on_key_sequences<EH: EventHandler<T, A, String>>(
+        self,
+        sequences: impl IntoIterator<Item = KeySequence>,
+        event_handler: EH,
+    ) -> KeySequences<Self, EH> {
+        KeySequences::new(self, sequences.into_iter().collect(), event_handler)
+    }
+
     fn on_hover<EH: EventHandler<T, A>>(self, event_handler: EH) -> OnHover<Self, EH> {
         OnHover {
             view: self,
@@ -211,3 +858,107 @@ pub trait ViewExt<T, A>: View<T, A> + Sized {
 }
 
 impl<T, A, V: View<T, A>> ViewExt<T, A> for V {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        widget::{
+            CxState, Event, EventCx, FocusState, Key, KeyEvent, LifeCycleCx, Pod, WidgetState,
+        },
+        WidgetBounds,
+    };
+
+    enum ItemAction {
+        Delete(usize),
+    }
+
+    enum ListAction {
+        Deleted(String),
+    }
+
+    fn item(index: usize, name: &str) -> impl View<(), ItemAction> {
+        h_stack((
+            name.to_string(),
+            "[x]"
+                .on_click(move |_: &mut ()| ItemAction::Delete(index))
+                .focusable(),
+        ))
+    }
+
+    /// A component, which translates the actions of its items into its own action
+    fn list(items: &[String]) -> impl View<Vec<String>, ListAction> {
+        let items = items
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                item(i, name).adapt(|items: &mut Vec<String>, thunk| match thunk.call(&mut ()) {
+                    MessageResult::Action(ItemAction::Delete(i)) => {
+                        MessageResult::Action(ListAction::Deleted(items.remove(i)))
+                    }
+                    other => other.map(|_| unreachable!()),
+                })
+            })
+            .collect::<Vec<_>>();
+        v_stack(items)
+    }
+
+    #[test]
+    fn actions_of_nested_views_are_translated_by_adapt() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let (wake_tx, _wake_rx) = tokio::sync::mpsc::channel(1);
+        let mut cx = Cx::new(
+            wake_tx,
+            runtime.handle().clone(),
+            Theme::default(),
+            WidgetBounds::default(),
+        );
+
+        let mut app_state = (vec!["a".to_string(), "b".to_string()], Vec::new());
+        let view = v_stack((
+            "Items",
+            list(&app_state.0).adapt(|(items, deleted): &mut (Vec<String>, Vec<String>), thunk| {
+                match thunk.call(items) {
+                    MessageResult::Action(ListAction::Deleted(name)) => {
+                        deleted.push(name);
+                        MessageResult::Action(())
+                    }
+                    other => other.map(|_| ()),
+                }
+            }),
+        ));
+        let (_, mut view_state, element) = view.build(&mut cx);
+        let mut root_pod = Pod::new(element);
+
+        // Focuses the "[x]" of the second item and presses it
+        let (mut messages, mut focus) = (Vec::new(), FocusState::default());
+        let mut root_state = WidgetState::new();
+        let cx_state = &mut CxState::new(&mut messages, &mut focus, Default::default());
+        for _ in 0..2 {
+            let mut cx = LifeCycleCx {
+                widget_state: &mut root_state,
+                cx_state,
+            };
+            root_pod.focus_next(&mut cx, true);
+        }
+        let mut cx = EventCx {
+            is_handled: false,
+            widget_state: &mut root_state,
+            cx_state,
+        };
+        root_pod.event(&mut cx, &Event::Key(KeyEvent::from(Key::Enter)));
+
+        assert_eq!(messages.len(), 1);
+        let message = messages.pop().unwrap();
+        let result = view.message(
+            &message.id_path[1..],
+            &mut view_state,
+            message.body,
+            &mut app_state,
+        );
+        assert!(matches!(result, MessageResult::Action(())));
+        assert_eq!(app_state, (vec!["a".to_string()], vec!["b".to_string()]));
+    }
+}
@@ -155,6 +155,108 @@ pub fn low_pass<AT: Animatable<f64>>(decay: f64, target: AT) -> LowPassIIR<AT> {
     LowPassIIR { decay, target }
 }
 
+/// How the progress of a [`Transition`] is mapped to the animated value.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadraticIn,
+    QuadraticOut,
+    QuadraticInOut,
+}
+
+impl Easing {
+    /// Maps `ratio` (between 0.0 and 1.0) to the eased ratio.
+    pub fn ease(self, ratio: f64) -> f64 {
+        let ratio = ratio.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => ratio,
+            Easing::QuadraticIn => ratio * ratio,
+            Easing::QuadraticOut => -(ratio * (ratio - 2.0)),
+            Easing::QuadraticInOut if ratio < 0.5 => 2.0 * ratio * ratio,
+            Easing::QuadraticInOut => (-2.0 * ratio * ratio) + (4.0 * ratio) - 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Transition<AT> {
+    duration: Duration,
+    easing: Easing,
+    target: AT,
+}
+
+impl<AT> Transition<AT> {
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+impl<AT: Animatable<f64>> Animatable<f64> for Transition<AT> {
+    type State = AT::State;
+
+    type Element = widget::animatables::Transition<AT::Element>;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, target) = self.target.build(cx);
+        (
+            id,
+            state,
+            widget::animatables::Transition::new(target, self.duration, self.easing),
+        )
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        self.target
+            .rebuild(cx, &prev.target, id, state, &mut element.target)
+            | element.set_duration(self.duration)
+            | element.set_easing(self.easing)
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+    ) -> MessageResult<()> {
+        self.target.message(id_path, state, message)
+    }
+}
+
+/// Animates from the previous to the current value of `target` within `duration`, whenever `target` changes.
+///
+/// The initial value isn't animated, and the animation stops requesting frames as soon as it reached the target.
+///
+/// # Examples
+/// ```
+/// # use std::time::Duration;
+/// # use trui::*;
+/// # App::new(false, move |expanded| {
+/// "Click to expand"
+///     .border(BorderKind::Rounded)
+///     .fill_max_width(
+///         transition(Duration::from_millis(300), if *expanded { 1.0 } else { 0.3 })
+///             .easing(Easing::QuadraticInOut),
+///     )
+///     .on_click(|expanded: &mut bool| *expanded = !*expanded)
+/// # });
+/// ```
+pub fn transition<AT: Animatable<f64>>(duration: Duration, target: AT) -> Transition<AT> {
+    Transition {
+        duration,
+        easing: Easing::default(),
+        target,
+    }
+}
+
 // TODO use a macro for primitive non-animating/"const" values like the following
 impl Animatable<u32> for u32 {
     type State = ();
@@ -624,3 +726,36 @@ pub mod ease {
     ease_fn!(QuadraticInOut);
     ease_fn!(ElasticInOut);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn easing_curves_start_at_0_and_end_at_1() {
+        let curves = [
+            Easing::Linear,
+            Easing::QuadraticIn,
+            Easing::QuadraticOut,
+            Easing::QuadraticInOut,
+        ];
+        for easing in curves {
+            assert_eq!(easing.ease(0.0), 0.0, "{easing:?}");
+            assert_eq!(easing.ease(1.0), 1.0, "{easing:?}");
+            // the ratio is clamped
+            assert_eq!(easing.ease(-0.5), 0.0, "{easing:?}");
+            assert_eq!(easing.ease(1.5), 1.0, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn easing_curves_follow_their_shape_in_between() {
+        assert_eq!(Easing::Linear.ease(0.5), 0.5);
+        assert_eq!(Easing::QuadraticIn.ease(0.5), 0.25);
+        assert_eq!(Easing::QuadraticOut.ease(0.5), 0.75);
+        assert_eq!(Easing::QuadraticInOut.ease(0.5), 0.5);
+        // both halves of the in-out curve
+        assert_eq!(Easing::QuadraticInOut.ease(0.25), 0.125);
+        assert_eq!(Easing::QuadraticInOut.ease(0.75), 0.875);
+    }
+}
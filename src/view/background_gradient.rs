@@ -0,0 +1,67 @@
+use std::any::Any;
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::{
+    color::{Gradient, GradientDirection},
+    widget::{self, ChangeFlags},
+};
+
+pub struct BackgroundGradient<V> {
+    content: V,
+    gradient: Gradient,
+    direction: GradientDirection,
+}
+
+impl<V> BackgroundGradient<V> {
+    pub(crate) fn new(content: V, gradient: Gradient, direction: GradientDirection) -> Self {
+        BackgroundGradient {
+            content,
+            gradient,
+            direction,
+        }
+    }
+}
+
+impl<V> ViewMarker for BackgroundGradient<V> {}
+
+impl<T, A, V: View<T, A>> View<T, A> for BackgroundGradient<V> {
+    type State = V::State;
+
+    type Element = widget::BackgroundGradient;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, element) = self.content.build(cx);
+        let element = widget::BackgroundGradient::new(element, self.gradient, self.direction);
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changeflags = element.set_gradient(self.gradient, self.direction);
+        let content_el = element.content().downcast_mut().expect(
+            "The content widget of the gradient changed its type, this should never happen!",
+        );
+        let content_changeflags = self
+            .content
+            .rebuild(cx, &prev.content, id, state, content_el);
+        changeflags | element.content().mark(content_changeflags)
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.content.message(id_path, state, message, app_state)
+    }
+}
@@ -0,0 +1,166 @@
+use std::borrow::Cow;
+
+use ratatui::style::Style;
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::{
+    geometry::Axis,
+    widget::{self, BarChartStyles, ChangeFlags},
+};
+
+/// A single bar of a [`BarChart`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bar {
+    pub label: Cow<'static, str>,
+    pub value: u64,
+    /// Patched on top of the bar style of the chart, e.g. to give each bar its own color.
+    pub style: Option<Style>,
+}
+
+impl Bar {
+    pub fn new(label: impl Into<Cow<'static, str>>, value: u64) -> Self {
+        Bar {
+            label: label.into(),
+            value,
+            style: None,
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+}
+
+impl<L: Into<Cow<'static, str>>> From<(L, u64)> for Bar {
+    fn from((label, value): (L, u64)) -> Self {
+        Bar::new(label, value)
+    }
+}
+
+pub struct BarChart {
+    bars: Vec<Bar>,
+    axis: Axis,
+    max: Option<u64>,
+    bar_style: Option<Style>,
+    label_style: Option<Style>,
+    value_style: Option<Style>,
+}
+
+impl BarChart {
+    /// Draws the bars from left to right (one row per bar) instead of bottom to top.
+    pub fn horizontal(mut self) -> Self {
+        self.axis = Axis::Horizontal;
+        self
+    }
+
+    /// Scales the bars to a fixed `max` instead of the largest value, values above it are clamped.
+    ///
+    /// This keeps the scale stable across frames when the values change.
+    pub fn max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    pub fn bar_style(mut self, style: Style) -> Self {
+        self.bar_style = Some(style);
+        self
+    }
+
+    pub fn label_style(mut self, style: Style) -> Self {
+        self.label_style = Some(style);
+        self
+    }
+
+    pub fn value_style(mut self, style: Style) -> Self {
+        self.value_style = Some(style);
+        self
+    }
+
+    fn resolve_styles(&self, cx: &Cx) -> BarChartStyles {
+        let theme = cx.theme();
+        BarChartStyles {
+            bar: self
+                .bar_style
+                .unwrap_or(Style::default().fg(theme.primary())),
+            label: self.label_style.unwrap_or(theme.text.default),
+            value: self
+                .value_style
+                .unwrap_or(Style::default().fg(theme.colors.numbers)),
+        }
+    }
+}
+
+impl ViewMarker for BarChart {}
+
+impl<T, A> View<T, A> for BarChart {
+    type State = ();
+
+    type Element = widget::BarChart;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, element) = cx.with_new_id(|cx| {
+            widget::BarChart::new(
+                self.bars.clone(),
+                self.axis,
+                self.max,
+                self.resolve_styles(cx),
+            )
+        });
+        (id, (), element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut Id,
+        _state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        element.set_bars(&self.bars)
+            | element.set_axis(self.axis)
+            | element.set_max(self.max)
+            | element.set_styles(self.resolve_styles(cx))
+    }
+
+    fn message(
+        &self,
+        _id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut T,
+    ) -> MessageResult<A> {
+        MessageResult::Stale(message)
+    }
+}
+
+/// A labeled bar chart, by default with vertical bars, that are scaled to the largest value.
+///
+/// The value is shown above (or next to) each bar and the label below (or in front of) it.
+/// The bar width is chosen from the available space, bars that don't fit are truncated.
+/// Colors are taken from the [`Theme`](crate::Theme), unless set explicitly.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new((), move |()| {
+/// bar_chart([
+///     Bar::new("mon", 3),
+///     Bar::new("tue", 8).style(Style::default().fg(Color::Red)),
+///     ("wed", 5).into(),
+/// ])
+/// .max(10)
+/// # });
+/// ```
+pub fn bar_chart(bars: impl IntoIterator<Item = impl Into<Bar>>) -> BarChart {
+    BarChart {
+        bars: bars.into_iter().map(Into::into).collect(),
+        axis: Axis::Vertical,
+        max: None,
+        bar_style: None,
+        label_style: None,
+        value_style: None,
+    }
+}
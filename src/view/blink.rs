@@ -0,0 +1,185 @@
+use std::{any::Any, time::Duration};
+
+use ratatui::style::{Color, Style};
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::widget::{self, ChangeFlags, StyleAnimation};
+
+/// Toggles a view between an "on" and an "off" state, see [`ViewExt::blink`](crate::ViewExt::blink).
+pub struct Blink<V> {
+    view: V,
+    period: Duration,
+    on_style: Style,
+    off_style: Option<Style>,
+}
+
+impl<V> Blink<V> {
+    pub(crate) fn new(view: V, period: Duration) -> Self {
+        Blink {
+            view,
+            period,
+            on_style: Style::default(),
+            off_style: None,
+        }
+    }
+
+    /// The style which is applied in the first half of the period, by default the view isn't changed.
+    pub fn on_style(mut self, style: Style) -> Self {
+        self.on_style = style;
+        self
+    }
+
+    /// The style which is applied in the second half of the period, instead of hiding the view.
+    pub fn off_style(mut self, style: Style) -> Self {
+        self.off_style = Some(style);
+        self
+    }
+
+    fn animation(&self) -> StyleAnimation {
+        StyleAnimation::Blink {
+            period: self.period,
+            on_style: self.on_style,
+            off_style: self.off_style,
+        }
+    }
+}
+
+/// Interpolates a color back and forth, see [`ViewExt::pulse`](crate::ViewExt::pulse).
+pub struct Pulse<V> {
+    view: V,
+    from: Color,
+    to: Color,
+    period: Duration,
+    background: bool,
+}
+
+impl<V> Pulse<V> {
+    pub(crate) fn new(view: V, from: Color, to: Color, period: Duration) -> Self {
+        Pulse {
+            view,
+            from,
+            to,
+            period,
+            background: false,
+        }
+    }
+
+    /// Pulses the background color instead of the foreground color.
+    pub fn background(mut self) -> Self {
+        self.background = true;
+        self
+    }
+
+    fn animation(&self) -> StyleAnimation {
+        StyleAnimation::Pulse {
+            from: self.from,
+            to: self.to,
+            period: self.period,
+            background: self.background,
+        }
+    }
+}
+
+pub struct StyleAnimationState<S> {
+    child_id: Id,
+    state: S,
+}
+
+macro_rules! style_animation_views {
+    ($($name:ident),*) => {
+        $(
+        impl<V> ViewMarker for $name<V> {}
+
+        impl<T, A, V: View<T, A>> View<T, A> for $name<V> {
+            type State = StyleAnimationState<V::State>;
+
+            type Element = widget::AnimatedStyle;
+
+            fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+                let (id, (state, element)) = cx.with_new_id(|cx| {
+                    let (child_id, state, element) = self.view.build(cx);
+                    let state = StyleAnimationState { child_id, state };
+                    (state, widget::AnimatedStyle::new(element, self.animation()))
+                });
+                (id, state, element)
+            }
+
+            fn rebuild(
+                &self,
+                cx: &mut Cx,
+                prev: &Self,
+                id: &mut Id,
+                state: &mut Self::State,
+                element: &mut Self::Element,
+            ) -> ChangeFlags {
+                cx.with_id(*id, |cx| {
+                    let changeflags = element.set_animation(self.animation());
+
+                    let element_changeflags = self.view.rebuild(
+                        cx,
+                        &prev.view,
+                        &mut state.child_id,
+                        &mut state.state,
+                        element.element.downcast_mut().expect(
+                            "The animated content widget changed its type, this should never happen!",
+                        ),
+                    );
+                    changeflags | element.element.mark(element_changeflags)
+                })
+            }
+
+            fn message(
+                &self,
+                id_path: &[Id],
+                state: &mut Self::State,
+                message: Box<dyn Any>,
+                app_state: &mut T,
+            ) -> MessageResult<A> {
+                match id_path {
+                    [id, rest_path @ ..] if *id == state.child_id => {
+                        self.view.message(rest_path, &mut state.state, message, app_state)
+                    }
+                    [..] => MessageResult::Stale(message),
+                }
+            }
+        }
+        )*
+    };
+}
+
+style_animation_views!(Blink, Pulse);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::{App, ViewExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn blinking_repaints_without_running_the_app_logic() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let runs_clone = runs.clone();
+        let mut app = App::new((), move |_: &mut ()| {
+            runs_clone.fetch_add(1, Ordering::SeqCst);
+            "Recording".blink(Duration::from_millis(400))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "Recording");
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "");
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "Recording");
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use crate::{
     widget::{self, ChangeFlags},
-    BorderStyle,
+    BorderStyle, SideStyles,
 };
 
 use super::{BorderKind, Borders, Cx, Styleable, View, ViewMarker};
@@ -15,9 +15,45 @@ pub struct Border<V, T, A> {
     pub(crate) borders: Borders,
     pub(crate) kind: BorderKind,
     pub(crate) style: Style,
+    pub(crate) side_styles: SideStyles,
+    /// Whether the hover and focus border styles of the [`Theme`](crate::Theme) are applied
+    pub(crate) focus_indicator: bool,
     pub(crate) phantom: PhantomData<fn() -> (T, A)>,
 }
 
+impl<V, T, A> Border<V, T, A> {
+    /// Patches `style` on top of the style of the border for each of the `sides`, e.g. to highlight
+    /// the top border of the active pane, see [`SideStyles`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |_| {
+    /// "Active pane"
+    ///     .border(BorderKind::Rounded)
+    ///     .side_style(Borders::TOP, Style::default().fg(Color::Yellow))
+    /// # });
+    /// ```
+    pub fn side_style(mut self, sides: Borders, style: Style) -> Self {
+        self.side_styles.set(sides, style);
+        self
+    }
+
+    /// The style of the border and the styles patched on top of it while it's hovered or focused.
+    fn resolve_styles(&self, cx: &Cx) -> (Style, Option<Style>, Option<Style>) {
+        if self.focus_indicator {
+            let theme = &cx.theme().border;
+            (
+                theme.default.patch(self.style),
+                Some(theme.hover),
+                Some(theme.focus),
+            )
+        } else {
+            (self.style, None, None)
+        }
+    }
+}
+
 impl<T, A, V> ViewMarker for Border<V, T, A> {}
 
 impl<T, A, V: View<T, A>> View<T, A> for Border<V, T, A> {
@@ -27,7 +63,10 @@ impl<T, A, V: View<T, A>> View<T, A> for Border<V, T, A> {
 
     fn build(&self, cx: &mut Cx) -> (xilem_core::Id, Self::State, Self::Element) {
         let (id, state, element) = self.content.build(cx);
-        let element = widget::Border::new(element, self.borders, self.style, self.kind);
+        let (style, hover_style, focus_style) = self.resolve_styles(cx);
+        let mut element = widget::Border::new(element, self.borders, style, self.kind);
+        let _ = element.set_side_styles(self.side_styles);
+        let _ = element.set_state_styles(hover_style, focus_style);
         (id, state, element)
     }
 
@@ -41,7 +80,10 @@ impl<T, A, V: View<T, A>> View<T, A> for Border<V, T, A> {
     ) -> crate::widget::ChangeFlags {
         let mut changeflags = ChangeFlags::empty();
         changeflags |= element.set_borders(self.borders);
-        changeflags |= element.set_style(self.style);
+        let (style, hover_style, focus_style) = self.resolve_styles(cx);
+        changeflags |= element.set_style(style);
+        changeflags |= element.set_side_styles(self.side_styles);
+        changeflags |= element.set_state_styles(hover_style, focus_style);
         changeflags |= element.set_kind(self.kind);
 
         let content_el = element
@@ -177,6 +219,7 @@ impl From<(Style, Borders, BorderKind)> for BorderStyle {
             borders,
             style,
             kind,
+            ..Default::default()
         }
     }
 }
@@ -187,6 +230,7 @@ impl From<(Borders, Style, BorderKind)> for BorderStyle {
             borders,
             style,
             kind,
+            ..Default::default()
         }
     }
 }
@@ -197,6 +241,7 @@ impl From<(Borders, BorderKind, Style)> for BorderStyle {
             borders,
             style,
             kind,
+            ..Default::default()
         }
     }
 }
@@ -207,6 +252,7 @@ impl From<(BorderKind, Borders, Style)> for BorderStyle {
             borders,
             style,
             kind,
+            ..Default::default()
         }
     }
 }
@@ -217,6 +263,7 @@ impl From<(BorderKind, Style, Borders)> for BorderStyle {
             borders,
             style,
             kind,
+            ..Default::default()
         }
     }
 }
@@ -227,6 +274,7 @@ impl From<(Style, BorderKind, Borders)> for BorderStyle {
             borders,
             style,
             kind,
+            ..Default::default()
         }
     }
 }
@@ -237,7 +285,7 @@ mod tests {
 
     use ratatui::layout::Size;
 
-    use crate::{test_helper::render_view, ViewExt};
+    use crate::{test_helper::render_view, BorderGlyphs, ViewExt};
 
     use super::*;
 
@@ -290,4 +338,64 @@ mod tests {
             })
             .await
     }
+
+    #[tokio::test]
+    async fn side_styles_are_patched_on_the_border_style() {
+        let local_set = tokio::task::LocalSet::new();
+        local_set
+            .run_until(async {
+                let sut = Arc::new(
+                    "text"
+                        .border((Borders::ALL, Style::default().fg(Color::Blue)))
+                        .side_style(Borders::TOP, Style::default().fg(Color::Yellow)),
+                );
+                let buffer = render_view(
+                    Size {
+                        width: 6,
+                        height: 3,
+                    },
+                    sut,
+                    AppState,
+                )
+                .await;
+                // the corners have the style of the top and bottom sides
+                assert_eq!(buffer.get(0, 0).fg, Color::Yellow);
+                assert_eq!(buffer.get(2, 0).fg, Color::Yellow);
+                assert_eq!(buffer.get(0, 1).fg, Color::Blue);
+                assert_eq!(buffer.get(5, 2).fg, Color::Blue);
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn custom_glyphs_take_a_single_cell() {
+        let local_set = tokio::task::LocalSet::new();
+        local_set
+            .run_until(async {
+                let glyphs = BorderGlyphs {
+                    vertical: "||",
+                    ..BorderGlyphs::ASCII
+                };
+                let sut = Arc::new("ab".border(BorderStyle::custom(glyphs)));
+                let buffer = render_view(
+                    Size {
+                        width: 4,
+                        height: 3,
+                    },
+                    sut,
+                    AppState,
+                )
+                .await;
+                let line = |y| {
+                    (0..4)
+                        .map(|x| buffer.get(x, y).symbol())
+                        .collect::<String>()
+                };
+                assert_eq!(line(0), "+--+");
+                // the too wide vertical glyph is replaced
+                assert_eq!(line(1), " ab ");
+                assert_eq!(line(2), "+--+");
+            })
+            .await
+    }
 }
@@ -0,0 +1,204 @@
+use std::sync::Arc;
+
+use ratatui::style::Style;
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+/// A rectangular grid of cells, each with a single character and a style, painted by a [`Canvas`].
+///
+/// Every character takes exactly one cell, so wide characters (e.g. CJK) overlap their right neighbour.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    width: u16,
+    height: u16,
+    cells: Vec<(char, Style)>,
+}
+
+impl Grid {
+    /// A grid of `width` x `height` spaces with the default style.
+    pub fn new(width: u16, height: u16) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![(' ', Style::default()); width as usize * height as usize],
+        }
+    }
+
+    /// A grid with one row per line, e.g. for ASCII art.
+    ///
+    /// It's as wide as the longest line, shorter lines are padded with spaces.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a str>, style: Style) -> Self {
+        let lines: Vec<_> = lines.into_iter().collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut grid = Grid::new(width as u16, lines.len() as u16);
+        for (y, line) in lines.iter().enumerate() {
+            for (x, symbol) in line.chars().enumerate() {
+                grid.set(x as u16, y as u16, symbol, style);
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// The cell at column `x` and row `y`, or `None` if it's outside of the grid.
+    pub fn get(&self, x: u16, y: u16) -> Option<(char, Style)> {
+        self.index(x, y).map(|i| self.cells[i])
+    }
+
+    /// Sets the cell at column `x` and row `y`, cells outside of the grid are ignored.
+    pub fn set(&mut self, x: u16, y: u16, symbol: char, style: Style) {
+        if let Some(i) = self.index(x, y) {
+            self.cells[i] = (symbol, style);
+        }
+    }
+
+    fn index(&self, x: u16, y: u16) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y as usize * self.width as usize + x as usize)
+    }
+}
+
+/// The content of a [`Canvas`], either a fixed grid or a function which draws it for a given size.
+#[derive(Clone)]
+pub(crate) enum CanvasContent {
+    Grid(Grid),
+    Draw {
+        draw: Arc<dyn Fn(u16, u16) -> Grid + Send + Sync>,
+        /// The size used in directions in which the parent doesn't bound the canvas
+        natural_size: (u16, u16),
+    },
+}
+
+impl PartialEq for CanvasContent {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (CanvasContent::Grid(a), CanvasContent::Grid(b)) => a == b,
+            (
+                CanvasContent::Draw {
+                    draw: a,
+                    natural_size: a_size,
+                },
+                CanvasContent::Draw {
+                    draw: b,
+                    natural_size: b_size,
+                },
+            ) => Arc::ptr_eq(a, b) && a_size == b_size,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Canvas {
+    content: CanvasContent,
+}
+
+impl ViewMarker for Canvas {}
+
+impl<T, A> View<T, A> for Canvas {
+    type State = ();
+
+    type Element = widget::Canvas;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, element) = cx.with_new_id(|_| widget::Canvas::new(self.content.clone()));
+        (id, (), element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut Id,
+        _state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        element.set_content(&self.content)
+    }
+
+    fn message(
+        &self,
+        _id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut T,
+    ) -> MessageResult<A> {
+        MessageResult::Stale(message)
+    }
+}
+
+/// Paints the cells of `grid`, with its top-left corner at the top-left corner of the view.
+///
+/// The view is as large as the grid (within the constraints of its parent).
+/// When it gets less space, the grid is clipped at the right and bottom,
+/// when it gets more space, the remaining cells are left untouched (i.e. they're transparent).
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new((), move |()| {
+/// canvas(Grid::from_lines(
+///     [r" /\_/\ ", r"( o.o )", r" > ^ < "],
+///     Style::default().fg(Color::Yellow),
+/// ))
+/// # });
+/// ```
+pub fn canvas(grid: Grid) -> Canvas {
+    Canvas {
+        content: CanvasContent::Grid(grid),
+    }
+}
+
+/// A [`canvas`] which draws its grid with `draw(width, height)` for the size it's given by its parent,
+/// so the content can adapt to the available space.
+///
+/// It takes all the space the parent allows (in unbounded directions only the minimum).
+/// `draw` is called again whenever that size changes or the view is rebuilt, a grid of another size
+/// than requested is clipped or left partially transparent as with [`canvas`].
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new((), move |()| {
+/// canvas_with(|width, height| {
+///     let mut grid = Grid::new(width, height);
+///     // a diagonal line from the top-left corner
+///     for i in 0..width.min(height) {
+///         grid.set(i, i, '\\', Style::default().fg(Color::Red));
+///     }
+///     grid
+/// })
+/// .fill_max_size(1.0)
+/// # });
+/// ```
+pub fn canvas_with(draw: impl Fn(u16, u16) -> Grid + Send + Sync + 'static) -> Canvas {
+    Canvas {
+        content: CanvasContent::Draw {
+            draw: Arc::new(draw),
+            natural_size: (0, 0),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_from_lines_pads_short_lines() {
+        let style = Style::default();
+        let grid = Grid::from_lines(["ab", "c"], style);
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid.get(1, 0), Some(('b', style)));
+        assert_eq!(grid.get(1, 1), Some((' ', style)));
+        assert_eq!(grid.get(2, 0), None);
+    }
+}
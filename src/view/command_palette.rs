@@ -0,0 +1,338 @@
+use std::{any::Any, borrow::Cow, marker::PhantomData, sync::Arc};
+
+use ratatui::style::{Modifier, Style};
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, EventHandler, View, ViewMarker};
+use crate::widget::{self, ChangeFlags, PaletteMessage, PaletteStyles};
+
+/// How well a query matches a text, see [`fuzzy_match`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better
+    pub score: i64,
+    /// The indices of the matched chars in the text
+    pub indices: Vec<usize>,
+}
+
+/// Points for each matched char
+const MATCH_SCORE: i64 = 1;
+/// Points for a matched char which directly follows the previously matched char
+const CONTIGUITY_BONUS: i64 = 5;
+/// Points for a matched char at the start of a word
+const WORD_START_BONUS: i64 = 3;
+/// Points subtracted for each skipped char between two matched chars
+const GAP_PENALTY: i64 = 1;
+
+/// Matches the chars of `query` case-insensitively as a subsequence of `text`.
+///
+/// Contiguous matches and matches at the start of words (also camelCase humps) score higher,
+/// gaps between matched chars score lower. An empty query matches everything with a score of 0.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(lowercase)
+        .collect();
+    let Some(&first) = query.first() else {
+        return Some(FuzzyMatch {
+            score: 0,
+            indices: Vec::new(),
+        });
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars.iter().copied().map(lowercase).collect();
+    let is_word_start = |i: usize| {
+        i == 0
+            || !chars[i - 1].is_alphanumeric()
+            || (chars[i - 1].is_lowercase() && chars[i].is_uppercase())
+    };
+
+    // The matches are greedy, so each possible start of the match is tried
+    let mut best: Option<FuzzyMatch> = None;
+    for start in (0..lower.len()).filter(|i| lower[*i] == first) {
+        let mut indices = vec![start];
+        let mut next = start + 1;
+        for c in &query[1..] {
+            match lower[next..].iter().position(|l| l == c) {
+                Some(offset) => {
+                    indices.push(next + offset);
+                    next += offset + 1;
+                }
+                None => break,
+            }
+        }
+        if indices.len() < query.len() {
+            // Later starts can't match either
+            break;
+        }
+        let mut score = 0;
+        for (n, i) in indices.iter().enumerate() {
+            score += MATCH_SCORE;
+            if is_word_start(*i) {
+                score += WORD_START_BONUS;
+            }
+            if n > 0 {
+                let gap = (i - indices[n - 1] - 1) as i64;
+                score += if gap == 0 {
+                    CONTIGUITY_BONUS
+                } else {
+                    -gap * GAP_PENALTY
+                };
+            }
+        }
+        let is_better = match &best {
+            Some(best) => score > best.score,
+            None => true,
+        };
+        if is_better {
+            best = Some(FuzzyMatch { score, indices });
+        }
+    }
+    best
+}
+
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// A command of a [`command_palette`], `action` is called when it's chosen.
+pub struct Command<T, A = ()> {
+    label: Cow<'static, str>,
+    action: Arc<dyn Fn(&mut T) -> A + Send + Sync>,
+}
+
+impl<T, A> Command<T, A> {
+    pub fn new(
+        label: impl Into<Cow<'static, str>>,
+        action: impl Fn(&mut T) -> A + Send + Sync + 'static,
+    ) -> Self {
+        Command {
+            label: label.into(),
+            action: Arc::new(action),
+        }
+    }
+}
+
+impl<T, A> Clone for Command<T, A> {
+    fn clone(&self) -> Self {
+        Command {
+            label: self.label.clone(),
+            action: self.action.clone(),
+        }
+    }
+}
+
+pub struct CommandPalette<T, A, CH> {
+    commands: Vec<Command<T, A>>,
+    on_close: CH,
+    placeholder: Cow<'static, str>,
+    matcher: fn(&str, &str) -> Option<FuzzyMatch>,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct CommandPaletteState<CHS> {
+    on_close_id: Id,
+    on_close_state: CHS,
+}
+
+impl<T, A, CH> CommandPalette<T, A, CH> {
+    /// The text which is shown while the query is empty, by default "Type a command".
+    pub fn placeholder(mut self, placeholder: impl Into<Cow<'static, str>>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Replaces [`fuzzy_match`], which filters and ranks the commands, it's called with the query
+    /// and the label of a command.
+    pub fn matcher(mut self, matcher: fn(&str, &str) -> Option<FuzzyMatch>) -> Self {
+        self.matcher = matcher;
+        self
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.commands.iter().map(|c| c.label.to_string()).collect()
+    }
+
+    fn resolve_styles(cx: &Cx) -> PaletteStyles {
+        let theme = cx.theme();
+        let background = theme.surface_variant();
+        PaletteStyles {
+            text: theme.text.default.bg(background),
+            border: theme.border.focus.bg(background),
+            selected: theme.text.selected,
+            highlight: Style::default()
+                .fg(theme.primary())
+                .add_modifier(Modifier::BOLD),
+            placeholder: theme.text.disabled.bg(background),
+        }
+    }
+}
+
+impl<T, A, CH> ViewMarker for CommandPalette<T, A, CH> {}
+
+impl<T, A, CH: EventHandler<T, A>> View<T, A> for CommandPalette<T, A, CH> {
+    type State = CommandPaletteState<CH::State>;
+
+    type Element = widget::CommandPalette;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let element = widget::CommandPalette::new(
+                cx.id_path(),
+                self.labels(),
+                self.placeholder.to_string(),
+                self.matcher,
+                Self::resolve_styles(cx),
+            );
+            let (on_close_id, on_close_state) = self.on_close.build(cx);
+            let state = CommandPaletteState {
+                on_close_id,
+                on_close_state,
+            };
+            (state, element)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            element.set_commands(self.labels())
+                | element.set_placeholder(&self.placeholder)
+                | element.set_matcher(self.matcher)
+                | element.set_styles(Self::resolve_styles(cx))
+                | self
+                    .on_close
+                    .rebuild(cx, &state.on_close_id, &mut state.on_close_state)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [id, rest_path @ ..] if *id == state.on_close_id => {
+                self.on_close
+                    .message(rest_path, &mut state.on_close_state, message, app_state)
+            }
+            [] if message.downcast_ref::<PaletteMessage>().is_some() => {
+                match *message.downcast::<PaletteMessage>().unwrap() {
+                    PaletteMessage::Run(i) => match self.commands.get(i) {
+                        Some(command) => {
+                            // The palette is closed before the command runs, so that the
+                            // command can e.g. open it again
+                            let _ = self.on_close.message(
+                                &[],
+                                &mut state.on_close_state,
+                                Box::new(()),
+                                app_state,
+                            );
+                            MessageResult::Action((command.action)(app_state))
+                        }
+                        None => MessageResult::Nop,
+                    },
+                    PaletteMessage::Close => self.on_close.message(
+                        &[],
+                        &mut state.on_close_state,
+                        Box::new(()),
+                        app_state,
+                    ),
+                }
+            }
+            [..] => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// A searchable list of `commands`, which runs the action of the chosen command and calls `on_close`.
+///
+/// It's meant to be shown via [`modal`](crate::ViewExt::modal), which traps the mouse and keyboard
+/// input and the focus while it's shown. Like other dialogs it's closed by not passing it to `modal`
+/// anymore, usually by updating the app state in `on_close`, which is called before a command
+/// runs and when the palette is dismissed with `Esc`.
+///
+/// Typing filters the commands with [`fuzzy_match`] (see [`matcher`](CommandPalette::matcher))
+/// and shows the best matches first with the matched characters highlighted, all commands are shown
+/// while the query is empty. `Up`/`Down` select a command and `Enter` or a click runs it.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// struct AppState {
+///     palette_open: bool,
+///     count: i32,
+/// }
+///
+/// # App::new(AppState { palette_open: false, count: 0 }, move |state: &mut AppState| {
+/// let palette = state.palette_open.then(|| {
+///     command_palette(
+///         vec![
+///             Command::new("Increment counter", |state: &mut AppState| state.count += 1),
+///             Command::new("Decrement counter", |state: &mut AppState| state.count -= 1),
+///             Command::new("Reset counter", |state: &mut AppState| state.count = 0),
+///         ],
+///         |state: &mut AppState| state.palette_open = false,
+///     )
+/// });
+/// format!("[Commands] count: {}", state.count)
+///     .on_click(|state: &mut AppState| state.palette_open = true)
+///     .fill_max_size(1.0)
+///     .modal(palette)
+/// # });
+/// ```
+pub fn command_palette<T, A, CH: EventHandler<T, A>>(
+    commands: Vec<Command<T, A>>,
+    on_close: CH,
+) -> CommandPalette<T, A, CH> {
+    CommandPalette {
+        commands,
+        on_close,
+        placeholder: "Type a command".into(),
+        matcher: fuzzy_match,
+        phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_everything() {
+        let m = fuzzy_match("", "Open file").unwrap();
+        assert_eq!(m.score, 0);
+        assert!(m.indices.is_empty());
+    }
+
+    #[test]
+    fn matches_subsequences_case_insensitively() {
+        assert_eq!(fuzzy_match("OF", "open file").unwrap().indices, vec![0, 5]);
+        assert_eq!(fuzzy_match("fo", "open file"), None);
+    }
+
+    #[test]
+    fn prefers_contiguous_and_word_start_matches() {
+        let contiguous = fuzzy_match("save", "Save all").unwrap();
+        let scattered = fuzzy_match("save", "Show active view").unwrap();
+        assert!(contiguous.score > scattered.score);
+
+        // "file" at the start of a word scores higher than inside "profile"
+        let m = fuzzy_match("file", "profile file").unwrap();
+        assert_eq!(m.indices, vec![8, 9, 10, 11]);
+        assert!(
+            fuzzy_match("cp", "CommandPalette").unwrap().score
+                > fuzzy_match("cp", "scope").unwrap().score
+        );
+    }
+}
@@ -1,10 +1,11 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use bitflags::bitflags;
 use ratatui::{
     style::{Color, Modifier, Style},
     symbols,
 };
+use unicode_width::UnicodeWidthStr;
 
 bitflags! {
     /// Bitflags that can be composed to set the visible borders essentially on the block widget.
@@ -91,11 +92,72 @@ bitflags! {
     }
 }
 
+bitflags! {
+    /// The axes along which a [`scroll`](crate::ViewExt::scroll) view scrolls its content.
+    #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct ScrollAxes: u8 {
+        const HORIZONTAL = 0b0001;
+        const VERTICAL   = 0b0010;
+        const BOTH       = Self::HORIZONTAL.bits() | Self::VERTICAL.bits();
+    }
+}
+
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct BorderStyle {
     pub borders: Borders,
     pub kind: BorderKind,
     pub style: Style, // TODO generally find a better name for "Style" as it only applies modifiers and colors for each character
+    /// Styles of single sides, e.g. to highlight the top border of the active pane
+    pub side_styles: SideStyles,
+}
+
+/// The styles of the single sides of a border, which are patched on top of the style of the whole
+/// border, sides without a style are drawn with the style of the whole border.
+///
+/// The corners are drawn with the style of the top or bottom side they belong to.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SideStyles {
+    pub top: Option<Style>,
+    pub right: Option<Style>,
+    pub bottom: Option<Style>,
+    pub left: Option<Style>,
+}
+
+impl SideStyles {
+    /// Sets `style` for each of the `sides` (i.e. [`Borders::TOP`], [`Borders::RIGHT`],
+    /// [`Borders::BOTTOM`] or [`Borders::LEFT`]), corners in `sides` are ignored.
+    pub fn set(&mut self, sides: Borders, style: Style) {
+        for (side, side_style) in [
+            (Borders::TOP, &mut self.top),
+            (Borders::RIGHT, &mut self.right),
+            (Borders::BOTTOM, &mut self.bottom),
+            (Borders::LEFT, &mut self.left),
+        ] {
+            if sides.contains(side) {
+                *side_style = Some(style);
+            }
+        }
+    }
+}
+
+impl BorderStyle {
+    /// All borders drawn with custom `glyphs`, e.g. [`BorderGlyphs::ASCII`] for terminals
+    /// without box-drawing characters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |_| {
+    /// "Plain".border(BorderStyle::custom(BorderGlyphs::ASCII))
+    /// # });
+    /// ```
+    pub fn custom(glyphs: BorderGlyphs) -> Self {
+        BorderStyle {
+            borders: Borders::ALL,
+            kind: BorderKind::Custom(glyphs),
+            ..Default::default()
+        }
+    }
 }
 
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
@@ -106,6 +168,8 @@ pub enum BorderKind {
     Rounded,
     DoubleStraight,
     ThickStraight,
+    /// User-defined glyphs, e.g. for dashed borders
+    Custom(BorderGlyphs),
 }
 
 impl BorderKind {
@@ -115,6 +179,87 @@ impl BorderKind {
             BorderKind::Rounded => symbols::line::ROUNDED,
             BorderKind::DoubleStraight => symbols::line::DOUBLE,
             BorderKind::ThickStraight => symbols::line::THICK,
+            BorderKind::Custom(glyphs) => glyphs.into(),
+        }
+    }
+}
+
+/// The glyphs of a [`BorderKind::Custom`] border.
+///
+/// A border always takes a single cell per side, glyphs which aren't a single cell wide
+/// are drawn as a space instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BorderGlyphs {
+    pub horizontal: &'static str,
+    pub vertical: &'static str,
+    pub top_left: &'static str,
+    pub top_right: &'static str,
+    pub bottom_left: &'static str,
+    pub bottom_right: &'static str,
+    /// The junctions, where borders meet (e.g. `├`), which aren't drawn by the border itself
+    pub vertical_left: &'static str,
+    pub vertical_right: &'static str,
+    pub horizontal_down: &'static str,
+    pub horizontal_up: &'static str,
+    pub cross: &'static str,
+}
+
+impl BorderGlyphs {
+    /// Only ASCII characters (`+`, `-` and `|`), for terminals or fonts without box-drawing characters.
+    pub const ASCII: BorderGlyphs = BorderGlyphs {
+        horizontal: "-",
+        vertical: "|",
+        top_left: "+",
+        top_right: "+",
+        bottom_left: "+",
+        bottom_right: "+",
+        vertical_left: "+",
+        vertical_right: "+",
+        horizontal_down: "+",
+        horizontal_up: "+",
+        cross: "+",
+    };
+
+    /// Dashed lines with the corners and junctions of [`BorderKind::Straight`].
+    pub const DASHED: BorderGlyphs = BorderGlyphs {
+        horizontal: "╌",
+        vertical: "╎",
+        ..BorderGlyphs::from_line_set(symbols::line::NORMAL)
+    };
+
+    const fn from_line_set(set: symbols::line::Set) -> Self {
+        BorderGlyphs {
+            horizontal: set.horizontal,
+            vertical: set.vertical,
+            top_left: set.top_left,
+            top_right: set.top_right,
+            bottom_left: set.bottom_left,
+            bottom_right: set.bottom_right,
+            vertical_left: set.vertical_left,
+            vertical_right: set.vertical_right,
+            horizontal_down: set.horizontal_down,
+            horizontal_up: set.horizontal_up,
+            cross: set.cross,
+        }
+    }
+}
+
+impl From<BorderGlyphs> for symbols::line::Set {
+    fn from(glyphs: BorderGlyphs) -> Self {
+        // Wider glyphs would be drawn over the content or beyond the border
+        let cell = |glyph: &'static str| if glyph.width() == 1 { glyph } else { " " };
+        symbols::line::Set {
+            vertical: cell(glyphs.vertical),
+            horizontal: cell(glyphs.horizontal),
+            top_right: cell(glyphs.top_right),
+            top_left: cell(glyphs.top_left),
+            bottom_right: cell(glyphs.bottom_right),
+            bottom_left: cell(glyphs.bottom_left),
+            vertical_left: cell(glyphs.vertical_left),
+            vertical_right: cell(glyphs.vertical_right),
+            horizontal_down: cell(glyphs.horizontal_down),
+            horizontal_up: cell(glyphs.horizontal_up),
+            cross: cell(glyphs.cross),
         }
     }
 }
@@ -152,3 +297,87 @@ impl<V: Styleable + Clone> Styleable for Arc<V> {
         (**self).current_style()
     }
 }
+
+/// The alignment of content along an axis, e.g. of the last line in a [`Flow`](crate::Flow).
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+/// Where text is cut off and replaced with an ellipsis, when it doesn't fit into its box.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TruncateMode {
+    /// `…the end`
+    Start,
+    /// `The…end`
+    Middle,
+    /// `The end…`
+    #[default]
+    End,
+}
+
+/// How control characters (e.g. in logs or other external data) are displayed by [`Text`](crate::Text),
+/// as writing them to the terminal as-is could corrupt the display.
+///
+/// Tabs are always expanded to the next tab stop, see [`Text::tab_width`](crate::Text::tab_width).
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlChars {
+    /// Escape sequences are removed, other control characters are shown in caret notation,
+    /// e.g. `^C` for `\x03`, or as `�`
+    #[default]
+    Visible,
+    /// Escape sequences and other control characters are removed
+    Strip,
+    /// ANSI escape sequences, which set colors and modifiers (SGR), style the following text,
+    /// other escape sequences are removed and other control characters are shown like with
+    /// [`ControlChars::Visible`]
+    Ansi,
+}
+
+/// Glyphs which scrollable views like [`table`](crate::table) or [`scroll`](crate::ViewExt::scroll)
+/// show on their first or last visible row, when there's hidden content above or below.
+///
+/// By default `▲` and `▼` are shown at the end of the row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverflowIndicator {
+    pub(crate) above: Cow<'static, str>,
+    pub(crate) below: Cow<'static, str>,
+    pub(crate) alignment: Alignment,
+    pub(crate) style: Style,
+}
+
+impl OverflowIndicator {
+    pub fn new(above: impl Into<Cow<'static, str>>, below: impl Into<Cow<'static, str>>) -> Self {
+        OverflowIndicator {
+            above: above.into(),
+            below: below.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Where the glyphs are placed in their row.
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Patches the style of the row the glyphs are drawn on.
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Default for OverflowIndicator {
+    fn default() -> Self {
+        OverflowIndicator {
+            above: "▲".into(),
+            below: "▼".into(),
+            alignment: Alignment::End,
+            style: Style::default(),
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::{any::Any, borrow::Cow, marker::PhantomData};
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, EventHandler, View, ViewMarker};
+use crate::widget::{self, ChangeFlags, ConfirmStyles};
+
+/// The buttons of a [`confirm`] dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfirmButton {
+    Yes,
+    No,
+    Cancel,
+}
+
+pub struct Confirm<T, A, EH, CH = fn(&mut T) -> A> {
+    message: Cow<'static, str>,
+    yes_label: Cow<'static, str>,
+    no_label: Cow<'static, str>,
+    cancel: Option<(Cow<'static, str>, CH)>,
+    default_button: ConfirmButton,
+    on_result: EH,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct ConfirmState<EHS, CHS> {
+    on_result_id: Id,
+    on_result_state: EHS,
+    on_cancel: Option<(Id, CHS)>,
+}
+
+impl<T, A, EH, CH> Confirm<T, A, EH, CH> {
+    /// Sets the labels of the buttons, by default "Yes" and "No".
+    pub fn labels(
+        mut self,
+        yes: impl Into<Cow<'static, str>>,
+        no: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.yes_label = yes.into();
+        self.no_label = no.into();
+        self
+    }
+
+    /// Adds a third button with `label`, which calls `on_cancel` instead of the result handler.
+    ///
+    /// `Esc` presses this button instead of "No".
+    pub fn cancel<CH2: EventHandler<T, A>>(
+        self,
+        label: impl Into<Cow<'static, str>>,
+        on_cancel: CH2,
+    ) -> Confirm<T, A, EH, CH2> {
+        Confirm {
+            message: self.message,
+            yes_label: self.yes_label,
+            no_label: self.no_label,
+            cancel: Some((label.into(), on_cancel)),
+            default_button: self.default_button,
+            on_result: self.on_result,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The button which is selected when the dialog is shown, i.e. which `Enter` presses,
+    /// by default [`ConfirmButton::Yes`].
+    pub fn default_button(mut self, button: ConfirmButton) -> Self {
+        self.default_button = button;
+        self
+    }
+
+    fn buttons(&self) -> Vec<(ConfirmButton, String)> {
+        let mut buttons = vec![
+            (ConfirmButton::Yes, self.yes_label.to_string()),
+            (ConfirmButton::No, self.no_label.to_string()),
+        ];
+        if let Some((label, _)) = &self.cancel {
+            buttons.push((ConfirmButton::Cancel, label.to_string()));
+        }
+        buttons
+    }
+
+    fn resolve_styles(cx: &Cx) -> ConfirmStyles {
+        let theme = cx.theme();
+        ConfirmStyles {
+            text: theme.text.default.bg(theme.surface_variant()),
+            border: theme.border.focus.bg(theme.surface_variant()),
+            selected: theme.text.selected,
+        }
+    }
+}
+
+impl<T, A, EH, CH> ViewMarker for Confirm<T, A, EH, CH> {}
+
+impl<T, A, EH, CH> View<T, A> for Confirm<T, A, EH, CH>
+where
+    EH: EventHandler<T, A, bool>,
+    CH: EventHandler<T, A>,
+{
+    type State = ConfirmState<EH::State, CH::State>;
+
+    type Element = widget::Confirm;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let element = widget::Confirm::new(
+                cx.id_path(),
+                self.message.to_string(),
+                self.buttons(),
+                self.default_button,
+                Self::resolve_styles(cx),
+            );
+            let (on_result_id, on_result_state) = self.on_result.build(cx);
+            let state = ConfirmState {
+                on_result_id,
+                on_result_state,
+                on_cancel: self
+                    .cancel
+                    .as_ref()
+                    .map(|(_, on_cancel)| on_cancel.build(cx)),
+            };
+            (state, element)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let mut changeflags = element.set_message(&self.message)
+                | element.set_buttons(self.buttons(), self.default_button)
+                | element.set_styles(Self::resolve_styles(cx))
+                | self
+                    .on_result
+                    .rebuild(cx, &state.on_result_id, &mut state.on_result_state);
+            state.on_cancel = match (&self.cancel, state.on_cancel.take()) {
+                (Some((_, on_cancel)), Some((cancel_id, mut cancel_state))) => {
+                    changeflags |= on_cancel.rebuild(cx, &cancel_id, &mut cancel_state);
+                    Some((cancel_id, cancel_state))
+                }
+                (Some((_, on_cancel)), None) => Some(on_cancel.build(cx)),
+                (None, _) => None,
+            };
+            changeflags
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [id, rest_path @ ..] if *id == state.on_result_id => {
+                self.on_result
+                    .message(rest_path, &mut state.on_result_state, message, app_state)
+            }
+            [] if message.downcast_ref::<ConfirmButton>().is_some() => {
+                let button = *message.downcast::<ConfirmButton>().unwrap();
+                match (button, &self.cancel, &mut state.on_cancel) {
+                    (ConfirmButton::Cancel, Some((_, on_cancel)), Some((_, cancel_state))) => {
+                        on_cancel.message(&[], cancel_state, Box::new(()), app_state)
+                    }
+                    (ConfirmButton::Cancel, _, _) => MessageResult::Nop,
+                    (button, _, _) => self.on_result.message(
+                        &[],
+                        &mut state.on_result_state,
+                        Box::new(button == ConfirmButton::Yes),
+                        app_state,
+                    ),
+                }
+            }
+            [id, rest_path @ ..] => match (&self.cancel, &mut state.on_cancel) {
+                (Some((_, on_cancel)), Some((cancel_id, cancel_state))) if cancel_id == id => {
+                    on_cancel.message(rest_path, cancel_state, message, app_state)
+                }
+                _ => MessageResult::Stale(message),
+            },
+            [] => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// A dialog with `message` and "Yes"/"No" buttons, which calls `on_result` with whether "Yes" was pressed.
+///
+/// It's meant to be shown via [`modal`](crate::ViewExt::modal), which traps the mouse and keyboard
+/// input and the focus while it's shown. The dialog is closed by not passing it to `modal` anymore,
+/// usually by updating the app state in `on_result`.
+///
+/// The focused button is pressed with `Enter` (initially the [`default_button`](Confirm::default_button)),
+/// `Left`/`Right` and `Tab`/`BackTab` select another button and `Esc` presses "No"
+/// (or the [`cancel`](Confirm::cancel) button if there is one).
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// struct AppState {
+///     confirm_delete: bool,
+///     files: Vec<String>,
+/// }
+///
+/// # App::new(AppState { confirm_delete: false, files: vec![] }, move |state: &mut AppState| {
+/// let dialog = state.confirm_delete.then(|| {
+///     confirm("Delete all files?", |state: &mut AppState, yes: bool| {
+///         if yes {
+///             state.files.clear();
+///         }
+///         state.confirm_delete = false;
+///     })
+///     .labels("Delete", "Keep")
+///     .default_button(ConfirmButton::No)
+///     .cancel("Cancel", |state: &mut AppState| state.confirm_delete = false)
+/// });
+/// "[Delete all]"
+///     .on_click(|state: &mut AppState| state.confirm_delete = true)
+///     .fill_max_size(1.0)
+///     .modal(dialog)
+/// # });
+/// ```
+pub fn confirm<T, A, EH: EventHandler<T, A, bool>>(
+    message: impl Into<Cow<'static, str>>,
+    on_result: EH,
+) -> Confirm<T, A, EH> {
+    Confirm {
+        message: message.into(),
+        yes_label: "Yes".into(),
+        no_label: "No".into(),
+        cancel: None,
+        default_button: ConfirmButton::Yes,
+        on_result,
+        phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{test_key, test_mouse},
+        widget::{Key, MouseButton, MouseKind},
+        App, ViewExt,
+    };
+
+    #[derive(Default)]
+    struct State {
+        clicks: i32,
+        deletes: i32,
+        confirming: bool,
+    }
+
+    #[tokio::test]
+    async fn a_modal_confirm_dialog_traps_the_input_until_it_is_answered() {
+        let mut app = App::new(State::default(), |state: &mut State| {
+            let dialog = state.confirming.then(|| {
+                confirm("Delete?", |state: &mut State, yes: bool| {
+                    if yes {
+                        state.deletes += 1;
+                    }
+                    state.confirming = false;
+                })
+            });
+            format!("{} {}", state.clicks, state.deletes)
+                .on_click(|state: &mut State| {
+                    state.clicks += 1;
+                    state.confirming = true;
+                })
+                .fill_max_size(1.0)
+                .modal(dialog)
+        })
+        .await;
+        let click = [
+            test_mouse(MouseKind::Down(MouseButton::Left), 0, 0),
+            test_mouse(MouseKind::Up(MouseButton::Left), 0, 0),
+        ];
+        app.render_once().await.unwrap();
+        app.dispatch(click.clone()).await;
+        // the content is covered by the dialog
+        app.dispatch(click.clone()).await;
+        assert_eq!(app.screen_line(0), "1 0");
+
+        // "Yes" is the default button
+        app.dispatch([test_key(Key::Enter)]).await;
+        assert_eq!(app.screen_line(0), "1 1");
+
+        app.dispatch(click.clone()).await;
+        app.dispatch([test_key(Key::Esc)]).await;
+        assert_eq!(app.screen_line(0), "2 1");
+        app.dispatch(click).await;
+        assert_eq!(app.screen_line(0), "3 1");
+    }
+}
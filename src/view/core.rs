@@ -1,6 +1,9 @@
 use std::{collections::HashSet, sync::Arc};
 
-use crate::widget::{AnyWidget, ChangeFlags, Pod, Widget};
+use crate::{
+    widget::{AnyWidget, ChangeFlags, Pod, Widget},
+    Theme, WidgetBounds,
+};
 use futures_task::{ArcWake, Waker};
 use xilem_core::{Id, IdPath};
 
@@ -17,18 +20,24 @@ pub struct Cx {
     req_chan: tokio::sync::mpsc::Sender<IdPath>,
     pub rt: tokio::runtime::Handle,
     pub(crate) pending_async: HashSet<Id>,
+    theme: Theme,
+    widget_bounds: WidgetBounds,
 }
 
 impl Cx {
     pub(crate) fn new(
         req_chan: tokio::sync::mpsc::Sender<IdPath>,
         rt: tokio::runtime::Handle,
+        theme: Theme,
+        widget_bounds: WidgetBounds,
     ) -> Self {
         Cx {
             id_path: Vec::new(),
             req_chan: req_chan.clone(),
             rt,
             pending_async: HashSet::new(),
+            theme,
+            widget_bounds,
         }
     }
 
@@ -69,6 +78,24 @@ impl Cx {
         (id, result)
     }
 
+    /// The theme of the app, views should use it to style their widgets unless they have an explicit style.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Where the bounds of the views tagged with [`ViewExt::measure`](crate::ViewExt::measure) are recorded.
+    pub(crate) fn widget_bounds(&self) -> &WidgetBounds {
+        &self.widget_bounds
+    }
+
+    /// Runs `f` with `theme` as the theme of the views built or rebuilt within it.
+    pub(crate) fn with_theme<R>(&mut self, theme: Theme, f: impl FnOnce(&mut Cx) -> R) -> R {
+        let parent_theme = std::mem::replace(&mut self.theme, theme);
+        let result = f(self);
+        self.theme = parent_theme;
+        result
+    }
+
     pub fn waker(&self) -> Waker {
         futures_task::waker(Arc::new(MyWaker {
             id_path: self.id_path.clone(),
@@ -0,0 +1,66 @@
+use std::any::Any;
+
+use ratatui::style::Style;
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+pub struct Enabled<V> {
+    content: V,
+    enabled: bool,
+}
+
+impl<V> Enabled<V> {
+    pub(crate) fn new(content: V, enabled: bool) -> Self {
+        Enabled { content, enabled }
+    }
+
+    fn resolve_disabled_style(cx: &Cx) -> Style {
+        Style::default().fg(cx.theme().disabled())
+    }
+}
+
+impl<V> ViewMarker for Enabled<V> {}
+
+impl<T, A, V: View<T, A>> View<T, A> for Enabled<V> {
+    type State = V::State;
+
+    type Element = widget::Enabled;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, element) = self.content.build(cx);
+        let element = widget::Enabled::new(element, self.enabled, Self::resolve_disabled_style(cx));
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changeflags = element.set_enabled(self.enabled)
+            | element.set_disabled_style(Self::resolve_disabled_style(cx));
+        let content_el = element
+            .content()
+            .downcast_mut()
+            .expect("The enabled content widget changed its type, this should never happen!");
+        let content_changeflags = self
+            .content
+            .rebuild(cx, &prev.content, id, state, content_el);
+        changeflags | element.content().mark(content_changeflags)
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.content.message(id_path, state, message, app_state)
+    }
+}
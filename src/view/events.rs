@@ -1,5 +1,10 @@
-use super::{Cx, PendingTask, Styleable, View, ViewMarker};
-use crate::widget::{self, CatchMouseButton, ChangeFlags};
+use super::{
+    memoized_future::RunningTask, Cx, FutureState, PendingTask, Styleable, View, ViewMarker,
+};
+use crate::{
+    geometry::Point,
+    widget::{self, CatchMouseButton, ChangeFlags, MouseButton},
+};
 use futures_util::{Future, Stream, StreamExt};
 use ratatui::style::Style;
 use std::marker::PhantomData;
@@ -26,12 +31,10 @@ pub trait EventHandler<T, A = (), E = ()>: Send + Sync {
     // fn keep_alive(&self) -> bool;
 }
 
-// TODO A blanket implementation for F where <E, F: Fn(&mut T, E) -> A>
-//      needs the negative bounds feature (E: !() because of the implementation below)
-//      I think it makes sense to be more explicit and implement it for concrete events,
-//      instead of a blanket implementation for all kinds of events,
-//      to avoid having something like |&mut T, ()| {} where otherwise |&mut T| {} is sufficient (and more convenient to use)
-//      Manual implementations with custom event callbacks could probably be simplified (less boilerplate) with macros
+// A blanket implementation for F where <E, F: Fn(&mut T, E) -> A> needs the negative bounds
+// feature (E: !() because of the implementation below), so closures are only event handlers for the
+// events of trui (see `impl_callback_event_handler` below), custom events are wrapped with `callback`.
+// This also avoids something like |&mut T, ()| {} where otherwise |&mut T| {} is sufficient.
 
 impl<T, A, F: Fn(&mut T) -> A + Send + Sync> EventHandler<T, A> for F {
     type State = ();
@@ -84,6 +87,118 @@ macro_rules! impl_callback_event_handler {
     };
 }
 
+/// An event handler for events of any type `E`, e.g. the custom events of a widget, see [`callback`].
+pub struct Callback<F, E> {
+    callback: F,
+    phantom: PhantomData<fn(E)>,
+}
+
+impl<T, A, E: 'static, F: Fn(&mut T, E) -> A + Send + Sync> EventHandler<T, A, E>
+    for Callback<F, E>
+{
+    type State = ();
+
+    fn build(&self, _cx: &mut Cx) -> (Id, Self::State) {
+        (Id::next(), ())
+    }
+
+    fn rebuild(&self, _cx: &mut Cx, _id: &Id, _state: &mut Self::State) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn message(
+        &self,
+        id_path: &[xilem_core::Id],
+        _state: &mut Self::State,
+        event: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        debug_assert!(id_path.is_empty());
+        match event.downcast::<E>() {
+            Ok(event) => MessageResult::Action((self.callback)(app_state, *event)),
+            Err(message) => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// Turns a closure into an [`EventHandler`] for events of type `E`.
+///
+/// Closures are event handlers themselves for the events of the views of trui, which are
+/// `()` (`|state| ..`), [`MouseEvent`](crate::widget::MouseEvent), `bool`, `String`, `usize`, `(usize, bool)`,
+/// `HashSet<usize>` and [`Validation`](super::Validation) (`|state, event| ..`).
+/// Other events, like the ones of custom widgets, need this wrapper, as the orphan rule doesn't allow
+/// implementing `EventHandler` for closures outside of trui.
+/// A view of a custom widget calls the `message` method of the handler with the event (boxed as
+/// [`Any`](std::any::Any)), which the widget has sent as [`Message`](crate::widget::Message).
+///
+/// See [`callback_result`] for a closure which decides about the [`MessageResult`] itself.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// /// The event of a custom knob widget
+/// pub struct Turned(pub i32);
+///
+/// /// The view of the knob stores `on_turn` and calls its `build`, `rebuild` and `message` methods.
+/// fn knob<T, A>(on_turn: impl EventHandler<T, A, Turned>) -> impl EventHandler<T, A, Turned> {
+///     on_turn
+/// }
+///
+/// let on_turn = knob(callback(|volume: &mut i32, Turned(delta)| *volume += delta));
+/// ```
+pub fn callback<T, A, E, F: Fn(&mut T, E) -> A + Send + Sync>(callback: F) -> Callback<F, E> {
+    Callback {
+        callback,
+        phantom: PhantomData,
+    }
+}
+
+/// An event handler for events of any type `E`, which returns a [`MessageResult`], see [`callback_result`].
+pub struct ResultCallback<F, E> {
+    callback: F,
+    phantom: PhantomData<fn(E)>,
+}
+
+impl<T, A, E: 'static, F: Fn(&mut T, E) -> MessageResult<A> + Send + Sync> EventHandler<T, A, E>
+    for ResultCallback<F, E>
+{
+    type State = ();
+
+    fn build(&self, _cx: &mut Cx) -> (Id, Self::State) {
+        (Id::next(), ())
+    }
+
+    fn rebuild(&self, _cx: &mut Cx, _id: &Id, _state: &mut Self::State) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn message(
+        &self,
+        id_path: &[xilem_core::Id],
+        _state: &mut Self::State,
+        event: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        debug_assert!(id_path.is_empty());
+        match event.downcast::<E>() {
+            Ok(event) => (self.callback)(app_state, *event),
+            Err(message) => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// Like [`callback`], but the closure returns the [`MessageResult`], e.g. `MessageResult::Nop` to
+/// ignore an event without triggering a rebuild, or `MessageResult::RequestRebuild`.
+pub fn callback_result<T, A, E, F>(callback: F) -> ResultCallback<F, E>
+where
+    F: Fn(&mut T, E) -> MessageResult<A> + Send + Sync,
+{
+    ResultCallback {
+        callback,
+        phantom: PhantomData,
+    }
+}
+
 /// This currently broadcasts the messages to each of the sub event handlers.
 /// TODO should this filter instead, or is this usable at all?
 impl<T, A, E: Clone + 'static, E1: EventHandler<T, A, E>, E2: EventHandler<T, A, E>>
@@ -372,13 +487,145 @@ where
     }
 }
 
+pub struct AsyncEventHandlerState<FO> {
+    runtime: tokio::runtime::Handle,
+    waker: Waker,
+    task: Option<RunningTask<FO>>,
+}
+
+pub struct AsyncEventHandler<T, A, FO, F, FF, SF> {
+    #[allow(clippy::complexity)]
+    phantom: PhantomData<fn() -> (T, A, FO, F)>,
+    future_fn: FF,
+    state_fn: SF,
+}
+
+impl<T, A, FO, F, FF, SF> EventHandler<T, A> for AsyncEventHandler<T, A, FO, F, FF, SF>
+where
+    FO: Send + 'static,
+    F: Future<Output = FO> + Send + 'static,
+    FF: Fn(&mut T) -> F + Send + Sync,
+    SF: Fn(&mut T, FutureState<FO>) + Send + Sync,
+{
+    type State = AsyncEventHandlerState<FO>;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State) {
+        // The waker is bound to the id of this handler, so that the wake up is routed back to it
+        cx.with_new_id(|cx| AsyncEventHandlerState {
+            runtime: cx.rt.clone(),
+            waker: cx.waker(),
+            task: None,
+        })
+    }
+
+    fn rebuild(&self, cx: &mut Cx, id: &Id, state: &mut Self::State) -> ChangeFlags {
+        if state.task.is_some() {
+            cx.add_pending_async(*id)
+        }
+        ChangeFlags::empty()
+    }
+
+    fn message(
+        &self,
+        id_path: &[xilem_core::Id],
+        state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        if !id_path.is_empty() {
+            return MessageResult::Stale(message);
+        }
+        if message.downcast_ref::<AsyncWake>().is_some() {
+            let Some(task) = &mut state.task else {
+                return MessageResult::Stale(message);
+            };
+            match task.poll(&state.waker) {
+                Some(result) => {
+                    state.task = None;
+                    (self.state_fn)(app_state, result);
+                    MessageResult::RequestRebuild
+                }
+                None => MessageResult::Nop,
+            }
+        } else if message.downcast_ref::<()>().is_some() {
+            // A still running task of a previous event is aborted when it's replaced
+            let mut task = RunningTask::spawn(&state.runtime, (self.future_fn)(app_state));
+            match task.poll(&state.waker) {
+                Some(result) => {
+                    state.task = None;
+                    (self.state_fn)(app_state, result);
+                }
+                None => {
+                    state.task = Some(task);
+                    (self.state_fn)(app_state, FutureState::Pending);
+                }
+            }
+            MessageResult::RequestRebuild
+        } else {
+            MessageResult::Stale(message)
+        }
+    }
+}
+
+/// An event handler which runs the future returned by `future_fn` on the runtime.
+///
+/// `state_fn` is called with [`FutureState::Pending`] when the future is started and with the
+/// result when it's finished, so that the state of the operation can be stored in the app state
+/// and rendered. The future is aborted when the event handler is removed from the tree, or when
+/// the event is triggered again before the future has finished.
+///
+/// See [`ViewExt::on_click_async`](super::ViewExt::on_click_async) for an example.
+pub fn async_handler<T, A, FO, F, FF, SF>(
+    future_fn: FF,
+    state_fn: SF,
+) -> AsyncEventHandler<T, A, FO, F, FF, SF>
+where
+    FO: Send + 'static,
+    F: Future<Output = FO> + Send + 'static,
+    FF: Fn(&mut T) -> F + Send + Sync,
+    SF: Fn(&mut T, FutureState<FO>) + Send + Sync,
+{
+    AsyncEventHandler {
+        phantom: PhantomData,
+        future_fn,
+        state_fn,
+    }
+}
+
+/// Builds, rebuilds or removes the state of an optional event handler.
+pub(crate) fn rebuild_handler<T, A, E, EH: EventHandler<T, A, E>>(
+    cx: &mut Cx,
+    handler: Option<&EH>,
+    state: &mut Option<(Id, EH::State)>,
+) -> ChangeFlags {
+    let mut changeflags = ChangeFlags::empty();
+    *state = match (handler, state.take()) {
+        (Some(handler), Some((id, mut handler_state))) => {
+            changeflags |= handler.rebuild(cx, &id, &mut handler_state);
+            Some((id, handler_state))
+        }
+        (Some(handler), None) => Some(handler.build(cx)),
+        (None, _) => None,
+    };
+    changeflags
+}
+
 impl_callback_event_handler!(widget::MouseEvent);
+impl_callback_event_handler!(Point);
+impl_callback_event_handler!(MouseButton);
+impl_callback_event_handler!(bool);
+impl_callback_event_handler!(String);
+impl_callback_event_handler!(usize);
+impl_callback_event_handler!((usize, bool));
+impl_callback_event_handler!(std::collections::HashSet<usize>);
+impl_callback_event_handler!(super::Validation);
 
 // TODO some description
 // TODO Is this view useful at all? Should this be already abstracted (e.g. via the other views such as Hoverable, or Clickable)
 pub struct OnMouse<V, EH> {
     pub(crate) view: V,
     pub(crate) catch_event: CatchMouseButton,
+    pub(crate) stop_propagation: bool,
     pub(crate) event_handler: EH,
 }
 
@@ -387,6 +634,14 @@ impl<V, EH> OnMouse<V, EH> {
         self.catch_event = buttons;
         self
     }
+
+    /// Marks mouse events over the element as handled, so that they don't reach ancestors.
+    ///
+    /// See [`OnClick::stop_propagation`].
+    pub fn stop_propagation(mut self) -> Self {
+        self.stop_propagation = true;
+        self
+    }
 }
 
 impl<V, EH> ViewMarker for OnMouse<V, EH> {}
@@ -406,7 +661,12 @@ where
 
             (
                 (state, child_id, self.event_handler.build(cx)),
-                widget::OnMouse::new(element, cx.id_path(), self.catch_event),
+                widget::OnMouse::new(
+                    element,
+                    cx.id_path(),
+                    self.catch_event,
+                    self.stop_propagation,
+                ),
             )
         });
         (id, state, element)
@@ -432,6 +692,7 @@ where
                 ),
             );
 
+            element.stop_propagation = self.stop_propagation;
             element.element.mark(content_changeflags)
                 | self.event_handler.rebuild(cx, eh_id, eh_state)
         })
@@ -459,6 +720,156 @@ where
         }
     }
 }
+pub struct OnTerminalFocus<V, EH> {
+    pub(crate) view: V,
+    pub(crate) event_handler: EH,
+}
+
+impl<V, EH> ViewMarker for OnTerminalFocus<V, EH> {}
+
+impl<T, A, V, EH> View<T, A> for OnTerminalFocus<V, EH>
+where
+    V: View<T, A>,
+    EH: EventHandler<T, A, bool>,
+{
+    type State = (V::State, Id, (Id, EH::State));
+
+    type Element = widget::OnTerminalFocus;
+
+    fn build(&self, cx: &mut Cx) -> (xilem_core::Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let (child_id, state, element) = self.view.build(cx);
+
+            (
+                (state, child_id, self.event_handler.build(cx)),
+                widget::OnTerminalFocus::new(element, cx.id_path()),
+            )
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut xilem_core::Id,
+        (state, child_id, (eh_id, eh_state)): &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let content_changeflags = self.view.rebuild(
+                cx,
+                &prev.view,
+                child_id,
+                state,
+                element.element.downcast_mut().expect(
+                    "The terminal focus content widget changed its type, this should never happen!",
+                ),
+            );
+
+            element.element.mark(content_changeflags)
+                | self.event_handler.rebuild(cx, eh_id, eh_state)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[xilem_core::Id],
+        (state, child_id, (event_handler_id, event_handler_state)): &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> xilem_core::MessageResult<A> {
+        match id_path {
+            [first, rest_path @ ..] if first == child_id => {
+                self.view.message(rest_path, state, message, app_state)
+            }
+            [first, rest_path @ ..] if first == event_handler_id => {
+                self.event_handler
+                    .message(rest_path, event_handler_state, message, app_state)
+            }
+            [] => self
+                .event_handler
+                .message(&[], event_handler_state, message, app_state),
+            [..] => xilem_core::MessageResult::Stale(message),
+        }
+    }
+}
+
+pub struct OnOriginChange<V, EH> {
+    pub(crate) view: V,
+    pub(crate) event_handler: EH,
+}
+
+impl<V, EH> ViewMarker for OnOriginChange<V, EH> {}
+
+impl<T, A, V, EH> View<T, A> for OnOriginChange<V, EH>
+where
+    V: View<T, A>,
+    EH: EventHandler<T, A, Point>,
+{
+    type State = (V::State, Id, (Id, EH::State));
+
+    type Element = widget::OnOriginChange;
+
+    fn build(&self, cx: &mut Cx) -> (xilem_core::Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let (child_id, state, element) = self.view.build(cx);
+
+            (
+                (state, child_id, self.event_handler.build(cx)),
+                widget::OnOriginChange::new(element, cx.id_path()),
+            )
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut xilem_core::Id,
+        (state, child_id, (eh_id, eh_state)): &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let content_changeflags = self.view.rebuild(
+                cx,
+                &prev.view,
+                child_id,
+                state,
+                element.element.downcast_mut().expect(
+                    "The content widget of on_origin_change changed its type, this should never happen!",
+                ),
+            );
+
+            element.element.mark(content_changeflags)
+                | self.event_handler.rebuild(cx, eh_id, eh_state)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[xilem_core::Id],
+        (state, child_id, (event_handler_id, event_handler_state)): &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> xilem_core::MessageResult<A> {
+        match id_path {
+            [first, rest_path @ ..] if first == child_id => {
+                self.view.message(rest_path, state, message, app_state)
+            }
+            [first, rest_path @ ..] if first == event_handler_id => {
+                self.event_handler
+                    .message(rest_path, event_handler_state, message, app_state)
+            }
+            [] => self
+                .event_handler
+                .message(&[], event_handler_state, message, app_state),
+            [..] => xilem_core::MessageResult::Stale(message),
+        }
+    }
+}
+
 macro_rules! styled_event_views {
     ($($name:ident),*) => {
         $(
@@ -742,18 +1153,102 @@ macro_rules! event_views {
 event_views!(OnHover, OnHoverLost);
 
 // TODO this should probably be generated by the macro above (but for better IDE experience and easier prototyping this not yet)
-pub struct OnClick<V, EH> {
+pub struct OnClick<V, EH, E = ()> {
     pub(crate) view: V,
+    pub(crate) buttons: CatchMouseButton,
+    pub(crate) stop_propagation: bool,
+    pub(crate) focusable: bool,
     pub(crate) event_handler: EH,
+    pub(crate) phantom: PhantomData<fn() -> E>,
+}
+
+/// What the event handler of an [`OnClick`] view gets for a click, either nothing (`()`, see
+/// [`ViewExt::on_click`](super::ViewExt::on_click)), the position of the click
+/// (a [`Point`], see [`ViewExt::on_click_at`](super::ViewExt::on_click_at))
+/// or the clicked [`MouseButton`] (see [`ViewExt::on_button_click`](super::ViewExt::on_button_click)).
+pub trait ClickMessage: 'static {
+    /// The message for a click with `button` at `position`,
+    /// relative to the top-left corner of the clicked view.
+    fn from_click(position: Point, button: MouseButton) -> Self;
+}
+
+impl ClickMessage for () {
+    fn from_click(_position: Point, _button: MouseButton) -> Self {}
+}
+
+impl ClickMessage for Point {
+    fn from_click(position: Point, _button: MouseButton) -> Self {
+        position
+    }
+}
+
+impl ClickMessage for MouseButton {
+    fn from_click(_position: Point, button: MouseButton) -> Self {
+        button
+    }
 }
 
-impl<V, EH> ViewMarker for OnClick<V, EH> {}
+impl<V, EH, E> OnClick<V, EH, E> {
+    /// Marks the click as handled, so that it doesn't trigger click handlers of ancestors,
+    /// e.g. for a button inside a clickable row.
+    ///
+    /// Event handlers run after the event was dispatched through the widget tree,
+    /// which is why this has to be decided up front, instead of within the handler.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(0, move |selected_row| {
+    /// h_stack((
+    ///     "Row 1 ",
+    ///     "[Delete]"
+    ///         .on_click(|_: &mut i32| { /* delete the row */ })
+    ///         .stop_propagation(),
+    /// ))
+    /// .on_click(|selected_row: &mut i32| *selected_row = 1)
+    /// # });
+    /// ```
+    pub fn stop_propagation(mut self) -> Self {
+        self.stop_propagation = true;
+        self
+    }
+
+    /// Makes this view a stop of the focus traversal via `Tab`, so that it can be activated with
+    /// `Enter` or `Space` while it's focused, e.g. for buttons.
+    ///
+    /// The view is painted with the selected text style of the theme while it's focused.
+    /// Keys which are handled by a focused descendant, like a space typed into a text input,
+    /// don't activate it.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(0, move |count: &mut i32| {
+    /// "[Increment]"
+    ///     .on_click(|count: &mut i32| *count += 1)
+    ///     .focusable()
+    /// # });
+    /// ```
+    pub fn focusable(mut self) -> Self {
+        self.focusable = true;
+        self
+    }
 
-impl<T, A, V, EH> View<T, A> for OnClick<V, EH>
+    /// The mouse buttons which click this view, only the left one by default.
+    pub fn buttons(mut self, buttons: CatchMouseButton) -> Self {
+        self.buttons = buttons;
+        self
+    }
+}
+
+impl<V, EH, E> ViewMarker for OnClick<V, EH, E> {}
+
+impl<T, A, V, EH, E> View<T, A> for OnClick<V, EH, E>
 where
     V: View<T, A>,
     <V as View<T, A>>::Element: 'static,
-    EH: EventHandler<T, A>,
+    EH: EventHandler<T, A, E>,
+    E: ClickMessage,
 {
     type State = (V::State, Id, (Id, EH::State));
 
@@ -763,10 +1258,15 @@ where
         let (id, (state, element)) = cx.with_new_id(|cx| {
             let (child_id, state, element) = self.view.build(cx);
 
-            (
-                (state, child_id, self.event_handler.build(cx)),
-                widget::OnClick::new(element, cx.id_path()),
-            )
+            let mut element = widget::OnClick::new(
+                element,
+                cx.id_path(),
+                self.buttons,
+                self.stop_propagation,
+                cx.theme().text.selected,
+            );
+            let _ = element.set_focusable(self.focusable);
+            ((state, child_id, self.event_handler.build(cx)), element)
         });
         (id, state, element)
     }
@@ -790,7 +1290,11 @@ where
                      this should never happen!",
                 ),
             );
+            element.buttons = self.buttons;
+            element.stop_propagation = self.stop_propagation;
             element.element.mark(element_changeflags)
+                | element.set_focusable(self.focusable)
+                | element.set_focus_style(cx.theme().text.selected)
                 | self.event_handler.rebuild(cx, eh_id, eh_state)
         })
     }
@@ -810,42 +1314,63 @@ where
                 self.event_handler
                     .message(rest_path, event_handler_state, message, app_state)
             }
-            [] => self
-                .event_handler
-                .message(&[], event_handler_state, message, app_state),
+            [] => {
+                let message = match message.downcast::<widget::Click>() {
+                    Ok(click) => Box::new(E::from_click(click.position, click.button)),
+                    Err(message) => message,
+                };
+                self.event_handler
+                    .message(&[], event_handler_state, message, app_state)
+            }
             [..] => xilem_core::MessageResult::Stale(message),
         }
     }
 }
 
-impl<V: Styleable, EH> Styleable for OnClick<V, EH> {
-    type Output = OnClick<<V as Styleable>::Output, EH>;
+impl<V: Styleable, EH, E> Styleable for OnClick<V, EH, E> {
+    type Output = OnClick<<V as Styleable>::Output, EH, E>;
 
     fn fg(self, color: ratatui::style::Color) -> Self::Output {
         OnClick {
             view: self.view.fg(color),
+            buttons: self.buttons,
+            stop_propagation: self.stop_propagation,
+            focusable: self.focusable,
             event_handler: self.event_handler,
+            phantom: PhantomData,
         }
     }
 
     fn bg(self, color: ratatui::style::Color) -> Self::Output {
         OnClick {
             view: self.view.bg(color),
+            buttons: self.buttons,
+            stop_propagation: self.stop_propagation,
+            focusable: self.focusable,
             event_handler: self.event_handler,
+            phantom: PhantomData,
         }
     }
 
     fn modifier(self, modifier: ratatui::style::Modifier) -> Self::Output {
         OnClick {
             view: self.view.modifier(modifier),
+            buttons: self.buttons,
+            stop_propagation: self.stop_propagation,
+            focusable: self.focusable,
             event_handler: self.event_handler,
+            phantom: PhantomData,
         }
     }
 
     fn style(self, style: ratatui::style::Style) -> Self::Output {
         OnClick {
             view: self.view.style(style),
+            buttons: self.buttons,
+            stop_propagation: self.stop_propagation,
+            focusable: self.focusable,
             event_handler: self.event_handler,
+            phantom: PhantomData,
         }
     }
 
@@ -853,3 +1378,258 @@ impl<V: Styleable, EH> Styleable for OnClick<V, EH> {
         self.view.current_style()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::{test_key, test_mouse, App, TestGate},
+        geometry::Point,
+        text_input, v_stack,
+        widget::{CatchMouseButton, Event, Key, MouseButton, MouseKind},
+        FutureState, Position, ViewExt,
+    };
+
+    /// A press and release of `button` at `column` and `row`
+    fn click(button: MouseButton, column: i16, row: i16) -> [Event; 2] {
+        [
+            test_mouse(MouseKind::Down(button), column, row),
+            test_mouse(MouseKind::Up(button), column, row),
+        ]
+    }
+
+    #[tokio::test]
+    async fn terminal_focus_changes_are_passed_to_the_handler() {
+        let mut app = App::new(true, |focused: &mut bool| {
+            (if *focused { "focused" } else { "background" })
+                .on_terminal_focus(|focused: &mut bool, gained: bool| *focused = gained)
+        })
+        .await;
+        app.render_once().await.unwrap();
+        app.dispatch([Event::FocusLost]).await;
+        assert_eq!(app.screen_line(0), "background");
+        app.dispatch([Event::FocusGained]).await;
+        assert_eq!(app.screen_line(0), "focused");
+    }
+
+    #[tokio::test]
+    async fn clicks_are_only_focusable_when_opted_in() {
+        let mut app = App::new(0, |count: &mut i32| {
+            format!("{count}").on_click(|count: &mut i32| *count += 1)
+        })
+        .await;
+        app.render_once().await.unwrap();
+        app.dispatch([test_key(Key::Tab)]).await;
+        app.dispatch([test_key(Key::Enter)]).await;
+        assert_eq!(app.screen_line(0), "0");
+    }
+
+    #[tokio::test]
+    async fn enter_and_space_activate_a_focused_clickable_view_once() {
+        let mut app = App::new((0, 0), |(inner, outer): &mut (i32, i32)| {
+            format!("{inner} {outer}")
+                .on_click(|(inner, _): &mut (i32, i32)| *inner += 1)
+                .focusable()
+                .on_click(|(_, outer): &mut (i32, i32)| *outer += 1)
+                .focusable()
+        })
+        .await;
+        app.render_once().await.unwrap();
+        // the focus traversal visits parents before their children
+        app.dispatch([test_key(Key::Tab)]).await;
+        app.dispatch([test_key(Key::Enter)]).await;
+        app.dispatch([test_key(Key::Char(' '))]).await;
+        assert_eq!(app.screen_line(0), "0 2");
+
+        app.dispatch([test_key(Key::Tab)]).await;
+        app.dispatch([test_key(Key::Enter)]).await;
+        assert_eq!(app.screen_line(0), "1 2");
+    }
+
+    #[tokio::test]
+    async fn keys_handled_by_a_focused_descendant_dont_activate_it() {
+        let mut app = App::new((String::new(), 0), |(text, count): &mut (String, i32)| {
+            v_stack((
+                text_input(
+                    text.clone(),
+                    |(text, _): &mut (String, i32), new: String| {
+                        *text = new;
+                    },
+                ),
+                format!("[{text}] {count}"),
+            ))
+            .on_click(|(_, count): &mut (String, i32)| *count += 1)
+            .focusable()
+        })
+        .await;
+        app.render_once().await.unwrap();
+        app.dispatch([test_key(Key::Tab)]).await;
+        app.dispatch([test_key(Key::Tab)]).await;
+        app.dispatch([test_key(Key::Char(' '))]).await;
+        assert_eq!(app.screen_line(1), "[ ] 0");
+    }
+
+    #[tokio::test]
+    async fn clicks_propagate_to_outer_handlers_unless_stopped() {
+        for stop_propagation in [false, true] {
+            let mut app = App::new((0, 0), move |(inner, outer): &mut (i32, i32)| {
+                let view =
+                    format!("{inner} {outer}").on_click(|(inner, _): &mut (i32, i32)| *inner += 1);
+                let view = if stop_propagation {
+                    view.stop_propagation()
+                } else {
+                    view
+                };
+                view.on_click(|(_, outer): &mut (i32, i32)| *outer += 1)
+            })
+            .await;
+            app.render_once().await.unwrap();
+            app.dispatch(click(MouseButton::Left, 0, 0)).await;
+            let expected = if stop_propagation { "1 0" } else { "1 1" };
+            assert_eq!(app.screen_line(0), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn a_pending_async_handler_is_aborted_when_it_is_triggered_again() {
+        let gate = TestGate::new();
+        let app_gate = gate.clone();
+        let mut app = App::new(
+            (0, "idle".to_string()),
+            move |(_, shown): &mut (u32, String)| {
+                let gate = app_gate.clone();
+                shown.clone().on_click_async(
+                    move |(clicks, _): &mut (u32, String)| {
+                        *clicks += 1;
+                        gate.pass(format!("loaded {clicks}"))
+                    },
+                    |(_, shown): &mut (u32, String), state| {
+                        *shown = match state {
+                            FutureState::Ready(loaded) => loaded,
+                            _ => "pending".to_string(),
+                        };
+                    },
+                )
+            },
+        )
+        .await;
+        app.render_once().await.unwrap();
+        app.dispatch(click(MouseButton::Left, 0, 0)).await;
+        app.dispatch(click(MouseButton::Left, 0, 0)).await;
+        assert_eq!(app.screen_line(0), "pending");
+        app.render_until(|_| gate.dropped() == 1).await;
+
+        gate.open();
+        app.render_until(|app| app.screen_line(0) != "pending")
+            .await;
+        assert_eq!(app.screen_line(0), "loaded 2");
+        assert_eq!(gate.dropped(), 1);
+    }
+
+    #[tokio::test]
+    async fn click_positions_are_local_to_the_view() {
+        let mut app = App::new(Vec::new(), |positions: &mut Vec<(f64, f64)>| {
+            v_stack((
+                format!("{positions:?}"),
+                "0123456789".on_click_at(|positions: &mut Vec<(f64, f64)>, position: Point| {
+                    positions.push((position.x, position.y));
+                }),
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        // the first and last cell of the view
+        app.dispatch(click(MouseButton::Left, 0, 1)).await;
+        app.dispatch(click(MouseButton::Left, 9, 1)).await;
+        assert_eq!(app.screen_line(0), "[(0.0, 0.0), (9.0, 0.0)]");
+    }
+
+    #[tokio::test]
+    async fn only_the_left_button_clicks_by_default() {
+        let mut app = App::new(0, |count: &mut i32| {
+            format!("{count}").on_click(|count: &mut i32| *count += 1)
+        })
+        .await;
+        app.render_once().await.unwrap();
+        app.dispatch(click(MouseButton::Right, 0, 0)).await;
+        app.dispatch(click(MouseButton::Middle, 0, 0)).await;
+        assert_eq!(app.screen_line(0), "0");
+        app.dispatch(click(MouseButton::Left, 0, 0)).await;
+        assert_eq!(app.screen_line(0), "1");
+    }
+
+    #[tokio::test]
+    async fn the_buttons_which_click_a_view_can_be_chosen() {
+        let mut app = App::new(0, |count: &mut i32| {
+            format!("{count}")
+                .on_click(|count: &mut i32| *count += 1)
+                .buttons(CatchMouseButton::RIGHT)
+        })
+        .await;
+        app.render_once().await.unwrap();
+        app.dispatch(click(MouseButton::Left, 0, 0)).await;
+        assert_eq!(app.screen_line(0), "0");
+        app.dispatch(click(MouseButton::Right, 0, 0)).await;
+        assert_eq!(app.screen_line(0), "1");
+    }
+
+    #[tokio::test]
+    async fn origin_changes_are_only_reported_when_the_view_moves() {
+        type State = (u32, Point, u16);
+        let mut app = App::new((0, Point::ZERO, 0), |(calls, origin, shift): &mut State| {
+            v_stack((
+                format!("{calls} {} {}", origin.x, origin.y),
+                "anchor"
+                    .on_origin_change(|(calls, origin, _): &mut State, new: Point| {
+                        *calls += 1;
+                        *origin = new;
+                    })
+                    .margin((*shift, Position::LEFT)),
+            ))
+            .on_click(|(_, _, shift): &mut State| *shift += 1)
+        })
+        .await;
+        // the message of the layout is handled with the next render
+        app.render_once().await.unwrap();
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "1 0 1");
+
+        // laid out again, without moving
+        app.config.terminal.backend_mut().resize(70, 40);
+        app.render_once().await.unwrap();
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "1 0 1");
+
+        app.dispatch(click(MouseButton::Left, 0, 0)).await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "2 1 1");
+    }
+
+    #[tokio::test]
+    async fn on_click_async_reports_the_state_of_the_future() {
+        let gate = TestGate::new();
+        let app_gate = gate.clone();
+        let mut app = App::new(None, move |weather: &mut Option<FutureState<String>>| {
+            let gate = app_gate.clone();
+            let shown = match weather {
+                None => "refresh".to_string(),
+                Some(FutureState::Pending) => "loading".to_string(),
+                Some(FutureState::Ready(weather)) => weather.clone(),
+                Some(FutureState::Error(err)) => err.clone(),
+            };
+            shown.on_click_async(
+                move |_: &mut Option<FutureState<String>>| gate.pass("sunny".to_string()),
+                |weather: &mut Option<FutureState<String>>, state| *weather = Some(state),
+            )
+        })
+        .await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "refresh");
+        app.dispatch(click(MouseButton::Left, 0, 0)).await;
+        assert_eq!(app.screen_line(0), "loading");
+
+        gate.open();
+        app.render_until(|app| app.screen_line(0) != "loading")
+            .await;
+        assert_eq!(app.screen_line(0), "sunny");
+    }
+}
@@ -0,0 +1,172 @@
+use super::{Alignment, Cx, View, ViewMarker, ViewSequence};
+use crate::{
+    geometry::Axis,
+    widget::{self, ChangeFlags},
+};
+use std::{any::Any, marker::PhantomData};
+use xilem_core::{Id, VecSplice};
+
+pub struct Flow<T, A, VT> {
+    children: VT,
+    axis: Axis,
+    spacing: f64,
+    line_spacing: f64,
+    last_line_alignment: Alignment,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<T, A, VT> Flow<T, A, VT> {
+    /// The space between two children in a line.
+    pub fn spacing(mut self, spacing: f64) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// The space between two lines.
+    pub fn line_spacing(mut self, line_spacing: f64) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    /// The alignment of the last line, relative to the longest line.
+    pub fn last_line_alignment(mut self, alignment: Alignment) -> Self {
+        self.last_line_alignment = alignment;
+        self
+    }
+}
+
+impl<T, A, VT> ViewMarker for Flow<T, A, VT> {}
+
+impl<T, A, VT: ViewSequence<T, A>> View<T, A> for Flow<T, A, VT> {
+    type State = VT::State;
+
+    type Element = widget::Flow;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let mut elements = vec![];
+        let (id, state) = cx.with_new_id(|cx| self.children.build(cx, &mut elements));
+        let flow = widget::Flow::new(
+            elements,
+            self.axis,
+            self.spacing,
+            self.line_spacing,
+            self.last_line_alignment,
+        );
+        (id, state, flow)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changeflags = element.set_axis(self.axis)
+            | element.set_spacing(self.spacing, self.line_spacing)
+            | element.set_last_line_alignment(self.last_line_alignment);
+
+        let mut scratch = vec![];
+        let mut splice = VecSplice::new(&mut element.children, &mut scratch);
+
+        changeflags
+            | cx.with_id(*id, |cx| {
+                self.children
+                    .rebuild(cx, &prev.children, state, &mut splice)
+            })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        event: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> xilem_core::MessageResult<A> {
+        self.children.message(id_path, state, event, app_state)
+    }
+}
+
+/// Lays out its children in rows, a child that doesn't fit into the current row anymore
+/// is moved to the next row.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new((), move |()| {
+/// h_flow((
+///     "#rust".border(BorderKind::Rounded),
+///     "#tui".border(BorderKind::Rounded),
+///     "#xilem".border(BorderKind::Rounded),
+/// ))
+/// .spacing(1.0)
+/// .last_line_alignment(Alignment::Center)
+/// # });
+/// ```
+pub fn h_flow<T, A, VT: ViewSequence<T, A>>(children: VT) -> Flow<T, A, VT> {
+    Flow {
+        children,
+        axis: Axis::Horizontal,
+        spacing: 0.0,
+        line_spacing: 0.0,
+        last_line_alignment: Alignment::Start,
+        phantom: PhantomData,
+    }
+}
+
+/// Lays out its children in columns, a child that doesn't fit into the current column anymore
+/// is moved to the next column.
+pub fn v_flow<T, A, VT: ViewSequence<T, A>>(children: VT) -> Flow<T, A, VT> {
+    Flow {
+        children,
+        axis: Axis::Vertical,
+        spacing: 0.0,
+        line_spacing: 0.0,
+        last_line_alignment: Alignment::Start,
+        phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ratatui::{buffer::Buffer, layout::Size};
+
+    use crate::test_helper::render_view;
+
+    use super::*;
+
+    struct AppState;
+
+    #[tokio::test]
+    async fn wraps_children_into_the_next_row() {
+        let _guard = crate::test_helper::init_tracing("wraps_children_into_the_next_row").unwrap();
+
+        let local_set = tokio::task::LocalSet::new();
+        local_set
+            .run_until(async {
+                let sut = Arc::new(
+                    h_flow(("aaa", "bb", "cccc"))
+                        .spacing(1.0)
+                        .last_line_alignment(Alignment::End),
+                );
+                let buffer = render_view(
+                    Size {
+                        width: 8,
+                        height: 3,
+                    },
+                    sut,
+                    AppState,
+                )
+                .await;
+
+                assert_eq!(
+                    buffer,
+                    Buffer::with_lines(vec!["aaa bb  ", "  cccc  ", "        "])
+                );
+            })
+            .await
+    }
+}
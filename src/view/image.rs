@@ -0,0 +1,275 @@
+use std::sync::Arc;
+
+use ratatui::style::{Color, Style};
+use xilem_core::{Id, MessageResult};
+
+use super::{CanvasContent, Cx, Grid, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+/// A buffer of RGBA pixels, which can be shown with an [`image`] view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl RgbaImage {
+    /// An image from `width * height` RGBA pixels with 4 bytes each, row by row.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bytes` doesn't match the size.
+    pub fn from_rgba(width: usize, height: usize, bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), width * height * 4, "RGBA buffer of wrong size");
+        let pixels = bytes
+            .chunks_exact(4)
+            .map(|p| [p[0], p[1], p[2], p[3]])
+            .collect();
+        RgbaImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// An opaque image from `width * height` RGB pixels with 3 bytes each, row by row.
+    ///
+    /// # Panics
+    ///
+    /// If the length of `bytes` doesn't match the size.
+    pub fn from_rgb(width: usize, height: usize, bytes: &[u8]) -> Self {
+        assert_eq!(bytes.len(), width * height * 3, "RGB buffer of wrong size");
+        let pixels = bytes
+            .chunks_exact(3)
+            .map(|p| [p[0], p[1], p[2], u8::MAX])
+            .collect();
+        RgbaImage {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The color of the pixel at `x`/`y` of this image scaled to `width` x `height` pixels.
+    fn sample(
+        &self,
+        x: usize,
+        y: usize,
+        width: usize,
+        height: usize,
+        filter: ImageFilter,
+    ) -> [u8; 4] {
+        if self.width == 0 || self.height == 0 {
+            return [0; 4];
+        }
+        match filter {
+            ImageFilter::Nearest => {
+                // the source pixel under the center of the target pixel
+                let sx = ((2 * x + 1) * self.width / (2 * width)).min(self.width - 1);
+                let sy = ((2 * y + 1) * self.height / (2 * height)).min(self.height - 1);
+                self.pixels[sy * self.width + sx]
+            }
+            ImageFilter::Box => {
+                let x0 = x * self.width / width;
+                let x1 = ((x + 1) * self.width / width).clamp(x0 + 1, self.width);
+                let y0 = y * self.height / height;
+                let y1 = ((y + 1) * self.height / height).clamp(y0 + 1, self.height);
+
+                // the colors are weighted by alpha, so that transparent pixels don't darken the result
+                let (mut sum, mut alpha) = ([0u64; 3], 0u64);
+                for sy in y0..y1 {
+                    for [r, g, b, a] in &self.pixels[sy * self.width + x0..sy * self.width + x1] {
+                        let a = *a as u64;
+                        sum[0] += *r as u64 * a;
+                        sum[1] += *g as u64 * a;
+                        sum[2] += *b as u64 * a;
+                        alpha += a;
+                    }
+                }
+                if alpha == 0 {
+                    return [0; 4];
+                }
+                let count = ((x1 - x0) * (y1 - y0)) as u64;
+                [
+                    (sum[0] / alpha) as u8,
+                    (sum[1] / alpha) as u8,
+                    (sum[2] / alpha) as u8,
+                    (alpha / count) as u8,
+                ]
+            }
+        }
+    }
+}
+
+/// How an [`image`] is scaled to the size of the view.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFilter {
+    /// Every cell shows the pixel under its center, which keeps hard edges (e.g. of pixel art).
+    #[default]
+    Nearest,
+    /// Every cell shows the average of all pixels it covers, which is smoother when downscaling photos.
+    Box,
+}
+
+/// Pixels with less alpha are transparent, i.e. they keep the colors of the cells behind them.
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// Packs two vertically stacked pixels into one cell, using the half block glyphs with the
+/// upper pixel as foreground and the lower as background color.
+fn half_block(upper: [u8; 4], lower: [u8; 4]) -> (char, Style) {
+    let color = |[r, g, b, _]: [u8; 4]| Color::Rgb(r, g, b);
+    match (upper[3] >= ALPHA_THRESHOLD, lower[3] >= ALPHA_THRESHOLD) {
+        (true, true) => ('▀', Style::default().fg(color(upper)).bg(color(lower))),
+        (true, false) => ('▀', Style::default().fg(color(upper))),
+        (false, true) => ('▄', Style::default().fg(color(lower))),
+        (false, false) => (' ', Style::default()),
+    }
+}
+
+/// Renders `image` scaled to `width` x `height` cells, i.e. `width` x `2 * height` pixels.
+fn half_block_grid(image: &RgbaImage, width: u16, height: u16, filter: ImageFilter) -> Grid {
+    let mut grid = Grid::new(width, height);
+    let (w, h) = (width as usize, 2 * height as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let upper = image.sample(x as usize, 2 * y as usize, w, h, filter);
+            let lower = image.sample(x as usize, 2 * y as usize + 1, w, h, filter);
+            let (symbol, style) = half_block(upper, lower);
+            grid.set(x, y, symbol, style);
+        }
+    }
+    grid
+}
+
+pub struct Image {
+    image: Arc<RgbaImage>,
+    filter: ImageFilter,
+}
+
+impl Image {
+    /// How the image is scaled, [`ImageFilter::Nearest`] by default.
+    pub fn filter(mut self, filter: ImageFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    fn content(&self) -> CanvasContent {
+        let (image, filter) = (self.image.clone(), self.filter);
+        CanvasContent::Draw {
+            natural_size: (image.width as u16, image.height.div_ceil(2) as u16),
+            draw: Arc::new(move |width, height| half_block_grid(&image, width, height, filter)),
+        }
+    }
+}
+
+impl ViewMarker for Image {}
+
+impl<T, A> View<T, A> for Image {
+    type State = ();
+
+    type Element = widget::Canvas;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, element) = cx.with_new_id(|_| widget::Canvas::new(self.content()));
+        (id, (), element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        prev: &Self,
+        _id: &mut Id,
+        _state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let unchanged = (Arc::ptr_eq(&self.image, &prev.image) || self.image == prev.image)
+            && self.filter == prev.filter;
+        if unchanged {
+            ChangeFlags::empty()
+        } else {
+            element.set_content(&self.content())
+        }
+    }
+
+    fn message(
+        &self,
+        _id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut T,
+    ) -> MessageResult<A> {
+        MessageResult::Stale(message)
+    }
+}
+
+/// Shows `image` with two vertically stacked pixels per cell (via the `▀`/`▄` half blocks).
+///
+/// The image is stretched to the size given by its parent, in unbounded directions it has its
+/// natural size of one column per pixel and one row per two pixels.
+/// Pixels with an alpha below 50% are transparent, the colors are always [`Color::Rgb`],
+/// so a terminal with true color support is needed to show them correctly.
+///
+/// # Examples
+/// ```
+/// # use std::sync::Arc;
+/// # use trui::*;
+/// // a 2x2 checkerboard
+/// let logo = Arc::new(RgbaImage::from_rgb(
+///     2,
+///     2,
+///     &[255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255],
+/// ));
+/// # App::new((), move |()| {
+/// image(logo.clone())
+///     .filter(ImageFilter::Box)
+///     .fill_max_size(0.5)
+/// # });
+/// ```
+pub fn image(image: impl Into<Arc<RgbaImage>>) -> Image {
+    Image {
+        image: image.into(),
+        filter: ImageFilter::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_filter_averages_opaque_pixels() {
+        let image = RgbaImage::from_rgba(2, 1, &[200, 100, 0, 255, /* transparent */ 0, 0, 0, 0]);
+        assert_eq!(
+            image.sample(0, 0, 1, 1, ImageFilter::Box),
+            [200, 100, 0, 127]
+        );
+        assert_eq!(image.sample(0, 0, 1, 1, ImageFilter::Nearest), [0, 0, 0, 0]);
+        assert_eq!(
+            image.sample(0, 0, 2, 1, ImageFilter::Nearest),
+            [200, 100, 0, 255]
+        );
+    }
+
+    #[test]
+    fn two_pixels_are_packed_into_a_half_block() {
+        let image = RgbaImage::from_rgb(1, 2, &[255, 0, 0, 0, 0, 255]);
+        let grid = half_block_grid(&image, 1, 1, ImageFilter::Nearest);
+        let style = Style::default()
+            .fg(Color::Rgb(255, 0, 0))
+            .bg(Color::Rgb(0, 0, 255));
+        assert_eq!(grid.get(0, 0), Some(('▀', style)));
+
+        let transparent = [0, 0, 0, 0];
+        assert_eq!(half_block(transparent, [0, 255, 0, 255]).0, '▄');
+        assert_eq!(half_block(transparent, transparent).0, ' ');
+    }
+}
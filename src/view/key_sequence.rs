@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use crate::widget::{Key, KeyEvent, Modifiers};
+use xilem_core::Id;
+
+use super::{Cx, EventHandler, View, ViewMarker};
+use crate::widget::{self, normalize_key, ChangeFlags, KeyStroke};
+
+/// The default time after which the keys of an incomplete sequence are dropped.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A sequence of keys like `g g` in vim, which is bound to an id via
+/// [`on_key_sequences`](crate::ViewExt::on_key_sequences).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence {
+    pub(crate) id: String,
+    pub(crate) keys: Vec<KeyStroke>,
+}
+
+impl KeySequence {
+    /// A sequence of keys with modifiers, e.g. `KeyEvent::new(Key::Char('w'), Modifiers::CONTROL)`
+    /// or just a `Key`.
+    ///
+    /// `Shift` is ignored for characters, an uppercase character has to be used instead.
+    pub fn new(id: impl Into<String>, keys: impl IntoIterator<Item = impl Into<KeyEvent>>) -> Self {
+        KeySequence {
+            id: id.into(),
+            keys: keys
+                .into_iter()
+                .map(|key| normalize_key(&key.into()))
+                .collect(),
+        }
+    }
+
+    /// A sequence of characters without modifiers, e.g. `"gg"`.
+    pub fn chars(id: impl Into<String>, chars: &str) -> Self {
+        KeySequence {
+            id: id.into(),
+            keys: chars
+                .chars()
+                .map(|c| (Key::Char(c), Modifiers::NONE))
+                .collect(),
+        }
+    }
+}
+
+pub struct KeySequences<V, EH> {
+    view: V,
+    sequences: Vec<KeySequence>,
+    timeout: Duration,
+    event_handler: EH,
+}
+
+impl<V, EH> KeySequences<V, EH> {
+    pub(crate) fn new(view: V, sequences: Vec<KeySequence>, event_handler: EH) -> Self {
+        KeySequences {
+            view,
+            sequences,
+            timeout: DEFAULT_TIMEOUT,
+            event_handler,
+        }
+    }
+
+    /// The time after which the keys of an incomplete sequence are dropped, by default one second.
+    ///
+    /// If the keys are a complete sequence, which is also the start of a longer one, it's completed then.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl<V, EH> ViewMarker for KeySequences<V, EH> {}
+
+impl<T, A, V, EH> View<T, A> for KeySequences<V, EH>
+where
+    V: View<T, A>,
+    EH: EventHandler<T, A, String>,
+{
+    type State = (V::State, Id, (Id, EH::State));
+
+    type Element = widget::KeySequences;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let (child_id, state, element) = self.view.build(cx);
+
+            (
+                (state, child_id, self.event_handler.build(cx)),
+                widget::KeySequences::new(
+                    element,
+                    cx.id_path(),
+                    self.sequences.clone(),
+                    self.timeout,
+                ),
+            )
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        (state, child_id, (eh_id, eh_state)): &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let content_changeflags = self.view.rebuild(
+                cx,
+                &prev.view,
+                child_id,
+                state,
+                element.element.downcast_mut().expect(
+                    "The key sequences content widget changed its type, this should never happen!",
+                ),
+            );
+
+            if element.sequences != self.sequences {
+                element.sequences = self.sequences.clone();
+            }
+            element.timeout = self.timeout;
+            element.element.mark(content_changeflags)
+                | self.event_handler.rebuild(cx, eh_id, eh_state)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        (state, child_id, (event_handler_id, event_handler_state)): &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> xilem_core::MessageResult<A> {
+        match id_path {
+            [first, rest_path @ ..] if first == child_id => {
+                self.view.message(rest_path, state, message, app_state)
+            }
+            [first, rest_path @ ..] if first == event_handler_id => {
+                self.event_handler
+                    .message(rest_path, event_handler_state, message, app_state)
+            }
+            [] => self
+                .event_handler
+                .message(&[], event_handler_state, message, app_state),
+            [..] => xilem_core::MessageResult::Stale(message),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::{any::Any, marker::PhantomData};
+
+use xilem_core::{Id, MessageResult, VecSplice};
+
+use super::{Cx, View, ViewMarker, ViewSequence};
+use crate::widget::{self, ChangeFlags};
+
+pub struct Layers<T, A, VT> {
+    children: VT,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<T, A, VT> ViewMarker for Layers<T, A, VT> {}
+
+impl<T, A, VT: ViewSequence<T, A>> View<T, A> for Layers<T, A, VT> {
+    type State = VT::State;
+
+    type Element = widget::Layers;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let mut elements = vec![];
+        let (id, state) = cx.with_new_id(|cx| self.children.build(cx, &mut elements));
+        (id, state, widget::Layers::new(elements))
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut scratch = vec![];
+        let mut splice = VecSplice::new(&mut element.children, &mut scratch);
+
+        cx.with_id(*id, |cx| {
+            self.children
+                .rebuild(cx, &prev.children, state, &mut splice)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.children.message(id_path, state, message, app_state)
+    }
+}
+
+/// Stacks `layers` on top of each other, e.g. the base UI of the app and a global overlay for dialogs,
+/// so that the overlay isn't tangled into the view tree of the base UI.
+///
+/// It's usually the root view of the app, see [`App::new_layered`](crate::App::new_layered).
+/// The layers are painted in order, i.e. the first layer is at the bottom. Each layer is laid out
+/// to the full size of the terminal, like the root view of an app.
+///
+/// Events are sent to the layers from the top down. The topmost layer captures the mouse, also
+/// where it has no content, so the layers below only receive mouse events while it's not shown,
+/// e.g. when an optional layer is `None` (a widget that started a drag keeps receiving mouse events
+/// though). Key events are sent to the focused widget, in whichever layer it is, `Tab` moves the
+/// focus through the layers from the bottom up.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new(false, move |show_help: &mut bool| {
+/// layers((
+///     "The main UI".fill_max_size(1.0),
+///     show_help.then(|| "Press h to close the help".border(BorderKind::Rounded)),
+/// ))
+/// # });
+/// ```
+pub fn layers<T, A, VT: ViewSequence<T, A>>(layers: VT) -> Layers<T, A, VT> {
+    Layers {
+        children: layers,
+        phantom: PhantomData,
+    }
+}
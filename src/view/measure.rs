@@ -0,0 +1,61 @@
+use std::{any::Any, borrow::Cow};
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+pub struct Measure<V> {
+    content: V,
+    key: Cow<'static, str>,
+}
+
+impl<V> Measure<V> {
+    pub(crate) fn new(content: V, key: Cow<'static, str>) -> Self {
+        Measure { content, key }
+    }
+}
+
+impl<V> ViewMarker for Measure<V> {}
+
+impl<T, A, V: View<T, A>> View<T, A> for Measure<V> {
+    type State = V::State;
+
+    type Element = widget::Measure;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, element) = self.content.build(cx);
+        let element =
+            widget::Measure::new(element, self.key.to_string(), cx.widget_bounds().clone());
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changeflags = element.set_key(&self.key);
+        let content_el = element
+            .content()
+            .downcast_mut()
+            .expect("The measured content widget changed its type, this should never happen!");
+        let content_changeflags = self
+            .content
+            .rebuild(cx, &prev.content, id, state, content_el);
+        changeflags | element.content().mark(content_changeflags)
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.content.message(id_path, state, message, app_state)
+    }
+}
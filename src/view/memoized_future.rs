@@ -0,0 +1,373 @@
+use std::{future::Future, marker::PhantomData, pin::Pin, sync::Arc};
+
+use futures_task::{Context, Poll, Waker};
+use tokio::task::{AbortHandle, JoinHandle, Unconstrained};
+use xilem_core::{AsyncWake, Id, MessageResult};
+
+use crate::widget::ChangeFlags;
+
+use super::{Cx, View, ViewMarker};
+
+/// The current state of the future of a [`MemoizedFuture`].
+///
+/// This is passed to the view builder of [`memoized_future`], so that every state can be rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FutureState<O> {
+    /// The future is still running.
+    Pending,
+    /// The future resolved with the contained value.
+    Ready(O),
+    /// The task running the future failed (e.g. it panicked), contains the error message.
+    Error(String),
+}
+
+/// A spawned future, which is aborted when it's dropped.
+pub(crate) struct RunningTask<O> {
+    task: Unconstrained<JoinHandle<O>>,
+    abort_handle: AbortHandle,
+}
+
+impl<O: Send + 'static> RunningTask<O> {
+    pub(crate) fn spawn<F: Future<Output = O> + Send + 'static>(
+        rt: &tokio::runtime::Handle,
+        future: F,
+    ) -> Self {
+        let join_handle = rt.spawn(Box::pin(future));
+        let abort_handle = join_handle.abort_handle();
+        RunningTask {
+            task: tokio::task::unconstrained(join_handle),
+            abort_handle,
+        }
+    }
+
+    /// Returns `None` as long as the future is pending.
+    pub(crate) fn poll(&mut self, waker: &Waker) -> Option<FutureState<O>> {
+        let mut future_cx = Context::from_waker(waker);
+        match Pin::new(&mut self.task).poll(&mut future_cx) {
+            Poll::Ready(Ok(value)) => Some(FutureState::Ready(value)),
+            Poll::Ready(Err(err)) => {
+                tracing::error!("error in spawned future: {err}");
+                Some(FutureState::Error(err.to_string()))
+            }
+            Poll::Pending => None,
+        }
+    }
+}
+
+impl<O> Drop for RunningTask<O> {
+    fn drop(&mut self) {
+        // Does nothing if the task has already completed
+        self.abort_handle.abort();
+    }
+}
+
+/// Is called with the app state and the result of the future, see [`MemoizedFuture::on_resolve`].
+type ResolveHandler<T, O> = Arc<dyn Fn(&mut T, &FutureState<O>) + Send + Sync>;
+
+pub struct MemoizedFutureState<T, A, O, V: View<T, A>> {
+    waker: Waker,
+    task: Option<RunningTask<O>>,
+    result: FutureState<O>,
+    /// Whether the result still has to be passed to the `on_resolve` handler, when the future
+    /// resolved right away while the view was (re)built, as the app state isn't available there.
+    result_unhandled: bool,
+    view: V,
+    view_id: Id,
+    view_state: V::State,
+}
+
+pub struct MemoizedFuture<T, A, D, O, FF, VF> {
+    key: D,
+    future_fn: FF,
+    view_fn: VF,
+    on_resolve: Option<ResolveHandler<T, O>>,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<T: 'static, A, D, O: Clone + 'static, FF, VF> MemoizedFuture<T, A, D, O, FF, VF> {
+    /// Calls `on_resolve` with the final [`FutureState`] (the value or the error of the task),
+    /// when a future completed, e.g. to keep the loaded data in the app state.
+    ///
+    /// It's called before the view is rebuilt with the result, not for cancelled futures.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// struct AppState {
+    ///     user_id: u32,
+    ///     user_name: Option<String>,
+    /// }
+    ///
+    /// # App::new(AppState { user_id: 1, user_name: None }, move |state: &mut AppState| {
+    /// memoized_future(
+    ///     state.user_id,
+    ///     |user_id| {
+    ///         let user_id = *user_id;
+    ///         async move { format!("User {user_id}") }
+    ///     },
+    ///     |future: &FutureState<String>| {
+    ///         if *future == FutureState::Pending { "Loading..." } else { "Loaded" }
+    ///     },
+    /// )
+    /// .on_resolve(|state: &mut AppState, result| {
+    ///     if let FutureState::Ready(name) = result {
+    ///         state.user_name = Some(name);
+    ///     }
+    /// })
+    /// # });
+    /// ```
+    pub fn on_resolve(
+        mut self,
+        on_resolve: impl Fn(&mut T, FutureState<O>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_resolve = Some(Arc::new(
+            move |app_state: &mut T, result: &FutureState<O>| on_resolve(app_state, result.clone()),
+        ));
+        self
+    }
+}
+
+impl<T, A, D, O, FF, VF> ViewMarker for MemoizedFuture<T, A, D, O, FF, VF> {}
+
+impl<T, A, D, O, F, FF, V, VF> View<T, A> for MemoizedFuture<T, A, D, O, FF, VF>
+where
+    D: PartialEq + Send + Sync,
+    O: Send + 'static,
+    F: Future<Output = O> + Send + 'static,
+    FF: Fn(&D) -> F + Send + Sync,
+    V: View<T, A>,
+    VF: Fn(&FutureState<O>) -> V + Send + Sync,
+{
+    type State = MemoizedFutureState<T, A, O, V>;
+
+    type Element = V::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let waker = cx.waker();
+            let mut task = RunningTask::spawn(&cx.rt, (self.future_fn)(&self.key));
+            let (task, result) = match task.poll(&waker) {
+                Some(result) => (None, result),
+                None => (Some(task), FutureState::Pending),
+            };
+            let result_unhandled = task.is_none() && self.on_resolve.is_some();
+            if result_unhandled {
+                waker.wake_by_ref();
+            }
+            let view = (self.view_fn)(&result);
+            let (view_id, view_state, element) = view.build(cx);
+            let state = MemoizedFutureState {
+                waker,
+                task,
+                result,
+                result_unhandled,
+                view,
+                view_id,
+                view_state,
+            };
+            (state, element)
+        });
+        if state.task.is_some() || state.result_unhandled {
+            cx.add_pending_async(id);
+        }
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changeflags = cx.with_id(*id, |cx| {
+            if self.key != prev.key {
+                // This drops (and thereby cancels) a previous task that is possibly still in flight
+                state.task = None;
+                let mut task = RunningTask::spawn(&cx.rt, (self.future_fn)(&self.key));
+                match task.poll(&state.waker) {
+                    Some(result) => {
+                        state.result = result;
+                        if self.on_resolve.is_some() {
+                            state.result_unhandled = true;
+                            state.waker.wake_by_ref();
+                        }
+                    }
+                    None => {
+                        state.result = FutureState::Pending;
+                        state.task = Some(task);
+                    }
+                }
+            }
+            let view = (self.view_fn)(&state.result);
+            let changeflags = view.rebuild(
+                cx,
+                &state.view,
+                &mut state.view_id,
+                &mut state.view_state,
+                element,
+            );
+            state.view = view;
+            changeflags
+        });
+        if state.task.is_some() || state.result_unhandled {
+            cx.add_pending_async(*id);
+        }
+        changeflags
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] if message.downcast_ref::<AsyncWake>().is_some() => {
+                if let Some(task) = &mut state.task {
+                    let Some(result) = task.poll(&state.waker) else {
+                        return MessageResult::Nop;
+                    };
+                    state.result = result;
+                    state.task = None;
+                } else if !state.result_unhandled {
+                    // The wake of an already resolved or cancelled task
+                    return MessageResult::Nop;
+                }
+                state.result_unhandled = false;
+                if let Some(on_resolve) = &self.on_resolve {
+                    on_resolve(app_state, &state.result);
+                }
+                MessageResult::RequestRebuild
+            }
+            [id, rest_path @ ..] if *id == state.view_id => {
+                state
+                    .view
+                    .message(rest_path, &mut state.view_state, message, app_state)
+            }
+            [..] => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// Runs the future returned by `future_fn` on the runtime and renders the view returned by `view_fn`
+/// with the current [`FutureState`] of that future.
+///
+/// The future is only started again when `key` changes (compared via [`PartialEq`]), which makes
+/// this the canonical way to load async data depending on the app state.
+/// A future that is still in flight when `key` changes or when this view is removed from the
+/// tree is cancelled.
+///
+/// To keep the result in the app state, pass a handler to [`on_resolve`](MemoizedFuture::on_resolve).
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new(1u32, move |user_id| {
+/// memoized_future(
+///     *user_id,
+///     |user_id| {
+///         let user_id = *user_id;
+///         async move { format!("User {user_id}") }
+///     },
+///     |state: &FutureState<String>| match state {
+///         FutureState::Pending => "Loading...".to_string(),
+///         FutureState::Ready(name) => name.clone(),
+///         FutureState::Error(err) => format!("Loading failed: {err}"),
+///     },
+/// )
+/// # });
+/// ```
+pub fn memoized_future<T, A, D, O, F, FF, V, VF>(
+    key: D,
+    future_fn: FF,
+    view_fn: VF,
+) -> MemoizedFuture<T, A, D, O, FF, VF>
+where
+    D: PartialEq + Send + Sync,
+    O: Send + 'static,
+    F: Future<Output = O> + Send + 'static,
+    FF: Fn(&D) -> F + Send + Sync,
+    V: View<T, A>,
+    VF: Fn(&FutureState<O>) -> V + Send + Sync,
+{
+    MemoizedFuture {
+        key,
+        future_fn,
+        view_fn,
+        on_resolve: None,
+        phantom: PhantomData,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{test_mouse, TestGate},
+        widget::{MouseButton, MouseKind},
+        App, ViewExt,
+    };
+
+    #[tokio::test]
+    async fn a_pending_future_is_aborted_when_the_key_changes() {
+        let gate = TestGate::new();
+        let app_gate = gate.clone();
+        let mut app = App::new(0u32, move |key: &mut u32| {
+            let gate = app_gate.clone();
+            memoized_future(
+                *key,
+                move |key| gate.pass(format!("loaded {key}")),
+                |state: &FutureState<String>| match state {
+                    FutureState::Ready(loaded) => loaded.clone(),
+                    _ => "pending".to_string(),
+                },
+            )
+            .on_click(|key: &mut u32| *key += 1)
+        })
+        .await;
+        app.render_once().await.unwrap();
+        app.dispatch([
+            test_mouse(MouseKind::Down(MouseButton::Left), 0, 0),
+            test_mouse(MouseKind::Up(MouseButton::Left), 0, 0),
+        ])
+        .await;
+        assert_eq!(app.screen_line(0), "pending");
+        app.render_until(|_| gate.dropped() == 1).await;
+
+        gate.open();
+        app.render_until(|app| app.screen_line(0) != "pending")
+            .await;
+        assert_eq!(app.screen_line(0), "loaded 1");
+        assert_eq!(gate.dropped(), 1);
+    }
+
+    #[tokio::test]
+    async fn the_resolved_value_is_passed_to_the_app_state() {
+        let gate = TestGate::new();
+        let app_gate = gate.clone();
+        let mut app = App::new(None, move |loaded: &mut Option<String>| {
+            let gate = app_gate.clone();
+            let shown = format!("{loaded:?}");
+            memoized_future(
+                (),
+                move |_| gate.pass("value".to_string()),
+                move |_: &FutureState<String>| shown.clone(),
+            )
+            .on_resolve(|loaded: &mut Option<String>, result| {
+                if let FutureState::Ready(value) = result {
+                    *loaded = Some(value);
+                }
+            })
+        })
+        .await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "None");
+
+        gate.open();
+        app.render_until(|app| app.screen_line(0) != "None").await;
+        assert_eq!(app.screen_line(0), "Some(\"value\")");
+    }
+}
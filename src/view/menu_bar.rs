@@ -0,0 +1,207 @@
+use std::any::Any;
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, EventHandler, View, ViewMarker};
+use crate::widget::{self, ChangeFlags, MenuStyles};
+
+/// An entry of a [`Menu`], which calls the `on_action` handler of the [`menu_bar`](crate::ViewExt::menu_bar)
+/// with its id when it's chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    pub(crate) id: String,
+    pub(crate) label: String,
+    pub(crate) accelerator: Option<char>,
+    pub(crate) enabled: bool,
+}
+
+impl MenuItem {
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        MenuItem {
+            id: id.into(),
+            label: label.into(),
+            accelerator: None,
+            enabled: true,
+        }
+    }
+
+    /// Chooses this item when `key` is pressed while its menu is open, the key is shown next to the label.
+    pub fn accelerator(mut self, key: char) -> Self {
+        self.accelerator = Some(key);
+        self
+    }
+
+    /// A disabled item is dimmed and can't be chosen.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuEntry {
+    Item(MenuItem),
+    /// A horizontal line between groups of items
+    Separator,
+}
+
+impl MenuEntry {
+    /// The item, if it can be chosen.
+    pub(crate) fn enabled_item(&self) -> Option<&MenuItem> {
+        match self {
+            MenuEntry::Item(item) if item.enabled => Some(item),
+            _ => None,
+        }
+    }
+}
+
+/// A dropdown menu of a [`menu_bar`](crate::ViewExt::menu_bar).
+///
+/// It's opened by clicking its title or with `Alt` and the first letter of its title.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Menu {
+    pub(crate) title: String,
+    pub(crate) entries: Vec<MenuEntry>,
+}
+
+impl Menu {
+    pub fn new(title: impl Into<String>) -> Self {
+        Menu {
+            title: title.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// The key which opens this menu together with `Alt`.
+    pub(crate) fn mnemonic(&self) -> Option<char> {
+        self.title.chars().next().map(|c| c.to_ascii_lowercase())
+    }
+}
+
+pub struct MenuBar<V, EH> {
+    content: V,
+    menus: Vec<Menu>,
+    on_action: EH,
+}
+
+impl<V, EH> MenuBar<V, EH> {
+    pub(crate) fn new(content: V, menus: Vec<Menu>, on_action: EH) -> Self {
+        MenuBar {
+            content,
+            menus,
+            on_action,
+        }
+    }
+
+    fn resolve_styles(cx: &Cx) -> MenuStyles {
+        let theme = cx.theme();
+        let background = theme.surface_variant();
+        MenuStyles {
+            bar: theme.text.default.bg(background),
+            border: theme.border.default.bg(background),
+            selected: theme.text.selected,
+            disabled: theme.text.disabled.bg(background),
+        }
+    }
+}
+
+pub struct MenuBarState<S, EHS> {
+    content_id: Id,
+    content_state: S,
+    on_action_id: Id,
+    on_action_state: EHS,
+}
+
+impl<V, EH> ViewMarker for MenuBar<V, EH> {}
+
+impl<T, A, V: View<T, A>, EH: EventHandler<T, A, String>> View<T, A> for MenuBar<V, EH> {
+    type State = MenuBarState<V::State, EH::State>;
+
+    type Element = widget::MenuBar;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let (content_id, content_state, content) = self.content.build(cx);
+            let (on_action_id, on_action_state) = self.on_action.build(cx);
+            let element = widget::MenuBar::new(
+                cx.id_path(),
+                content,
+                self.menus.clone(),
+                Self::resolve_styles(cx),
+            );
+            let state = MenuBarState {
+                content_id,
+                content_state,
+                on_action_id,
+                on_action_state,
+            };
+            (state, element)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let mut changeflags = element.set_menus(&self.menus)
+                | element.set_styles(Self::resolve_styles(cx))
+                | self
+                    .on_action
+                    .rebuild(cx, &state.on_action_id, &mut state.on_action_state);
+
+            let content_el = element
+                .content
+                .downcast_mut()
+                .expect("The menu bar content widget changed its type, this should never happen!");
+            let content_changeflags = self.content.rebuild(
+                cx,
+                &prev.content,
+                &mut state.content_id,
+                &mut state.content_state,
+                content_el,
+            );
+            changeflags |= element.content.mark(content_changeflags);
+            changeflags
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [id, rest_path @ ..] if *id == state.content_id => {
+                self.content
+                    .message(rest_path, &mut state.content_state, message, app_state)
+            }
+            [id, rest_path @ ..] if *id == state.on_action_id => {
+                self.on_action
+                    .message(rest_path, &mut state.on_action_state, message, app_state)
+            }
+            [] if message.downcast_ref::<String>().is_some() => {
+                self.on_action
+                    .message(&[], &mut state.on_action_state, message, app_state)
+            }
+            [..] => MessageResult::Stale(message),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::{any::Any, marker::PhantomData};
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::widget::{self, ChangeFlags, Pod};
+
+pub struct Modal<V, D, T, A> {
+    content: V,
+    dialog: Option<D>,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct ModalState<S, DS> {
+    content_id: Id,
+    content_state: S,
+    dialog: Option<(Id, DS)>,
+}
+
+impl<V, D, T, A> Modal<V, D, T, A> {
+    pub(crate) fn new(content: V, dialog: Option<D>) -> Self {
+        Modal {
+            content,
+            dialog,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<V, D, T, A> ViewMarker for Modal<V, D, T, A> {}
+
+impl<T, A, V: View<T, A>, D: View<T, A>> View<T, A> for Modal<V, D, T, A> {
+    type State = ModalState<V::State, D::State>;
+
+    type Element = widget::Modal;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let (content_id, content_state, content) = self.content.build(cx);
+            let (dialog, dialog_pod) = match &self.dialog {
+                Some(dialog) => {
+                    let (dialog_id, dialog_state, dialog) = dialog.build(cx);
+                    (Some((dialog_id, dialog_state)), Some(Pod::new(dialog)))
+                }
+                None => (None, None),
+            };
+            let state = ModalState {
+                content_id,
+                content_state,
+                dialog,
+            };
+            (state, widget::Modal::new(content, dialog_pod))
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let content_el = element
+                .content()
+                .downcast_mut()
+                .expect("The modal content widget changed its type, this should never happen!");
+            let content_changeflags = self.content.rebuild(
+                cx,
+                &prev.content,
+                &mut state.content_id,
+                &mut state.content_state,
+                content_el,
+            );
+            let mut changeflags = element.content().mark(content_changeflags);
+
+            match (
+                &self.dialog,
+                &prev.dialog,
+                &mut state.dialog,
+                element.dialog(),
+            ) {
+                (Some(dialog), Some(prev_dialog), Some((dialog_id, dialog_state)), Some(pod)) => {
+                    let dialog_el = pod.downcast_mut().expect(
+                        "The modal dialog widget changed its type, this should never happen!",
+                    );
+                    let dialog_changeflags =
+                        dialog.rebuild(cx, prev_dialog, dialog_id, dialog_state, dialog_el);
+                    changeflags |= pod.mark(dialog_changeflags);
+                }
+                (Some(dialog), ..) => {
+                    let (dialog_id, dialog_state, dialog) = dialog.build(cx);
+                    state.dialog = Some((dialog_id, dialog_state));
+                    element.set_dialog(Some(Pod::new(dialog)));
+                    changeflags |= ChangeFlags::tree_structure();
+                }
+                (None, _, Some(_), _) => {
+                    state.dialog = None;
+                    element.set_dialog(None);
+                    changeflags |= ChangeFlags::tree_structure();
+                }
+                (None, ..) => (),
+            }
+            changeflags
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [id, rest_path @ ..] if *id == state.content_id => {
+                self.content
+                    .message(rest_path, &mut state.content_state, message, app_state)
+            }
+            [id, rest_path @ ..] => match (&self.dialog, &mut state.dialog) {
+                (Some(dialog), Some((dialog_id, dialog_state))) if dialog_id == id => {
+                    dialog.message(rest_path, dialog_state, message, app_state)
+                }
+                _ => MessageResult::Stale(message),
+            },
+            [] => MessageResult::Stale(message),
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use std::{
+    any::Any,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures_task::Waker;
+use ratatui::style::Style;
+use xilem_core::{AsyncWake, Id, MessageResult};
+
+use super::{memoized_future::RunningTask, Cx, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+/// Identifies a toast of [`Notifications`], e.g. to dismiss it before it times out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+struct Toast {
+    id: ToastId,
+    message: String,
+    expires_at: Instant,
+}
+
+#[derive(Default)]
+struct ToastQueue {
+    toasts: Vec<Toast>,
+    next_id: u64,
+}
+
+/// An app-level queue of transient messages (toasts), which are shown by [`ViewExt::notifications`](crate::ViewExt::notifications).
+///
+/// This is a cheap handle to a shared queue, it's usually stored in the app state,
+/// so that event handlers can enqueue toasts via [`Notifications::notify`].
+#[derive(Clone, Default)]
+pub struct Notifications {
+    queue: Arc<Mutex<ToastQueue>>,
+}
+
+impl Notifications {
+    pub fn new() -> Self {
+        Notifications::default()
+    }
+
+    /// Shows `message` for `duration`, toasts are stacked with the newest at the bottom.
+    pub fn notify(&self, message: impl Into<String>, duration: Duration) -> ToastId {
+        let mut queue = self.queue.lock().unwrap();
+        let id = ToastId(queue.next_id);
+        queue.next_id += 1;
+        queue.toasts.push(Toast {
+            id,
+            message: message.into(),
+            expires_at: Instant::now() + duration,
+        });
+        id
+    }
+
+    /// Removes the toast `id` before it times out, does nothing if it's already gone.
+    pub fn dismiss(&self, id: ToastId) {
+        self.queue
+            .lock()
+            .unwrap()
+            .toasts
+            .retain(|toast| toast.id != id);
+    }
+
+    /// Drops the toasts which are expired at `now` and returns the remaining ones,
+    /// together with the time when the next one expires.
+    fn visible(&self, now: Instant) -> (Vec<(ToastId, String)>, Option<Instant>) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.toasts.retain(|toast| toast.expires_at > now);
+        let toasts = queue
+            .toasts
+            .iter()
+            .map(|toast| (toast.id, toast.message.clone()))
+            .collect();
+        let next_expiry = queue.toasts.iter().map(|toast| toast.expires_at).min();
+        (toasts, next_expiry)
+    }
+}
+
+pub struct NotificationOverlay<V, T, A> {
+    content: V,
+    notifications: Notifications,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct NotificationOverlayState<S> {
+    content_id: Id,
+    content_state: S,
+    waker: Waker,
+    /// Wakes the view when the next toast expires
+    timer: Option<(Instant, RunningTask<()>)>,
+}
+
+impl<V, T, A> NotificationOverlay<V, T, A> {
+    pub(crate) fn new(content: V, notifications: &Notifications) -> Self {
+        NotificationOverlay {
+            content,
+            notifications: notifications.clone(),
+            phantom: PhantomData,
+        }
+    }
+
+    fn resolve_style(cx: &Cx) -> Style {
+        let theme = cx.theme();
+        theme.text.default.bg(theme.colors.notifications)
+    }
+
+    fn schedule_timer<S>(
+        cx: &Cx,
+        state: &mut NotificationOverlayState<S>,
+        next_expiry: Option<Instant>,
+    ) {
+        let Some(next_expiry) = next_expiry else {
+            state.timer = None;
+            return;
+        };
+        if matches!(state.timer, Some((expiry, _)) if expiry == next_expiry) {
+            return;
+        }
+        let mut timer = RunningTask::spawn(
+            &cx.rt,
+            tokio::time::sleep_until(tokio::time::Instant::from_std(next_expiry)),
+        );
+        // Registers the waker, the toast is removed with the next rebuild anyway
+        let _ = timer.poll(&state.waker);
+        state.timer = Some((next_expiry, timer));
+    }
+}
+
+impl<V, T, A> ViewMarker for NotificationOverlay<V, T, A> {}
+
+impl<T, A, V: View<T, A>> View<T, A> for NotificationOverlay<V, T, A> {
+    type State = NotificationOverlayState<V::State>;
+
+    type Element = widget::NotificationOverlay;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let (content_id, content_state, content) = self.content.build(cx);
+            let (toasts, next_expiry) = self.notifications.visible(Instant::now());
+            let element = widget::NotificationOverlay::new(
+                cx.id_path(),
+                content,
+                toasts,
+                Self::resolve_style(cx),
+            );
+            let mut state = NotificationOverlayState {
+                content_id,
+                content_state,
+                waker: cx.waker(),
+                timer: None,
+            };
+            Self::schedule_timer(cx, &mut state, next_expiry);
+            (state, element)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let (toasts, next_expiry) = self.notifications.visible(Instant::now());
+            Self::schedule_timer(cx, state, next_expiry);
+            let mut changeflags =
+                element.set_toasts(toasts) | element.set_style(Self::resolve_style(cx));
+
+            let content_el = element
+                .content()
+                .downcast_mut()
+                .expect("The notification overlay content widget changed its type, this should never happen!");
+            let content_changeflags = self.content.rebuild(
+                cx,
+                &prev.content,
+                &mut state.content_id,
+                &mut state.content_state,
+                content_el,
+            );
+            changeflags |= element.content().mark(content_changeflags);
+            changeflags
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [id, rest_path @ ..] if *id == state.content_id => {
+                self.content
+                    .message(rest_path, &mut state.content_state, message, app_state)
+            }
+            [] if message.downcast_ref::<AsyncWake>().is_some() => {
+                let Some((_, timer)) = &mut state.timer else {
+                    // The wake of an already replaced timer
+                    return MessageResult::Nop;
+                };
+                if timer.poll(&state.waker).is_some() {
+                    state.timer = None;
+                    MessageResult::RequestRebuild
+                } else {
+                    MessageResult::Nop
+                }
+            }
+            [] if message.downcast_ref::<ToastId>().is_some() => {
+                self.notifications
+                    .dismiss(*message.downcast::<ToastId>().unwrap());
+                MessageResult::RequestRebuild
+            }
+            [..] => MessageResult::Stale(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::test_mouse,
+        widget::{MouseButton, MouseKind},
+        App, ViewExt,
+    };
+
+    #[test]
+    fn toasts_age_out_in_order() {
+        let notifications = Notifications::new();
+        let now = Instant::now();
+        let first = notifications.notify("first", Duration::from_secs(1));
+        let second = notifications.notify("second", Duration::from_secs(2));
+        let third = notifications.notify("third", Duration::from_secs(3));
+        notifications.dismiss(second);
+
+        let (toasts, next_expiry) = notifications.visible(now);
+        assert_eq!(toasts, [(first, "first".into()), (third, "third".into())]);
+        assert!(next_expiry.unwrap() >= now + Duration::from_secs(1));
+
+        let (toasts, _) = notifications.visible(now + Duration::from_millis(2500));
+        assert_eq!(toasts, [(third, "third".into())]);
+
+        let (toasts, next_expiry) = notifications.visible(now + Duration::from_secs(4));
+        assert!(toasts.is_empty());
+        assert_eq!(next_expiry, None);
+    }
+
+    #[tokio::test]
+    async fn the_content_is_neither_hovered_nor_clicked_through_a_toast() {
+        let notifications = Notifications::new();
+        notifications.notify("hi", Duration::from_secs(60));
+        let app_notifications = notifications.clone();
+        let mut app = App::new((0, 0), move |(hovers, clicks): &mut (u32, u32)| {
+            format!("{:<20}", format!("h{hovers} r{clicks}"))
+                .on_hover(|(hovers, _): &mut (u32, u32)| *hovers += 1)
+                .on_right_click(|(_, clicks): &mut (u32, u32)| *clicks += 1)
+                .notifications(&app_notifications)
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(20, 1);
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "h0 r0            hi");
+
+        // the toast covers the columns 16..20
+        let right = MouseButton::Right;
+        app.dispatch([
+            test_mouse(MouseKind::Moved, 18, 0),
+            test_mouse(MouseKind::Down(right), 18, 0),
+            test_mouse(MouseKind::Up(right), 18, 0),
+        ])
+        .await;
+        assert_eq!(app.screen_line(0), "h0 r0            hi");
+
+        app.dispatch([
+            test_mouse(MouseKind::Moved, 2, 0),
+            test_mouse(MouseKind::Down(right), 2, 0),
+            test_mouse(MouseKind::Up(right), 2, 0),
+        ])
+        .await;
+        assert_eq!(app.screen_line(0), "h1 r1            hi");
+    }
+}
@@ -0,0 +1,133 @@
+use std::{any::Any, future::Future, marker::PhantomData};
+
+use futures_task::Waker;
+use xilem_core::{AsyncWake, Id, MessageResult};
+
+use crate::widget::ChangeFlags;
+
+use super::{memoized_future::RunningTask, Cx, FutureState, View, ViewMarker};
+
+pub struct OnMountAsyncState<O, S> {
+    waker: Waker,
+    task: Option<RunningTask<O>>,
+    /// A result, which was already available when the view was built, it's passed to the app state
+    /// with the next wake, since the app state isn't available while building.
+    result: Option<FutureState<O>>,
+    content_id: Id,
+    content_state: S,
+}
+
+pub struct OnMountAsync<V, T, A, FF, SF> {
+    content: V,
+    future_fn: FF,
+    state_fn: SF,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<V, T, A, FF, SF> OnMountAsync<V, T, A, FF, SF> {
+    pub(crate) fn new(content: V, future_fn: FF, state_fn: SF) -> Self {
+        OnMountAsync {
+            content,
+            future_fn,
+            state_fn,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<V, T, A, FF, SF> ViewMarker for OnMountAsync<V, T, A, FF, SF> {}
+
+impl<T, A, O, F, V, FF, SF> View<T, A> for OnMountAsync<V, T, A, FF, SF>
+where
+    O: Send + 'static,
+    F: Future<Output = O> + Send + 'static,
+    V: View<T, A>,
+    FF: Fn() -> F + Send + Sync,
+    SF: Fn(&mut T, FutureState<O>) + Send + Sync,
+{
+    type State = OnMountAsyncState<O, V::State>;
+
+    type Element = V::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let waker = cx.waker();
+            let mut task = RunningTask::spawn(&cx.rt, (self.future_fn)());
+            let (task, result) = match task.poll(&waker) {
+                Some(result) => {
+                    // Waking blocks, which isn't allowed on the async runtime
+                    let waker = waker.clone();
+                    cx.rt.spawn_blocking(move || waker.wake());
+                    (None, Some(result))
+                }
+                None => (Some(task), None),
+            };
+            let (content_id, content_state, element) = self.content.build(cx);
+            let state = OnMountAsyncState {
+                waker,
+                task,
+                result,
+                content_id,
+                content_state,
+            };
+            (state, element)
+        });
+        if state.task.is_some() {
+            cx.add_pending_async(id);
+        }
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changeflags = cx.with_id(*id, |cx| {
+            self.content.rebuild(
+                cx,
+                &prev.content,
+                &mut state.content_id,
+                &mut state.content_state,
+                element,
+            )
+        });
+        if state.task.is_some() {
+            cx.add_pending_async(*id);
+        }
+        changeflags
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] if message.downcast_ref::<AsyncWake>().is_some() => {
+                let result = match (state.result.take(), &mut state.task) {
+                    (Some(result), _) => result,
+                    (None, Some(task)) => match task.poll(&state.waker) {
+                        Some(result) => result,
+                        None => return MessageResult::Nop,
+                    },
+                    // The wake of an already delivered result
+                    (None, None) => return MessageResult::Nop,
+                };
+                state.task = None;
+                (self.state_fn)(app_state, result);
+                MessageResult::RequestRebuild
+            }
+            [id, rest_path @ ..] if *id == state.content_id => {
+                self.content
+                    .message(rest_path, &mut state.content_state, message, app_state)
+            }
+            [..] => MessageResult::Stale(message),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use ratatui::{buffer::Buffer, layout::Rect};
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+/// Renders into the area of the widget in the terminal buffer, see [`raw_widget`].
+pub(crate) type RenderFn = Arc<dyn Fn(Rect, &mut Buffer) + Send + Sync>;
+
+pub struct RawWidget {
+    render: RenderFn,
+    preferred_size: Option<(u16, u16)>,
+}
+
+impl RawWidget {
+    /// The size of the view (within the constraints of its parent), instead of taking all the space
+    /// the parent allows.
+    pub fn preferred_size(mut self, width: u16, height: u16) -> Self {
+        self.preferred_size = Some((width, height));
+        self
+    }
+}
+
+impl ViewMarker for RawWidget {}
+
+impl<T, A> View<T, A> for RawWidget {
+    type State = ();
+
+    type Element = widget::RawWidget;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, element) =
+            cx.with_new_id(|_| widget::RawWidget::new(self.render.clone(), self.preferred_size));
+        (id, (), element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut Id,
+        _state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        element.set_render(&self.render) | element.set_preferred_size(self.preferred_size)
+    }
+
+    fn message(
+        &self,
+        _id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut T,
+    ) -> MessageResult<A> {
+        MessageResult::Stale(message)
+    }
+}
+
+/// An escape hatch, which calls `render` with the area of the view and the terminal buffer while
+/// painting, e.g. to draw something with ratatui directly, see also [`ratatui_widget`].
+///
+/// The view takes all the space its parent allows (in unbounded directions only the minimum),
+/// unless it has a [`preferred_size`](RawWidget::preferred_size).
+/// `render` should only change cells within the area, only the visible part of it is changed in the
+/// terminal, e.g. when the view is partly scrolled out of a scroll view.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new((), move |()| {
+/// raw_widget(|area, buf| {
+///     buf.set_string(area.x, area.y, "drawn by ratatui", Style::default().fg(Color::Green));
+/// })
+/// .preferred_size(16, 1)
+/// # });
+/// ```
+pub fn raw_widget(render: impl Fn(Rect, &mut Buffer) + Send + Sync + 'static) -> RawWidget {
+    RawWidget {
+        render: Arc::new(render),
+        preferred_size: None,
+    }
+}
+
+/// Renders a ratatui `widget` (e.g. a `Chart` or a `Calendar`) into the area of the view,
+/// it's cloned for every paint, as ratatui widgets are consumed when they're rendered.
+///
+/// See [`raw_widget`] for how the view is laid out.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// use ratatui::widgets::{Block, Borders, Gauge};
+///
+/// # App::new(0.25, move |progress: &mut f64| {
+/// ratatui_widget(
+///     Gauge::default()
+///         .block(Block::default().borders(Borders::ALL).title("Progress"))
+///         .ratio(*progress),
+/// )
+/// .preferred_size(30, 3)
+/// # });
+/// ```
+pub fn ratatui_widget<W>(widget: W) -> RawWidget
+where
+    W: ratatui::widgets::Widget + Clone + Send + Sync + 'static,
+{
+    raw_widget(move |area, buf| widget.clone().render(area, buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{layout::Size, widgets::Paragraph};
+
+    use super::*;
+    use crate::{h_stack, test_helper::render_view};
+
+    #[tokio::test]
+    async fn ratatui_widgets_are_rendered_into_the_area_of_the_view() {
+        let local_set = tokio::task::LocalSet::new();
+        local_set
+            .run_until(async {
+                let sut = Arc::new(h_stack((
+                    "a",
+                    ratatui_widget(Paragraph::new("paragraph")).preferred_size(4, 1),
+                    "b",
+                )));
+                let buffer = render_view(
+                    Size {
+                        width: 8,
+                        height: 1,
+                    },
+                    sut,
+                    (),
+                )
+                .await;
+                let line: String = (0..6).map(|x| buffer.get(x, 0).symbol()).collect();
+                assert_eq!(line, "aparab");
+            })
+            .await
+    }
+}
@@ -0,0 +1,143 @@
+use std::{any::Any, marker::PhantomData};
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::{
+    geometry::Size,
+    widget::{self, ChangeFlags},
+};
+
+pub struct Responsive<F, V> {
+    build: F,
+    breakpoints: Vec<f64>,
+    phantom: PhantomData<fn() -> V>,
+}
+
+pub struct ResponsiveState<V, S> {
+    /// The last available size reported by the widget
+    size: Size,
+    content_id: Id,
+    content: V,
+    content_state: S,
+}
+
+impl<F, V> Responsive<F, V> {
+    /// Only rebuilds the content when the available width crosses one of the `breakpoints`,
+    /// instead of whenever the available size changes.
+    ///
+    /// The size passed to the builder is then only updated when a breakpoint is crossed.
+    pub fn breakpoints(mut self, breakpoints: impl Into<Vec<f64>>) -> Self {
+        self.breakpoints = breakpoints.into();
+        self
+    }
+}
+
+impl<F, V> ViewMarker for Responsive<F, V> {}
+
+impl<T, A, V: View<T, A>, F: Fn(Size) -> V + Send + Sync> View<T, A> for Responsive<F, V> {
+    type State = ResponsiveState<V, V::State>;
+
+    type Element = widget::Responsive;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            // The available size is only known after the first layout, which reports it
+            let size = Size::ZERO;
+            let content = (self.build)(size);
+            let (content_id, content_state, content_el) = content.build(cx);
+            let element =
+                widget::Responsive::new(cx.id_path(), content_el, self.breakpoints.clone());
+            let state = ResponsiveState {
+                size,
+                content_id,
+                content,
+                content_state,
+            };
+            (state, element)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let mut changeflags = element.set_breakpoints(&self.breakpoints);
+            let content = (self.build)(state.size);
+            let content_el = element.content.downcast_mut().expect(
+                "The responsive content widget changed its type, this should never happen!",
+            );
+            let content_changeflags = content.rebuild(
+                cx,
+                &state.content,
+                &mut state.content_id,
+                &mut state.content_state,
+                content_el,
+            );
+            state.content = content;
+            changeflags |= element.content.mark(content_changeflags);
+            changeflags
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [id, rest_path @ ..] if *id == state.content_id => {
+                state
+                    .content
+                    .message(rest_path, &mut state.content_state, message, app_state)
+            }
+            [] if message.downcast_ref::<Size>().is_some() => {
+                state.size = *message.downcast().unwrap();
+                MessageResult::RequestRebuild
+            }
+            [..] => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// A view whose content is built by `build` for the size that's available for it, e.g. to stack
+/// views vertically on narrow terminals and horizontally otherwise.
+///
+/// The content is rebuilt whenever the available size changes, or only when the width crosses one
+/// of the [`breakpoints`](Responsive::breakpoints). As the size is only known after the first layout,
+/// the content is built for [`Size::ZERO`] first and then rebuilt right away for the actual size.
+/// In unbounded directions (e.g. inside a stack) the available size is infinite.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # use trui::geometry::Size;
+/// # App::new((), move |_| {
+/// responsive(|size: Size| {
+///     let views = ("Navigation".border(BorderKind::Rounded), "Content".fill_max_size(1.0));
+///     if size.width < 80.0 {
+///         v_stack(views).boxed()
+///     } else {
+///         h_stack(views).boxed()
+///     }
+/// })
+/// .breakpoints([80.0])
+/// # });
+/// ```
+pub fn responsive<T, A, V: View<T, A>, F: Fn(Size) -> V + Send + Sync>(
+    build: F,
+) -> Responsive<F, V> {
+    Responsive {
+        build,
+        breakpoints: Vec::new(),
+        phantom: PhantomData,
+    }
+}
@@ -0,0 +1,192 @@
+use std::{any::Any, marker::PhantomData};
+
+use xilem_core::{Id, MessageResult, VecSplice};
+
+use super::{Cx, View, ViewMarker, ViewSequence};
+use crate::widget::{self, ChangeFlags, Pod};
+
+pub struct Scaffold<T, A, H, B, F> {
+    header: H,
+    body: B,
+    footer: F,
+    header_height: Option<f64>,
+    footer_height: Option<f64>,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct ScaffoldState<HS, BS, FS> {
+    header_state: HS,
+    body_id: Id,
+    body_state: BS,
+    footer_state: FS,
+}
+
+impl<T, A, H, B, F> Scaffold<T, A, H, B, F> {
+    /// Views which are placed at the top, e.g. a title or a menu bar.
+    pub fn header<NH: ViewSequence<T, A>>(self, header: NH) -> Scaffold<T, A, NH, B, F> {
+        Scaffold {
+            header,
+            body: self.body,
+            footer: self.footer,
+            header_height: self.header_height,
+            footer_height: self.footer_height,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Views which are placed at the bottom, e.g. a status bar or key hints.
+    pub fn footer<NF: ViewSequence<T, A>>(self, footer: NF) -> Scaffold<T, A, H, B, NF> {
+        Scaffold {
+            header: self.header,
+            body: self.body,
+            footer,
+            header_height: self.header_height,
+            footer_height: self.footer_height,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Reserves exactly `rows` for the header, instead of the height the header needs.
+    pub fn header_height(mut self, rows: u16) -> Self {
+        self.header_height = Some(rows as f64);
+        self
+    }
+
+    /// Reserves exactly `rows` for the footer, instead of the height the footer needs.
+    pub fn footer_height(mut self, rows: u16) -> Self {
+        self.footer_height = Some(rows as f64);
+        self
+    }
+}
+
+impl<T, A, H, B, F> ViewMarker for Scaffold<T, A, H, B, F> {}
+
+impl<T, A, H, B, F> View<T, A> for Scaffold<T, A, H, B, F>
+where
+    H: ViewSequence<T, A>,
+    B: View<T, A>,
+    B::Element: 'static,
+    F: ViewSequence<T, A>,
+{
+    type State = ScaffoldState<H::State, B::State, F::State>;
+
+    type Element = widget::Scaffold;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let mut children = vec![];
+        let (id, (state, header_count)) = cx.with_new_id(|cx| {
+            let header_state = self.header.build(cx, &mut children);
+            let header_count = children.len();
+            let (body_id, body_state, body) = self.body.build(cx);
+            children.push(Pod::new(body));
+            let footer_state = self.footer.build(cx, &mut children);
+            let state = ScaffoldState {
+                header_state,
+                body_id,
+                body_state,
+                footer_state,
+            };
+            (state, header_count)
+        });
+        let element = widget::Scaffold::new(
+            children,
+            header_count,
+            self.header_height,
+            self.footer_height,
+        );
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let mut changeflags = element.set_heights(self.header_height, self.footer_height);
+        let mut scratch = vec![];
+        let mut splice = VecSplice::new(&mut element.children, &mut scratch);
+
+        changeflags |= cx.with_id(*id, |cx| {
+            let header_changeflags =
+                self.header
+                    .rebuild(cx, &prev.header, &mut state.header_state, &mut splice);
+
+            let body = splice.mutate();
+            let body_el = body
+                .downcast_mut()
+                .expect("The scaffold body widget changed its type, this should never happen!");
+            let body_changeflags = self.body.rebuild(
+                cx,
+                &prev.body,
+                &mut state.body_id,
+                &mut state.body_state,
+                body_el,
+            );
+            let body_changeflags = body.mark(body_changeflags);
+
+            let footer_changeflags =
+                self.footer
+                    .rebuild(cx, &prev.footer, &mut state.footer_state, &mut splice);
+            header_changeflags | body_changeflags | footer_changeflags
+        });
+
+        // The amount of header views may have changed, e.g. when it's an `Option`
+        let header_count = self.header.count(&state.header_state);
+        if element.header_count != header_count {
+            element.header_count = header_count;
+            changeflags |= ChangeFlags::LAYOUT;
+        }
+        changeflags
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [id, rest_path @ ..] if *id == state.body_id => {
+                self.body
+                    .message(rest_path, &mut state.body_state, message, app_state)
+            }
+            _ => self
+                .header
+                .message(id_path, &mut state.header_state, message, app_state)
+                .or(|message| {
+                    self.footer
+                        .message(id_path, &mut state.footer_state, message, app_state)
+                }),
+        }
+    }
+}
+
+/// Places `body` between an optional header at the top and an optional footer at the bottom.
+///
+/// The header and footer are laid out first and keep their (natural or fixed) height,
+/// the body gets the remaining rows, so e.g. a status bar stays at the bottom of the terminal
+/// while the body shrinks when the terminal is resized.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new((), move |()| {
+/// scaffold("Content")
+///     .header("Title".modifier(Modifier::BOLD))
+///     .footer(("q: quit".fg(Color::Gray), "Ready"))
+/// # });
+/// ```
+pub fn scaffold<T, A, B: View<T, A>>(body: B) -> Scaffold<T, A, (), B, ()> {
+    Scaffold {
+        header: (),
+        body,
+        footer: (),
+        header_height: None,
+        footer_height: None,
+        phantom: PhantomData,
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+};
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::{
+    widget::{self, ChangeFlags, ScrollRequest},
+    OverflowIndicator, ScrollAxes,
+};
+
+/// Changes the offset of a [`scroll`](crate::ViewExt::scroll) view from the app logic,
+/// e.g. to jump to the end of a log or to show the selected row of a list.
+///
+/// This is a cheap handle, it's usually stored in the app state, so that event handlers can use it
+/// and passed to the scroll view via [`Scroll::controller`]. The requests are applied in order
+/// after the next rebuild (which follows every event handler), once the size of the content is known.
+/// They only change the scrolled axes and are clamped like scrolling with the mouse or keyboard.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// struct AppState {
+///     selected: usize,
+///     scroll: ScrollController,
+/// }
+///
+/// # App::new(AppState { selected: 0, scroll: ScrollController::new() }, move |state: &mut AppState| {
+/// let rows: Vec<_> = (0..100)
+///     .map(|i| if i == state.selected { format!("> {i}") } else { format!("  {i}") })
+///     .collect();
+/// v_stack((
+///     h_stack((
+///         "[Top] ".on_click(|state: &mut AppState| state.scroll.scroll_to_top()),
+///         "[Next]".on_click(|state: &mut AppState| {
+///             state.selected += 1;
+///             state.scroll.reveal_child(state.selected);
+///         }),
+///     )),
+///     v_stack(rows).scroll(ScrollAxes::VERTICAL).controller(&state.scroll),
+/// ))
+/// # });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ScrollController {
+    requests: Arc<Mutex<Vec<ScrollRequest>>>,
+}
+
+impl ScrollController {
+    pub fn new() -> Self {
+        ScrollController::default()
+    }
+
+    /// Scrolls vertically to `fraction` of the scrollable range, `0.0` is the top, `1.0` the bottom.
+    pub fn scroll_to_fraction(&self, fraction: f64) {
+        self.request(ScrollRequest::Fraction {
+            x: None,
+            y: Some(fraction),
+        });
+    }
+
+    /// Scrolls horizontally to `fraction` of the scrollable range, `0.0` is the left, `1.0` the right end.
+    pub fn scroll_to_horizontal_fraction(&self, fraction: f64) {
+        self.request(ScrollRequest::Fraction {
+            x: Some(fraction),
+            y: None,
+        });
+    }
+
+    pub fn scroll_to_top(&self) {
+        self.scroll_to_fraction(0.0);
+    }
+
+    pub fn scroll_to_bottom(&self) {
+        self.scroll_to_fraction(1.0);
+    }
+
+    /// Scrolls as little as possible to show the child with `index` of the content,
+    /// e.g. a row of a [`v_stack`](crate::v_stack).
+    pub fn reveal_child(&self, index: usize) {
+        self.request(ScrollRequest::RevealChild(index));
+    }
+
+    /// Scrolls as little as possible to show the descendant widget `id`,
+    /// as returned by e.g. [`App::hit_test`](crate::App::hit_test).
+    pub fn reveal(&self, id: Id) {
+        self.request(ScrollRequest::Reveal(id));
+    }
+
+    fn request(&self, request: ScrollRequest) {
+        self.requests.lock().unwrap().push(request);
+    }
+
+    pub(crate) fn take_requests(&self) -> Vec<ScrollRequest> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
+
+pub struct Scroll<V> {
+    content: V,
+    axes: ScrollAxes,
+    controller: Option<ScrollController>,
+    wheel_step: u16,
+    wheel_acceleration: bool,
+    overflow_indicator: Option<OverflowIndicator>,
+}
+
+impl<V> Scroll<V> {
+    pub(crate) fn new(content: V, axes: ScrollAxes) -> Self {
+        Scroll {
+            content,
+            axes,
+            controller: None,
+            wheel_step: widget::SCROLL_STEP,
+            wheel_acceleration: false,
+            overflow_indicator: None,
+        }
+    }
+
+    /// Applies the requests of `controller` to this scroll view, see [`ScrollController`].
+    pub fn controller(mut self, controller: &ScrollController) -> Self {
+        self.controller = Some(controller.clone());
+        self
+    }
+
+    /// The lines (or columns) that are scrolled with a notch of the mouse wheel, 3 by default.
+    pub fn scroll_step(mut self, step: u16) -> Self {
+        self.wheel_step = step;
+        self
+    }
+
+    /// Increases the [step](Scroll::scroll_step) of the mouse wheel while it's spun quickly,
+    /// up to 4 times the step, to get through large content faster.
+    ///
+    /// The step is reset after a short pause or when the direction changes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// v_stack((0..1000).map(|i| format!("Line {i}")).collect::<Vec<_>>())
+    ///     .scroll(ScrollAxes::VERTICAL)
+    ///     .scroll_step(2)
+    ///     .accelerate_wheel(true)
+    /// # });
+    /// ```
+    pub fn accelerate_wheel(mut self, accelerate: bool) -> Self {
+        self.wheel_acceleration = accelerate;
+        self
+    }
+
+    /// Shows `indicator` on the first or last visible row, when the content is scrolled out of view
+    /// above or below.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// v_stack((0..1000).map(|i| format!("Line {i}")).collect::<Vec<_>>())
+    ///     .scroll(ScrollAxes::VERTICAL)
+    ///     .overflow_indicator(OverflowIndicator::new("more ↑", "more ↓"))
+    /// # });
+    /// ```
+    pub fn overflow_indicator(mut self, indicator: OverflowIndicator) -> Self {
+        self.overflow_indicator = Some(indicator);
+        self
+    }
+
+    fn take_requests(&self) -> Vec<ScrollRequest> {
+        self.controller
+            .as_ref()
+            .map(ScrollController::take_requests)
+            .unwrap_or_default()
+    }
+}
+
+impl<V> ViewMarker for Scroll<V> {}
+
+impl<T, A, V: View<T, A>> View<T, A> for Scroll<V> {
+    type State = V::State;
+
+    type Element = widget::Scroll;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, element) = self.content.build(cx);
+        let mut element = widget::Scroll::new(element, self.axes);
+        element.set_wheel_step(self.wheel_step, self.wheel_acceleration);
+        let _ = element.set_overflow_indicator(self.overflow_indicator.clone());
+        let _ = element.request_scroll(self.take_requests());
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        element.set_wheel_step(self.wheel_step, self.wheel_acceleration);
+        let changeflags = element.set_axes(self.axes)
+            | element.set_overflow_indicator(self.overflow_indicator.clone())
+            | element.request_scroll(self.take_requests());
+        let content_el = element
+            .content()
+            .downcast_mut()
+            .expect("The scroll content widget changed its type, this should never happen!");
+        let content_changeflags = self
+            .content
+            .rebuild(cx, &prev.content, id, state, content_el);
+        changeflags | element.content().mark(content_changeflags)
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.content.message(id_path, state, message, app_state)
+    }
+}
@@ -0,0 +1,104 @@
+use ratatui::style::{Color, Modifier, Style};
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, Styleable, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sparkline {
+    data: Vec<u64>,
+    max: Option<u64>,
+    style: Style,
+}
+
+impl Sparkline {
+    /// Scales the bars to a fixed `max` instead of the largest value, values above it are clamped.
+    pub fn max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+}
+
+impl ViewMarker for Sparkline {}
+
+impl<T, A> View<T, A> for Sparkline {
+    type State = ();
+
+    type Element = widget::Sparkline;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, element) =
+            cx.with_new_id(|_| widget::Sparkline::new(self.data.clone(), self.max, self.style));
+        (id, (), element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut Id,
+        _state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        element.set_data(&self.data) | element.set_max(self.max) | element.set_style(self.style)
+    }
+
+    fn message(
+        &self,
+        _id_path: &[Id],
+        _state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut T,
+    ) -> MessageResult<A> {
+        MessageResult::Stale(message)
+    }
+}
+
+impl Styleable for Sparkline {
+    type Output = Self;
+
+    fn fg(mut self, color: Color) -> Self::Output {
+        self.style.fg = Some(color);
+        self
+    }
+
+    fn bg(mut self, color: Color) -> Self::Output {
+        self.style.bg = Some(color);
+        self
+    }
+
+    fn modifier(mut self, modifier: Modifier) -> Self::Output {
+        self.style = self.style.add_modifier(modifier);
+        self
+    }
+
+    fn style(mut self, style: Style) -> Self::Output {
+        self.style = style;
+        self
+    }
+
+    fn current_style(&self) -> Style {
+        self.style
+    }
+}
+
+/// A compact bar chart of `data`, drawn with the eight block-height glyphs (`▁` to `█`).
+///
+/// The bars are scaled to the largest value (or to a fixed [`max`](Sparkline::max)),
+/// every value takes one column and when there's not enough space only the most recent
+/// (i.e. last) values are shown.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new(vec![1u64, 4, 2, 8, 5, 7], move |cpu_load| {
+/// h_stack(("CPU ", sparkline(cpu_load.as_slice()).fg(Color::Green)))
+/// # });
+/// ```
+pub fn sparkline(data: impl Into<Vec<u64>>) -> Sparkline {
+    Sparkline {
+        data: data.into(),
+        max: None,
+        style: Style::default(),
+    }
+}
@@ -0,0 +1,139 @@
+use std::any::Any;
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::{widget::ChangeFlags, Theme};
+
+pub struct StyleOverride<V, F> {
+    content: V,
+    override_theme: F,
+}
+
+impl<V, F> StyleOverride<V, F> {
+    pub(crate) fn new(content: V, override_theme: F) -> Self {
+        StyleOverride {
+            content,
+            override_theme,
+        }
+    }
+}
+
+impl<V, F: Fn(&mut Theme)> StyleOverride<V, F> {
+    fn theme(&self, cx: &Cx) -> Theme {
+        let mut theme = cx.theme().clone();
+        (self.override_theme)(&mut theme);
+        theme
+    }
+}
+
+impl<V, F> ViewMarker for StyleOverride<V, F> {}
+
+impl<T, A, V, F> View<T, A> for StyleOverride<V, F>
+where
+    V: View<T, A>,
+    F: Fn(&mut Theme) + Send + Sync,
+{
+    type State = V::State;
+
+    type Element = V::Element;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let theme = self.theme(cx);
+        cx.with_theme(theme, |cx| self.content.build(cx))
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        // The theme is resolved on every rebuild, as closures can't be compared
+        let theme = self.theme(cx);
+        cx.with_theme(theme, |cx| {
+            self.content.rebuild(cx, &prev.content, id, state, element)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.content.message(id_path, state, message, app_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::test_mouse, v_stack, App, BorderKind, Color, MouseButton, MouseKind, Style, Theme,
+        ViewExt,
+    };
+
+    fn border_fg(color: Color) -> impl Fn(&mut Theme) + Send + Sync {
+        move |theme: &mut Theme| theme.border.default = theme.border.default.fg(color)
+    }
+
+    #[tokio::test]
+    async fn overrides_take_precedence_over_the_inherited_theme_within_their_subtree() {
+        let mut app = App::new((), |_: &mut ()| {
+            v_stack((
+                v_stack((
+                    "a".border(BorderKind::Straight)
+                        .style_override(border_fg(Color::Blue)),
+                    "b".border(BorderKind::Straight),
+                    "c".border(Style::default().fg(Color::Green)),
+                ))
+                .style_override(border_fg(Color::Red)),
+                "d".border(BorderKind::Straight),
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        let buffer = app.config.terminal.backend().buffer();
+        // the inner override wins over the outer one
+        assert_eq!(buffer.get(0, 0).fg, Color::Blue);
+        assert_eq!(buffer.get(0, 3).fg, Color::Red);
+        // explicit styles of views still take precedence
+        assert_eq!(buffer.get(0, 6).fg, Color::Green);
+        // outside of the subtree the app theme is used
+        assert_eq!(
+            Some(buffer.get(0, 9).fg),
+            Theme::default().border.default.fg
+        );
+    }
+
+    #[tokio::test]
+    async fn the_override_is_applied_again_when_it_changed() {
+        let mut app = App::new(Color::Red, |color: &mut Color| {
+            "a".border(BorderKind::Straight)
+                .on_click(|color: &mut Color| *color = Color::Blue)
+                .style_override(border_fg(*color))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        assert_eq!(
+            app.config.terminal.backend().buffer().get(0, 0).fg,
+            Color::Red
+        );
+
+        let left = MouseButton::Left;
+        // and move away again, so that the hover style isn't patched on the border
+        app.dispatch([
+            test_mouse(MouseKind::Down(left), 1, 1),
+            test_mouse(MouseKind::Up(left), 1, 1),
+            test_mouse(MouseKind::Moved, 20, 20),
+        ])
+        .await;
+        assert_eq!(
+            app.config.terminal.backend().buffer().get(0, 0).fg,
+            Color::Blue
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use std::{any::Any, borrow::Cow, collections::HashSet, marker::PhantomData, sync::Arc};
+
+use ratatui::style::{Modifier, Style};
+use xilem_core::{Id, MessageResult};
+
+use super::{rebuild_handler, Cx, EventHandler, View, ViewMarker};
+use crate::{
+    widget::{self, ChangeFlags, TableMessage, TableStyles},
+    Alignment, OverflowIndicator,
+};
+
+/// How the width of a table [`Column`] is computed from the width of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnWidth {
+    /// A fixed number of cells
+    Fixed(u16),
+    /// A percentage of the width of the table
+    Percent(u16),
+    /// A share of the width that's left over by the other columns, relative to the weights of all
+    /// columns with a weight. The column is at least as wide as its title.
+    Weight(u16),
+}
+
+/// A column of a [`table`], i.e. its header and how the cells are laid out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub(crate) title: Cow<'static, str>,
+    pub(crate) width: ColumnWidth,
+    pub(crate) alignment: Alignment,
+    pub(crate) sortable: bool,
+}
+
+impl Column {
+    /// A column with weight 1, whose cells are aligned at the start.
+    pub fn new(title: impl Into<Cow<'static, str>>) -> Self {
+        Column {
+            title: title.into(),
+            width: ColumnWidth::Weight(1),
+            alignment: Alignment::Start,
+            sortable: false,
+        }
+    }
+
+    pub fn width(mut self, width: ColumnWidth) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The alignment of the cells, e.g. [`Alignment::End`] for numbers.
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Clicking the header of a sortable column calls the [`on_sort`](Table::on_sort) handler.
+    pub fn sortable(mut self) -> Self {
+        self.sortable = true;
+        self
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub struct Table<
+    T,
+    A,
+    SH = fn(&mut T, usize) -> A,
+    OH = fn(&mut T, (usize, bool)) -> A,
+    CH = fn(&mut T, HashSet<usize>) -> A,
+> {
+    columns: Vec<Column>,
+    rows: Arc<Vec<Vec<String>>>,
+    selected: Option<usize>,
+    selected_rows: Option<HashSet<usize>>,
+    sort: Option<(usize, bool)>,
+    on_select: Option<SH>,
+    on_sort: Option<OH>,
+    on_selection_change: Option<CH>,
+    header_style: Option<Style>,
+    text_style: Option<Style>,
+    selected_style: Option<Style>,
+    overflow_indicator: Option<OverflowIndicator>,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+pub struct TableState<SHS, OHS, CHS> {
+    on_select: Option<(Id, SHS)>,
+    on_sort: Option<(Id, OHS)>,
+    on_selection_change: Option<(Id, CHS)>,
+}
+
+impl<T, A, SH, OH, CH> Table<T, A, SH, OH, CH> {
+    /// Selects `row` (and scrolls to it) when the table is built or when the selection passed here changes,
+    /// otherwise the table keeps the selection of the user.
+    pub fn selected(mut self, row: Option<usize>) -> Self {
+        self.selected = row;
+        self
+    }
+
+    /// Selects `rows` in the multi-select mode (see [`on_selection_change`](Table::on_selection_change)),
+    /// when the table is built or when the rows passed here change.
+    ///
+    /// The selection refers to the indices of the rows, so it doesn't follow reordered rows,
+    /// e.g. after sorting the app has to pass the new indices of the selected rows.
+    pub fn selected_rows(mut self, rows: HashSet<usize>) -> Self {
+        self.selected_rows = Some(rows);
+        self
+    }
+
+    /// Shows a sort indicator in the header of `column`, which should match the order of the rows.
+    ///
+    /// Like [`selected`](Table::selected) it's only applied when it changes.
+    pub fn sorted_by(mut self, column: usize, ascending: bool) -> Self {
+        self.sort = Some((column, ascending));
+        self
+    }
+
+    /// Calls `on_select` with the index of the row, which was selected with the keyboard or the mouse.
+    pub fn on_select<SH2: EventHandler<T, A, usize>>(
+        self,
+        on_select: SH2,
+    ) -> Table<T, A, SH2, OH, CH> {
+        Table {
+            columns: self.columns,
+            rows: self.rows,
+            selected: self.selected,
+            selected_rows: self.selected_rows,
+            sort: self.sort,
+            on_select: Some(on_select),
+            on_sort: self.on_sort,
+            on_selection_change: self.on_selection_change,
+            header_style: self.header_style,
+            text_style: self.text_style,
+            selected_style: self.selected_style,
+            overflow_indicator: self.overflow_indicator,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Calls `on_sort` with the index of the column and whether it should be sorted ascending,
+    /// when the header of a [`sortable`](Column::sortable) column is clicked.
+    ///
+    /// The first click sorts ascending, the next one descending. Sorting the rows is up to the app.
+    pub fn on_sort<OH2: EventHandler<T, A, (usize, bool)>>(
+        self,
+        on_sort: OH2,
+    ) -> Table<T, A, SH, OH2, CH> {
+        Table {
+            columns: self.columns,
+            rows: self.rows,
+            selected: self.selected,
+            selected_rows: self.selected_rows,
+            sort: self.sort,
+            on_select: self.on_select,
+            on_sort: Some(on_sort),
+            on_selection_change: self.on_selection_change,
+            header_style: self.header_style,
+            text_style: self.text_style,
+            selected_style: self.selected_style,
+            overflow_indicator: self.overflow_indicator,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Enables the multi-select mode and calls `on_selection_change` with the set of selected rows,
+    /// whenever it changes.
+    ///
+    /// `Space` toggles whether the row under the keyboard cursor (which is moved with the navigation
+    /// keys and underlined) is selected, `Shift` with a navigation key or a click selects the range
+    /// from the last toggled or clicked row. A click selects only the clicked row, `Ctrl`+click
+    /// toggles it. Selected rows which are removed are dropped from the selection, which is reported
+    /// with the next render pass. [`on_select`](Table::on_select) is called when the cursor moves.
+    pub fn on_selection_change<CH2: EventHandler<T, A, HashSet<usize>>>(
+        self,
+        on_selection_change: CH2,
+    ) -> Table<T, A, SH, OH, CH2> {
+        Table {
+            columns: self.columns,
+            rows: self.rows,
+            selected: self.selected,
+            selected_rows: self.selected_rows,
+            sort: self.sort,
+            on_select: self.on_select,
+            on_sort: self.on_sort,
+            on_selection_change: Some(on_selection_change),
+            header_style: self.header_style,
+            text_style: self.text_style,
+            selected_style: self.selected_style,
+            overflow_indicator: self.overflow_indicator,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = Some(style);
+        self
+    }
+
+    pub fn text_style(mut self, style: Style) -> Self {
+        self.text_style = Some(style);
+        self
+    }
+
+    pub fn selected_style(mut self, style: Style) -> Self {
+        self.selected_style = Some(style);
+        self
+    }
+
+    /// Shows `indicator` on the first or last visible row, when rows are scrolled out of view.
+    pub fn overflow_indicator(mut self, indicator: OverflowIndicator) -> Self {
+        self.overflow_indicator = Some(indicator);
+        self
+    }
+
+    fn resolve_styles(&self, cx: &Cx) -> TableStyles {
+        let theme = cx.theme();
+        TableStyles {
+            header: self.header_style.unwrap_or(
+                theme
+                    .text
+                    .default
+                    .bg(theme.surface_variant())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            text: self.text_style.unwrap_or(theme.text.default),
+            selected: self.selected_style.unwrap_or(theme.text.selected),
+        }
+    }
+}
+
+impl<T, A, SH, OH, CH> ViewMarker for Table<T, A, SH, OH, CH> {}
+
+impl<T, A, SH, OH, CH> View<T, A> for Table<T, A, SH, OH, CH>
+where
+    SH: EventHandler<T, A, usize>,
+    OH: EventHandler<T, A, (usize, bool)>,
+    CH: EventHandler<T, A, HashSet<usize>>,
+{
+    type State = TableState<SH::State, OH::State, CH::State>;
+
+    type Element = widget::Table;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let mut element = widget::Table::new(
+                cx.id_path(),
+                self.columns.clone(),
+                self.rows.clone(),
+                self.selected,
+                self.sort,
+                self.resolve_styles(cx),
+            );
+            let _ = element.set_overflow_indicator(self.overflow_indicator.clone());
+            let _ = element.set_multi_select(self.on_selection_change.is_some());
+            if let Some(rows) = &self.selected_rows {
+                let _ = element.set_selection(rows.clone());
+            }
+            let state = TableState {
+                on_select: self.on_select.as_ref().map(|h| h.build(cx)),
+                on_sort: self.on_sort.as_ref().map(|h| h.build(cx)),
+                on_selection_change: self.on_selection_change.as_ref().map(|h| h.build(cx)),
+            };
+            (state, element)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let mut changeflags = element.set_columns(&self.columns)
+                | element.set_rows(&self.rows)
+                | element.set_styles(self.resolve_styles(cx))
+                | element.set_overflow_indicator(self.overflow_indicator.clone())
+                | rebuild_handler(cx, self.on_select.as_ref(), &mut state.on_select)
+                | rebuild_handler(cx, self.on_sort.as_ref(), &mut state.on_sort)
+                | rebuild_handler(
+                    cx,
+                    self.on_selection_change.as_ref(),
+                    &mut state.on_selection_change,
+                )
+                | element.set_multi_select(self.on_selection_change.is_some());
+            if self.selected != prev.selected {
+                changeflags |= element.set_selected(self.selected);
+            }
+            if let Some(rows) = self
+                .selected_rows
+                .as_ref()
+                .filter(|rows| prev.selected_rows.as_ref() != Some(*rows))
+            {
+                changeflags |= element.set_selection(rows.clone());
+            }
+            if self.sort != prev.sort {
+                changeflags |= element.set_sort(self.sort);
+            }
+            changeflags
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] if message.downcast_ref::<TableMessage>().is_some() => {
+                match *message.downcast::<TableMessage>().unwrap() {
+                    TableMessage::Select(row) => match (&self.on_select, &mut state.on_select) {
+                        (Some(on_select), Some((_, select_state))) => {
+                            on_select.message(&[], select_state, Box::new(row), app_state)
+                        }
+                        _ => MessageResult::Nop,
+                    },
+                    TableMessage::Sort { column, ascending } => {
+                        match (&self.on_sort, &mut state.on_sort) {
+                            (Some(on_sort), Some((_, sort_state))) => on_sort.message(
+                                &[],
+                                sort_state,
+                                Box::new((column, ascending)),
+                                app_state,
+                            ),
+                            _ => MessageResult::Nop,
+                        }
+                    }
+                    TableMessage::SelectionChange(rows) => {
+                        match (&self.on_selection_change, &mut state.on_selection_change) {
+                            (Some(on_change), Some((_, change_state))) => {
+                                on_change.message(&[], change_state, Box::new(rows), app_state)
+                            }
+                            _ => MessageResult::Nop,
+                        }
+                    }
+                }
+            }
+            [id, rest_path @ ..] => {
+                if let (Some(on_select), Some((select_id, select_state))) =
+                    (&self.on_select, &mut state.on_select)
+                {
+                    if select_id == id {
+                        return on_select.message(rest_path, select_state, message, app_state);
+                    }
+                }
+                if let (Some(on_sort), Some((sort_id, sort_state))) =
+                    (&self.on_sort, &mut state.on_sort)
+                {
+                    if sort_id == id {
+                        return on_sort.message(rest_path, sort_state, message, app_state);
+                    }
+                }
+                if let (Some(on_change), Some((change_id, change_state))) =
+                    (&self.on_selection_change, &mut state.on_selection_change)
+                {
+                    if change_id == id {
+                        return on_change.message(rest_path, change_state, message, app_state);
+                    }
+                }
+                MessageResult::Stale(message)
+            }
+            [] => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// A table with a header row and the cells of `rows`, which are laid out in `columns`.
+///
+/// A row can be selected with the mouse or with `Up`/`Down`, `PageUp`/`PageDown` and `Home`/`End`
+/// while the table is focused, the table scrolls to keep the selected row visible.
+/// If the columns are wider than the table, `Left`/`Right` (or the horizontal mouse wheel) scroll
+/// them horizontally. Only the visible rows are painted, so very long tables are cheap,
+/// especially when `rows` is an `Arc` that's kept across frames, as it doesn't have to be compared then.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// struct AppState {
+///     files: Vec<(String, u64)>,
+///     selected: Option<usize>,
+/// }
+///
+/// # App::new(AppState { files: vec![], selected: None }, move |state: &mut AppState| {
+/// let rows = state
+///     .files
+///     .iter()
+///     .map(|(name, size)| vec![name.clone(), size.to_string()])
+///     .collect::<Vec<_>>();
+/// table(
+///     [
+///         Column::new("Name").sortable(),
+///         Column::new("Size")
+///             .width(ColumnWidth::Fixed(10))
+///             .alignment(Alignment::End)
+///             .sortable(),
+///     ],
+///     rows,
+/// )
+/// .on_select(|state: &mut AppState, row: usize| state.selected = Some(row))
+/// .on_sort(|state: &mut AppState, (column, ascending): (usize, bool)| {
+///     state.files.sort_by(|a, b| match column {
+///         0 => a.0.cmp(&b.0),
+///         _ => a.1.cmp(&b.1),
+///     });
+///     if !ascending {
+///         state.files.reverse();
+///     }
+/// })
+/// .overflow_indicator(OverflowIndicator::default())
+/// # });
+/// ```
+pub fn table<T, A>(
+    columns: impl IntoIterator<Item = Column>,
+    rows: impl Into<Arc<Vec<Vec<String>>>>,
+) -> Table<T, A> {
+    Table {
+        columns: columns.into_iter().collect(),
+        rows: rows.into(),
+        selected: None,
+        selected_rows: None,
+        sort: None,
+        on_select: None,
+        on_sort: None,
+        on_selection_change: None,
+        header_style: None,
+        text_style: None,
+        selected_style: None,
+        overflow_indicator: None,
+        phantom: PhantomData,
+    }
+}
@@ -1,25 +1,22 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::Range};
 
-use super::{common::Styleable, Cx, View, ViewMarker};
+use super::{
+    common::{ControlChars, Styleable, TruncateMode},
+    Cx, View, ViewMarker,
+};
 use crate::widget::{self, ChangeFlags};
 use ratatui::style::{Color, Modifier, Style};
 use unicode_segmentation::UnicodeSegmentation;
 
 impl From<&'static str> for Text {
     fn from(text: &'static str) -> Self {
-        Text {
-            text: text.into(),
-            style: Style::default(),
-        }
+        Cow::Borrowed(text).into()
     }
 }
 
 impl From<String> for Text {
     fn from(text: String) -> Self {
-        Text {
-            text: text.into(),
-            style: Style::default(),
-        }
+        Cow::<'static, str>::Owned(text).into()
     }
 }
 
@@ -28,6 +25,12 @@ impl From<Cow<'static, str>> for Text {
         Text {
             text,
             style: Style::default(),
+            truncate: None,
+            ellipsis: "…".into(),
+            tab_width: 4,
+            control_chars: ControlChars::default(),
+            highlights: Vec::new(),
+            highlight_style: None,
         }
     }
 }
@@ -36,6 +39,142 @@ impl From<Cow<'static, str>> for Text {
 pub struct Text {
     text: Cow<'static, str>,
     style: Style,
+    truncate: Option<TruncateMode>,
+    ellipsis: Cow<'static, str>,
+    tab_width: u16,
+    control_chars: ControlChars,
+    highlights: Vec<Range<usize>>,
+    highlight_style: Option<Style>,
+}
+
+impl Text {
+    /// Cuts the text off with an ellipsis (`…` by default) when it's wider than the available space.
+    ///
+    /// Every line is truncated separately, without splitting wide characters or graphemes.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// v_stack((
+    ///     "/home/user/projects/trui/src/widget/text.rs".truncate(TruncateMode::Middle),
+    ///     "Some very long status message".truncate(TruncateMode::End).ellipsis("..."),
+    /// ))
+    /// .fill_max_width(0.3)
+    /// # });
+    /// ```
+    pub fn truncate(mut self, mode: TruncateMode) -> Self {
+        self.truncate = Some(mode);
+        self
+    }
+
+    /// The replacement of the cut off part of the text, when it's truncated via [`Text::truncate`].
+    pub fn ellipsis(mut self, ellipsis: impl Into<Cow<'static, str>>) -> Self {
+        self.ellipsis = ellipsis.into();
+        self
+    }
+
+    /// The distance between tab stops in cells, `4` by default.
+    ///
+    /// Tabs are expanded with spaces up to the next tab stop, so that columns of tab separated text
+    /// are aligned, as long as the text between the tabs is shorter than the tab width.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// Text::from("name\tsize\nfoo.rs\t42").tab_width(8)
+    /// # });
+    /// ```
+    pub fn tab_width(mut self, tab_width: u16) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// How control characters and escape sequences in the text are displayed,
+    /// by default they're shown in caret notation (e.g. `^C`) or removed, see [`ControlChars`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// // e.g. the output of a process, which is colored with ANSI escape sequences
+    /// Text::from("\x1b[32mok\x1b[0m 3 tests passed").control_chars(ControlChars::Ansi)
+    /// # });
+    /// ```
+    pub fn control_chars(mut self, control_chars: ControlChars) -> Self {
+        self.control_chars = control_chars;
+        self
+    }
+
+    /// Highlights the byte `range` of the text, e.g. a search result, with the highlight background
+    /// of the theme or the [`highlight_style`](Text::highlight_style).
+    ///
+    /// The range is extended to whole graphemes, so that no characters are split,
+    /// it can be called multiple times to highlight multiple ranges.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// let text = "fn main() { main_loop() }";
+    /// let query = "main";
+    /// Text::from(text).highlights(text.match_indices(query).map(|(i, m)| i..i + m.len()))
+    /// # });
+    /// ```
+    pub fn highlight(mut self, range: Range<usize>) -> Self {
+        self.highlights.push(range);
+        self
+    }
+
+    /// Highlights multiple byte ranges of the text, see [`Text::highlight`].
+    pub fn highlights(mut self, ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        self.highlights.extend(ranges);
+        self
+    }
+
+    /// The style which is patched onto the highlighted ranges, by default the highlight background
+    /// of the theme, e.g. `Style::default().add_modifier(Modifier::REVERSED)` shows them in reversed video.
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = Some(style);
+        self
+    }
+}
+
+/// Text with ANSI escape sequences, e.g. the output of a command, whose colors and modifiers (SGR)
+/// are rendered as styled text, see [`ControlChars::Ansi`].
+///
+/// Other escape sequences (e.g. cursor movement) are ignored, as well as invalid or unterminated ones,
+/// other control characters are shown in caret notation (e.g. `^C`).
+/// The style of the text is the base style, which is patched with the styles of the escape sequences.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new((), move |()| {
+/// ansi_text("\x1b[1;31merror\x1b[0m: expected `;`\n\x1b[34m -->\x1b[0m src/main.rs:2:5").fg(Color::Gray)
+/// # });
+/// ```
+pub fn ansi_text(text: impl Into<Cow<'static, str>>) -> Text {
+    Text::from(text.into()).control_chars(ControlChars::Ansi)
+}
+
+pub trait ToTruncatedText {
+    /// See [`Text::truncate`].
+    fn truncate(self, mode: TruncateMode) -> Text;
+}
+
+// Not implemented for `String`, as it would shadow `String::truncate`, use `Text::from(string).truncate(..)` instead.
+impl ToTruncatedText for &'static str {
+    fn truncate(self, mode: TruncateMode) -> Text {
+        Text::from(self).truncate(mode)
+    }
+}
+
+impl ToTruncatedText for Cow<'static, str> {
+    fn truncate(self, mode: TruncateMode) -> Text {
+        Text::from(self).truncate(mode)
+    }
 }
 
 impl<T: Into<Text>> ViewMarker for T {}
@@ -47,26 +186,40 @@ impl<T, A, S: Into<Text> + Clone + Send + Sync + Eq> View<T, A> for S {
 
     fn build(&self, cx: &mut Cx) -> (xilem_core::Id, Self::State, Self::Element) {
         let text = self.clone().into();
-        let (id, element) = cx.with_new_id(|_| widget::Text {
+        let (id, element) = cx.with_new_id(|cx| widget::Text {
             text: text.text,
             style: text.style,
+            truncate: text.truncate,
+            ellipsis: text.ellipsis,
+            tab_width: text.tab_width,
+            control_chars: text.control_chars,
+            hover_style: cx.theme().text.hover,
+            highlights: text.highlights,
+            highlight_style: text.highlight_style,
+            theme_highlight_style: cx.theme().text.highlight,
         });
         (id, (), element)
     }
 
     fn rebuild(
         &self,
-        _cx: &mut Cx,
+        cx: &mut Cx,
         prev: &Self,
         _id: &mut xilem_core::Id,
         _state: &mut Self::State,
         element: &mut Self::Element,
     ) -> ChangeFlags {
-        let mut changeflags = ChangeFlags::empty();
+        let theme = &cx.theme().text;
+        let mut changeflags = element.set_hover_style(theme.hover)
+            | element.set_theme_highlight_style(theme.highlight);
         if self != prev {
             let text = self.clone().into();
+            changeflags |= element.set_highlights(&text.highlights, text.highlight_style);
             changeflags |= element.set_text(text.text.clone());
             changeflags |= element.set_style(text.style);
+            changeflags |= element.set_truncation(text.truncate, &text.ellipsis);
+            changeflags |= element.set_tab_width(text.tab_width);
+            changeflags |= element.set_control_chars(text.control_chars);
         }
         changeflags
     }
@@ -222,3 +375,129 @@ impl<T, A> View<T, A> for WrappedText {
         xilem_core::MessageResult::Stale(message)
     }
 }
+
+/// A single line of text, that scrolls horizontally in a loop when it doesn't fit into its box.
+///
+/// Created via [`ToMarqueeText::marquee`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarqueeText {
+    text: Cow<'static, str>,
+    style: Style,
+    tab_width: u16,
+    control_chars: ControlChars,
+    speed: f64,
+}
+
+impl MarqueeText {
+    /// The scroll speed in cells per second (default is 8).
+    pub fn speed(mut self, cells_per_second: f64) -> Self {
+        self.speed = cells_per_second;
+        self
+    }
+}
+
+pub trait ToMarqueeText {
+    /// Scrolls the text in a loop, instead of truncating it, when it's wider than the available space.
+    ///
+    /// The animation is paused while the text fits. Tabs and control characters are displayed
+    /// like in a [`Text`], see [`Text::tab_width`] and [`Text::control_chars`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// "Now playing: a song with a title that's way too long for the status bar"
+    ///     .marquee()
+    ///     .speed(4.0)
+    ///     .fill_max_width(0.5)
+    /// # });
+    /// ```
+    fn marquee(self) -> MarqueeText;
+}
+
+impl<T: Into<Text>> ToMarqueeText for T {
+    fn marquee(self) -> MarqueeText {
+        let text = self.into();
+        MarqueeText {
+            text: text.text,
+            style: text.style,
+            tab_width: text.tab_width,
+            control_chars: text.control_chars,
+            speed: 8.0,
+        }
+    }
+}
+
+impl ViewMarker for MarqueeText {}
+
+impl<T, A> View<T, A> for MarqueeText {
+    type State = ();
+
+    type Element = widget::MarqueeText;
+
+    fn build(&self, cx: &mut Cx) -> (xilem_core::Id, Self::State, Self::Element) {
+        let (id, element) = cx.with_new_id(|_| {
+            widget::MarqueeText::new(
+                self.text.clone(),
+                self.style,
+                self.tab_width,
+                self.control_chars,
+                self.speed,
+            )
+        });
+        (id, (), element)
+    }
+
+    fn rebuild(
+        &self,
+        _cx: &mut Cx,
+        _prev: &Self,
+        _id: &mut xilem_core::Id,
+        _state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        element.set_text(&self.text)
+            | element.set_style(self.style)
+            | element.set_tab_width(self.tab_width)
+            | element.set_control_chars(self.control_chars)
+            | element.set_speed(self.speed)
+    }
+
+    fn message(
+        &self,
+        _id_path: &[xilem_core::Id],
+        _state: &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        _app_state: &mut T,
+    ) -> xilem_core::MessageResult<A> {
+        xilem_core::MessageResult::Stale(message)
+    }
+}
+
+impl Styleable for MarqueeText {
+    type Output = Self;
+
+    fn fg(mut self, color: Color) -> Self::Output {
+        self.style.fg = Some(color);
+        self
+    }
+
+    fn bg(mut self, color: Color) -> Self::Output {
+        self.style.bg = Some(color);
+        self
+    }
+
+    fn modifier(mut self, modifier: Modifier) -> Self::Output {
+        self.style = self.style.add_modifier(modifier);
+        self
+    }
+
+    fn style(mut self, style: Style) -> Self::Output {
+        self.style = style;
+        self
+    }
+
+    fn current_style(&self) -> Style {
+        self.style
+    }
+}
@@ -0,0 +1,147 @@
+use ratatui::style::{Color, Modifier, Style};
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, EventHandler, Styleable, View, ViewMarker};
+use crate::{
+    widget::{self, ChangeFlags},
+    OverflowIndicator,
+};
+
+pub struct TextArea<EH> {
+    text: String,
+    style: Style,
+    overflow_indicator: Option<OverflowIndicator>,
+    event_handler: EH,
+}
+
+impl<EH> TextArea<EH> {
+    /// Shows `indicator` on the first or last row, when the text is scrolled out of view.
+    pub fn overflow_indicator(mut self, indicator: OverflowIndicator) -> Self {
+        self.overflow_indicator = Some(indicator);
+        self
+    }
+
+    /// The style of the [`Theme`](crate::Theme) patched with the explicitly set style.
+    fn resolve_style(&self, cx: &Cx) -> Style {
+        let theme = cx.theme();
+        theme
+            .text
+            .default
+            .bg(theme.surface_variant())
+            .patch(self.style)
+    }
+}
+
+impl<EH> ViewMarker for TextArea<EH> {}
+
+impl<T, A, EH: EventHandler<T, A, String>> View<T, A> for TextArea<EH> {
+    type State = (Id, EH::State);
+
+    type Element = widget::TextArea;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let mut element =
+                widget::TextArea::new(cx.id_path(), self.text.clone(), self.resolve_style(cx));
+            let _ = element.set_overflow_indicator(self.overflow_indicator.clone());
+            (self.event_handler.build(cx), element)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        id: &mut Id,
+        (event_handler_id, event_handler_state): &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            element.set_text(&self.text)
+                | element.set_style(self.resolve_style(cx))
+                | element.set_overflow_indicator(self.overflow_indicator.clone())
+                | self
+                    .event_handler
+                    .rebuild(cx, event_handler_id, event_handler_state)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        (event_handler_id, event_handler_state): &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [first, rest_path @ ..] if first == event_handler_id => {
+                self.event_handler
+                    .message(rest_path, event_handler_state, message, app_state)
+            }
+            [] => self
+                .event_handler
+                .message(&[], event_handler_state, message, app_state),
+            [..] => MessageResult::Stale(message),
+        }
+    }
+}
+
+impl<EH> Styleable for TextArea<EH> {
+    type Output = Self;
+
+    fn fg(mut self, color: Color) -> Self::Output {
+        self.style.fg = Some(color);
+        self
+    }
+
+    fn bg(mut self, color: Color) -> Self::Output {
+        self.style.bg = Some(color);
+        self
+    }
+
+    fn modifier(mut self, modifier: Modifier) -> Self::Output {
+        self.style = self.style.add_modifier(modifier);
+        self
+    }
+
+    fn style(mut self, style: Style) -> Self::Output {
+        self.style = style;
+        self
+    }
+
+    fn current_style(&self) -> Style {
+        self.style
+    }
+}
+
+/// A multi-line text editor, which is edited while it's focused (by clicking on it or via `Tab`).
+///
+/// Lines are wrapped at the width of the text area, and it scrolls vertically to keep the cursor
+/// visible. Besides the usual editing keys it supports `Up`/`Down`, `PageUp`/`PageDown`, `Home`/`End`
+/// and `Ctrl+Home`/`Ctrl+End` for navigation. `on_change` is called with the whole text after every edit.
+///
+/// It fills the available space, use e.g. [`fill_max_size`](crate::ViewExt::fill_max_size) inside of stacks.
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new(String::new(), move |notes| {
+/// v_stack((
+///     "Notes:",
+///     text_area(notes.clone(), |notes: &mut String, new_notes: String| *notes = new_notes)
+///         .fill_max_size(1.0),
+/// ))
+/// # });
+/// ```
+pub fn text_area<T, A, EH: EventHandler<T, A, String>>(
+    text: impl Into<String>,
+    on_change: EH,
+) -> TextArea<EH> {
+    TextArea {
+        text: text.into(),
+        style: Style::default(),
+        overflow_indicator: None,
+        event_handler: on_change,
+    }
+}
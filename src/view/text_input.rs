@@ -0,0 +1,380 @@
+use std::{borrow::Cow, marker::PhantomData, sync::Arc, time::Duration};
+
+use ratatui::style::{Color, Modifier, Style};
+use xilem_core::{Id, MessageResult};
+
+use super::{rebuild_handler, Cx, EventHandler, Styleable, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+/// The result of the [`validator`](TextInput::validate) of a text input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Validation {
+    Valid,
+    /// The text is invalid, with a message which describes why.
+    Invalid(String),
+}
+
+impl Validation {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validation::Valid)
+    }
+}
+
+/// How line breaks in text, which is pasted into a (single line) text input, are handled.
+///
+/// A trailing line break, as it's often copied with a line, is always dropped.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PasteNewlines {
+    /// The lines are joined with a space
+    #[default]
+    Join,
+    /// Text with line breaks isn't pasted at all
+    Reject,
+}
+
+pub struct TextInput<T, A, EH, VH = fn(&mut T, Validation) -> A> {
+    text: String,
+    style: Style,
+    placeholder: Cow<'static, str>,
+    mask: Option<char>,
+    validator: Option<widget::Validator>,
+    validation_delay: Duration,
+    show_error_message: bool,
+    suggestions: Vec<String>,
+    cursor_blink: Option<Duration>,
+    paste_newlines: PasteNewlines,
+    event_handler: EH,
+    on_validation: Option<VH>,
+    phantom: PhantomData<fn() -> (T, A)>,
+}
+
+impl<T, A, EH, VH> TextInput<T, A, EH, VH> {
+    /// Dimmed text, which is shown while the text is empty, e.g. "Search…".
+    ///
+    /// It's not part of the text, which is passed to `on_change`.
+    pub fn placeholder(mut self, placeholder: impl Into<Cow<'static, str>>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Paints `mask` instead of each character (grapheme) of the text, e.g. for credentials.
+    ///
+    /// Only the painting is affected, `on_change` is still called with the actual text,
+    /// which is never written to the terminal.
+    pub fn mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Masks the text with `•`, see [`mask`](TextInput::mask).
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(String::new(), move |password| {
+    /// text_input(password.clone(), |password: &mut String, new: String| *password = new)
+    ///     .password()
+    ///     .placeholder("Password")
+    /// # });
+    /// ```
+    pub fn password(self) -> Self {
+        self.mask('•')
+    }
+
+    /// Shows `suggestions` in a dropdown below the input while it's focused, usually the candidates
+    /// matching the current text, which are filtered by the app with every rebuild.
+    ///
+    /// `Up`/`Down` select a suggestion, `Tab`/`Enter` replace the text with it and `Esc` closes the
+    /// dropdown (keeping the typed text) until the next edit. A [masked](TextInput::mask) input
+    /// shows no suggestions, as they would reveal the text.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// const LANGUAGES: [&str; 4] = ["C", "Haskell", "Python", "Rust"];
+    ///
+    /// # App::new(String::new(), move |language| {
+    /// let matches = LANGUAGES
+    ///     .iter()
+    ///     .filter(|l| !language.is_empty() && l.to_lowercase().starts_with(&language.to_lowercase()));
+    /// text_input(language.clone(), |language: &mut String, new: String| *language = new)
+    ///     .suggestions(matches.copied())
+    /// # });
+    /// ```
+    pub fn suggestions<S: Into<String>>(
+        mut self,
+        suggestions: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.suggestions = suggestions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Checks the text after every edit, an error is shown in the error color of the
+    /// [`Theme`](crate::Theme) with its message below the input, until the text is valid again.
+    ///
+    /// The result is passed to [`on_validation`](TextInput::on_validation),
+    /// `on_change` is called with every edit regardless of the result.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new(String::new(), move |port| {
+    /// text_input(port.clone(), |port: &mut String, new: String| *port = new)
+    ///     .validate(|port| match port.parse::<u16>() {
+    ///         Ok(_) => Ok(()),
+    ///         Err(_) => Err("Not a port number".to_string()),
+    ///     })
+    ///     .placeholder("Port")
+    /// # });
+    /// ```
+    pub fn validate(
+        mut self,
+        validator: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.validator = Some(Arc::new(validator));
+        self
+    }
+
+    /// Delays the [validation](TextInput::validate) until the text hasn't changed for `delay`,
+    /// e.g. for expensive checks, which shouldn't run with every key press.
+    pub fn validation_delay(mut self, delay: Duration) -> Self {
+        self.validation_delay = delay;
+        self
+    }
+
+    /// Whether the message of a failed [validation](TextInput::validate) is shown below the input,
+    /// otherwise only the color of the input changes (default `true`).
+    pub fn show_error_message(mut self, show: bool) -> Self {
+        self.show_error_message = show;
+        self
+    }
+
+    /// Blinks the cursor while the input is focused, by toggling it on and off every `interval`
+    /// (e.g. 500ms), otherwise the cursor is always shown.
+    ///
+    /// The cursor stays visible while typing, it starts blinking again after the last key press.
+    pub fn blink_cursor(mut self, interval: Duration) -> Self {
+        self.cursor_blink = Some(interval);
+        self
+    }
+
+    /// How line breaks in pasted text are handled, they're joined with a space by default.
+    ///
+    /// Pasted text is inserted as a whole, i.e. `on_change` and the validation are called once.
+    /// Tabs are replaced with a space, other control characters and escape sequences are removed.
+    pub fn paste_newlines(mut self, paste_newlines: PasteNewlines) -> Self {
+        self.paste_newlines = paste_newlines;
+        self
+    }
+
+    /// Calls `on_validation` with the result of every [validation](TextInput::validate).
+    pub fn on_validation<VH2: EventHandler<T, A, Validation>>(
+        self,
+        on_validation: VH2,
+    ) -> TextInput<T, A, EH, VH2> {
+        TextInput {
+            text: self.text,
+            style: self.style,
+            placeholder: self.placeholder,
+            mask: self.mask,
+            validator: self.validator,
+            validation_delay: self.validation_delay,
+            show_error_message: self.show_error_message,
+            suggestions: self.suggestions,
+            cursor_blink: self.cursor_blink,
+            paste_newlines: self.paste_newlines,
+            event_handler: self.event_handler,
+            on_validation: Some(on_validation),
+            phantom: PhantomData,
+        }
+    }
+
+    /// The styles of the suggestions and the selected suggestion in the dropdown.
+    fn resolve_suggestion_styles(&self, cx: &Cx) -> (Style, Style) {
+        let theme = cx.theme();
+        (
+            theme.text.default.bg(theme.surface_variant()),
+            theme.text.selected,
+        )
+    }
+
+    /// The style of the [`Theme`](crate::Theme) patched with the explicitly set style.
+    fn resolve_style(&self, cx: &Cx) -> Style {
+        let theme = cx.theme();
+        theme
+            .text
+            .default
+            .bg(theme.surface_variant())
+            .patch(self.style)
+    }
+}
+
+impl<T, A, EH, VH> ViewMarker for TextInput<T, A, EH, VH> {}
+
+impl<T, A, EH, VH> View<T, A> for TextInput<T, A, EH, VH>
+where
+    EH: EventHandler<T, A, String>,
+    VH: EventHandler<T, A, Validation>,
+{
+    type State = (Id, EH::State, Option<(Id, VH::State)>);
+
+    type Element = widget::TextInput;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let mut element =
+                widget::TextInput::new(cx.id_path(), self.text.clone(), self.resolve_style(cx));
+            let _ = element.set_placeholder(&self.placeholder, cx.theme().text.disabled);
+            let _ = element.set_mask(self.mask);
+            element.set_validator(self.validator.clone(), self.validation_delay);
+            let _ = element.set_suggestions(&self.suggestions);
+            let _ = element.set_cursor_blink(self.cursor_blink);
+            element.set_paste_newlines(self.paste_newlines);
+            let _ = element.set_suggestion_styles(self.resolve_suggestion_styles(cx));
+            let _ = element.set_error_style(
+                Style::default().fg(cx.theme().error()),
+                self.show_error_message,
+            );
+            let (event_handler_id, event_handler_state) = self.event_handler.build(cx);
+            let on_validation = self.on_validation.as_ref().map(|h| h.build(cx));
+            (
+                (event_handler_id, event_handler_state, on_validation),
+                element,
+            )
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        id: &mut Id,
+        (event_handler_id, event_handler_state, on_validation): &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            element.set_validator(self.validator.clone(), self.validation_delay);
+            element.set_paste_newlines(self.paste_newlines);
+            element.set_text(&self.text)
+                | element.set_style(self.resolve_style(cx))
+                | element.set_placeholder(&self.placeholder, cx.theme().text.disabled)
+                | element.set_mask(self.mask)
+                | element.set_error_style(
+                    Style::default().fg(cx.theme().error()),
+                    self.show_error_message,
+                )
+                | element.set_suggestions(&self.suggestions)
+                | element.set_cursor_blink(self.cursor_blink)
+                | element.set_suggestion_styles(self.resolve_suggestion_styles(cx))
+                | rebuild_handler(cx, self.on_validation.as_ref(), on_validation)
+                | self
+                    .event_handler
+                    .rebuild(cx, event_handler_id, event_handler_state)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        (event_handler_id, event_handler_state, on_validation): &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match (id_path, on_validation) {
+            ([first, rest_path @ ..], _) if first == event_handler_id => self
+                .event_handler
+                .message(rest_path, event_handler_state, message, app_state),
+            ([first, rest_path @ ..], Some((validation_id, validation_state)))
+                if first == validation_id =>
+            {
+                self.on_validation.as_ref().unwrap().message(
+                    rest_path,
+                    validation_state,
+                    message,
+                    app_state,
+                )
+            }
+            ([], on_validation) if message.is::<Validation>() => {
+                match (&self.on_validation, on_validation) {
+                    (Some(handler), Some((_, validation_state))) => {
+                        handler.message(&[], validation_state, message, app_state)
+                    }
+                    _ => MessageResult::Nop,
+                }
+            }
+            ([], _) => self
+                .event_handler
+                .message(&[], event_handler_state, message, app_state),
+            _ => MessageResult::Stale(message),
+        }
+    }
+}
+
+impl<T, A, EH, VH> Styleable for TextInput<T, A, EH, VH> {
+    type Output = Self;
+
+    fn fg(mut self, color: Color) -> Self::Output {
+        self.style.fg = Some(color);
+        self
+    }
+
+    fn bg(mut self, color: Color) -> Self::Output {
+        self.style.bg = Some(color);
+        self
+    }
+
+    fn modifier(mut self, modifier: Modifier) -> Self::Output {
+        self.style = self.style.add_modifier(modifier);
+        self
+    }
+
+    fn style(mut self, style: Style) -> Self::Output {
+        self.style = style;
+        self
+    }
+
+    fn current_style(&self) -> Style {
+        self.style
+    }
+}
+
+/// A single line text field, which is edited while it's focused (by clicking on it or via `Tab`).
+///
+/// `on_change` is called with the new text after every edit. The cursor moves over and deletes
+/// whole graphemes, so e.g. emoji with modifiers or accented characters are edited as a unit.
+/// While the text is empty, the [`placeholder`](TextInput::placeholder) is shown.
+/// The text can be checked with a [`validator`](TextInput::validate).
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new(String::new(), move |name| {
+/// v_stack((
+///     text_input(name.clone(), |name: &mut String, new_name: String| *name = new_name)
+///         .placeholder("Your name")
+///         .fill_max_width(1.0),
+///     format!("Hello {name}!"),
+/// ))
+/// # });
+/// ```
+pub fn text_input<T, A, EH: EventHandler<T, A, String>>(
+    text: impl Into<String>,
+    on_change: EH,
+) -> TextInput<T, A, EH> {
+    TextInput {
+        text: text.into(),
+        style: Style::default(),
+        placeholder: Cow::Borrowed(""),
+        mask: None,
+        validator: None,
+        validation_delay: Duration::ZERO,
+        show_error_message: true,
+        suggestions: Vec::new(),
+        cursor_blink: None,
+        paste_newlines: PasteNewlines::default(),
+        event_handler: on_change,
+        on_validation: None,
+        phantom: PhantomData,
+    }
+}
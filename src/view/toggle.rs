@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, EventHandler, View, ViewMarker};
+use crate::{
+    widget::{self, ChangeFlags},
+    ToggleStyles,
+};
+
+pub struct Toggle<EH> {
+    is_on: bool,
+    on_glyph: Cow<'static, str>,
+    off_glyph: Cow<'static, str>,
+    styles: Option<ToggleStyles>,
+    event_handler: EH,
+}
+
+impl<EH> Toggle<EH> {
+    /// Sets the glyphs that are shown in the on and off state, by default `[ ●]` and `[● ]`.
+    pub fn glyphs(
+        mut self,
+        on: impl Into<Cow<'static, str>>,
+        off: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.on_glyph = on.into();
+        self.off_glyph = off.into();
+        self
+    }
+
+    /// Sets the styles explicitly, instead of using the ones of the [`Theme`](crate::Theme).
+    pub fn styles(mut self, styles: ToggleStyles) -> Self {
+        self.styles = Some(styles);
+        self
+    }
+
+    fn resolve_styles(&self, cx: &Cx) -> ToggleStyles {
+        self.styles.unwrap_or(cx.theme().toggle)
+    }
+}
+
+impl<EH> ViewMarker for Toggle<EH> {}
+
+impl<T, A, EH: EventHandler<T, A, bool>> View<T, A> for Toggle<EH> {
+    type State = (Id, EH::State);
+
+    type Element = widget::Toggle;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, (state, element)) = cx.with_new_id(|cx| {
+            let element = widget::Toggle::new(
+                cx.id_path(),
+                self.is_on,
+                self.on_glyph.clone(),
+                self.off_glyph.clone(),
+                self.resolve_styles(cx),
+            );
+            (self.event_handler.build(cx), element)
+        });
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        id: &mut Id,
+        (event_handler_id, event_handler_state): &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            element.set_is_on(self.is_on)
+                | element.set_glyphs(&self.on_glyph, &self.off_glyph)
+                | element.set_styles(self.resolve_styles(cx))
+                | self
+                    .event_handler
+                    .rebuild(cx, event_handler_id, event_handler_state)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        (event_handler_id, event_handler_state): &mut Self::State,
+        message: Box<dyn std::any::Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [first, rest_path @ ..] if first == event_handler_id => {
+                self.event_handler
+                    .message(rest_path, event_handler_state, message, app_state)
+            }
+            [] => self
+                .event_handler
+                .message(&[], event_handler_state, message, app_state),
+            [..] => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// A compact on/off switch, which is toggled by clicking it or by pressing `Space` or `Enter` while
+/// it's focused.
+///
+/// `on_toggle` is called with the new value, the styles are taken from
+/// [`Theme::toggle`](crate::Theme::toggle) unless they are set via [`Toggle::styles`].
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new(false, move |dark_mode| {
+/// h_stack((
+///     "Dark mode ",
+///     toggle(*dark_mode, |dark_mode: &mut bool, is_on: bool| *dark_mode = is_on),
+/// ))
+/// # });
+/// ```
+pub fn toggle<T, A, EH: EventHandler<T, A, bool>>(is_on: bool, on_toggle: EH) -> Toggle<EH> {
+    Toggle {
+        is_on,
+        on_glyph: "[ ●]".into(),
+        off_glyph: "[● ]".into(),
+        styles: None,
+        event_handler: on_toggle,
+    }
+}
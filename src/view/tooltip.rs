@@ -0,0 +1,94 @@
+use std::{any::Any, borrow::Cow, time::Duration};
+
+use ratatui::style::Style;
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, View, ViewMarker};
+use crate::widget::{self, ChangeFlags};
+
+pub struct Tooltip<V> {
+    content: V,
+    text: Cow<'static, str>,
+    delay: Duration,
+    style: Option<Style>,
+}
+
+impl<V> Tooltip<V> {
+    pub(crate) fn new(content: V, text: Cow<'static, str>) -> Self {
+        Tooltip {
+            content,
+            text,
+            delay: Duration::from_millis(500),
+            style: None,
+        }
+    }
+
+    /// How long the mouse cursor has to rest on the content until the tooltip is shown,
+    /// by default 500ms.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// The style of the tooltip, by default the text style of the theme on the second background color.
+    pub fn tooltip_style(mut self, style: Style) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    fn resolve_style(&self, cx: &Cx) -> Style {
+        let theme = cx.theme();
+        self.style
+            .unwrap_or(theme.text.default.bg(theme.surface_variant()))
+    }
+}
+
+impl<V> ViewMarker for Tooltip<V> {}
+
+impl<T, A, V: View<T, A>> View<T, A> for Tooltip<V> {
+    type State = V::State;
+
+    type Element = widget::Tooltip;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        let (id, state, element) = self.content.build(cx);
+        let element = widget::Tooltip::new(
+            element,
+            self.text.to_string(),
+            self.delay,
+            self.resolve_style(cx),
+        );
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        let changeflags = element.set_text(&self.text)
+            | element.set_delay(self.delay)
+            | element.set_style(self.resolve_style(cx));
+        let content_el = element
+            .content()
+            .downcast_mut()
+            .expect("The tooltip content widget changed its type, this should never happen!");
+        let content_changeflags = self
+            .content
+            .rebuild(cx, &prev.content, id, state, content_el);
+        changeflags | element.content().mark(content_changeflags)
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        self.content.message(id_path, state, message, app_state)
+    }
+}
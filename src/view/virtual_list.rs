@@ -0,0 +1,199 @@
+use std::{any::Any, marker::PhantomData, ops::Range, sync::Arc};
+
+use xilem_core::{Id, MessageResult};
+
+use super::{Cx, ScrollController, View, ViewMarker};
+use crate::widget::{self, ChangeFlags, ItemHeights, Pod};
+
+pub struct VirtualList<T, A, V, F> {
+    count: usize,
+    heights: ItemHeights,
+    overscan: usize,
+    controller: Option<ScrollController>,
+    item: F,
+    phantom: PhantomData<fn() -> (T, A, V)>,
+}
+
+/// The built items of a [`VirtualList`], with their views for the next rebuild.
+pub struct VirtualListState<V: View<T, A>, T, A> {
+    /// The range of items, which is visible (including the overscan), as reported by the widget
+    range: Range<usize>,
+    items: Vec<(Id, V::State, V)>,
+}
+
+impl<T, A, V, F> VirtualList<T, A, V, F> {
+    /// Sets the height of each item, instead of the fixed height passed to [`virtual_list`],
+    /// items without a height are one line high.
+    ///
+    /// The heights of all items have to be known upfront, so that the visible items can be found
+    /// without building the others. The items are laid out with exactly this height.
+    pub fn item_heights(mut self, heights: impl Into<Arc<[u16]>>) -> Self {
+        self.heights = ItemHeights::Variable(heights.into());
+        self
+    }
+
+    /// The amount of items, which are built above and below the visible ones (default `2`),
+    /// so that e.g. their state is kept while scrolling back and forth a little.
+    pub fn overscan(mut self, items: usize) -> Self {
+        self.overscan = items;
+        self
+    }
+
+    /// Applies the requests of `controller` to this list, see [`ScrollController`].
+    ///
+    /// [`reveal_child`](ScrollController::reveal_child) scrolls to the item with the index,
+    /// even if it's not built yet.
+    pub fn controller(mut self, controller: &ScrollController) -> Self {
+        self.controller = Some(controller.clone());
+        self
+    }
+
+    fn take_requests(&self) -> Vec<widget::ScrollRequest> {
+        self.controller
+            .as_ref()
+            .map(ScrollController::take_requests)
+            .unwrap_or_default()
+    }
+}
+
+impl<T, A, V, F> ViewMarker for VirtualList<T, A, V, F> {}
+
+impl<T, A, V, F> View<T, A> for VirtualList<T, A, V, F>
+where
+    V: View<T, A>,
+    V::Element: 'static,
+    F: Fn(usize) -> V + Send + Sync,
+{
+    type State = VirtualListState<V, T, A>;
+
+    type Element = widget::VirtualList;
+
+    fn build(&self, cx: &mut Cx) -> (Id, Self::State, Self::Element) {
+        // The items are built after the first layout pass, when the widget knows which are visible
+        let (id, element) = cx.with_new_id(|cx| {
+            let mut element = widget::VirtualList::new(
+                cx.id_path(),
+                self.count,
+                self.heights.clone(),
+                self.overscan,
+            );
+            let _ = element.request_scroll(self.take_requests());
+            element
+        });
+        let state = VirtualListState {
+            range: 0..0,
+            items: Vec::new(),
+        };
+        (id, state, element)
+    }
+
+    fn rebuild(
+        &self,
+        cx: &mut Cx,
+        _prev: &Self,
+        id: &mut Id,
+        state: &mut Self::State,
+        element: &mut Self::Element,
+    ) -> ChangeFlags {
+        cx.with_id(*id, |cx| {
+            let mut changeflags = element.set_items(self.count, &self.heights)
+                | element.set_overscan(self.overscan)
+                | element.request_scroll(self.take_requests());
+
+            // Items which are still visible are rebuilt with their previous view and widget,
+            // the others are dropped
+            let range = state.range.start.min(self.count)..state.range.end.min(self.count);
+            let (first, pods) = element.take_children();
+            let mut prev_items: Vec<_> = state.items.drain(..).zip(pods).map(Some).collect();
+            let mut pods = Vec::with_capacity(range.len());
+            for index in range.clone() {
+                let view = (self.item)(index);
+                let prev_item = index
+                    .checked_sub(first)
+                    .and_then(|i| prev_items.get_mut(i))
+                    .and_then(Option::take);
+                match prev_item {
+                    Some(((mut item_id, mut item_state, prev_view), mut pod)) => {
+                        let item_element = pod
+                            .downcast_mut()
+                            .expect("The item widget changed its type, this should never happen!");
+                        let item_changeflags = view.rebuild(
+                            cx,
+                            &prev_view,
+                            &mut item_id,
+                            &mut item_state,
+                            item_element,
+                        );
+                        changeflags |= pod.mark(item_changeflags);
+                        state.items.push((item_id, item_state, view));
+                        pods.push(pod);
+                    }
+                    None => {
+                        let (item_id, item_state, item_element) = view.build(cx);
+                        state.items.push((item_id, item_state, view));
+                        pods.push(Pod::new(item_element));
+                    }
+                }
+            }
+            changeflags | element.set_children(range.start, pods)
+        })
+    }
+
+    fn message(
+        &self,
+        id_path: &[Id],
+        state: &mut Self::State,
+        message: Box<dyn Any>,
+        app_state: &mut T,
+    ) -> MessageResult<A> {
+        match id_path {
+            [] if message.is::<Range<usize>>() => {
+                // The widget scrolled, the visible items are built in the next rebuild
+                state.range = *message.downcast().unwrap();
+                MessageResult::RequestRebuild
+            }
+            [id, rest_path @ ..] => {
+                match state.items.iter_mut().find(|(item_id, ..)| item_id == id) {
+                    Some((_, item_state, view)) => {
+                        view.message(rest_path, item_state, message, app_state)
+                    }
+                    None => MessageResult::Stale(message),
+                }
+            }
+            [] => MessageResult::Stale(message),
+        }
+    }
+}
+
+/// A vertical list of `count` items, which are `item_height` lines high and of which only the visible
+/// ones are built, by calling `item` with their index.
+///
+/// This is the way to show lists with many (e.g. 100.000) items, which would be too expensive to build
+/// as children of a [`v_stack`](crate::v_stack). Items are built again when they're scrolled into view,
+/// as they're identified by their index, their state is only kept while they're visible
+/// (or within the [`overscan`](VirtualList::overscan)).
+/// The list scrolls itself with the mouse wheel or with `Up`/`Down`, `PageUp`/`PageDown` and
+/// `Home`/`End`, while it's focused. Items with different heights are supported with
+/// [`item_heights`](VirtualList::item_heights).
+///
+/// # Examples
+/// ```
+/// # use trui::*;
+/// # App::new((), move |()| {
+/// virtual_list(100_000, 1, |index| format!("Row {index}"))
+/// # });
+/// ```
+pub fn virtual_list<T, A, V, F>(count: usize, item_height: u16, item: F) -> VirtualList<T, A, V, F>
+where
+    V: View<T, A>,
+    F: Fn(usize) -> V + Send + Sync,
+{
+    VirtualList {
+        count,
+        heights: ItemHeights::Fixed(item_height),
+        overscan: 2,
+        controller: None,
+        item,
+        phantom: PhantomData,
+    }
+}
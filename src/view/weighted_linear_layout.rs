@@ -78,9 +78,38 @@ pub fn weighted_v_stack<T, A, VT: ViewSequence<T, A>>(
 pub struct WeightedLayoutElement<V, W, T, A> {
     pub(crate) content: V,
     pub(crate) weight: W,
+    pub(crate) reserve_min_size: bool,
     pub(crate) phantom: PhantomData<fn() -> (T, A)>,
 }
 
+impl<V, W, T, A> WeightedLayoutElement<V, W, T, A> {
+    /// Reserves the size of the content before the remaining space is distributed by weight,
+    /// so that e.g. a wrapped text isn't cut off on small terminals.
+    ///
+    /// The content is laid out with an unbounded main axis to measure it, so this is only meant for
+    /// content with a natural size. Content which takes any space it's given, like a scroll view,
+    /// a virtual list or a text area, would reserve its whole content.
+    /// When the reserved sizes exceed the space, the children get it in the order of their weight.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::*;
+    /// # App::new((), move |()| {
+    /// weighted_v_stack((
+    ///     "A long description, which wraps on narrow terminals"
+    ///         .wrapped()
+    ///         .weight(1.0)
+    ///         .reserve_min_size(),
+    ///     "Details".weight(3.0),
+    /// ))
+    /// # });
+    /// ```
+    pub fn reserve_min_size(mut self) -> Self {
+        self.reserve_min_size = true;
+        self
+    }
+}
+
 impl<T, A, V, W> ViewMarker for WeightedLayoutElement<V, W, T, A> {}
 
 impl<T, A, V: View<T, A>, W: Animatable<f64>> View<T, A> for WeightedLayoutElement<V, W, T, A> {
@@ -92,7 +121,8 @@ impl<T, A, V: View<T, A>, W: Animatable<f64>> View<T, A> for WeightedLayoutEleme
         let (id, (state, element)) = cx.with_new_id(|cx| {
             let (content_id, content_state, element) = self.content.build(cx);
             let (weight_id, weight_state, weight_element) = self.weight.build(cx);
-            let element = widget::WeightedLayoutElement::new(element, weight_element);
+            let mut element = widget::WeightedLayoutElement::new(element, weight_element);
+            let _ = element.set_reserve_min_size(self.reserve_min_size);
             (
                 (content_id, content_state, weight_id, weight_state),
                 element,
@@ -131,7 +161,9 @@ impl<T, A, V: View<T, A>, W: Animatable<f64>> View<T, A> for WeightedLayoutEleme
                 self.content
                     .rebuild(cx, &prev.content, content_id, content_state, content_el);
 
-            changeflags | element.content.mark(content_changeflags)
+            changeflags
+                | element.set_reserve_min_size(self.reserve_min_size)
+                | element.content.mark(content_changeflags)
         })
     }
 
@@ -168,6 +200,7 @@ pub fn weighted<T, A, V: View<T, A>, W: Animatable<f64>>(
     WeightedLayoutElement {
         content,
         weight,
+        reserve_min_size: false,
         phantom: PhantomData,
     }
 }
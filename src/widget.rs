@@ -1,5 +1,12 @@
+mod animated_style;
+mod background_gradient;
+mod bar_chart;
 mod border;
 mod box_constraints;
+mod canvas;
+mod clip;
+mod command_palette;
+mod confirm;
 
 #[cfg(not(any(test, doctest, feature = "doctests")))]
 mod core;
@@ -8,22 +15,72 @@ mod core;
 pub(crate) mod core;
 
 pub(crate) mod animatables;
+mod enabled;
 mod events;
 mod fill_max_size;
+mod flow;
+mod input_event;
+mod key_sequence;
+mod layers;
 mod linear_layout;
 mod margin;
+mod measure;
+mod menu_bar;
+mod modal;
+mod notifications;
+mod overflow_indicator;
+mod raw_widget;
+mod responsive;
+mod scaffold;
+mod scroll;
+mod sparkline;
+mod table;
 mod text;
+mod text_area;
+mod text_input;
+mod toggle;
+mod tooltip;
+mod virtual_list;
 mod weighted_linear_layout;
 
 pub use self::core::{
     AnyWidget, ChangeFlags, CxState, EventCx, LayoutCx, LifeCycleCx, Message, PaintCx, Pod, Widget,
 };
-pub(crate) use self::core::{PodFlags, WidgetState};
+pub(crate) use self::core::{FocusState, PodFlags, WidgetState};
+pub(crate) use animated_style::{AnimatedStyle, StyleAnimation};
+pub(crate) use background_gradient::BackgroundGradient;
+pub(crate) use bar_chart::{BarChart, BarChartStyles};
 pub(crate) use border::Border;
 pub use box_constraints::BoxConstraints;
+pub(crate) use canvas::Canvas;
+pub(crate) use clip::set_stringn_clipped;
+pub(crate) use command_palette::{CommandPalette, PaletteMessage, PaletteStyles};
+pub(crate) use confirm::{Confirm, ConfirmStyles};
+pub(crate) use enabled::Enabled;
 pub use events::*;
 pub(crate) use fill_max_size::FillMaxSize;
+pub(crate) use flow::Flow;
+pub use input_event::{Key, KeyEvent, KeyKind, Modifiers, MouseButton, MouseKind};
+pub(crate) use key_sequence::KeySequences;
+pub(crate) use key_sequence::{normalize_key, KeyStroke};
+pub(crate) use layers::Layers;
 pub(crate) use linear_layout::LinearLayout;
 pub(crate) use margin::Margin;
+pub(crate) use measure::Measure;
+pub(crate) use menu_bar::{MenuBar, MenuStyles};
+pub(crate) use modal::Modal;
+pub(crate) use notifications::NotificationOverlay;
+pub(crate) use overflow_indicator::paint_overflow_indicator;
+pub(crate) use raw_widget::RawWidget;
+pub(crate) use responsive::Responsive;
+pub(crate) use scaffold::Scaffold;
+pub(crate) use scroll::{Scroll, ScrollRequest, SCROLL_STEP};
+pub(crate) use sparkline::Sparkline;
+pub(crate) use table::{Table, TableMessage, TableStyles};
 pub(crate) use text::*;
+pub(crate) use text_area::TextArea;
+pub(crate) use text_input::{TextInput, Validator};
+pub(crate) use toggle::Toggle;
+pub(crate) use tooltip::Tooltip;
+pub(crate) use virtual_list::{ItemHeights, VirtualList};
 pub(crate) use weighted_linear_layout::{WeightedLayoutElement, WeightedLinearLayout};
@@ -1,5 +1,7 @@
 use std::{any::Any, ops::DerefMut, time::Duration};
 
+use crate::Easing;
+
 use super::{ChangeFlags, LifeCycleCx};
 
 pub trait AnimatableElement<V>: 'static + AnyAnimatableElement<V> {
@@ -108,6 +110,81 @@ impl<AT: AnimatableElement<f64>> AnimatableElement<f64> for LowPassIIR<AT, f64>
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct Transition<AT> {
+    pub(crate) target: AT,
+    duration: Duration,
+    easing: Easing,
+    /// The value at the start of the current transition
+    start: f64,
+    /// The target of the current transition
+    end: f64,
+    elapsed: Duration,
+    value: Option<f64>,
+}
+
+impl<AT> Transition<AT> {
+    pub(crate) fn new(target: AT, duration: Duration, easing: Easing) -> Self {
+        Transition {
+            target,
+            duration,
+            easing,
+            start: 0.0,
+            end: 0.0,
+            elapsed: Duration::ZERO,
+            value: None,
+        }
+    }
+
+    pub(crate) fn set_duration(&mut self, duration: Duration) -> ChangeFlags {
+        if self.duration != duration {
+            self.duration = duration;
+            ChangeFlags::ANIMATION
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_easing(&mut self, easing: Easing) -> ChangeFlags {
+        if self.easing != easing {
+            self.easing = easing;
+            ChangeFlags::ANIMATION
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+impl<AT: AnimatableElement<f64>> AnimatableElement<f64> for Transition<AT> {
+    fn animate(&mut self, cx: &mut LifeCycleCx) -> &f64 {
+        let target_value = *self.target.animate(cx);
+        let Some(value) = self.value else {
+            self.start = target_value;
+            self.end = target_value;
+            self.elapsed = self.duration;
+            return self.value.insert(target_value);
+        };
+        if target_value != self.end {
+            // A new target interrupts the current transition, it continues from the current value
+            self.start = value;
+            self.end = target_value;
+            self.elapsed = Duration::ZERO;
+        } else {
+            self.elapsed = (self.elapsed + cx.time_since_last_render_request()).min(self.duration);
+        }
+        let ratio = if self.duration.is_zero() {
+            1.0
+        } else {
+            self.elapsed.as_secs_f64() / self.duration.as_secs_f64()
+        };
+        if ratio < 1.0 {
+            cx.request_animation_update();
+        }
+        self.value
+            .insert(self.start + (self.end - self.start) * self.easing.ease(ratio))
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Lerp<T, R> {
     pub(crate) tweenable: T,
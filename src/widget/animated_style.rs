@@ -0,0 +1,202 @@
+use std::{
+    f64::consts::TAU,
+    time::{Duration, Instant},
+};
+
+use ratatui::style::{Color, Style};
+
+use crate::{color::lerp, geometry::Size};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, Pod, Widget,
+};
+
+/// Animations are computed with a fixed minimum frame time (60 frames per second).
+const MIN_FRAME_TIME: Duration = Duration::from_millis(16);
+
+/// How the style of an [`AnimatedStyle`] changes over time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum StyleAnimation {
+    /// Applies `on_style` in the first half of the period and `off_style` (or hides the content)
+    /// in the second half.
+    Blink {
+        period: Duration,
+        on_style: Style,
+        off_style: Option<Style>,
+    },
+    /// Interpolates the foreground (or background) color from `from` to `to` and back within the
+    /// period.
+    Pulse {
+        from: Color,
+        to: Color,
+        period: Duration,
+        background: bool,
+    },
+}
+
+impl StyleAnimation {
+    /// The style and the visibility of the content at `elapsed` since the start of the animation.
+    fn frame(&self, elapsed: Duration) -> (Style, bool) {
+        match *self {
+            StyleAnimation::Blink {
+                period,
+                on_style,
+                off_style,
+            } => {
+                let half_period = (period / 2).max(MIN_FRAME_TIME).as_secs_f64();
+                let is_on = (elapsed.as_secs_f64() / half_period).floor() as u64 % 2 == 0;
+                match off_style {
+                    Some(off_style) if !is_on => (off_style, true),
+                    _ => (on_style, is_on),
+                }
+            }
+            StyleAnimation::Pulse {
+                from,
+                to,
+                period,
+                background,
+            } => {
+                let period = period.max(MIN_FRAME_TIME).as_secs_f64();
+                let phase = (elapsed.as_secs_f64() / period).fract();
+                // starts and ends at `from`, with `to` in the middle of the period
+                let ratio = (1.0 - (phase * TAU).cos()) / 2.0;
+                let color = lerp(from, to, ratio);
+                let style = if background {
+                    Style::default().bg(color)
+                } else {
+                    Style::default().fg(color)
+                };
+                (style, true)
+            }
+        }
+    }
+}
+
+/// Overrides the style of its content or hides it, driven by a [`StyleAnimation`], e.g.
+/// [`blink`](crate::ViewExt::blink).
+///
+/// The animation advances in [`LifeCycle::Animate`], so only the paint repeats, not the app logic.
+pub struct AnimatedStyle {
+    pub(crate) element: Pod,
+    animation: StyleAnimation,
+    start: Instant,
+    style: Style,
+    visible: bool,
+}
+
+impl AnimatedStyle {
+    pub(crate) fn new(element: impl Widget, animation: StyleAnimation) -> Self {
+        let (style, visible) = animation.frame(Duration::ZERO);
+        AnimatedStyle {
+            element: Pod::new(element),
+            animation,
+            start: Instant::now(),
+            style,
+            visible,
+        }
+    }
+
+    /// Changes the animation, it continues from the current time.
+    pub(crate) fn set_animation(&mut self, animation: StyleAnimation) -> ChangeFlags {
+        if self.animation != animation {
+            self.animation = animation;
+            self.update_frame()
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// Applies the frame of the animation at the current time.
+    fn update_frame(&mut self) -> ChangeFlags {
+        let (style, visible) = self.animation.frame(self.start.elapsed());
+        if (style, visible) != (self.style, self.visible) {
+            self.style = style;
+            self.visible = visible;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+impl Widget for AnimatedStyle {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        // The content keeps its space in the layout while it's hidden
+        if self.visible {
+            cx.override_style = self.style.patch(cx.override_style);
+            self.element.paint(cx);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // Starts the animation, it's kept running in `lifecycle`
+        cx.request_animation_update();
+        self.element.layout(cx, bc)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.element.event(cx, event)
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        self.element.lifecycle(cx, event);
+        if let LifeCycle::Animate = event {
+            let flags = self.update_frame();
+            cx.request(flags);
+            cx.request_animation_update();
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blink_toggles_every_half_period() {
+        let blink = StyleAnimation::Blink {
+            period: Duration::from_secs(1),
+            on_style: Style::default(),
+            off_style: None,
+        };
+        assert_eq!(
+            blink.frame(Duration::from_millis(200)),
+            (Style::default(), true)
+        );
+        assert_eq!(
+            blink.frame(Duration::from_millis(700)),
+            (Style::default(), false)
+        );
+        assert_eq!(
+            blink.frame(Duration::from_millis(1200)),
+            (Style::default(), true)
+        );
+
+        let off_style = Style::default().fg(Color::Red);
+        let blink = StyleAnimation::Blink {
+            period: Duration::from_secs(1),
+            on_style: Style::default(),
+            off_style: Some(off_style),
+        };
+        assert_eq!(blink.frame(Duration::from_millis(700)), (off_style, true));
+    }
+
+    #[test]
+    fn pulse_reaches_the_target_color_in_the_middle_of_the_period() {
+        let pulse = StyleAnimation::Pulse {
+            from: Color::Rgb(0, 0, 0),
+            to: Color::Rgb(200, 100, 0),
+            period: Duration::from_secs(2),
+            background: true,
+        };
+        let bg = |elapsed| pulse.frame(Duration::from_millis(elapsed)).0.bg;
+        assert_eq!(bg(0), Some(Color::Rgb(0, 0, 0)));
+        assert_eq!(bg(1000), Some(Color::Rgb(200, 100, 0)));
+        assert_eq!(bg(2000), Some(Color::Rgb(0, 0, 0)));
+    }
+}
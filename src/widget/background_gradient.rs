@@ -0,0 +1,84 @@
+use crate::{
+    color::{Gradient, GradientDirection},
+    geometry::{to_ratatui_rect, Size},
+};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, Pod, Widget,
+};
+
+/// Fills the background of its content with a [`Gradient`], before the content is painted.
+pub struct BackgroundGradient {
+    content: Pod,
+    gradient: Gradient,
+    direction: GradientDirection,
+}
+
+impl BackgroundGradient {
+    pub(crate) fn new(
+        content: impl Widget,
+        gradient: Gradient,
+        direction: GradientDirection,
+    ) -> Self {
+        BackgroundGradient {
+            content: Pod::new(content),
+            gradient,
+            direction,
+        }
+    }
+
+    pub(crate) fn content(&mut self) -> &mut Pod {
+        &mut self.content
+    }
+
+    pub(crate) fn set_gradient(
+        &mut self,
+        gradient: Gradient,
+        direction: GradientDirection,
+    ) -> ChangeFlags {
+        if self.gradient != gradient || self.direction != direction {
+            self.gradient = gradient;
+            self.direction = direction;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+impl Widget for BackgroundGradient {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let buf = cx.buffer_mut();
+        // The colors depend on the whole area, even if only a part of it is visible
+        let area = rect.intersection(buf.area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let color = self.gradient.color_at(
+                    self.direction,
+                    (x - rect.x, y - rect.y),
+                    (rect.width, rect.height),
+                );
+                buf.get_mut(x, y).set_bg(color);
+            }
+        }
+        self.content.paint(cx);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        self.content.layout(cx, bc)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.content.event(cx, event);
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        self.content.lifecycle(cx, event);
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
+}
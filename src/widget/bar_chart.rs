@@ -0,0 +1,367 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, symbols::block};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{
+    geometry::{to_ratatui_rect, Axis, Size},
+    view::Bar,
+};
+
+use super::{
+    core::{LifeCycleCx, PaintCx},
+    set_stringn_clipped,
+    sparkline::{bar_height, bar_symbol},
+    BoxConstraints, ChangeFlags, Event, EventCx, LayoutCx, LifeCycle, Widget,
+};
+
+/// The length of horizontal bars, when the available width is unbounded.
+const DEFAULT_BAR_LENGTH: f64 = 20.0;
+/// The height of vertical bars (without value and label).
+const DEFAULT_BAR_HEIGHT: f64 = 8.0;
+/// The minimum width of vertical bars, they are as wide as their labels if there's enough space.
+const MIN_BAR_WIDTH: u16 = 3;
+const BAR_GAP: u16 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BarChartStyles {
+    pub(crate) bar: Style,
+    pub(crate) label: Style,
+    pub(crate) value: Style,
+}
+
+pub struct BarChart {
+    bars: Vec<Bar>,
+    axis: Axis,
+    max: Option<u64>,
+    styles: BarChartStyles,
+}
+
+impl BarChart {
+    pub(crate) fn new(
+        bars: Vec<Bar>,
+        axis: Axis,
+        max: Option<u64>,
+        styles: BarChartStyles,
+    ) -> Self {
+        BarChart {
+            bars,
+            axis,
+            max,
+            styles,
+        }
+    }
+
+    pub(crate) fn set_bars(&mut self, bars: &[Bar]) -> ChangeFlags {
+        if self.bars != bars {
+            self.bars = bars.to_vec();
+            // TODO more sophisticated check for needed ChangeFlags (labels could have the same width)
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_axis(&mut self, axis: Axis) -> ChangeFlags {
+        if self.axis != axis {
+            self.axis = axis;
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_max(&mut self, max: Option<u64>) -> ChangeFlags {
+        if self.max != max {
+            self.max = max;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_styles(&mut self, styles: BarChartStyles) -> ChangeFlags {
+        if self.styles != styles {
+            self.styles = styles;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn max_value(&self) -> u64 {
+        self.max
+            .unwrap_or_else(|| self.bars.iter().map(|bar| bar.value).max().unwrap_or(0))
+    }
+
+    fn label_width(&self) -> u16 {
+        self.bars
+            .iter()
+            .map(|bar| bar.label.width())
+            .max()
+            .unwrap_or(0) as u16
+    }
+
+    fn value_width(&self) -> u16 {
+        self.max_value().to_string().len() as u16
+    }
+
+    /// The preferred width of vertical bars, so that labels and values are not truncated.
+    fn vertical_bar_width(&self) -> u16 {
+        MIN_BAR_WIDTH
+            .max(self.label_width())
+            .max(self.value_width())
+    }
+
+    /// One row per bar: `label █████▌ value`
+    fn paint_horizontal(&self, buf: &mut Buffer, rect: Rect, area: Rect, styles: BarChartStyles) {
+        let label_width = self.label_width();
+        let bar_x = rect.x + label_width + 1;
+        let bar_space = rect
+            .width
+            .saturating_sub(label_width + 1 + self.value_width() + 1);
+        let max = self.max_value();
+
+        for (y, bar) in (rect.top()..rect.bottom()).zip(&self.bars) {
+            let bar_style = styles.bar.patch(bar.style.unwrap_or_default());
+            set_string_clipped(buf, area, rect.x, y, &bar.label, styles.label);
+
+            let eighths = bar_height(bar.value, max, bar_space);
+            let cells = (eighths / 8) as u16;
+            for x in bar_x..bar_x + cells {
+                set_string_clipped(buf, area, x, y, block::FULL, bar_style);
+            }
+            let mut value_x = bar_x + cells;
+            if eighths % 8 > 0 {
+                let symbol = horizontal_bar_symbol(eighths % 8);
+                set_string_clipped(buf, area, value_x, y, symbol, bar_style);
+                value_x += 1;
+            }
+            let value = bar.value.to_string();
+            set_string_clipped(buf, area, value_x + 1, y, &value, styles.value);
+        }
+    }
+
+    /// One column per bar, with the value above and the label below the bar
+    fn paint_vertical(&self, buf: &mut Buffer, rect: Rect, area: Rect, styles: BarChartStyles) {
+        let bar_count = self.bars.len() as u16;
+        // Bars that don't fit anymore with a width of 1 are truncated
+        let bar_width = ((rect.width + BAR_GAP) / bar_count)
+            .saturating_sub(BAR_GAP)
+            .clamp(1, self.vertical_bar_width());
+        // rows for the label and the value
+        let bar_space = rect.height.saturating_sub(2);
+        let max = self.max_value();
+
+        let bar_xs = (rect.left()..rect.right()).step_by((bar_width + BAR_GAP) as usize);
+        for (bar_x, bar) in bar_xs.zip(&self.bars) {
+            let bar_style = styles.bar.patch(bar.style.unwrap_or_default());
+            let label_y = rect.bottom().saturating_sub(1);
+            let mut eighths = bar_height(bar.value, max, bar_space);
+            let mut y = label_y;
+            while eighths > 0 && y > rect.top() {
+                y -= 1;
+                for x in bar_x..bar_x + bar_width {
+                    set_string_clipped(buf, area, x, y, bar_symbol(eighths), bar_style);
+                }
+                eighths = eighths.saturating_sub(8);
+            }
+
+            let value = bar.value.to_string();
+            if y > rect.top() && value.len() as u16 <= bar_width {
+                set_string_clipped(buf, area, bar_x, y - 1, &value, styles.value);
+            }
+            let label = truncate(&bar.label, bar_width);
+            set_string_clipped(buf, area, bar_x, label_y, label, styles.label);
+        }
+    }
+}
+
+fn horizontal_bar_symbol(eighths: u64) -> &'static str {
+    match eighths {
+        0 => " ",
+        1 => block::ONE_EIGHTH,
+        2 => block::ONE_QUARTER,
+        3 => block::THREE_EIGHTHS,
+        4 => block::HALF,
+        5 => block::FIVE_EIGHTHS,
+        6 => block::THREE_QUARTERS,
+        7 => block::SEVEN_EIGHTHS,
+        _ => block::FULL,
+    }
+}
+
+/// Returns the longest prefix of `text` that fits into `width` cells.
+fn truncate(text: &str, width: u16) -> &str {
+    let mut current_width = 0;
+    for (i, c) in text.char_indices() {
+        current_width += c.width().unwrap_or(0);
+        if current_width > width as usize {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+/// Sets the string at `x`/`y`, but only the part that is within `area`.
+fn set_string_clipped(buf: &mut Buffer, area: Rect, x: u16, y: u16, text: &str, style: Style) {
+    if y >= area.top() && y < area.bottom() && x < area.right() {
+        set_stringn_clipped(buf, x, y, text, (area.right() - x) as usize, style);
+    }
+}
+
+impl Widget for BarChart {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let override_style = cx.override_style();
+        let styles = BarChartStyles {
+            bar: self.styles.bar.patch(override_style),
+            label: self.styles.label.patch(override_style),
+            value: self.styles.value.patch(override_style),
+        };
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        if area.area() == 0 || self.bars.is_empty() {
+            return;
+        }
+        match self.axis {
+            Axis::Horizontal => self.paint_horizontal(buf, rect, area, styles),
+            Axis::Vertical => self.paint_vertical(buf, rect, area, styles),
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let bar_count = self.bars.len() as f64;
+        let size = match self.axis {
+            Axis::Horizontal => {
+                let width = if bc.is_width_bounded() {
+                    bc.max().width
+                } else {
+                    (self.label_width() + self.value_width() + 2) as f64 + DEFAULT_BAR_LENGTH
+                };
+                Size::new(width, bar_count)
+            }
+            Axis::Vertical => {
+                let width = (bar_count * (self.vertical_bar_width() + BAR_GAP) as f64
+                    - BAR_GAP as f64)
+                    .max(0.0);
+                // bars, value and label
+                Size::new(width, DEFAULT_BAR_HEIGHT + 2.0)
+            }
+        };
+        bc.constrain(size)
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
+
+    fn lifecycle(&mut self, _cx: &mut LifeCycleCx, _event: &LifeCycle) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        widget::{CxState, FocusState, WidgetState},
+        App,
+    };
+
+    fn chart(bars: &[(&'static str, u64)], axis: Axis, max: Option<u64>) -> BarChart {
+        let bars = bars.iter().map(|bar| Bar::from(*bar)).collect();
+        let styles = BarChartStyles {
+            bar: Style::default(),
+            label: Style::default(),
+            value: Style::default(),
+        };
+        BarChart::new(bars, axis, max, styles)
+    }
+
+    /// Lays out `chart` within `bc` and paints it, returns the painted lines
+    fn render(chart: &mut BarChart, bc: BoxConstraints) -> Vec<String> {
+        let (mut messages, mut focus) = (Vec::new(), FocusState::default());
+        let cx_state = &mut CxState::new(&mut messages, &mut focus, Default::default());
+        let mut widget_state = WidgetState::new();
+        widget_state.size = chart.layout(
+            &mut LayoutCx {
+                cx_state,
+                widget_state: &mut widget_state,
+            },
+            &bc,
+        );
+        let (width, height) = (
+            widget_state.size.width as u16,
+            widget_state.size.height as u16,
+        );
+        let mut buffer = Buffer::empty(Rect::new(0, 0, width, height));
+        chart.paint(&mut PaintCx {
+            cx_state,
+            widget_state: &mut widget_state,
+            buffer: &mut buffer,
+            override_style: Style::default(),
+        });
+        (0..height)
+            .map(|y| {
+                let line: String = (0..width).map(|x| buffer.get(x, y).symbol()).collect();
+                line.trim_end().to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn vertical_bars_are_scaled_to_the_largest_value() {
+        let mut chart = chart(&[("a", 2), ("b", 4)], Axis::Vertical, None);
+        let lines = render(&mut chart, BoxConstraints::tight(Size::new(7.0, 6.0)));
+        assert_eq!(
+            lines,
+            ["    4", "    ███", "2   ███", "███ ███", "███ ███", "a   b"]
+        );
+    }
+
+    #[test]
+    fn a_fixed_max_keeps_the_scale() {
+        let mut chart = chart(&[("a", 2), ("b", 4)], Axis::Vertical, Some(8));
+        let lines = render(&mut chart, BoxConstraints::tight(Size::new(7.0, 6.0)));
+        assert_eq!(lines, ["", "", "    4", "2   ███", "███ ███", "a   b"]);
+    }
+
+    #[test]
+    fn horizontal_bars_are_scaled_with_eighths_of_a_cell() {
+        let mut chart = chart(&[("ab", 3), ("c", 6)], Axis::Horizontal, None);
+        let lines = render(&mut chart, BoxConstraints::tight(Size::new(12.0, 2.0)));
+        assert_eq!(lines, ["ab ███▌ 3", "c  ███████ 6"]);
+    }
+
+    #[test]
+    fn vertical_bars_are_as_wide_as_their_labels() {
+        let mut chart = chart(&[("monday", 1), ("tue", 2)], Axis::Vertical, None);
+        let bc = BoxConstraints::new(Size::ZERO, Size::new(80.0, 4.0));
+        let lines = render(&mut chart, bc);
+        assert_eq!(
+            lines,
+            ["       2", "1      ██████", "██████ ██████", "monday tue"]
+        );
+    }
+
+    #[test]
+    fn bars_which_dont_fit_are_truncated() {
+        let bars = [
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+        ];
+        let mut chart = chart(&bars, Axis::Vertical, None);
+        // only 2 bars with a width of 1 fit, their labels are truncated
+        let lines = render(&mut chart, BoxConstraints::tight(Size::new(4.0, 3.0)));
+        assert_eq!(lines, ["1 2", "▂ ▃", "o t"]);
+    }
+
+    #[tokio::test]
+    async fn horizontal_bars_fill_the_width_of_the_terminal() {
+        let mut app = App::new((), |_: &mut ()| {
+            crate::bar_chart([("a", 5)]).horizontal().max(10)
+        })
+        .await;
+        app.render_once().await.unwrap();
+        // 75 of the 80 cells are left for the bar, half of them are filled
+        assert_eq!(app.screen_line(0), format!("a {}▌ 5", "█".repeat(37)));
+    }
+}
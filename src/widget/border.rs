@@ -4,7 +4,7 @@ use super::{
 use crate::{
     geometry::{to_ratatui_rect, Point, Size},
     view::Borders,
-    BorderKind,
+    BorderKind, SideStyles,
 };
 use ratatui::{style::Style, symbols};
 
@@ -13,6 +13,11 @@ pub struct Border {
     borders: Borders,
     kind: BorderKind,
     style: Style,
+    side_styles: SideStyles,
+    /// Patched on top of `style` while the pointer is over the border
+    hover_style: Option<Style>,
+    /// Patched on top of `style` while the content has the keyboard focus
+    focus_style: Option<Style>,
 }
 
 impl Border {
@@ -27,6 +32,9 @@ impl Border {
             borders,
             kind,
             style,
+            side_styles: SideStyles::default(),
+            hover_style: None,
+            focus_style: None,
         }
     }
 
@@ -59,8 +67,43 @@ impl Border {
         }
     }
 
+    pub(crate) fn set_side_styles(&mut self, side_styles: SideStyles) -> ChangeFlags {
+        if side_styles != self.side_styles {
+            self.side_styles = side_styles;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_state_styles(
+        &mut self,
+        hover_style: Option<Style>,
+        focus_style: Option<Style>,
+    ) -> ChangeFlags {
+        if self.hover_style != hover_style || self.focus_style != focus_style {
+            self.hover_style = hover_style;
+            self.focus_style = focus_style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
     fn render_border(&self, cx: &mut PaintCx) {
+        let state_style = match (self.focus_style, self.hover_style) {
+            (Some(focus_style), _) if cx.has_focus() => focus_style,
+            (_, Some(hover_style)) if cx.is_hot() => hover_style,
+            _ => Style::default(),
+        };
         let style = self.style.patch(cx.override_style);
+        // The hover and focus styles apply to the whole border, they take precedence over the sides
+        let side_style =
+            |side: Option<Style>| style.patch(side.unwrap_or_default()).patch(state_style);
+        let top_style = side_style(self.side_styles.top);
+        let right_style = side_style(self.side_styles.right);
+        let bottom_style = side_style(self.side_styles.bottom);
+        let left_style = side_style(self.side_styles.left);
         cx.override_style = Style::default();
         let r = to_ratatui_rect(cx.rect());
 
@@ -69,10 +112,11 @@ impl Border {
             return;
         }
 
-        let buf = cx.terminal.current_buffer_mut();
+        let buf = cx.buffer_mut();
+        let area = buf.area;
 
         let mut draw = |x, y, symbol, style| {
-            if buf.area.x + x < buf.area.width && buf.area.y + y < buf.area.height {
+            if area.left() <= x && x < area.right() && area.top() <= y && y < area.bottom() {
                 buf.get_mut(x, y).set_symbol(symbol).set_style(style);
             }
         };
@@ -97,12 +141,13 @@ impl Border {
             };
             if self.borders.contains(B::TOP) {
                 for x in start..end {
-                    draw(x, r.y, self.kind.symbols().horizontal, style);
+                    draw(x, r.y, self.kind.symbols().horizontal, top_style);
                 }
             }
             if self.borders.contains(B::BOTTOM) {
                 for x in start..end {
-                    draw(x, r.y + r.height - 1, self.kind.symbols().horizontal, style);
+                    let symbol = self.kind.symbols().horizontal;
+                    draw(x, r.y + r.height - 1, symbol, bottom_style);
                 }
             }
         }
@@ -119,31 +164,36 @@ impl Border {
             };
             if self.borders.contains(B::LEFT) {
                 for y in start..end {
-                    draw(r.x, y, self.kind.symbols().vertical, style);
+                    draw(r.x, y, self.kind.symbols().vertical, left_style);
                 }
             }
             if self.borders.contains(B::RIGHT) {
                 for y in start..end {
-                    draw(r.x + r.width - 1, y, self.kind.symbols().vertical, style);
+                    draw(
+                        r.x + r.width - 1,
+                        y,
+                        self.kind.symbols().vertical,
+                        right_style,
+                    );
                 }
             }
         }
 
-        // corners
+        // corners, with the style of the top or bottom side
         if self.borders.contains(B::TOP_LEFT_CORNER) {
-            draw(r.x, r.y, self.kind.symbols().top_left, style);
+            draw(r.x, r.y, self.kind.symbols().top_left, top_style);
         }
         if self.borders.contains(B::BOTTOM_LEFT_CORNER) {
             let symbol = self.kind.symbols().bottom_left;
-            draw(r.x, r.y + r.height - 1, symbol, style);
+            draw(r.x, r.y + r.height - 1, symbol, bottom_style);
         }
         if self.borders.contains(B::BOTTOM_RIGHT_CORNER) {
             let symbol = self.kind.symbols().bottom_right;
-            draw(r.x + r.width - 1, r.y + r.height - 1, symbol, style);
+            draw(r.x + r.width - 1, r.y + r.height - 1, symbol, bottom_style);
         }
         if self.borders.contains(B::TOP_RIGHT_CORNER) {
             let symbol = self.kind.symbols().top_right;
-            draw(r.x + r.width - 1, r.y, symbol, style);
+            draw(r.x + r.width - 1, r.y, symbol, top_style);
         }
     }
 }
@@ -178,6 +228,16 @@ impl Widget for Border {
     }
 
     fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &super::LifeCycle) {
+        match event {
+            // Only the border itself needs to be repainted, the layout stays the same
+            super::LifeCycle::FocusChanged(_) if self.focus_style.is_some() => cx.request_paint(),
+            super::LifeCycle::HotChanged(_) if self.hover_style.is_some() => cx.request_paint(),
+            _ => (),
+        }
         self.content.lifecycle(cx, event);
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
 }
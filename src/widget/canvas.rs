@@ -0,0 +1,90 @@
+use crate::{
+    geometry::{to_ratatui_rect, Size},
+    CanvasContent, Grid,
+};
+
+use super::{
+    core::{LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, EventCx, LayoutCx, LifeCycle, Widget,
+};
+
+pub struct Canvas {
+    content: CanvasContent,
+    /// The grid which is painted, it's drawn in layout when the content is a function
+    grid: Grid,
+}
+
+impl Canvas {
+    pub(crate) fn new(content: CanvasContent) -> Self {
+        let grid = match &content {
+            CanvasContent::Grid(grid) => grid.clone(),
+            CanvasContent::Draw { .. } => Grid::new(0, 0),
+        };
+        Canvas { content, grid }
+    }
+
+    pub(crate) fn set_content(&mut self, content: &CanvasContent) -> ChangeFlags {
+        if self.content == *content {
+            return ChangeFlags::empty();
+        }
+        self.content = content.clone();
+        match &self.content {
+            CanvasContent::Grid(grid)
+                if (grid.width(), grid.height()) == (self.grid.width(), self.grid.height()) =>
+            {
+                self.grid = grid.clone();
+                ChangeFlags::PAINT
+            }
+            CanvasContent::Grid(grid) => {
+                self.grid = grid.clone();
+                ChangeFlags::LAYOUT | ChangeFlags::PAINT
+            }
+            // The grid is drawn again in layout
+            CanvasContent::Draw { .. } => ChangeFlags::LAYOUT | ChangeFlags::PAINT,
+        }
+    }
+}
+
+impl Widget for Canvas {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+
+        // Cells outside of the rect are clipped, cells of the rect outside of the grid are not painted
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                if let Some((symbol, style)) = self.grid.get(x - rect.x, y - rect.y) {
+                    buf.get_mut(x, y).set_char(symbol).set_style(style);
+                }
+            }
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        match &self.content {
+            CanvasContent::Grid(grid) => {
+                bc.constrain(Size::new(grid.width() as f64, grid.height() as f64))
+            }
+            CanvasContent::Draw { draw, natural_size } => {
+                let width = if bc.is_width_bounded() {
+                    bc.max().width
+                } else {
+                    natural_size.0 as f64
+                };
+                let height = if bc.is_height_bounded() {
+                    bc.max().height
+                } else {
+                    natural_size.1 as f64
+                };
+                let size = bc.constrain(Size::new(width, height));
+                self.grid = draw(size.width as u16, size.height as u16);
+                size
+            }
+        }
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
+
+    fn lifecycle(&mut self, _cx: &mut LifeCycleCx, _event: &LifeCycle) {}
+}
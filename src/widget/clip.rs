@@ -0,0 +1,71 @@
+use ratatui::{buffer::Buffer, style::Style};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Like [`Buffer::set_stringn`], but `x` and `y` may be outside of the area of `buf`, e.g. when
+/// the widget is partly scrolled out of view. Only the graphemes within the area are set,
+/// the visible cells of a wide grapheme which is cut off at the left edge are cleared.
+///
+/// Returns the position after the last grapheme (set or not), like `set_stringn`.
+pub(crate) fn set_stringn_clipped(
+    buf: &mut Buffer,
+    x: u16,
+    y: u16,
+    text: &str,
+    max_width: usize,
+    style: Style,
+) -> (u16, u16) {
+    let area = buf.area;
+    if y < area.top() || y >= area.bottom() {
+        return (x, y);
+    }
+    let end = x as usize + max_width;
+    let mut x_offset = x as usize;
+    for (i, grapheme) in text.grapheme_indices(true) {
+        if x_offset >= area.left() as usize {
+            let remaining = end.saturating_sub(x_offset);
+            return buf.set_stringn(x_offset as u16, y, &text[i..], remaining, style);
+        }
+        // `set_stringn` skips control characters as well
+        if grapheme.contains(char::is_control) {
+            continue;
+        }
+        let width = grapheme.width();
+        if x_offset + width > end {
+            break;
+        }
+        for cell in area.left() as usize..(x_offset + width).min(area.right() as usize) {
+            buf.get_mut(cell as u16, y).set_symbol(" ").set_style(style);
+        }
+        x_offset += width;
+    }
+    (x_offset.min(u16::MAX as usize) as u16, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+
+    use super::*;
+
+    #[test]
+    fn only_the_graphemes_within_the_buffer_are_set() {
+        let mut buf = Buffer::empty(Rect::new(3, 1, 4, 2));
+        let (x, _) = set_stringn_clipped(&mut buf, 1, 1, "abcdefgh", 8, Style::default());
+        assert_eq!(x, 7);
+        // outside of the rows of the buffer
+        set_stringn_clipped(&mut buf, 3, 0, "xxxx", 4, Style::default());
+        set_stringn_clipped(&mut buf, 3, 3, "xxxx", 4, Style::default());
+        let mut expected = Buffer::with_lines(vec!["cdef", "    "]);
+        expected.area = buf.area;
+        assert_eq!(buf, expected);
+
+        // the visible half of a wide grapheme is cleared
+        let mut buf = Buffer::with_lines(vec!["....", "...."]);
+        buf.area.x = 3;
+        set_stringn_clipped(&mut buf, 2, 1, "界a", 3, Style::default());
+        let mut expected = Buffer::with_lines(vec!["....", " a.."]);
+        expected.area = buf.area;
+        assert_eq!(buf, expected);
+    }
+}
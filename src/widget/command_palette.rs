@@ -0,0 +1,400 @@
+use ratatui::{style::Style, symbols};
+use unicode_width::UnicodeWidthChar;
+
+use crate::{
+    geometry::{to_ratatui_rect, Size},
+    FuzzyMatch,
+};
+
+use super::{
+    core::{IdPath, LifeCycleCx, PaintCx},
+    text_input::edit_line,
+    BoxConstraints, ChangeFlags, Event, EventCx, Key, KeyEvent, LayoutCx, LifeCycle, Message,
+    MouseButton, MouseKind, RawMouseEvent, Widget,
+};
+
+pub(crate) type Matcher = fn(&str, &str) -> Option<FuzzyMatch>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PaletteStyles {
+    pub(crate) text: Style,
+    pub(crate) border: Style,
+    pub(crate) selected: Style,
+    /// The characters of a command which are matched by the query
+    pub(crate) highlight: Style,
+    pub(crate) placeholder: Style,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PaletteMessage {
+    /// Runs the command with this index
+    Run(usize),
+    Close,
+}
+
+/// A bordered box with a query input and the commands which match the query, best matches first.
+pub struct CommandPalette {
+    id_path: IdPath,
+    commands: Vec<String>,
+    placeholder: String,
+    matcher: Matcher,
+    query: String,
+    /// The byte index of the cursor in the query
+    cursor: usize,
+    /// The indices of the matching commands together with their match
+    matches: Vec<(usize, FuzzyMatch)>,
+    /// The index into `matches`
+    selected: usize,
+    /// The first visible match
+    scroll: usize,
+    styles: PaletteStyles,
+    /// The palette takes the focus once, when it's shown
+    focus_requested: bool,
+}
+
+/// The maximum width of the palette
+const MAX_WIDTH: f64 = 60.0;
+/// The maximum amount of visible commands
+const MAX_ROWS: usize = 10;
+
+impl CommandPalette {
+    pub(crate) fn new(
+        id_path: &IdPath,
+        commands: Vec<String>,
+        placeholder: String,
+        matcher: Matcher,
+        styles: PaletteStyles,
+    ) -> Self {
+        let mut palette = CommandPalette {
+            id_path: id_path.clone(),
+            commands,
+            placeholder,
+            matcher,
+            query: String::new(),
+            cursor: 0,
+            matches: Vec::new(),
+            selected: 0,
+            scroll: 0,
+            styles,
+            focus_requested: false,
+        };
+        palette.filter();
+        palette
+    }
+
+    pub(crate) fn set_commands(&mut self, commands: Vec<String>) -> ChangeFlags {
+        if self.commands != commands {
+            self.commands = commands;
+            self.filter();
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_placeholder(&mut self, placeholder: &str) -> ChangeFlags {
+        if self.placeholder != placeholder {
+            self.placeholder = placeholder.to_string();
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_matcher(&mut self, matcher: Matcher) -> ChangeFlags {
+        self.matcher = matcher;
+        let prev_matches = self.matches.clone();
+        self.filter();
+        if self.matches != prev_matches {
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_styles(&mut self, styles: PaletteStyles) -> ChangeFlags {
+        if self.styles != styles {
+            self.styles = styles;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// Matches the commands against the query, the selected command stays selected if it still matches.
+    fn filter(&mut self) {
+        let selected_command = self.matches.get(self.selected).map(|(i, _)| *i);
+        self.matches = self
+            .commands
+            .iter()
+            .enumerate()
+            .filter_map(|(i, command)| (self.matcher)(&self.query, command).map(|m| (i, m)))
+            .collect();
+        // stable, so that commands with the same score keep their order
+        self.matches.sort_by(|(_, a), (_, b)| b.score.cmp(&a.score));
+        self.selected = selected_command
+            .and_then(|command| self.matches.iter().position(|(i, _)| *i == command))
+            .unwrap_or(0);
+        self.scroll_to_selected();
+    }
+
+    fn rows(&self) -> usize {
+        MAX_ROWS.min(self.commands.len()).max(1)
+    }
+
+    fn select(&mut self, selected: usize) {
+        self.selected = selected.min(self.matches.len().saturating_sub(1));
+        self.scroll_to_selected();
+    }
+
+    fn scroll_to_selected(&mut self) {
+        let rows = self.rows();
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + rows {
+            self.scroll = self.selected + 1 - rows;
+        }
+        self.scroll = self.scroll.min(self.matches.len().saturating_sub(rows));
+    }
+
+    fn send(&self, cx: &mut EventCx, message: PaletteMessage) {
+        cx.add_message(Message::new(self.id_path.clone(), message));
+    }
+
+    fn run_selected(&self, cx: &mut EventCx) {
+        if let Some((i, _)) = self.matches.get(self.selected) {
+            self.send(cx, PaletteMessage::Run(*i));
+        }
+    }
+}
+
+impl Widget for CommandPalette {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let is_focused = cx.is_focused();
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        if area.width < 3 || area.height < 3 {
+            return;
+        }
+
+        // The palette covers the content behind it
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf.get_mut(x, y)
+                    .set_symbol(" ")
+                    .set_style(self.styles.text);
+            }
+        }
+
+        let line = symbols::line::ROUNDED;
+        let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+        for x in area.left() + 1..right {
+            buf.get_mut(x, area.top()).set_symbol(line.horizontal);
+            buf.get_mut(x, bottom).set_symbol(line.horizontal);
+        }
+        for y in area.top() + 1..bottom {
+            buf.get_mut(area.left(), y).set_symbol(line.vertical);
+            buf.get_mut(right, y).set_symbol(line.vertical);
+        }
+        buf.get_mut(area.left(), area.top())
+            .set_symbol(line.top_left);
+        buf.get_mut(right, area.top()).set_symbol(line.top_right);
+        buf.get_mut(area.left(), bottom)
+            .set_symbol(line.bottom_left);
+        buf.get_mut(right, bottom).set_symbol(line.bottom_right);
+        for x in area.left()..area.right() {
+            buf.get_mut(x, area.top()).set_style(self.styles.border);
+            buf.get_mut(x, bottom).set_style(self.styles.border);
+        }
+        for y in area.top()..area.bottom() {
+            buf.get_mut(area.left(), y).set_style(self.styles.border);
+            buf.get_mut(right, y).set_style(self.styles.border);
+        }
+
+        // The query input, the text is cut off on the left so that the cursor stays visible
+        let x = area.x + 1;
+        let width = right.saturating_sub(x) as usize;
+        let input_y = area.y + 1;
+        buf.set_stringn(x, input_y, "> ", width, self.styles.border);
+        let input_x = x + 2;
+        let input_width = width.saturating_sub(2);
+        let cursor_x = if self.query.is_empty() {
+            buf.set_stringn(
+                input_x,
+                input_y,
+                &self.placeholder,
+                input_width,
+                self.styles.placeholder,
+            );
+            input_x
+        } else {
+            let before_cursor: Vec<char> = self.query[..self.cursor].chars().collect();
+            let mut start = 0;
+            let mut cursor_column: usize = before_cursor.iter().filter_map(|c| c.width()).sum();
+            while cursor_column >= input_width && start < before_cursor.len() {
+                cursor_column -= before_cursor[start].width().unwrap_or(0);
+                start += 1;
+            }
+            let visible_start = before_cursor[..start].iter().map(|c| c.len_utf8()).sum();
+            buf.set_stringn(
+                input_x,
+                input_y,
+                &self.query[visible_start..],
+                input_width,
+                self.styles.text,
+            );
+            input_x + cursor_column as u16
+        };
+        if is_focused && cursor_x < right {
+            cx.set_cursor_position(cursor_x, input_y);
+        }
+        let buf = cx.buffer_mut();
+
+        // The separator between the input and the commands
+        let separator_y = area.y + 2;
+        if separator_y < bottom {
+            buf.get_mut(area.left(), separator_y)
+                .set_symbol(line.vertical_right);
+            buf.get_mut(right, separator_y)
+                .set_symbol(line.vertical_left);
+            for x in area.left() + 1..right {
+                buf.get_mut(x, separator_y)
+                    .set_symbol(line.horizontal)
+                    .set_style(self.styles.border);
+            }
+        }
+
+        let first_row = area.y + 3;
+        if self.matches.is_empty() {
+            if first_row < bottom {
+                buf.set_stringn(
+                    x + 1,
+                    first_row,
+                    "No matching commands",
+                    width.saturating_sub(1),
+                    self.styles.placeholder,
+                );
+            }
+            return;
+        }
+        let rows = (first_row..bottom).zip(self.matches.iter().enumerate().skip(self.scroll));
+        for (y, (i, (command, fuzzy_match))) in rows {
+            let style = if i == self.selected {
+                self.styles.selected
+            } else {
+                self.styles.text
+            };
+            for x in x..right {
+                buf.get_mut(x, y).set_style(style);
+            }
+            let mut column = x + 1;
+            for (char_index, c) in self.commands[*command].chars().enumerate() {
+                let char_width = c.width().unwrap_or(0) as u16;
+                if column + char_width > right {
+                    break;
+                }
+                let char_style = if fuzzy_match.indices.contains(&char_index) {
+                    style.patch(self.styles.highlight)
+                } else {
+                    style
+                };
+                buf.set_stringn(column, y, c.to_string(), char_width as usize, char_style);
+                column += char_width;
+            }
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // borders, the input, the separator and the commands
+        let height = self.rows() + 4;
+        bc.constrain(Size::new(MAX_WIDTH.min(bc.max().width), height as f64))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        match event {
+            Event::Key(key @ KeyEvent { code, .. }) if cx.is_focused() => {
+                match code {
+                    Key::Up | Key::BackTab => {
+                        self.select(self.selected.saturating_sub(1));
+                        cx.request_paint();
+                    }
+                    Key::Down | Key::Tab => {
+                        self.select(self.selected + 1);
+                        cx.request_paint();
+                    }
+                    Key::PageUp => {
+                        self.select(self.selected.saturating_sub(self.rows()));
+                        cx.request_paint();
+                    }
+                    Key::PageDown => {
+                        self.select(self.selected + self.rows());
+                        cx.request_paint();
+                    }
+                    Key::Enter => self.run_selected(cx),
+                    Key::Esc => self.send(cx, PaletteMessage::Close),
+                    _ => {
+                        if let Some(query_changed) =
+                            edit_line(&mut self.query, &mut self.cursor, key)
+                        {
+                            if query_changed {
+                                self.filter();
+                                // The best match is selected while typing
+                                self.select(0);
+                            }
+                            cx.request_paint();
+                        }
+                    }
+                }
+                // The palette traps the focus, so `Tab` doesn't move it elsewhere
+                cx.set_handled(true);
+            }
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Down(MouseButton::Left),
+                row,
+                ..
+            }) if cx.is_hot() => {
+                // Mouse events are relative to the origin of the widget, the commands start
+                // below the border, the input and the separator
+                if let Some(row) = ((*row).max(0) as usize).checked_sub(3) {
+                    if row < self.rows() && self.scroll + row < self.matches.len() {
+                        self.select(self.scroll + row);
+                        self.run_selected(cx);
+                        cx.request_paint();
+                    }
+                }
+            }
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::ScrollUp,
+                ..
+            }) if cx.is_hot() => {
+                self.select(self.selected.saturating_sub(1));
+                cx.request_paint();
+            }
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::ScrollDown,
+                ..
+            }) if cx.is_hot() => {
+                self.select(self.selected + 1);
+                cx.request_paint();
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        match event {
+            LifeCycle::ViewContextChanged(_) if !self.focus_requested => {
+                self.focus_requested = true;
+                cx.request_focus();
+            }
+            // show or hide the cursor
+            LifeCycle::FocusChanged(_) => cx.request_paint(),
+            _ => (),
+        }
+    }
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+}
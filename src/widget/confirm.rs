@@ -0,0 +1,286 @@
+use ratatui::{style::Style, symbols};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    geometry::{to_ratatui_rect, Point, Rect, Size},
+    ConfirmButton,
+};
+
+use super::{
+    core::{IdPath, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, EventCx, Key, KeyEvent, LayoutCx, LifeCycle, Message,
+    MouseButton, MouseKind, RawMouseEvent, Widget,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ConfirmStyles {
+    pub(crate) text: Style,
+    pub(crate) border: Style,
+    pub(crate) selected: Style,
+}
+
+/// A bordered box with a message and a row of buttons, which sends the pressed [`ConfirmButton`]
+/// to its view.
+pub struct Confirm {
+    id_path: IdPath,
+    message: String,
+    buttons: Vec<(ConfirmButton, String)>,
+    /// The index of the button which is pressed with `Enter`
+    selected: usize,
+    styles: ConfirmStyles,
+    /// The dialog takes the focus once, when it's shown
+    focus_requested: bool,
+    size: Size,
+}
+
+/// Columns between the border and the content
+const PADDING: u16 = 1;
+/// Columns between two buttons
+const BUTTON_GAP: u16 = 2;
+
+impl Confirm {
+    pub(crate) fn new(
+        id_path: &IdPath,
+        message: String,
+        buttons: Vec<(ConfirmButton, String)>,
+        default: ConfirmButton,
+        styles: ConfirmStyles,
+    ) -> Self {
+        let mut confirm = Confirm {
+            id_path: id_path.clone(),
+            message,
+            buttons,
+            selected: 0,
+            styles,
+            focus_requested: false,
+            size: Size::ZERO,
+        };
+        confirm.select(default);
+        confirm
+    }
+
+    pub(crate) fn set_message(&mut self, message: &str) -> ChangeFlags {
+        if self.message != message {
+            self.message = message.to_string();
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// The selection is reset to `default`, when the buttons change.
+    pub(crate) fn set_buttons(
+        &mut self,
+        buttons: Vec<(ConfirmButton, String)>,
+        default: ConfirmButton,
+    ) -> ChangeFlags {
+        if self.buttons != buttons {
+            self.buttons = buttons;
+            self.select(default);
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_styles(&mut self, styles: ConfirmStyles) -> ChangeFlags {
+        if self.styles != styles {
+            self.styles = styles;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn select(&mut self, button: ConfirmButton) {
+        if let Some(i) = self.buttons.iter().position(|(b, _)| *b == button) {
+            self.selected = i;
+        }
+    }
+
+    /// The button `Esc` presses, `Cancel` if there is one, otherwise `No`
+    fn cancel_button(&self) -> ConfirmButton {
+        if self
+            .buttons
+            .iter()
+            .any(|(b, _)| *b == ConfirmButton::Cancel)
+        {
+            ConfirmButton::Cancel
+        } else {
+            ConfirmButton::No
+        }
+    }
+
+    fn press(&self, cx: &mut EventCx, button: ConfirmButton) {
+        cx.add_message(Message::new(self.id_path.clone(), button));
+    }
+
+    fn buttons_width(&self) -> u16 {
+        let labels: usize = self.buttons.iter().map(|(_, l)| l.width() + 4).sum();
+        labels as u16 + BUTTON_GAP * (self.buttons.len() as u16).saturating_sub(1)
+    }
+
+    /// The local rects of the buttons, which are centered in the row above the bottom border.
+    fn button_rects(&self) -> impl Iterator<Item = Rect> + '_ {
+        let y = (self.size.height - 2.0).max(0.0);
+        let mut x = ((self.size.width - self.buttons_width() as f64) / 2.0)
+            .floor()
+            .max(0.0);
+        self.buttons.iter().map(move |(_, label)| {
+            let width = (label.width() + 4) as f64;
+            let rect = Rect::new(x, y, x + width, y + 1.0);
+            x += width + BUTTON_GAP as f64;
+            rect
+        })
+    }
+}
+
+impl Widget for Confirm {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let origin = cx.rect().origin().to_vec2();
+        let button_rects: Vec<_> = self.button_rects().collect();
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        if area.width < 2 || area.height < 2 {
+            return;
+        }
+
+        // The dialog covers the content behind it
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf.get_mut(x, y)
+                    .set_symbol(" ")
+                    .set_style(self.styles.text);
+            }
+        }
+
+        let line = symbols::line::ROUNDED;
+        let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+        for x in area.left() + 1..right {
+            buf.get_mut(x, area.top()).set_symbol(line.horizontal);
+            buf.get_mut(x, bottom).set_symbol(line.horizontal);
+        }
+        for y in area.top() + 1..bottom {
+            buf.get_mut(area.left(), y).set_symbol(line.vertical);
+            buf.get_mut(right, y).set_symbol(line.vertical);
+        }
+        buf.get_mut(area.left(), area.top())
+            .set_symbol(line.top_left);
+        buf.get_mut(right, area.top()).set_symbol(line.top_right);
+        buf.get_mut(area.left(), bottom)
+            .set_symbol(line.bottom_left);
+        buf.get_mut(right, bottom).set_symbol(line.bottom_right);
+        for x in area.left()..area.right() {
+            buf.get_mut(x, area.top()).set_style(self.styles.border);
+            buf.get_mut(x, bottom).set_style(self.styles.border);
+        }
+        for y in area.top()..area.bottom() {
+            buf.get_mut(area.left(), y).set_style(self.styles.border);
+            buf.get_mut(right, y).set_style(self.styles.border);
+        }
+
+        let x = area.x + 1 + PADDING;
+        let max_width = right.saturating_sub(x + PADDING) as usize;
+        // The last two rows above the bottom border are the gap and the buttons
+        let message_rows = (area.y + 1..bottom.saturating_sub(2)).zip(self.message.lines());
+        for (y, message_line) in message_rows {
+            buf.set_stringn(x, y, message_line, max_width, self.styles.text);
+        }
+
+        for (i, ((_, label), button_rect)) in self.buttons.iter().zip(button_rects).enumerate() {
+            let button_rect = to_ratatui_rect(button_rect + origin).intersection(area);
+            if button_rect.area() == 0 {
+                continue;
+            }
+            let style = if i == self.selected {
+                self.styles.selected
+            } else {
+                self.styles.text
+            };
+            buf.set_stringn(
+                button_rect.x,
+                button_rect.y,
+                format!("[ {label} ]"),
+                button_rect.width as usize,
+                style,
+            );
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let message_width = self.message.lines().map(|l| l.width()).max().unwrap_or(0);
+        let width = message_width.max(self.buttons_width() as usize) + 2 + 2 * PADDING as usize;
+        // borders, message, an empty row and the buttons
+        let height = self.message.lines().count() + 4;
+        self.size = bc.constrain(Size::new(width as f64, height as f64));
+        self.size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        match event {
+            Event::Key(KeyEvent { code, .. }) if cx.is_focused() => {
+                let count = self.buttons.len();
+                match code {
+                    Key::Left | Key::BackTab if count > 0 => {
+                        self.selected = (self.selected + count - 1) % count;
+                        cx.request_paint();
+                    }
+                    Key::Right | Key::Tab if count > 0 => {
+                        self.selected = (self.selected + 1) % count;
+                        cx.request_paint();
+                    }
+                    Key::Enter => {
+                        if let Some((button, _)) = self.buttons.get(self.selected) {
+                            self.press(cx, *button);
+                        }
+                    }
+                    Key::Esc => self.press(cx, self.cancel_button()),
+                    _ => (),
+                }
+                // The dialog traps the focus, so `Tab` doesn't move it elsewhere
+                cx.set_handled(true);
+            }
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Down(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) => {
+                // Mouse events are relative to the origin of the widget
+                let point = Point::new(*column as f64, *row as f64);
+                if let Some(i) = self.button_rects().position(|rect| rect.contains(point)) {
+                    self.selected = i;
+                    cx.set_active(true);
+                    cx.request_paint();
+                }
+            }
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Up(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) => {
+                let point = Point::new(*column as f64, *row as f64);
+                let pressed = self.button_rects().position(|rect| rect.contains(point));
+                if cx.is_active() && pressed == Some(self.selected) {
+                    self.press(cx, self.buttons[self.selected].0);
+                }
+                cx.set_active(false);
+            }
+            Event::FocusLost => cx.set_active(false),
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        if matches!(event, LifeCycle::ViewContextChanged(_)) && !self.focus_requested {
+            self.focus_requested = true;
+            cx.request_focus();
+        }
+    }
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+}
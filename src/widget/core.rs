@@ -1,33 +1,88 @@
 use super::{BoxConstraints, Event, LifeCycle};
-use crate::geometry::{Point, Rect, Size};
+use crate::geometry::{Point, Rect, Size, Vec2};
 use bitflags::bitflags;
-use crossterm::event::MouseEventKind;
-use ratatui::Terminal;
-use std::{any::Any, ops::DerefMut, time::Duration};
+use ratatui::{buffer::Buffer, style::Style};
+use std::{
+    any::Any,
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
 use xilem_core::{message, Id};
 
-#[cfg(any(test, doctest, feature = "doctests"))]
-use ratatui::backend::TestBackend;
-
-#[cfg(not(any(test, doctest, feature = "doctests")))]
-use ratatui::backend::CrosstermBackend;
-
-#[cfg(not(any(test, doctest, feature = "doctests")))]
-use std::io::Stdout;
-
 message!(Send);
 
 /// Static state that is shared between most contexts.
 pub struct CxState<'a> {
     messages: &'a mut Vec<Message>,
     pub(crate) time_since_last_render_request: Duration, // in seconds TODO Duration instead of f64?
+    /// The ids of the pods from the root down to the topmost widget under the mouse cursor.
+    ///
+    /// Exactly the pods in this path are hot.
+    hot_path: Vec<Id>,
+    focus: &'a mut FocusState,
+    /// Where the terminal cursor should be shown after painting, it's hidden if this is `None`.
+    cursor_position: Option<(u16, u16)>,
+    /// Buffers which are painted on top of the widget tree, see [`PaintCx::paint_overlay`]
+    overlays: Vec<Buffer>,
+    /// Moves the rects of the widgets which are painted into an offscreen buffer,
+    /// see [`PaintCx::paint_offscreen`]
+    paint_translation: Vec2,
+}
+
+/// Keyboard focus state, which is kept by the app across frames.
+#[derive(Debug, Default)]
+pub(crate) struct FocusState {
+    /// The id of the pod of the focused widget.
+    focused: Option<Id>,
+    /// The ids of the pods from the root down to the focused widget.
+    path: Vec<Id>,
+    /// The ids of the pods a [`LifeCycle::FocusChanged`] is currently routed to.
+    route: Vec<Id>,
+    /// A focus change requested by a widget, `Some(None)` means the focus should be resigned.
+    request: Option<Option<Id>>,
 }
 
 impl<'a> CxState<'a> {
-    pub fn new(messages: &'a mut Vec<Message>, time_since_last_render_request: Duration) -> Self {
+    pub(crate) fn new(
+        messages: &'a mut Vec<Message>,
+        focus: &'a mut FocusState,
+        time_since_last_render_request: Duration,
+    ) -> Self {
         Self {
             messages,
             time_since_last_render_request,
+            hot_path: Vec::new(),
+            focus,
+            cursor_position: None,
+            overlays: Vec::new(),
+            paint_translation: Vec2::ZERO,
+        }
+    }
+
+    pub(crate) fn cursor_position(&self) -> Option<(u16, u16)> {
+        self.cursor_position
+    }
+
+    /// Copies the overlays painted by the widgets into `buffer`, in the order they were painted.
+    pub(crate) fn paint_overlays(&mut self, buffer: &mut Buffer) {
+        for overlay in self.overlays.drain(..) {
+            let area = overlay.area.intersection(buffer.area);
+            for y in area.top()..area.bottom() {
+                for x in area.left()..area.right() {
+                    *buffer.get_mut(x, y) = overlay.get(x, y).clone();
+                }
+            }
+        }
+    }
+
+    /// Recomputes the hot path via hit-testing `root` with the mouse position (in window coordinates).
+    ///
+    /// This has to be done before dispatching a mouse event or a [`LifeCycle::ViewContextChanged`],
+    /// so that only the topmost widget under the mouse (and its ancestors) become hot.
+    pub(crate) fn update_hot_path(&mut self, root: &Pod, mouse_pos: Option<Point>) {
+        self.hot_path.clear();
+        if let Some(pos) = mouse_pos {
+            root.hit_path(pos, &mut self.hot_path);
         }
     }
 }
@@ -58,15 +113,11 @@ pub struct PaintCx<'a, 'b> {
     pub(crate) cx_state: &'a mut CxState<'b>,
     // TODO mutable? (xilem doesn't do this, but I think there are use cases for this...)
     pub(crate) widget_state: &'a mut WidgetState,
-
-    #[cfg(any(test, doctest, feature = "doctests"))]
-    pub(crate) terminal: &'a mut Terminal<TestBackend>,
-
-    #[cfg(not(any(test, doctest, feature = "doctests")))]
-    pub(crate) terminal: &'a mut Terminal<CrosstermBackend<Stdout>>,
+    /// The current buffer of the terminal, it covers the whole terminal area
+    pub(crate) buffer: &'a mut Buffer,
     // TODO this kinda feels hacky, find a better solution for this issue:
     // this is currently necessary because the most outer styleable widget should be able to override the style for a styleable widget
-    pub(crate) override_style: ratatui::style::Style,
+    pub(crate) override_style: Style,
 }
 
 /// A macro for implementing methods on multiple contexts.
@@ -104,8 +155,18 @@ impl_context_method!(
 
         // TODO do this differently?
         /// absolute positioned Rect
+        ///
+        /// While painting, it's relative to the buffer which is painted into, which is only
+        /// different from the window while the content of a scroll view is painted.
         pub fn rect(&self) -> Rect {
-            self.widget_state.rect()
+            self.widget_state.rect() + self.cx_state.paint_translation
+        }
+
+        /// The region in which this widget is visible, in its local coordinate space.
+        ///
+        /// It's updated with [`LifeCycle::ViewContextChanged`], see [`ViewContext::clip`](super::ViewContext::clip).
+        pub fn clip(&self) -> Rect {
+            self.widget_state.clip
         }
 
         /// Returns whether this widget is active.
@@ -126,28 +187,74 @@ impl_context_method!(
 
         /// Requests a call to [`paint`] for this widget.
         ///
+        /// This is the context equivalent of returning [`ChangeFlags::PAINT`] from a view rebuild.
+        ///
         /// [`paint`]: super::Widget::paint
         pub fn request_paint(&mut self) {
             self.widget_state.flags |= PodFlags::REQUEST_PAINT;
         }
 
-        /// Requests a call to [`paint`] for this widget.
+        /// Requests a call to [`layout`] for this widget.
         ///
-        /// [`paint`]: super::Widget::paint
+        /// Since the size of this widget may change, the layout of all its ancestors is recomputed
+        /// as well, followed by a paint.
+        /// This is the context equivalent of returning [`ChangeFlags::LAYOUT`] from a view rebuild.
+        ///
+        /// [`layout`]: super::Widget::layout
         pub fn request_layout(&mut self) {
             self.widget_state.flags |= PodFlags::REQUEST_LAYOUT;
         }
 
         /// Requests an animation update for this widget.
         /// This effectively means currently a request for a new render/frame
+        ///
+        /// The widget will receive a [`LifeCycle::Animate`] event in the next frame.
+        /// This is the context equivalent of returning [`ChangeFlags::ANIMATION`] from a view rebuild.
+        ///
+        /// [`LifeCycle::Animate`]: super::LifeCycle::Animate
         pub fn request_animation_update(&mut self) {
             self.widget_state.flags |= PodFlags::REQUEST_ANIMATION;
         }
 
+        /// Requests the invalidations described by `flags` for this widget.
+        ///
+        /// This is mostly useful to forward the [`ChangeFlags`] of an operation (such as a setter
+        /// on a widget) that reports what has changed, e.g. `cx.request(self.set_text(text))`.
+        pub fn request(&mut self, flags: ChangeFlags) {
+            self.widget_state
+                .request(PodFlags::from_bits_truncate(flags.bits() as _));
+        }
+
+        /// Returns whether a call to [`layout`] is pending for this widget.
+        ///
+        /// [`layout`]: super::Widget::layout
+        pub fn layout_requested(&self) -> bool {
+            self.widget_state.flags.contains(PodFlags::REQUEST_LAYOUT)
+        }
+
+        /// Returns whether a call to [`paint`] is pending for this widget.
+        ///
+        /// [`paint`]: super::Widget::paint
+        pub fn paint_requested(&self) -> bool {
+            self.widget_state.flags.contains(PodFlags::REQUEST_PAINT)
+        }
+
         pub fn time_since_last_render_request(&self) -> Duration {
             self.cx_state.time_since_last_render_request
         }
 
+        /// Returns whether this widget has the keyboard focus.
+        ///
+        /// The focused widget receives all [`Event::Key`] and [`Event::Paste`] events.
+        pub fn is_focused(&self) -> bool {
+            self.cx_state.focus.focused == Some(self.widget_state.id)
+        }
+
+        /// Returns whether this widget or any of its descendants has the keyboard focus.
+        pub fn has_focus(&self) -> bool {
+            self.cx_state.focus.path.contains(&self.widget_state.id)
+        }
+
         /// Notify Trui that this widgets view context changed.
         ///
         /// A [`LifeCycle::ViewContextChanged`] event will be scheduled.
@@ -163,7 +270,7 @@ impl_context_method!(
 
 // TODO add the other contexts
 // Methods on EventCx, UpdateCx, and LifeCycleCx
-impl_context_method!(EventCx<'_, '_>, {
+impl_context_method!(EventCx<'_, '_>, LayoutCx<'_, '_>, LifeCycleCx<'_, '_>, {
     /// Sends a message to the view tree.
     ///
     /// Sending messages is the main way of interacting with views.
@@ -171,12 +278,50 @@ impl_context_method!(EventCx<'_, '_>, {
     /// view will schedule a rebuild if necessary and update the widget accordingly.
     /// Since widget can send messages to all views control widgets store the IdPath of their view
     /// to target them.
-    //TODO: Decide whether it should be possible to send messages from Layout?
+    ///
+    /// Messages sent during layout or a lifecycle pass are handled in another render pass right
+    /// after the current one, so a widget must not send them in every pass, otherwise the app never
+    /// stops rendering.
     pub fn add_message(&mut self, message: Message) {
         self.cx_state.messages.push(message);
     }
 });
 
+// Methods on EventCx and LifeCycleCx
+impl_context_method!(EventCx<'_, '_>, LifeCycleCx<'_, '_>, {
+    /// Requests the keyboard focus for this widget.
+    ///
+    /// The focus changes after the current event (or lifecycle pass) and the widgets
+    /// which lose and gain the focus will receive a [`LifeCycle::FocusChanged`].
+    /// Only widgets that [accept focus](super::Widget::accepts_focus) should request it.
+    pub fn request_focus(&mut self) {
+        self.cx_state.focus.request = Some(Some(self.widget_state.id));
+    }
+
+    /// Gives up the keyboard focus, if this widget is focused.
+    pub fn resign_focus(&mut self) {
+        if self.is_focused() {
+            self.cx_state.focus.request = Some(None);
+        }
+    }
+
+    /// The id of the pod of the focused widget, e.g. to give the focus back to it later
+    /// with [`pass_focus`](Self::pass_focus).
+    pub(crate) fn focused(&self) -> Option<Id> {
+        self.cx_state.focus.focused
+    }
+
+    /// Passes the keyboard focus to the widget with the id `focus` (or removes it with `None`),
+    /// if this widget is focused.
+    ///
+    /// The focus is removed if that widget isn't part of the widget tree anymore.
+    pub(crate) fn pass_focus(&mut self, focus: Option<Id>) {
+        if self.is_focused() {
+            self.cx_state.focus.request = Some(focus);
+        }
+    }
+});
+
 impl<'a, 'b> EventCx<'a, 'b> {
     /// Set the [`active`] state of the widget.
     ///
@@ -187,6 +332,12 @@ impl<'a, 'b> EventCx<'a, 'b> {
 
     /// Set the event as "handled", which stops its propagation to other
     /// widgets.
+    ///
+    /// Events are dispatched depth first, so the ancestors of a widget receive an event after it.
+    /// Widgets which wrap a child (e.g. [`OnClick`](super::OnClick)) have to check
+    /// [`is_handled`](EventCx::is_handled) after dispatching the event to it and ignore the
+    /// event if it's set. Siblings which weren't visited yet don't receive the event anymore,
+    /// siblings that were already visited aren't affected.
     pub fn set_handled(&mut self, is_handled: bool) {
         self.is_handled = is_handled;
     }
@@ -197,14 +348,126 @@ impl<'a, 'b> EventCx<'a, 'b> {
     }
 }
 
+impl<'a, 'b> PaintCx<'a, 'b> {
+    /// The buffer of the terminal which the widget should paint into.
+    ///
+    /// Widgets are expected to only paint within their [`rect`](PaintCx::rect) and within the area
+    /// of the buffer, which may only cover a part of the rect, e.g. when the widget is partly
+    /// scrolled out of view.
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        self.buffer
+    }
+
+    /// The style set by an ancestor (e.g. by [`on_hover_style`](crate::ViewExt::on_hover_style)),
+    /// which should be patched on top of the widget's own style when painting.
+    pub fn override_style(&self) -> Style {
+        self.override_style
+    }
+
+    /// Shows the terminal cursor at the cell `x`/`y` (in window coordinates) after painting,
+    /// e.g. at the insertion point of a focused text input.
+    ///
+    /// The cursor is hidden, if no widget sets its position while painting.
+    pub fn set_cursor_position(&mut self, x: u16, y: u16) {
+        self.cx_state.cursor_position = Some((x, y));
+    }
+
+    /// Paints `overlay` on top of all widgets after the widget tree was painted, e.g. a tooltip which
+    /// isn't limited to the [`rect`](PaintCx::rect) of the widget and must not be painted over by
+    /// its siblings.
+    ///
+    /// The area of the overlay is in window coordinates, all of its cells replace the cells below.
+    pub fn paint_overlay(&mut self, overlay: Buffer) {
+        self.cx_state.overlays.push(overlay);
+    }
+
+    /// Paints `child` into `buffer` instead of the current buffer, with the rects of all widgets
+    /// moved by `translation`, e.g. to clip scrolled content, which may be partly outside of the window.
+    ///
+    /// Widgets only paint within the bounds of the buffer, so `buffer` only has to cover the visible
+    /// part of the child, it doesn't have to start at the origin. The cursor position and the
+    /// overlays set while painting are moved back, the cursor is hidden when it's outside of `clip`
+    /// (in the coordinates of the current buffer).
+    pub(crate) fn paint_offscreen(
+        &mut self,
+        child: &mut Pod,
+        buffer: &mut Buffer,
+        translation: Vec2,
+        clip: ratatui::layout::Rect,
+    ) {
+        let prev_translation = self.cx_state.paint_translation;
+        self.cx_state.paint_translation += translation;
+        let prev_cursor_position = self.cx_state.cursor_position.take();
+        let prev_overlays = self.cx_state.overlays.len();
+        child.paint(&mut PaintCx {
+            cx_state: self.cx_state,
+            widget_state: self.widget_state,
+            buffer,
+            override_style: self.override_style,
+        });
+        self.cx_state.paint_translation = prev_translation;
+
+        let translate_back = |x: u16, y: u16| {
+            let x = x as f64 - translation.x;
+            let y = y as f64 - translation.y;
+            let range = 0.0..=u16::MAX as f64;
+            (range.contains(&x) && range.contains(&y)).then_some((x as u16, y as u16))
+        };
+        self.cx_state.cursor_position = match self.cx_state.cursor_position {
+            Some((x, y)) => translate_back(x, y)
+                .filter(|(x, y)| {
+                    (clip.left()..clip.right()).contains(x)
+                        && (clip.top()..clip.bottom()).contains(y)
+                })
+                .or(prev_cursor_position),
+            None => prev_cursor_position,
+        };
+        let overlays = self.cx_state.overlays.split_off(prev_overlays);
+        for mut overlay in overlays {
+            if let Some((x, y)) = translate_back(overlay.area.x, overlay.area.y) {
+                overlay.area.x = x;
+                overlay.area.y = y;
+                self.cx_state.overlays.push(overlay);
+            }
+        }
+    }
+}
+
 bitflags! {
+    /// Describes which passes have to be run again for a widget after it has changed.
+    ///
+    /// These are returned by [`View::rebuild`](crate::View::rebuild) and by the setters of widgets.
+    /// A view which wraps a [`Pod`] has to [`mark`](Pod::mark) it with the flags returned by the
+    /// rebuild of its child view, and return the result of that to its parent.
+    ///
+    /// A custom container view returns the flags of all of its children combined with its own,
+    /// see [`accumulate`](ChangeFlags::accumulate):
+    ///
+    /// - The flags of every child are propagated, not only the ones of the last or a changed child.
+    ///   Dropping e.g. [`LAYOUT`](ChangeFlags::LAYOUT) of a child, which got bigger, leaves the
+    ///   container with its old size.
+    /// - If the rebuild changed the container widget itself, e.g. its spacing, it adds the flags
+    ///   returned by its setters, usually [`LAYOUT`](ChangeFlags::LAYOUT) or
+    ///   [`PAINT`](ChangeFlags::PAINT).
+    /// - [`TREE`](ChangeFlags::TREE) is added when children were added or removed, which
+    ///   [`ViewSequence::rebuild`](crate::ViewSequence) already does for the children it manages.
+    /// - An empty result means that nothing has to be done, so the passes are skipped for the
+    ///   container and everything below it.
+    ///
+    /// Inside of a widget the same invalidations can be requested via the contexts,
+    /// e.g. with [`EventCx::request_paint`] or [`LifeCycleCx::request_layout`].
     #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Hash)]
     #[must_use]
     pub struct ChangeFlags: u8 {
+        /// Currently unused, reserved for an update pass.
         const UPDATE = 1;
+        /// The size of the widget may have changed, layout (and paint) has to be recomputed.
         const LAYOUT = 2;
+        /// The widget has to be painted again.
         const PAINT = 8;
+        /// The structure of the widget tree has changed, e.g. a child was added or removed.
         const TREE = 0x10;
+        /// The widget requests an animation frame, see [`LifeCycle::Animate`].
         const ANIMATION = 0x20;
     }
 }
@@ -253,11 +516,28 @@ impl PodFlags {
 }
 
 impl ChangeFlags {
-    // Change flags representing change of tree structure.
+    /// Change flags representing change of tree structure.
     pub fn tree_structure() -> Self {
         ChangeFlags::TREE
     }
 
+    /// Combines the flags of a container with the ones returned by the rebuild of one of its
+    /// children (or by [`Pod::mark`], if the child is wrapped in a pod), keeping only the flags
+    /// which are propagated to the parent.
+    ///
+    /// # Examples
+    /// ```
+    /// # use trui::widget::ChangeFlags;
+    /// let children = [ChangeFlags::PAINT, ChangeFlags::empty(), ChangeFlags::LAYOUT];
+    /// let changeflags = children
+    ///     .into_iter()
+    ///     .fold(ChangeFlags::empty(), ChangeFlags::accumulate);
+    /// assert_eq!(changeflags, ChangeFlags::LAYOUT | ChangeFlags::PAINT);
+    /// ```
+    pub fn accumulate(self, child: ChangeFlags) -> Self {
+        self | child.upwards()
+    }
+
     pub(crate) fn upwards(self) -> Self {
         // Note: this assumes PodFlags are a superset of ChangeFlags. This might
         // not always be the case, for example on "structure changed."
@@ -269,25 +549,26 @@ pub type IdPath = Vec<Id>;
 
 #[derive(Debug)]
 pub(crate) struct WidgetState {
-    // TODO could be useful in the future, but not needed currently...
-    // pub(crate) id: Id,
+    pub(crate) id: Id,
     pub(crate) flags: PodFlags,
     pub(crate) size: Size,
     /// The origin of the child in the parent's coordinate space.
     pub(crate) origin: Point,
     /// The origin of the parent in the window coordinate space.
     pub(crate) parent_window_origin: Point,
+    /// The visible region in the local coordinate space, see [`ViewContext::clip`](super::ViewContext::clip).
+    pub(crate) clip: Rect,
 }
 
 impl WidgetState {
     pub(crate) fn new() -> Self {
-        // let id = Id::next();
         WidgetState {
-            // id,
+            id: Id::next(),
             flags: PodFlags::INIT_FLAGS,
             size: Default::default(),
             origin: Default::default(),
             parent_window_origin: Default::default(),
+            clip: Rect::ZERO,
         }
     }
 
@@ -306,8 +587,7 @@ impl WidgetState {
     // TODO do this differently?
     /// absolute positioned Rect
     pub(crate) fn rect(&self) -> Rect {
-        let origin = self.window_origin();
-        Rect::new(origin.x, origin.y, self.size.width, self.size.height)
+        Rect::from_origin_size(self.window_origin(), self.size)
     }
 }
 
@@ -353,6 +633,143 @@ impl Pod {
         flags.upwards()
     }
 
+    /// The id of this pod, which identifies the wrapped widget e.g. in [`Pod::hit_test`].
+    ///
+    /// This is not related to the [`Id`] of the view which created the widget.
+    pub fn id(&self) -> Id {
+        self.state.id
+    }
+
+    /// Returns the id of the deepest widget which contains `point`.
+    ///
+    /// `point` is in the coordinate space of the parent of this pod (for the root pod that's the
+    /// window coordinate space). Children are tested in reverse order, as later children are
+    /// painted on top of earlier ones, so the topmost widget wins when widgets overlap.
+    /// A point has to be within the bounds of all ancestors, so widgets only receive clicks within
+    /// their [clip](super::ViewContext::clip).
+    ///
+    /// The result is only meaningful after the layout pass.
+    pub fn hit_test(&self, point: Point) -> Option<Id> {
+        let mut path = Vec::new();
+        self.hit_path(point, &mut path);
+        path.last().copied()
+    }
+
+    /// Collects the ids of the widgets from this pod down to the widget returned by [`Pod::hit_test`].
+    ///
+    /// Returns whether `point` is contained in this pod.
+    pub(crate) fn hit_path(&self, point: Point, path: &mut Vec<Id>) -> bool {
+        if !Rect::from_origin_size(self.state.origin, self.state.size).contains(point) {
+            return false;
+        }
+        path.push(self.state.id);
+        let point = point - self.state.origin.to_vec2();
+        for child in self.widget.children().iter().rev() {
+            if child.hit_path(point, path) {
+                break;
+            }
+        }
+        true
+    }
+
+    /// Collects the ids of the pods from this pod down to the pod with `id`.
+    ///
+    /// Returns whether the pod with `id` was found, `path` is left untouched otherwise.
+    pub(crate) fn path_to(&self, id: Id, path: &mut Vec<Id>) -> bool {
+        path.push(self.state.id);
+        if self.state.id == id
+            || self
+                .widget
+                .children()
+                .iter()
+                .any(|child| child.path_to(id, path))
+        {
+            return true;
+        }
+        path.pop();
+        false
+    }
+
+    /// Collects the ids of all widgets which [accept focus](Widget::accepts_focus) in tree order.
+    fn focus_chain(&self, chain: &mut Vec<Id>) {
+        if self.widget.accepts_focus() {
+            chain.push(self.state.id);
+        }
+        for child in self.widget.children() {
+            child.focus_chain(chain);
+        }
+    }
+
+    /// Moves the focus to the widget with the id `focus` (or removes it with `None`) and sends
+    /// [`LifeCycle::FocusChanged`] to the widgets which lost and gained the focus.
+    ///
+    /// This should only be called on the root pod.
+    pub(crate) fn change_focus(&mut self, cx: &mut LifeCycleCx, focus: Option<Id>) {
+        if cx.cx_state.focus.focused == focus {
+            return;
+        }
+        let mut path = Vec::new();
+        let focus = focus.filter(|id| self.path_to(*id, &mut path));
+        let old_path = std::mem::replace(&mut cx.cx_state.focus.path, path);
+        cx.cx_state.focus.focused = focus;
+
+        cx.cx_state.focus.route = old_path;
+        self.lifecycle(cx, &LifeCycle::FocusChanged(false));
+        cx.cx_state.focus.route = cx.cx_state.focus.path.clone();
+        self.lifecycle(cx, &LifeCycle::FocusChanged(true));
+        cx.cx_state.focus.route.clear();
+    }
+
+    /// Moves the focus to the next (or previous if `forward` is `false`) widget that accepts focus.
+    ///
+    /// This should only be called on the root pod.
+    pub(crate) fn focus_next(&mut self, cx: &mut LifeCycleCx, forward: bool) {
+        let mut chain = Vec::new();
+        self.focus_chain(&mut chain);
+        if chain.is_empty() {
+            return;
+        }
+        let current = cx
+            .cx_state
+            .focus
+            .focused
+            .and_then(|focused| chain.iter().position(|id| *id == focused));
+        let next = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => chain.len() - 1,
+            (Some(i), true) => (i + 1) % chain.len(),
+            (Some(i), false) => (i + chain.len() - 1) % chain.len(),
+        };
+        self.change_focus(cx, Some(chain[next]));
+    }
+
+    /// Applies a pending focus request of a widget (see [`EventCx::request_focus`]) and makes sure,
+    /// that the focus path is still valid after the widget tree has changed.
+    ///
+    /// This should only be called on the root pod.
+    pub(crate) fn update_focus(&mut self, cx: &mut LifeCycleCx) {
+        if let Some(focus) = cx.cx_state.focus.request.take() {
+            self.change_focus(cx, focus);
+        } else if let Some(focused) = cx.cx_state.focus.focused {
+            let focus = &mut *cx.cx_state.focus;
+            focus.path.clear();
+            if !self.path_to(focused, &mut focus.path) {
+                // The focused widget was removed
+                focus.focused = None;
+            }
+        }
+    }
+
+    /// Returns whether a call to [`layout`](Pod::layout) is pending for the wrapped widget.
+    pub fn layout_requested(&self) -> bool {
+        self.state.flags.contains(PodFlags::REQUEST_LAYOUT)
+    }
+
+    /// Returns whether a call to [`paint`](Pod::paint) is pending for the wrapped widget.
+    pub fn paint_requested(&self) -> bool {
+        self.state.flags.contains(PodFlags::REQUEST_PAINT)
+    }
+
     pub fn size(&self) -> Size {
         self.state.size
     }
@@ -409,7 +826,7 @@ impl Pod {
         let inner_cx = &mut PaintCx {
             cx_state: cx.cx_state,
             widget_state: &mut self.state,
-            terminal: cx.terminal,
+            buffer: cx.buffer,
             override_style: cx.override_style,
         };
         self.widget.paint(inner_cx);
@@ -422,15 +839,11 @@ impl Pod {
         widget: &mut dyn AnyWidget,
         widget_state: &mut WidgetState,
         cx_state: &mut CxState,
-        mouse_pos: Option<Point>,
     ) -> bool {
-        let rect = Rect::from_origin_size(widget_state.origin, widget_state.size);
         let had_hot = widget_state.flags.contains(PodFlags::IS_HOT);
-
-        let is_hot = match mouse_pos {
-            Some(pos) => rect.contains(pos),
-            None => false,
-        };
+        // Only the widgets on the hot path are hot, so widgets that are covered by other widgets
+        // (e.g. by an overlay) don't become hot, even though the mouse is within their rect.
+        let is_hot = cx_state.hot_path.contains(&widget_state.id);
         widget_state.flags.set(PodFlags::IS_HOT, is_hot);
         if had_hot != is_hot {
             let hot_changed_event = LifeCycle::HotChanged(is_hot);
@@ -449,6 +862,12 @@ impl Pod {
     ///
     /// This method calls [event](crate::widget::Widget::event) on the wrapped Widget if this event
     /// is relevant to this widget.
+    ///
+    /// Mouse events are only dispatched along the path from the root to the topmost widget under
+    /// the mouse (see [`Pod::hit_test`]). Additionally widgets which are [active](Pod::is_active)
+    /// (or have an active descendant) receive all mouse events, so that e.g. a drag can continue
+    /// outside of the widget, and widgets which have just lost their hot state receive the event
+    /// that moved the mouse away from them.
     pub fn event(&mut self, cx: &mut EventCx, event: &Event) {
         if cx.is_handled {
             return;
@@ -457,23 +876,9 @@ impl Pod {
         let had_active = self.state.flags.contains(PodFlags::HAS_ACTIVE);
         let recurse = match event {
             Event::Mouse(mouse_event) => {
-                let hot_changed = Pod::set_hot_state(
-                    &mut self.widget,
-                    &mut self.state,
-                    cx.cx_state,
-                    Some(Point {
-                        x: mouse_event.column as f64,
-                        y: mouse_event.row as f64,
-                    }),
-                );
-                if had_active
-                    || self.state.flags.contains(PodFlags::IS_HOT)
-                    || (hot_changed
-                        && matches!(
-                            mouse_event.kind,
-                            MouseEventKind::Moved | MouseEventKind::Drag(_)
-                        ))
-                {
+                let hot_changed =
+                    Pod::set_hot_state(&mut self.widget, &mut self.state, cx.cx_state);
+                if had_active || self.state.flags.contains(PodFlags::IS_HOT) || hot_changed {
                     let mut mouse_event = *mouse_event;
                     let (x, y) = (
                         self.state.origin.x.round() as i16,
@@ -494,6 +899,8 @@ impl Pod {
                     .request(PodFlags::REQUEST_PAINT | PodFlags::REQUEST_LAYOUT);
                 true
             }
+            // Key events are only sent along the path to the focused widget
+            Event::Key(_) | Event::Paste(_) => cx.cx_state.focus.path.contains(&self.state.id),
             Event::FocusLost => {
                 // right now a FocusLost event will disable any ongoing pointer events,
                 // since we can't really track if the state has changed in the meantime.
@@ -504,6 +911,7 @@ impl Pod {
                     .set(PodFlags::IS_HOT | PodFlags::IS_ACTIVE, false);
                 true
             }
+            Event::FocusGained => true,
             _ => return,
         };
         if recurse {
@@ -511,10 +919,7 @@ impl Pod {
             // only set flags.
             // This needs to happen before the `event` call, as that will also set our `HAS_ACTIVE`
             // flag if any of our children were active
-            self.state.flags.set(
-                PodFlags::HAS_ACTIVE,
-                self.state.flags.contains(PodFlags::IS_ACTIVE),
-            );
+            self.state.flags.remove(PodFlags::HAS_ACTIVE);
             let mut inner_cx = EventCx {
                 cx_state: cx.cx_state,
                 widget_state: &mut self.state,
@@ -523,6 +928,10 @@ impl Pod {
             self.widget
                 .event(&mut inner_cx, modified_event.as_ref().unwrap_or(event));
             cx.is_handled |= inner_cx.is_handled;
+            // The widget itself may have become active or inactive while handling the event
+            if self.state.flags.contains(PodFlags::IS_ACTIVE) {
+                self.state.flags.insert(PodFlags::HAS_ACTIVE);
+            }
 
             cx.widget_state.merge_up(&mut self.state);
         }
@@ -536,18 +945,14 @@ impl Pod {
         let mut modified_event = None;
         let recurse = match event {
             LifeCycle::HotChanged(_) => false,
+            LifeCycle::FocusChanged(_) => cx.cx_state.focus.route.contains(&self.state.id),
             LifeCycle::ViewContextChanged(view) => {
                 self.state.parent_window_origin = view.window_origin;
 
-                Pod::set_hot_state(
-                    &mut self.widget,
-                    &mut self.state,
-                    cx.cx_state,
-                    view.mouse_position,
-                );
-                modified_event = Some(LifeCycle::ViewContextChanged(
-                    view.translate_to(self.state.origin),
-                ));
+                Pod::set_hot_state(&mut self.widget, &mut self.state, cx.cx_state);
+                let view = view.translate_to(self.state.origin, self.state.size);
+                self.state.clip = view.clip;
+                modified_event = Some(LifeCycle::ViewContextChanged(view));
                 self.state.flags.remove(PodFlags::VIEW_CONTEXT_CHANGED);
                 true
             }
@@ -621,6 +1026,25 @@ pub trait Widget: 'static {
     fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle);
 
     fn event(&mut self, cx: &mut EventCx, event: &Event);
+
+    /// Returns the children of this widget in paint order.
+    ///
+    /// Container widgets should return all the [`Pod`]s they contain, so that operations that
+    /// walk the widget tree (such as [`Pod::hit_test`]) can reach them.
+    ///
+    /// The default implementation returns no children and is only correct for leaf widgets.
+    /// A container that doesn't override it silently breaks its subtree: its children are never
+    /// hot, don't receive mouse events (hit testing stops at the container) and are skipped by the
+    /// focus traversal. Children may be left out on purpose, e.g. to make content inert while an
+    /// overlay is shown.
+    fn children(&self) -> &[Pod] {
+        &[]
+    }
+
+    /// Whether this widget can receive the keyboard focus, e.g. by using `Tab`/`BackTab`.
+    fn accepts_focus(&self) -> bool {
+        false
+    }
 }
 
 pub trait AnyWidget: Widget {
@@ -661,4 +1085,430 @@ impl Widget for Box<dyn AnyWidget> {
     fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
         self.deref_mut().lifecycle(cx, event)
     }
+
+    fn children(&self) -> &[Pod] {
+        self.deref().children()
+    }
+
+    fn accepts_focus(&self) -> bool {
+        self.deref().accepts_focus()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{Key, KeyEvent, Modifiers, MouseButton, MouseKind, RawMouseEvent};
+
+    struct Leaf;
+
+    impl Widget for Leaf {
+        fn paint(&mut self, _cx: &mut PaintCx) {}
+
+        fn layout(&mut self, _cx: &mut LayoutCx, _bc: &BoxConstraints) -> Size {
+            Size::ZERO
+        }
+
+        fn lifecycle(&mut self, _cx: &mut LifeCycleCx, _event: &LifeCycle) {}
+
+        fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
+    }
+
+    /// Children are painted in order, so later children are on top of earlier ones
+    struct Stack(Vec<Pod>);
+
+    impl Widget for Stack {
+        fn paint(&mut self, _cx: &mut PaintCx) {}
+
+        fn layout(&mut self, _cx: &mut LayoutCx, _bc: &BoxConstraints) -> Size {
+            Size::ZERO
+        }
+
+        fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+            for child in &mut self.0 {
+                child.lifecycle(cx, event);
+            }
+        }
+
+        fn event(&mut self, cx: &mut EventCx, event: &Event) {
+            for child in self.0.iter_mut().rev() {
+                child.event(cx, event);
+            }
+        }
+
+        fn children(&self) -> &[Pod] {
+            &self.0
+        }
+    }
+
+    /// Records the events it receives, it's active from a mouse button press until its release
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<Event>,
+        hot: bool,
+    }
+
+    impl Widget for Recorder {
+        fn paint(&mut self, _cx: &mut PaintCx) {}
+
+        fn layout(&mut self, _cx: &mut LayoutCx, _bc: &BoxConstraints) -> Size {
+            Size::ZERO
+        }
+
+        fn lifecycle(&mut self, _cx: &mut LifeCycleCx, event: &LifeCycle) {
+            if let LifeCycle::HotChanged(hot) = event {
+                self.hot = *hot;
+            }
+        }
+
+        fn event(&mut self, cx: &mut EventCx, event: &Event) {
+            if let Event::Mouse(mouse) = event {
+                match mouse.kind {
+                    MouseKind::Down(_) => cx.set_active(true),
+                    MouseKind::Up(_) => cx.set_active(false),
+                    _ => (),
+                }
+            }
+            self.events.push(event.clone());
+        }
+    }
+
+    /// A pod at `rect` in the coordinate space of its parent, like after the layout pass
+    fn pod(widget: impl Widget, rect: Rect) -> Pod {
+        let mut pod = Pod::new(widget);
+        pod.state.origin = rect.origin();
+        pod.state.size = rect.size();
+        pod
+    }
+
+    fn child(pod: &Pod, index: usize) -> &Pod {
+        &pod.widget.children()[index]
+    }
+
+    #[test]
+    fn the_topmost_of_overlapping_siblings_is_hit() {
+        let root = pod(
+            Stack(vec![
+                pod(Leaf, Rect::new(0.0, 0.0, 6.0, 6.0)),
+                pod(Leaf, Rect::new(4.0, 4.0, 10.0, 10.0)),
+            ]),
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+        );
+        let (bottom, top) = (child(&root, 0).id(), child(&root, 1).id());
+        assert_eq!(root.hit_test(Point::new(5.0, 5.0)), Some(top));
+        assert_eq!(root.hit_test(Point::new(1.0, 1.0)), Some(bottom));
+        assert_eq!(root.hit_test(Point::new(8.0, 1.0)), Some(root.id()));
+        assert_eq!(root.hit_test(Point::new(10.0, 1.0)), None);
+
+        let mut path = Vec::new();
+        assert!(root.hit_path(Point::new(5.0, 5.0), &mut path));
+        assert_eq!(path, vec![root.id(), top]);
+    }
+
+    #[test]
+    fn children_are_hit_in_the_coordinate_space_of_their_parent() {
+        let root = pod(
+            Stack(vec![pod(
+                Stack(vec![pod(Leaf, Rect::new(1.0, 1.0, 3.0, 3.0))]),
+                Rect::new(2.0, 2.0, 8.0, 8.0),
+            )]),
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+        );
+        let nested = child(&root, 0);
+        let leaf = child(nested, 0).id();
+        assert_eq!(root.hit_test(Point::new(3.5, 3.5)), Some(leaf));
+        assert_eq!(root.hit_test(Point::new(2.5, 2.5)), Some(nested.id()));
+        assert_eq!(root.hit_test(Point::new(1.5, 1.5)), Some(root.id()));
+    }
+
+    #[test]
+    fn clipped_parts_of_children_are_not_hit() {
+        let root = pod(
+            Stack(vec![pod(
+                Stack(vec![pod(Leaf, Rect::new(3.0, 0.0, 12.0, 5.0))]),
+                Rect::new(0.0, 0.0, 6.0, 10.0),
+            )]),
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+        );
+        let nested = child(&root, 0);
+        let leaf = child(nested, 0).id();
+        assert_eq!(root.hit_test(Point::new(4.0, 1.0)), Some(leaf));
+        // outside of the bounds of the parent of the leaf
+        assert_eq!(root.hit_test(Point::new(8.0, 1.0)), Some(root.id()));
+        // outside of the bounds of the root
+        assert_eq!(root.hit_test(Point::new(11.0, 1.0)), None);
+
+        let mut path = Vec::new();
+        assert!(!root.hit_path(Point::new(11.0, 1.0), &mut path));
+        assert!(path.is_empty());
+    }
+
+    /// Two recorders side by side, the left one at x 0..5, the right one at x 5..10
+    fn siblings() -> Pod {
+        pod(
+            Stack(vec![
+                pod(Recorder::default(), Rect::new(0.0, 0.0, 5.0, 1.0)),
+                pod(Recorder::default(), Rect::new(5.0, 0.0, 10.0, 1.0)),
+            ]),
+            Rect::new(0.0, 0.0, 10.0, 1.0),
+        )
+    }
+
+    fn recorder(root: &Pod, index: usize) -> &Recorder {
+        child(root, index).downcast_ref().unwrap()
+    }
+
+    /// The mouse events `recorder` has received, with the columns relative to it
+    fn mouse_events(recorder: &Recorder) -> Vec<(MouseKind, i16)> {
+        recorder
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Mouse(mouse) => Some((mouse.kind, mouse.column)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Dispatches `event` to `root` like the app does
+    fn dispatch(root: &mut Pod, focus: &mut FocusState, event: Event) {
+        let mut messages = Vec::new();
+        let cx_state = &mut CxState::new(&mut messages, focus, Duration::ZERO);
+        if let Event::Mouse(mouse) = &event {
+            let pos = Point::new(mouse.column as f64, mouse.row as f64);
+            cx_state.update_hot_path(root, Some(pos));
+        }
+        let mut root_state = WidgetState::new();
+        let mut cx = EventCx {
+            cx_state,
+            widget_state: &mut root_state,
+            is_handled: false,
+        };
+        root.event(&mut cx, &event);
+    }
+
+    fn mouse(kind: MouseKind, column: i16) -> Event {
+        Event::Mouse(RawMouseEvent {
+            kind,
+            column,
+            row: 0,
+            modifiers: Modifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn a_drag_keeps_reaching_the_active_widget_outside_of_it() {
+        let mut root = siblings();
+        let focus = &mut FocusState::default();
+        let left = MouseButton::Left;
+        dispatch(&mut root, focus, mouse(MouseKind::Down(left), 2));
+        dispatch(&mut root, focus, mouse(MouseKind::Drag(left), 7));
+        dispatch(&mut root, focus, mouse(MouseKind::Up(left), 8));
+        // not active anymore
+        dispatch(&mut root, focus, mouse(MouseKind::Moved, 9));
+        assert_eq!(
+            mouse_events(recorder(&root, 0)),
+            vec![
+                (MouseKind::Down(left), 2),
+                (MouseKind::Drag(left), 7),
+                (MouseKind::Up(left), 8),
+            ]
+        );
+        assert!(!child(&root, 0).state.flags.contains(PodFlags::IS_ACTIVE));
+    }
+
+    #[test]
+    fn hover_is_lost_when_the_mouse_moves_to_a_sibling() {
+        let mut root = siblings();
+        let focus = &mut FocusState::default();
+        dispatch(&mut root, focus, mouse(MouseKind::Moved, 2));
+        assert!(recorder(&root, 0).hot);
+        assert!(!recorder(&root, 1).hot);
+
+        dispatch(&mut root, focus, mouse(MouseKind::Moved, 6));
+        assert!(!recorder(&root, 0).hot);
+        assert!(recorder(&root, 1).hot);
+        // the left one receives the move which made it lose its hot state, but no further ones
+        dispatch(&mut root, focus, mouse(MouseKind::Moved, 7));
+        assert_eq!(
+            mouse_events(recorder(&root, 0)),
+            vec![(MouseKind::Moved, 2), (MouseKind::Moved, 6)]
+        );
+        assert_eq!(
+            mouse_events(recorder(&root, 1)),
+            vec![(MouseKind::Moved, 1), (MouseKind::Moved, 2)]
+        );
+    }
+
+    #[test]
+    fn key_events_only_reach_the_focus_path() {
+        let mut root = siblings();
+        let focus = &mut FocusState::default();
+        dispatch(&mut root, focus, Event::Key(KeyEvent::from(Key::Enter)));
+        assert!(recorder(&root, 0).events.is_empty());
+        assert!(recorder(&root, 1).events.is_empty());
+
+        focus.focused = Some(child(&root, 1).id());
+        focus.path = vec![root.id(), child(&root, 1).id()];
+        dispatch(&mut root, focus, Event::Key(KeyEvent::from(Key::Enter)));
+        dispatch(&mut root, focus, Event::Paste("text".into()));
+        assert!(recorder(&root, 0).events.is_empty());
+        assert_eq!(recorder(&root, 1).events.len(), 2);
+    }
+
+    /// Records the focus changes it receives and requests the focus when it's clicked
+    #[derive(Default)]
+    struct Focusable {
+        focus_changes: Vec<bool>,
+    }
+
+    impl Widget for Focusable {
+        fn paint(&mut self, _cx: &mut PaintCx) {}
+
+        fn layout(&mut self, _cx: &mut LayoutCx, _bc: &BoxConstraints) -> Size {
+            Size::ZERO
+        }
+
+        fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+            if let LifeCycle::FocusChanged(focused) = event {
+                assert_eq!(cx.is_focused(), *focused);
+                self.focus_changes.push(*focused);
+            }
+        }
+
+        fn event(&mut self, cx: &mut EventCx, event: &Event) {
+            if let Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Down(_),
+                ..
+            }) = event
+            {
+                cx.request_focus();
+            }
+        }
+
+        fn accepts_focus(&self) -> bool {
+            true
+        }
+    }
+
+    /// A focusable widget at x 0..4, a leaf at x 4..5 and a nested focusable widget at x 5..10
+    fn focus_tree() -> Pod {
+        pod(
+            Stack(vec![
+                pod(Focusable::default(), Rect::new(0.0, 0.0, 4.0, 1.0)),
+                pod(Leaf, Rect::new(4.0, 0.0, 5.0, 1.0)),
+                pod(
+                    Stack(vec![pod(
+                        Focusable::default(),
+                        Rect::new(0.0, 0.0, 5.0, 1.0),
+                    )]),
+                    Rect::new(5.0, 0.0, 10.0, 1.0),
+                ),
+            ]),
+            Rect::new(0.0, 0.0, 10.0, 1.0),
+        )
+    }
+
+    fn first_focusable(root: &Pod) -> &Pod {
+        child(root, 0)
+    }
+
+    fn nested_focusable(root: &Pod) -> &Pod {
+        child(child(root, 2), 0)
+    }
+
+    fn focus_changes(pod: &Pod) -> &[bool] {
+        &pod.downcast_ref::<Focusable>().unwrap().focus_changes
+    }
+
+    /// Runs `f` with a lifecycle context for the root pod like the app does
+    fn with_lifecycle_cx(focus: &mut FocusState, f: impl FnOnce(&mut LifeCycleCx)) {
+        let mut messages = Vec::new();
+        let cx_state = &mut CxState::new(&mut messages, focus, Duration::ZERO);
+        let mut root_state = WidgetState::new();
+        f(&mut LifeCycleCx {
+            cx_state,
+            widget_state: &mut root_state,
+        });
+    }
+
+    fn focus_next(root: &mut Pod, focus: &mut FocusState, forward: bool) {
+        with_lifecycle_cx(focus, |cx| root.focus_next(cx, forward));
+    }
+
+    #[test]
+    fn tab_moves_the_focus_forward_and_wraps_around() {
+        let mut root = focus_tree();
+        let (first, nested) = (first_focusable(&root).id(), nested_focusable(&root).id());
+        let focus = &mut FocusState::default();
+
+        focus_next(&mut root, focus, true);
+        assert_eq!(focus.focused, Some(first));
+        assert_eq!(focus.path, vec![root.id(), first]);
+
+        focus_next(&mut root, focus, true);
+        assert_eq!(focus.focused, Some(nested));
+        assert_eq!(focus.path, vec![root.id(), child(&root, 2).id(), nested]);
+
+        focus_next(&mut root, focus, true);
+        assert_eq!(focus.focused, Some(first));
+    }
+
+    #[test]
+    fn back_tab_moves_the_focus_backward_and_wraps_around() {
+        let mut root = focus_tree();
+        let (first, nested) = (first_focusable(&root).id(), nested_focusable(&root).id());
+        let focus = &mut FocusState::default();
+
+        focus_next(&mut root, focus, false);
+        assert_eq!(focus.focused, Some(nested));
+        focus_next(&mut root, focus, false);
+        assert_eq!(focus.focused, Some(first));
+        focus_next(&mut root, focus, false);
+        assert_eq!(focus.focused, Some(nested));
+    }
+
+    #[test]
+    fn the_widgets_which_lose_and_gain_the_focus_are_notified() {
+        let mut root = focus_tree();
+        let focus = &mut FocusState::default();
+
+        focus_next(&mut root, focus, true);
+        assert_eq!(focus_changes(first_focusable(&root)), [true]);
+        assert!(focus_changes(nested_focusable(&root)).is_empty());
+
+        focus_next(&mut root, focus, true);
+        assert_eq!(focus_changes(first_focusable(&root)), [true, false]);
+        assert_eq!(focus_changes(nested_focusable(&root)), [true]);
+    }
+
+    #[test]
+    fn a_requested_focus_is_applied_after_the_event() {
+        let mut root = focus_tree();
+        let nested = nested_focusable(&root).id();
+        let focus = &mut FocusState::default();
+
+        dispatch(
+            &mut root,
+            focus,
+            mouse(MouseKind::Down(MouseButton::Left), 7),
+        );
+        assert_eq!(focus.focused, None);
+        with_lifecycle_cx(focus, |cx| root.update_focus(cx));
+        assert_eq!(focus.focused, Some(nested));
+        assert_eq!(focus_changes(nested_focusable(&root)), [true]);
+    }
+
+    #[test]
+    fn the_focus_is_removed_with_the_focused_widget() {
+        let mut root = focus_tree();
+        let focus = &mut FocusState::default();
+        focus_next(&mut root, focus, false);
+        assert!(focus.focused.is_some());
+
+        root.downcast_mut::<Stack>().unwrap().0.pop();
+        with_lifecycle_cx(focus, |cx| root.update_focus(cx));
+        assert_eq!(focus.focused, None);
+        assert!(focus.path.is_empty());
+    }
 }
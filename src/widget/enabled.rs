@@ -0,0 +1,147 @@
+use ratatui::style::Style;
+
+use crate::geometry::{to_ratatui_rect, Size};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, Pod, Widget,
+};
+
+/// Disables its content, which then doesn't receive mouse, key and paste events and is painted dimmed.
+pub struct Enabled {
+    content: Pod,
+    enabled: bool,
+    disabled_style: Style,
+}
+
+impl Enabled {
+    pub(crate) fn new(content: impl Widget, enabled: bool, disabled_style: Style) -> Self {
+        Enabled {
+            content: Pod::new(content),
+            enabled,
+            disabled_style,
+        }
+    }
+
+    pub(crate) fn content(&mut self) -> &mut Pod {
+        &mut self.content
+    }
+
+    pub(crate) fn set_enabled(&mut self, enabled: bool) -> ChangeFlags {
+        if self.enabled != enabled {
+            self.enabled = enabled;
+            // The content is hidden from hit testing and focus traversal while it's disabled
+            ChangeFlags::PAINT | ChangeFlags::tree_structure()
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_disabled_style(&mut self, style: Style) -> ChangeFlags {
+        if self.disabled_style != style {
+            self.disabled_style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+impl Widget for Enabled {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        if self.enabled {
+            self.content.paint(cx);
+            return;
+        }
+        cx.override_style = cx.override_style.patch(self.disabled_style);
+        self.content.paint(cx);
+        // Not every widget passes the override style on to its content (e.g. a border doesn't),
+        // so the whole area is dimmed afterwards
+        let rect = to_ratatui_rect(cx.rect());
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        buf.set_style(area, self.disabled_style);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        self.content.layout(cx, bc)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        match event {
+            Event::Mouse(_) | Event::Key(_) | Event::Paste(_) if !self.enabled => (),
+            _ => self.content.event(cx, event),
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        self.content.lifecycle(cx, event)
+    }
+
+    fn children(&self) -> &[Pod] {
+        // A disabled content can't be hovered or focused
+        if self.enabled {
+            std::slice::from_ref(&self.content)
+        } else {
+            &[]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::{test_key, test_mouse},
+        text_input, v_stack,
+        widget::{Key, MouseButton, MouseKind},
+        App, ViewExt,
+    };
+
+    struct State {
+        clicks: i32,
+        text: String,
+        enabled: bool,
+    }
+
+    #[tokio::test]
+    async fn a_disabled_subtree_within_an_enabled_one_gets_no_clicks_and_no_focus() {
+        let state = State {
+            clicks: 0,
+            text: String::new(),
+            enabled: false,
+        };
+        let mut app = App::new(state, |state: &mut State| {
+            v_stack((
+                format!("{} [{}]", state.clicks, state.text),
+                v_stack((
+                    "click".on_click(|state: &mut State| state.clicks += 1),
+                    text_input(state.text.clone(), |state: &mut State, text: String| {
+                        state.text = text;
+                    }),
+                ))
+                .enabled(state.enabled),
+                "toggle".on_click(|state: &mut State| state.enabled = !state.enabled),
+            ))
+            .enabled(true)
+        })
+        .await;
+        let click = |row| {
+            [
+                test_mouse(MouseKind::Down(MouseButton::Left), 0, row),
+                test_mouse(MouseKind::Up(MouseButton::Left), 0, row),
+            ]
+        };
+        app.render_once().await.unwrap();
+        app.dispatch(click(1)).await;
+        app.dispatch([test_key(Key::Tab)]).await;
+        app.dispatch([test_key(Key::Char('a'))]).await;
+        assert_eq!(app.screen_line(0), "0 []");
+
+        // enables the inner subtree
+        app.dispatch(click(3)).await;
+        app.dispatch(click(1)).await;
+        app.dispatch([test_key(Key::Tab)]).await;
+        app.dispatch([test_key(Key::Char('a'))]).await;
+        assert_eq!(app.screen_line(0), "1 [a]");
+    }
+}
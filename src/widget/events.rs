@@ -1,13 +1,13 @@
 use bitflags::bitflags;
 use std::marker::PhantomData;
 
-use crate::geometry::{Point, Size};
-use crossterm::event::{MouseButton, MouseEventKind};
+use crate::geometry::{Point, Rect, Size};
 use ratatui::style::Style;
 
 use super::{
     core::{IdPath, PaintCx},
-    EventCx, LayoutCx, Message, Pod, Widget,
+    ChangeFlags, EventCx, Key, KeyEvent, LayoutCx, Message, Modifiers, MouseButton, MouseKind, Pod,
+    Widget,
 };
 
 #[derive(Debug, Clone)]
@@ -24,12 +24,21 @@ pub enum Event {
         height: u16,
     },
     Mouse(RawMouseEvent),
-    Key(crossterm::event::KeyEvent),
+    Key(KeyEvent),
+    /// Text pasted into the terminal at once, it's sent to the focused widget like key events.
+    Paste(String),
 }
 
 #[derive(Debug)]
 pub enum LifeCycle {
     HotChanged(bool),
+    /// Sent when the keyboard focus changed, to the widget which lost (`false`) or gained (`true`)
+    /// the focus and to all of its ancestors.
+    ///
+    /// Use [`LifeCycleCx::is_focused`](super::LifeCycleCx::is_focused) to check whether the
+    /// widget itself is focused now, and [`LifeCycleCx::has_focus`](super::LifeCycleCx::has_focus)
+    /// whether it or one of its descendants is.
+    FocusChanged(bool),
     ViewContextChanged(ViewContext),
     TreeUpdate,
     Animate,
@@ -38,42 +47,40 @@ pub enum LifeCycle {
 #[derive(Debug)]
 pub struct ViewContext {
     pub window_origin: Point,
-    // pub clip: Rect,
+    /// The region in which the widget is visible, in its local coordinate space.
+    ///
+    /// Nested clips intersect, a widget is only visible within the window and within the bounds
+    /// of all of its ancestors. It's empty if the widget is completely outside of it (e.g. scrolled off).
+    pub clip: Rect,
     pub mouse_position: Option<Point>,
 }
 
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct RawMouseEvent {
-    pub kind: MouseEventKind,
+    pub kind: MouseKind,
     pub column: i16,
     pub row: i16,
-    pub modifiers: crossterm::event::KeyModifiers,
+    pub modifiers: Modifiers,
 }
 
 impl From<crossterm::event::MouseEvent> for RawMouseEvent {
     fn from(event: crossterm::event::MouseEvent) -> Self {
         RawMouseEvent {
-            kind: event.kind,
+            kind: event.kind.into(),
             column: event.column as i16,
             row: event.row as i16,
-            modifiers: event.modifiers,
+            modifiers: event.modifiers.into(),
         }
     }
 }
 
 impl ViewContext {
-    pub fn translate_to(&self, new_origin: Point) -> ViewContext {
-        // TODO I think the clip calculation is buggy in xilem (width/height?)
-        // let clip = Rect {
-        //     x: self.clip.x - new_origin.x,
-        //     y: self.clip.y - new_origin.y,
-        //     width: self.clip.width,
-        //     height: self.clip.height,
-        // };
+    /// The context of a child at `new_origin` with `size`, its clip is limited to its bounds.
+    pub fn translate_to(&self, new_origin: Point, size: Size) -> ViewContext {
         let translate = new_origin.to_vec2();
         ViewContext {
             window_origin: self.window_origin + translate,
-            // clip,
+            clip: (self.clip - translate).intersect(Rect::from_origin_size(Point::ORIGIN, size)),
             mouse_position: self.mouse_position.map(|p| p - translate),
         }
     }
@@ -86,10 +93,10 @@ impl ViewContext {
 pub struct MouseEvent {
     pub over_element: bool,
     pub is_active: bool,
-    pub kind: MouseEventKind,
+    pub kind: MouseKind,
     pub column: i16,
     pub row: i16,
-    pub modifiers: crossterm::event::KeyModifiers,
+    pub modifiers: Modifiers,
 }
 
 impl MouseEvent {
@@ -115,20 +122,39 @@ bitflags! {
     }
 }
 
+impl CatchMouseButton {
+    /// Whether `button` is one of these buttons.
+    pub(crate) fn catches(self, button: MouseButton) -> bool {
+        self.intersects(match button {
+            MouseButton::Left => CatchMouseButton::LEFT,
+            MouseButton::Right => CatchMouseButton::RIGHT,
+            MouseButton::Middle => CatchMouseButton::MIDDLE,
+        })
+    }
+}
+
 pub struct OnMouse<E> {
     pub(crate) element: Pod,
     id_path: IdPath,
     catch_event: CatchMouseButton,
+    /// Whether mouse events over the element are marked as handled
+    pub(crate) stop_propagation: bool,
     phantom: PhantomData<E>,
 }
 
 impl<E: Widget> OnMouse<E> {
-    pub fn new(element: E, id_path: &IdPath, catch_event: CatchMouseButton) -> Self {
+    pub fn new(
+        element: E,
+        id_path: &IdPath,
+        catch_event: CatchMouseButton,
+        stop_propagation: bool,
+    ) -> Self {
         OnMouse {
             element: Pod::new(element),
             id_path: id_path.clone(),
             phantom: PhantomData,
             catch_event,
+            stop_propagation,
         }
     }
 }
@@ -145,18 +171,20 @@ impl<E: Widget> Widget for OnMouse<E> {
     fn event(&mut self, cx: &mut EventCx, event: &Event) {
         self.element.event(cx, event);
 
+        // A descendant has stopped the propagation of the event
+        if cx.is_handled() {
+            cx.set_active(false);
+            return;
+        }
+
         match event {
             Event::Mouse(
                 event @ RawMouseEvent {
-                    kind: MouseEventKind::Down(button),
+                    kind: MouseKind::Down(button),
                     ..
                 },
             ) => {
-                let catch_event = matches!(button, MouseButton::Left if self.catch_event.intersects(CatchMouseButton::LEFT))
-                    || matches!(button, MouseButton::Right if self.catch_event.intersects(CatchMouseButton::RIGHT))
-                    || matches!(button, MouseButton::Middle if self.catch_event.intersects(CatchMouseButton::MIDDLE));
-
-                if catch_event && cx.is_hot() {
+                if self.catch_event.catches(*button) && cx.is_hot() {
                     cx.set_active(true);
                 }
 
@@ -165,11 +193,12 @@ impl<E: Widget> Widget for OnMouse<E> {
                         self.id_path.clone(),
                         MouseEvent::new(*event, true, cx.is_active()),
                     ));
+                    cx.set_handled(self.stop_propagation);
                 }
             }
             Event::Mouse(event @ RawMouseEvent { kind, .. }) => {
                 let is_active = cx.is_active();
-                if matches!(kind, MouseEventKind::Up(_)) {
+                if matches!(kind, MouseKind::Up(_)) {
                     cx.set_active(false);
                 }
                 if cx.is_hot() {
@@ -177,6 +206,7 @@ impl<E: Widget> Widget for OnMouse<E> {
                         self.id_path.clone(),
                         MouseEvent::new(*event, true, cx.is_active()),
                     ));
+                    cx.set_handled(self.stop_propagation);
                 // if it's not hot, and not active the event will likely not be propagated until here, but double checking doesn't hurt (much...)
                 } else if is_active {
                     cx.add_message(Message::new(
@@ -197,26 +227,82 @@ impl<E: Widget> Widget for OnMouse<E> {
     fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &LifeCycle) {
         self.element.lifecycle(cx, event);
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
 }
 
+/// Sends a message when its element is clicked, or activated with `Enter` or `Space` while it's focused,
+/// if it's [focusable](OnClick::set_focusable).
+///
+/// The message is a [`Click`], an activation via the keyboard is sent as a left click in the
+/// middle of the element.
 pub struct OnClick<E> {
     pub(crate) element: Pod,
     id_path: IdPath,
+    /// The mouse buttons which click the element
+    pub(crate) buttons: CatchMouseButton,
+    /// Whether clicks on the element are marked as handled
+    pub(crate) stop_propagation: bool,
+    /// Whether the element is a stop of the focus traversal and can be activated via the keyboard
+    focusable: bool,
+    /// Patched onto the style of the element while it's focused
+    focus_style: Style,
     phantom: PhantomData<E>,
 }
 
+/// The message of [`OnClick`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Click {
+    /// Relative to the origin of the element and clamped to its bounds
+    pub position: Point,
+    pub button: MouseButton,
+}
+
 impl<E: Widget> OnClick<E> {
-    pub fn new(element: E, id_path: &IdPath) -> Self {
+    pub fn new(
+        element: E,
+        id_path: &IdPath,
+        buttons: CatchMouseButton,
+        stop_propagation: bool,
+        focus_style: Style,
+    ) -> Self {
         OnClick {
             element: Pod::new(element),
             id_path: id_path.clone(),
+            buttons,
+            stop_propagation,
+            focusable: false,
+            focus_style,
             phantom: PhantomData,
         }
     }
+
+    pub(crate) fn set_focusable(&mut self, focusable: bool) -> ChangeFlags {
+        if self.focusable != focusable {
+            self.focusable = focusable;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_focus_style(&mut self, style: Style) -> ChangeFlags {
+        if self.focus_style != style {
+            self.focus_style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
 }
 
 impl<E: Widget> Widget for OnClick<E> {
     fn paint(&mut self, cx: &mut PaintCx) {
+        if cx.is_focused() {
+            cx.override_style = cx.override_style.patch(self.focus_style);
+        }
         self.element.paint(cx);
     }
 
@@ -227,29 +313,167 @@ impl<E: Widget> Widget for OnClick<E> {
     fn event(&mut self, cx: &mut EventCx, event: &Event) {
         self.element.event(cx, event);
 
-        if let Event::Mouse(RawMouseEvent {
-            kind: MouseEventKind::Down(MouseButton::Left),
-            ..
-        }) = event
-        {
-            cx.set_active(cx.is_hot());
+        // A descendant has stopped the propagation of the event, e.g. a nested clickable element
+        // or a focused text input which typed the space
+        if cx.is_handled() {
+            cx.set_active(false);
+            return;
         }
 
-        // TODO handle other events like e.g. FocusLost
-        if let Event::Mouse(RawMouseEvent {
-            kind: MouseEventKind::Up(MouseButton::Left),
-            ..
-        }) = event
-        {
-            if cx.is_hot() && cx.is_active() {
-                cx.add_message(Message::new(self.id_path.clone(), ()));
+        match event {
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Down(button),
+                ..
+            }) if self.buttons.catches(*button) => {
+                cx.set_active(cx.is_hot());
+                cx.set_handled(self.stop_propagation && cx.is_hot());
             }
-            cx.set_active(false);
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Up(button),
+                column,
+                row,
+                ..
+            }) if self.buttons.catches(*button) => {
+                if cx.is_hot() && cx.is_active() {
+                    // Mouse events are relative to the origin of the widget
+                    let size = cx.rect().size();
+                    let position = Point::new(
+                        (*column as f64).clamp(0.0, (size.width - 1.0).max(0.0)),
+                        (*row as f64).clamp(0.0, (size.height - 1.0).max(0.0)),
+                    );
+                    let click = Click {
+                        position,
+                        button: *button,
+                    };
+                    cx.add_message(Message::new(self.id_path.clone(), click));
+                    cx.set_handled(self.stop_propagation);
+                }
+                cx.set_active(false);
+            }
+            // Key events are also sent to the ancestors of the focused widget,
+            // so only the focused clickable element itself is activated
+            Event::Key(KeyEvent {
+                code: Key::Enter | Key::Char(' '),
+                ..
+            }) if cx.is_focused() => {
+                let size = cx.rect().size();
+                let center = Point::new((size.width / 2.0).floor(), (size.height / 2.0).floor());
+                let click = Click {
+                    position: center,
+                    button: MouseButton::Left,
+                };
+                cx.add_message(Message::new(self.id_path.clone(), click));
+                cx.set_handled(true);
+            }
+            // TODO handle other events like e.g. FocusLost
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &LifeCycle) {
+        if matches!(event, LifeCycle::FocusChanged(_)) {
+            cx.request_paint();
+        }
+        self.element.lifecycle(cx, event);
+    }
+
+    fn accepts_focus(&self) -> bool {
+        self.focusable
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
+}
+
+/// Sends whether the terminal gained (`true`) or lost (`false`) the focus to its view.
+pub struct OnTerminalFocus {
+    pub(crate) element: Pod,
+    id_path: IdPath,
+}
+
+impl OnTerminalFocus {
+    pub fn new<E: Widget>(element: E, id_path: &IdPath) -> Self {
+        OnTerminalFocus {
+            element: Pod::new(element),
+            id_path: id_path.clone(),
+        }
+    }
+}
+
+impl Widget for OnTerminalFocus {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.element.paint(cx);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &super::BoxConstraints) -> Size {
+        self.element.layout(cx, bc)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.element.event(cx, event);
+
+        match event {
+            Event::FocusGained => cx.add_message(Message::new(self.id_path.clone(), true)),
+            Event::FocusLost => cx.add_message(Message::new(self.id_path.clone(), false)),
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &LifeCycle) {
+        self.element.lifecycle(cx, event);
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
+}
+
+/// Sends the origin of its element in window coordinates to its view, whenever it has moved.
+pub struct OnOriginChange {
+    pub(crate) element: Pod,
+    id_path: IdPath,
+    /// The last sent origin
+    origin: Option<Point>,
+}
+
+impl OnOriginChange {
+    pub fn new<E: Widget>(element: E, id_path: &IdPath) -> Self {
+        OnOriginChange {
+            element: Pod::new(element),
+            id_path: id_path.clone(),
+            origin: None,
         }
     }
+}
+
+impl Widget for OnOriginChange {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.element.paint(cx);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &super::BoxConstraints) -> Size {
+        self.element.layout(cx, bc)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.element.event(cx, event);
+    }
 
     fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &LifeCycle) {
         self.element.lifecycle(cx, event);
+        // The view context is also propagated when e.g. only the mouse or a sibling has moved
+        if matches!(event, LifeCycle::ViewContextChanged(_)) {
+            let origin = cx.rect().origin();
+            if self.origin != Some(origin) {
+                self.origin = Some(origin);
+                cx.add_message(Message::new(self.id_path.clone(), origin));
+            }
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
     }
 }
 
@@ -294,6 +518,10 @@ impl Widget for OnHover {
     fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &LifeCycle) {
         self.element.lifecycle(cx, event);
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
 }
 
 pub struct OnHoverLost {
@@ -337,6 +565,10 @@ impl Widget for OnHoverLost {
     fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &LifeCycle) {
         self.element.lifecycle(cx, event);
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
 }
 
 pub struct StyleOnHover {
@@ -381,6 +613,10 @@ impl Widget for StyleOnHover {
     fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &LifeCycle) {
         self.element.lifecycle(cx, event);
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
 }
 
 pub struct StyleOnPressed {
@@ -414,14 +650,14 @@ impl Widget for StyleOnPressed {
 
         match event {
             Event::Mouse(RawMouseEvent {
-                kind: MouseEventKind::Down(MouseButton::Left),
+                kind: MouseKind::Down(MouseButton::Left),
                 ..
             }) => {
                 cx.request_paint();
                 cx.set_active(cx.is_hot());
             }
             Event::Mouse(RawMouseEvent {
-                kind: MouseEventKind::Up(MouseButton::Left) | MouseEventKind::Moved,
+                kind: MouseKind::Up(MouseButton::Left) | MouseKind::Moved,
                 ..
             })
             | Event::FocusLost => {
@@ -435,4 +671,8 @@ impl Widget for StyleOnPressed {
     fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &LifeCycle) {
         self.element.lifecycle(cx, event);
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
 }
@@ -39,18 +39,19 @@ impl<P: AnimatableElement<f64> + 'static> Widget for FillMaxSize<P> {
     }
 
     fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
-        let mut bc = *bc;
+        // The percentage is of the maximum size of the parent, also when the parent requires a
+        // larger minimum size (e.g. a tight constraint), so that nested percentages multiply
+        let (mut min, mut max) = (bc.min(), bc.max());
         if self.fill.contains(Fill::WIDTH) && bc.is_width_bounded() {
-            bc = bc
-                .constrain_width_to(bc.max().width * self.percent_value)
-                .tighten_max_width();
+            min.width = (max.width * self.percent_value).min(max.width);
+            max.width = min.width;
         }
         if self.fill.contains(Fill::HEIGHT) && bc.is_height_bounded() {
-            bc = bc
-                .constrain_height_to(bc.max().height * self.percent_value)
-                .tighten_max_height();
+            min.height = (max.height * self.percent_value).min(max.height);
+            max.height = min.height;
         }
-        self.content.layout(cx, &bc)
+        let size = self.content.layout(cx, &BoxConstraints::new(min, max));
+        bc.constrain(size)
     }
 
     fn event(&mut self, cx: &mut EventCx, event: &Event) {
@@ -67,4 +68,8 @@ impl<P: AnimatableElement<f64> + 'static> Widget for FillMaxSize<P> {
         }
         self.content.lifecycle(cx, event);
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
 }
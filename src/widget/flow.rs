@@ -0,0 +1,146 @@
+use crate::{
+    geometry::{Axis, Size},
+    view::Alignment,
+};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, Pod, Widget,
+};
+
+pub struct Flow {
+    pub children: Vec<Pod>,
+    pub axis: Axis,
+    spacing: f64,
+    line_spacing: f64,
+    last_line_alignment: Alignment,
+}
+
+/// A line of children in a flow layout.
+struct Line {
+    start: usize,
+    end: usize,
+    major: f64,
+    minor: f64,
+}
+
+impl Flow {
+    pub(crate) fn new(
+        children: Vec<Pod>,
+        axis: Axis,
+        spacing: f64,
+        line_spacing: f64,
+        last_line_alignment: Alignment,
+    ) -> Self {
+        Flow {
+            children,
+            axis,
+            spacing,
+            line_spacing,
+            last_line_alignment,
+        }
+    }
+
+    pub(crate) fn set_axis(&mut self, axis: Axis) -> ChangeFlags {
+        if self.axis != axis {
+            self.axis = axis;
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_spacing(&mut self, spacing: f64, line_spacing: f64) -> ChangeFlags {
+        if self.spacing != spacing || self.line_spacing != line_spacing {
+            self.spacing = spacing;
+            self.line_spacing = line_spacing;
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_last_line_alignment(&mut self, alignment: Alignment) -> ChangeFlags {
+        if self.last_line_alignment != alignment {
+            self.last_line_alignment = alignment;
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+impl Widget for Flow {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        for child in self.children.iter_mut() {
+            child.paint(cx);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let major_max = self.axis.major(bc.max());
+        let child_bc = bc.loosen();
+
+        // Break the children into lines, a child that doesn't fit anymore starts a new line
+        let mut lines: Vec<Line> = Vec::new();
+        let mut sizes = Vec::with_capacity(self.children.len());
+        for (index, child) in self.children.iter_mut().enumerate() {
+            let size = child.layout(cx, &child_bc);
+            let (major, minor) = (self.axis.major(size), self.axis.minor(size));
+            match lines.last_mut() {
+                Some(line) if line.major + self.spacing + major <= major_max => {
+                    line.major += self.spacing + major;
+                    line.minor = line.minor.max(minor);
+                    line.end = index + 1;
+                }
+                _ => lines.push(Line {
+                    start: index,
+                    end: index + 1,
+                    major,
+                    minor,
+                }),
+            }
+            sizes.push(size);
+        }
+
+        let major_used = lines.iter().map(|line| line.major).fold(0.0, f64::max);
+        let mut minor_used = 0.0;
+        for (line_index, line) in lines.iter().enumerate() {
+            let mut major_offset = if line_index == lines.len() - 1 {
+                match self.last_line_alignment {
+                    Alignment::Start => 0.0,
+                    Alignment::Center => ((major_used - line.major) / 2.0).floor(),
+                    Alignment::End => major_used - line.major,
+                }
+            } else {
+                0.0
+            };
+            for index in line.start..line.end {
+                self.children[index].set_origin(cx, self.axis.pack(major_offset, minor_used));
+                major_offset += self.axis.major(sizes[index]) + self.spacing;
+            }
+            minor_used += line.minor;
+            if line_index < lines.len() - 1 {
+                minor_used += self.line_spacing;
+            }
+        }
+
+        bc.constrain(self.axis.pack::<Size>(major_used, minor_used))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        for child in &mut self.children {
+            child.event(cx, event);
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        for child in &mut self.children {
+            child.lifecycle(cx, event);
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        &self.children
+    }
+}
@@ -0,0 +1,223 @@
+//! The key and mouse input of [`Event`](super::Event), which is independent of the terminal library,
+//! so that handlers don't depend on its version and input can be read from other sources as well.
+//!
+//! The events of crossterm are converted without losing information, except for media and modifier keys,
+//! which are only reported with enhanced keyboard reporting (which isn't enabled by trui).
+use bitflags::bitflags;
+use crossterm::event as ct;
+
+/// A key of the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Key {
+    Backspace,
+    Enter,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Tab,
+    /// `Shift` + `Tab`
+    BackTab,
+    Delete,
+    Insert,
+    /// A function key, e.g. `F(1)` for `F1`
+    F(u8),
+    /// A character, which already reflects `Shift`, e.g. `Char('A')`
+    Char(char),
+    Null,
+    Esc,
+    CapsLock,
+    ScrollLock,
+    NumLock,
+    PrintScreen,
+    Pause,
+    Menu,
+    KeypadBegin,
+}
+
+bitflags! {
+    /// The modifier keys, which were held while a key was pressed or the mouse was used.
+    #[derive(Default, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Modifiers: u8 {
+        const SHIFT = 1;
+        const CONTROL = 2;
+        const ALT = 4;
+        const SUPER = 8;
+        const HYPER = 16;
+        const META = 32;
+        const NONE = 0;
+    }
+}
+
+/// Whether a key was pressed, repeated or released.
+///
+/// Most terminals only report key presses.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum KeyKind {
+    #[default]
+    Press,
+    Repeat,
+    Release,
+}
+
+/// A key with the modifiers that were held, as sent with [`Event::Key`](super::Event::Key).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeyEvent {
+    pub code: Key,
+    pub modifiers: Modifiers,
+    pub kind: KeyKind,
+}
+
+impl KeyEvent {
+    /// A key press of `code` with `modifiers`.
+    pub const fn new(code: Key, modifiers: Modifiers) -> Self {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyKind::Press,
+        }
+    }
+}
+
+impl From<Key> for KeyEvent {
+    fn from(code: Key) -> Self {
+        KeyEvent::new(code, Modifiers::NONE)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// What the mouse did, see [`RawMouseEvent`](super::RawMouseEvent).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MouseKind {
+    Down(MouseButton),
+    Up(MouseButton),
+    /// The mouse moved while the button was held
+    Drag(MouseButton),
+    Moved,
+    ScrollDown,
+    ScrollUp,
+    ScrollLeft,
+    ScrollRight,
+}
+
+/// Returns the key code back, if it's a media or modifier key.
+impl TryFrom<ct::KeyCode> for Key {
+    type Error = ct::KeyCode;
+
+    fn try_from(code: ct::KeyCode) -> Result<Self, Self::Error> {
+        Ok(match code {
+            ct::KeyCode::Backspace => Key::Backspace,
+            ct::KeyCode::Enter => Key::Enter,
+            ct::KeyCode::Left => Key::Left,
+            ct::KeyCode::Right => Key::Right,
+            ct::KeyCode::Up => Key::Up,
+            ct::KeyCode::Down => Key::Down,
+            ct::KeyCode::Home => Key::Home,
+            ct::KeyCode::End => Key::End,
+            ct::KeyCode::PageUp => Key::PageUp,
+            ct::KeyCode::PageDown => Key::PageDown,
+            ct::KeyCode::Tab => Key::Tab,
+            ct::KeyCode::BackTab => Key::BackTab,
+            ct::KeyCode::Delete => Key::Delete,
+            ct::KeyCode::Insert => Key::Insert,
+            ct::KeyCode::F(n) => Key::F(n),
+            ct::KeyCode::Char(c) => Key::Char(c),
+            ct::KeyCode::Null => Key::Null,
+            ct::KeyCode::Esc => Key::Esc,
+            ct::KeyCode::CapsLock => Key::CapsLock,
+            ct::KeyCode::ScrollLock => Key::ScrollLock,
+            ct::KeyCode::NumLock => Key::NumLock,
+            ct::KeyCode::PrintScreen => Key::PrintScreen,
+            ct::KeyCode::Pause => Key::Pause,
+            ct::KeyCode::Menu => Key::Menu,
+            ct::KeyCode::KeypadBegin => Key::KeypadBegin,
+            ct::KeyCode::Media(_) | ct::KeyCode::Modifier(_) => return Err(code),
+        })
+    }
+}
+
+impl From<ct::KeyModifiers> for Modifiers {
+    fn from(modifiers: ct::KeyModifiers) -> Self {
+        Modifiers::from_bits_truncate(modifiers.bits())
+    }
+}
+
+impl From<ct::KeyEventKind> for KeyKind {
+    fn from(kind: ct::KeyEventKind) -> Self {
+        match kind {
+            ct::KeyEventKind::Press => KeyKind::Press,
+            ct::KeyEventKind::Repeat => KeyKind::Repeat,
+            ct::KeyEventKind::Release => KeyKind::Release,
+        }
+    }
+}
+
+/// Returns the event back, if it's a media or modifier key.
+impl TryFrom<ct::KeyEvent> for KeyEvent {
+    type Error = ct::KeyEvent;
+
+    fn try_from(event: ct::KeyEvent) -> Result<Self, Self::Error> {
+        Ok(KeyEvent {
+            code: event.code.try_into().map_err(|_| event)?,
+            modifiers: event.modifiers.into(),
+            kind: event.kind.into(),
+        })
+    }
+}
+
+impl From<ct::MouseButton> for MouseButton {
+    fn from(button: ct::MouseButton) -> Self {
+        match button {
+            ct::MouseButton::Left => MouseButton::Left,
+            ct::MouseButton::Right => MouseButton::Right,
+            ct::MouseButton::Middle => MouseButton::Middle,
+        }
+    }
+}
+
+impl From<ct::MouseEventKind> for MouseKind {
+    fn from(kind: ct::MouseEventKind) -> Self {
+        match kind {
+            ct::MouseEventKind::Down(button) => MouseKind::Down(button.into()),
+            ct::MouseEventKind::Up(button) => MouseKind::Up(button.into()),
+            ct::MouseEventKind::Drag(button) => MouseKind::Drag(button.into()),
+            ct::MouseEventKind::Moved => MouseKind::Moved,
+            ct::MouseEventKind::ScrollDown => MouseKind::ScrollDown,
+            ct::MouseEventKind::ScrollUp => MouseKind::ScrollUp,
+            ct::MouseEventKind::ScrollLeft => MouseKind::ScrollLeft,
+            ct::MouseEventKind::ScrollRight => MouseKind::ScrollRight,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crossterm_keys_are_converted_with_their_modifiers() {
+        let event = ct::KeyEvent::new(
+            ct::KeyCode::Char('W'),
+            ct::KeyModifiers::CONTROL | ct::KeyModifiers::SHIFT | ct::KeyModifiers::META,
+        );
+        assert_eq!(
+            KeyEvent::try_from(event),
+            Ok(KeyEvent::new(
+                Key::Char('W'),
+                Modifiers::CONTROL | Modifiers::SHIFT | Modifiers::META
+            ))
+        );
+        let media = ct::KeyEvent::from(ct::KeyCode::Media(ct::MediaKeyCode::Play));
+        assert_eq!(KeyEvent::try_from(media), Err(media));
+    }
+}
@@ -0,0 +1,213 @@
+use std::time::{Duration, Instant};
+
+use crate::{geometry::Size, KeySequence};
+
+use super::{
+    core::{IdPath, LifeCycleCx, PaintCx},
+    BoxConstraints, Event, EventCx, Key, KeyEvent, LayoutCx, LifeCycle, Message, Modifiers, Pod,
+    Widget,
+};
+
+/// A key press, as it's compared with the keys of a [`KeySequence`].
+pub(crate) type KeyStroke = (Key, Modifiers);
+
+/// Ignores `Shift` for characters, as it's already reflected in the character itself.
+pub(crate) fn normalize_key(key: &KeyEvent) -> KeyStroke {
+    match key.code {
+        Key::Char(_) => (key.code, key.modifiers - Modifiers::SHIFT),
+        code => (code, key.modifiers),
+    }
+}
+
+/// The index of the sequence which is exactly `pending`, and whether a longer sequence starts with it.
+fn classify(sequences: &[KeySequence], pending: &[KeyStroke]) -> (Option<usize>, bool) {
+    let exact = sequences.iter().position(|s| s.keys == pending);
+    let longer = sequences
+        .iter()
+        .any(|s| s.keys.len() > pending.len() && s.keys.starts_with(pending));
+    (exact, longer)
+}
+
+/// Adds `key` to the `pending` keys and returns the indices of the completed sequences.
+///
+/// A sequence completes when the pending keys match it and no longer sequence starts with them.
+/// If a longer one does, the decision is deferred until either the next key disambiguates it
+/// or the timeout expires (see [`expire`]). A key that doesn't continue any sequence completes
+/// a pending exact match and then starts a new sequence itself.
+fn advance(sequences: &[KeySequence], pending: &mut Vec<KeyStroke>, key: KeyStroke) -> Vec<usize> {
+    let mut completed = Vec::new();
+    pending.push(key);
+    loop {
+        match classify(sequences, pending) {
+            (Some(index), false) => {
+                completed.push(index);
+                pending.clear();
+                break;
+            }
+            (_, true) => break,
+            (None, false) => {
+                let key = pending.pop().expect("the new key is pending");
+                if pending.is_empty() {
+                    break;
+                }
+                if let (Some(index), _) = classify(sequences, pending) {
+                    completed.push(index);
+                }
+                pending.clear();
+                pending.push(key);
+            }
+        }
+    }
+    completed
+}
+
+/// Resets the `pending` keys after the timeout and returns the sequence they match exactly, if any.
+fn expire(sequences: &[KeySequence], pending: &mut Vec<KeyStroke>) -> Option<usize> {
+    let (exact, _) = classify(sequences, pending);
+    pending.clear();
+    exact
+}
+
+/// Sends the id of a [`KeySequence`] to its view, when its keys were pressed.
+///
+/// Key events only reach this widget while it or one of its descendants is focused,
+/// and only if they weren't handled by a descendant.
+pub struct KeySequences {
+    pub(crate) element: Pod,
+    id_path: IdPath,
+    pub(crate) sequences: Vec<KeySequence>,
+    pub(crate) timeout: Duration,
+    pending: Vec<KeyStroke>,
+    /// When the pending keys are reset
+    deadline: Option<Instant>,
+}
+
+impl KeySequences {
+    pub(crate) fn new<E: Widget>(
+        element: E,
+        id_path: &IdPath,
+        sequences: Vec<KeySequence>,
+        timeout: Duration,
+    ) -> Self {
+        KeySequences {
+            element: Pod::new(element),
+            id_path: id_path.clone(),
+            sequences,
+            timeout,
+            pending: Vec::new(),
+            deadline: None,
+        }
+    }
+
+    /// The messages with the ids of the `completed` sequences.
+    fn messages(&self, completed: Vec<usize>) -> impl Iterator<Item = Message> + '_ {
+        completed
+            .into_iter()
+            .map(|index| Message::new(self.id_path.clone(), self.sequences[index].id.clone()))
+    }
+}
+
+impl Widget for KeySequences {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.element.paint(cx);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        self.element.layout(cx, bc)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.element.event(cx, event);
+        if cx.is_handled() {
+            return;
+        }
+
+        if let Event::Key(key) = event {
+            let completed = advance(&self.sequences, &mut self.pending, normalize_key(key));
+            let consumed = !completed.is_empty() || !self.pending.is_empty();
+            for message in self.messages(completed) {
+                cx.add_message(message);
+            }
+            // The timeout is checked in every frame while keys are pending
+            self.deadline = if self.pending.is_empty() {
+                None
+            } else {
+                cx.request_animation_update();
+                Some(Instant::now() + self.timeout)
+            };
+            cx.set_handled(consumed);
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        self.element.lifecycle(cx, event);
+
+        if let (LifeCycle::Animate, Some(deadline)) = (event, self.deadline) {
+            if Instant::now() < deadline {
+                cx.request_animation_update();
+            } else {
+                self.deadline = None;
+                let completed = expire(&self.sequences, &mut self.pending);
+                for message in self.messages(completed.into_iter().collect()) {
+                    cx.add_message(message);
+                }
+            }
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.element)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(c: char) -> KeyStroke {
+        (Key::Char(c), Modifiers::NONE)
+    }
+
+    #[test]
+    fn sequences_complete_when_they_are_unambiguous() {
+        let sequences = [
+            KeySequence::chars("top", "gg"),
+            KeySequence::chars("delete_line", "dd"),
+            KeySequence::chars("delete_word", "dw"),
+        ];
+        let mut pending = Vec::new();
+        assert!(advance(&sequences, &mut pending, key('g')).is_empty());
+        assert_eq!(advance(&sequences, &mut pending, key('g')), vec![0]);
+        assert!(pending.is_empty());
+
+        // `x` doesn't continue `d`, so both are dropped
+        assert!(advance(&sequences, &mut pending, key('d')).is_empty());
+        assert!(advance(&sequences, &mut pending, key('x')).is_empty());
+        assert!(pending.is_empty());
+
+        // `d` doesn't continue `g`, but starts a new sequence
+        advance(&sequences, &mut pending, key('g'));
+        advance(&sequences, &mut pending, key('d'));
+        assert_eq!(advance(&sequences, &mut pending, key('w')), vec![2]);
+    }
+
+    #[test]
+    fn a_prefix_of_a_longer_sequence_waits_for_the_next_key_or_the_timeout() {
+        let sequences = [
+            KeySequence::chars("delete", "d"),
+            KeySequence::chars("delete_line", "dd"),
+        ];
+        let mut pending = Vec::new();
+        assert!(advance(&sequences, &mut pending, key('d')).is_empty());
+        assert_eq!(advance(&sequences, &mut pending, key('d')), vec![1]);
+
+        assert!(advance(&sequences, &mut pending, key('d')).is_empty());
+        assert_eq!(expire(&sequences, &mut pending), Some(0));
+        assert!(pending.is_empty());
+
+        // Another key disambiguates the prefix
+        advance(&sequences, &mut pending, key('d'));
+        assert_eq!(advance(&sequences, &mut pending, key('x')), vec![0]);
+        assert!(pending.is_empty());
+    }
+}
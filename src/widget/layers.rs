@@ -0,0 +1,103 @@
+use crate::geometry::{Point, Size};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, Event, LayoutCx, LifeCycle, Pod, Widget,
+};
+
+/// Stacks its children on top of each other, the first one is at the bottom.
+///
+/// Each layer is laid out to the full size at the origin, so the topmost layer captures the mouse.
+pub struct Layers {
+    pub children: Vec<Pod>,
+}
+
+impl Layers {
+    pub(crate) fn new(children: Vec<Pod>) -> Self {
+        Layers { children }
+    }
+}
+
+impl Widget for Layers {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        for child in &mut self.children {
+            child.paint(cx);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // Layers are usually the root of the app, where each of them fills the terminal,
+        // otherwise they're only as large as the largest one
+        let child_bc = if bc.is_width_bounded() && bc.is_height_bounded() {
+            BoxConstraints::tight(bc.max())
+        } else {
+            bc.loosen()
+        };
+        let mut size = Size::ZERO;
+        for child in &mut self.children {
+            let child_size = child.layout(cx, &child_bc);
+            child.set_origin(cx, Point::ORIGIN);
+            size = Size::new(
+                size.width.max(child_size.width),
+                size.height.max(child_size.height),
+            );
+        }
+        bc.constrain(size)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        // The topmost layer gets the event first, a layer which handles it hides it from the layers below.
+        // Mouse events only reach the topmost layer (or one with an active widget) anyway,
+        // key events only the layer with the focused widget.
+        for child in self.children.iter_mut().rev() {
+            child.event(cx, event);
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        for child in &mut self.children {
+            child.lifecycle(cx, event);
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        &self.children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::test_mouse,
+        widget::{MouseButton, MouseKind},
+        App, Position, ViewExt,
+    };
+
+    type Clicks = Vec<&'static str>;
+
+    #[tokio::test]
+    async fn the_topmost_layer_takes_the_clicks_and_the_lower_layers_are_still_painted() {
+        let mut app = App::new_layered(Vec::new(), |clicks: &mut Clicks| {
+            (
+                format!("{clicks:?}").on_click(|clicks: &mut Clicks| clicks.push("base")),
+                crate::v_stack(vec!["overlay"
+                    .on_click(|clicks: &mut Clicks| clicks.push("overlay"))
+                    .margin((Position::TOP, 1))]),
+            )
+        })
+        .await;
+        app.render_once().await.unwrap();
+
+        // the overlay covers the base layer, also where it has no content
+        let left = MouseButton::Left;
+        for (column, row) in [(1, 0), (70, 30), (1, 1)] {
+            app.dispatch([
+                test_mouse(MouseKind::Down(left), column, row),
+                test_mouse(MouseKind::Up(left), column, row),
+            ])
+            .await;
+        }
+        assert_eq!(app.screen_line(0), r#"["overlay"]"#);
+        assert_eq!(app.screen_line(1), "overlay");
+    }
+}
@@ -61,4 +61,8 @@ impl Widget for LinearLayout {
             child.lifecycle(cx, event);
         }
     }
+
+    fn children(&self) -> &[Pod] {
+        &self.children
+    }
 }
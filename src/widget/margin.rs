@@ -64,7 +64,8 @@ impl Widget for Margin {
 
         self.content
             .set_origin(cx, Point::new(margin_left, margin_top));
-        content_size + margin
+        // Like the border, the margin is cut off, when it doesn't fit into the constraints
+        bc.constrain(content_size + margin)
     }
 
     fn event(&mut self, cx: &mut EventCx, event: &Event) {
@@ -74,4 +75,8 @@ impl Widget for Margin {
     fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
         self.content.lifecycle(cx, event)
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
 }
@@ -0,0 +1,80 @@
+use crate::{
+    geometry::{Rect, Size},
+    WidgetBounds,
+};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, Pod, Widget,
+};
+
+/// Records the bounds of its content in [`WidgetBounds`] under a key, whenever they change.
+pub struct Measure {
+    content: Pod,
+    key: String,
+    bounds: WidgetBounds,
+    /// The bounds in window coordinates, after the content was laid out
+    rect: Option<Rect>,
+}
+
+impl Measure {
+    pub(crate) fn new(content: impl Widget, key: String, bounds: WidgetBounds) -> Self {
+        Measure {
+            content: Pod::new(content),
+            key,
+            bounds,
+            rect: None,
+        }
+    }
+
+    pub(crate) fn content(&mut self) -> &mut Pod {
+        &mut self.content
+    }
+
+    pub(crate) fn set_key(&mut self, key: &str) -> ChangeFlags {
+        if self.key != key {
+            self.bounds.remove(&self.key);
+            self.key = key.to_string();
+            if let Some(rect) = self.rect {
+                self.bounds.set(&self.key, rect);
+            }
+        }
+        ChangeFlags::empty()
+    }
+}
+
+impl Drop for Measure {
+    fn drop(&mut self) {
+        self.bounds.remove(&self.key);
+    }
+}
+
+impl Widget for Measure {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.content.paint(cx);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        self.content.layout(cx, bc)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.content.event(cx, event);
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        self.content.lifecycle(cx, event);
+        // The origin in the window is only known, when the view context is propagated after layout
+        if matches!(event, LifeCycle::ViewContextChanged(_)) {
+            let rect = cx.rect();
+            if self.rect != Some(rect) {
+                self.rect = Some(rect);
+                self.bounds.set(&self.key, rect);
+            }
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
+}
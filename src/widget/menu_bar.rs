@@ -0,0 +1,496 @@
+use ratatui::{buffer::Buffer, style::Style, symbols};
+use unicode_width::UnicodeWidthStr;
+use xilem_core::Id;
+
+use crate::{
+    geometry::{to_ratatui_rect, Point, Rect, Size, Vec2},
+    Menu, MenuEntry,
+};
+
+use super::{
+    core::{IdPath, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, EventCx, Key, KeyEvent, LayoutCx, LifeCycle, Message,
+    Modifiers, MouseButton, MouseKind, Pod, RawMouseEvent, Widget,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MenuStyles {
+    pub(crate) bar: Style,
+    pub(crate) border: Style,
+    pub(crate) selected: Style,
+    pub(crate) disabled: Style,
+}
+
+/// A row of menu titles above its content, the dropdown of the open menu is painted on top of the content.
+///
+/// While a menu is open, the menu bar has the keyboard focus and the content doesn't receive
+/// mouse and key events. Closing the menu gives the focus back to the previously focused widget.
+pub struct MenuBar {
+    id_path: IdPath,
+    pub(crate) content: Pod,
+    menus: Vec<Menu>,
+    /// The index of the open menu and of its highlighted entry
+    open: Option<(usize, Option<usize>)>,
+    /// The widget which was focused before a menu was opened, it gets the focus back when it's closed
+    previous_focus: Option<Id>,
+    styles: MenuStyles,
+    size: Size,
+}
+
+/// The index of the next entry that can be chosen after `from` (or before it if `forward` is false),
+/// wrapping around at the end, or the first (last) one if `from` is `None`.
+fn next_enabled(entries: &[MenuEntry], from: Option<usize>, forward: bool) -> Option<usize> {
+    let len = entries.len();
+    (1..=len)
+        .map(|step| match (from, forward) {
+            (None, true) => step - 1,
+            (None, false) => len - step,
+            (Some(i), true) => (i + step) % len,
+            (Some(i), false) => (i + len - step % len) % len,
+        })
+        .find(|i| entries[*i].enabled_item().is_some())
+}
+
+impl MenuBar {
+    pub(crate) fn new(
+        id_path: &IdPath,
+        content: impl Widget,
+        menus: Vec<Menu>,
+        styles: MenuStyles,
+    ) -> Self {
+        MenuBar {
+            id_path: id_path.clone(),
+            content: Pod::new(content),
+            menus,
+            open: None,
+            previous_focus: None,
+            styles,
+            size: Size::ZERO,
+        }
+    }
+
+    pub(crate) fn set_menus(&mut self, menus: &[Menu]) -> ChangeFlags {
+        if self.menus != menus {
+            self.menus = menus.to_vec();
+            // The entries may have changed, so the highlight is reset
+            self.open = self
+                .open
+                .filter(|(menu, _)| *menu < self.menus.len())
+                .map(|(menu, _)| (menu, None));
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_styles(&mut self, styles: MenuStyles) -> ChangeFlags {
+        if self.styles != styles {
+            self.styles = styles;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// The local rects of the menu titles in the top row, each title is padded with a space on both sides.
+    fn title_rects(&self) -> impl Iterator<Item = Rect> + '_ {
+        let mut x = 0.0;
+        self.menus.iter().map(move |menu| {
+            let width = (menu.title.width() + 2) as f64;
+            let rect = Rect::new(x, 0.0, x + width, 1.0);
+            x += width;
+            rect
+        })
+    }
+
+    /// The local rect of the dropdown of `menu` (including its border), below its title.
+    fn dropdown_rect(&self, menu: usize) -> Rect {
+        let x = self.title_rects().nth(menu).map_or(0.0, |r| r.x0);
+        let entries = &self.menus[menu].entries;
+        let width = entries
+            .iter()
+            .map(|entry| match entry {
+                MenuEntry::Item(item) => {
+                    item.label.width() + if item.accelerator.is_some() { 2 } else { 0 }
+                }
+                MenuEntry::Separator => 0,
+            })
+            .max()
+            .unwrap_or(0)
+            + 4;
+        Rect::new(x, 1.0, x + width as f64, 1.0 + entries.len() as f64 + 2.0)
+    }
+
+    /// The index of the entry of the open menu at the local `point`.
+    fn entry_at(&self, point: Point) -> Option<usize> {
+        let (menu, _) = self.open?;
+        let rect = self.dropdown_rect(menu).inset(-1.0);
+        rect.contains(point)
+            .then_some((point.y - rect.y0) as usize)
+            .filter(|i| *i < self.menus[menu].entries.len())
+    }
+
+    fn open(&mut self, cx: &mut EventCx, menu: usize) {
+        if self.open.is_none() {
+            self.previous_focus = cx.focused();
+        }
+        self.open = Some((menu, None));
+        cx.request_focus();
+        cx.request_paint();
+    }
+
+    fn close(&mut self, cx: &mut EventCx) {
+        self.open = None;
+        cx.pass_focus(self.previous_focus.take());
+        cx.request_paint();
+    }
+
+    /// Sends the id of the entry `index` of the open menu to the view, if it can be chosen.
+    fn choose(&mut self, cx: &mut EventCx, index: usize) {
+        let Some((menu, _)) = self.open else {
+            return;
+        };
+        if let Some(item) = self.menus[menu].entries[index].enabled_item() {
+            cx.add_message(Message::new(self.id_path.clone(), item.id.clone()));
+            self.close(cx);
+        }
+    }
+
+    fn menu_with_mnemonic(&self, key: char) -> Option<usize> {
+        let key = key.to_ascii_lowercase();
+        self.menus.iter().position(|m| m.mnemonic() == Some(key))
+    }
+
+    fn key_event(&mut self, cx: &mut EventCx, key: &KeyEvent) {
+        let Some((menu, highlighted)) = self.open else {
+            return;
+        };
+        let entries = &self.menus[menu].entries;
+        let count = self.menus.len();
+        match key.code {
+            Key::Left => self.open(cx, (menu + count - 1) % count),
+            Key::Right => self.open(cx, (menu + 1) % count),
+            Key::Up | Key::Down => {
+                let forward = key.code == Key::Down;
+                self.open = Some((menu, next_enabled(entries, highlighted, forward)));
+                cx.request_paint();
+            }
+            Key::Enter => {
+                if let Some(index) = highlighted {
+                    self.choose(cx, index);
+                }
+            }
+            Key::Esc => self.close(cx),
+            Key::Char(c) if key.modifiers.contains(Modifiers::ALT) => {
+                if let Some(menu) = self.menu_with_mnemonic(c) {
+                    self.open(cx, menu);
+                }
+            }
+            Key::Char(c) => {
+                let accelerated = entries.iter().position(|entry| {
+                    matches!(entry.enabled_item(), Some(item)
+                        if item.accelerator.map(|a| a.to_ascii_lowercase()) == Some(c.to_ascii_lowercase()))
+                });
+                if let Some(index) = accelerated {
+                    self.choose(cx, index);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn mouse_event(&mut self, cx: &mut EventCx, mouse: &RawMouseEvent) {
+        // Mouse events are relative to the origin of the widget
+        let point = Point::new(mouse.column as f64, mouse.row as f64);
+        let title = self.title_rects().position(|rect| rect.contains(point));
+        match mouse.kind {
+            MouseKind::Down(MouseButton::Left) => match (title, self.open) {
+                (Some(title), Some((menu, _))) if title == menu => self.close(cx),
+                (Some(title), _) => self.open(cx, title),
+                // A click outside of the open menu closes it
+                (None, Some(_)) if self.entry_at(point).is_none() => self.close(cx),
+                _ => (),
+            },
+            MouseKind::Up(MouseButton::Left) => {
+                if let Some(index) = self.entry_at(point) {
+                    self.choose(cx, index);
+                }
+            }
+            MouseKind::Moved | MouseKind::Drag(_) => {
+                if let (Some((menu, highlighted)), Some(index)) = (self.open, self.entry_at(point))
+                {
+                    let index = self.menus[menu].entries[index]
+                        .enabled_item()
+                        .map(|_| index);
+                    if index != highlighted {
+                        self.open = Some((menu, index));
+                        cx.request_paint();
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn paint_dropdown(
+        &self,
+        buf: &mut Buffer,
+        origin: Vec2,
+        menu: usize,
+        highlighted: Option<usize>,
+    ) {
+        let rect = to_ratatui_rect(self.dropdown_rect(menu) + origin);
+        let area = rect.intersection(buf.area);
+        if area.width < 2 || area.height < 2 {
+            return;
+        }
+        let line = symbols::line::ROUNDED;
+        let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let symbol = match (x, y) {
+                    (x, y) if x == area.left() && y == area.top() => line.top_left,
+                    (x, y) if x == right && y == area.top() => line.top_right,
+                    (x, y) if x == area.left() && y == bottom => line.bottom_left,
+                    (x, y) if x == right && y == bottom => line.bottom_right,
+                    (x, _) if x == area.left() || x == right => line.vertical,
+                    (_, y) if y == area.top() || y == bottom => line.horizontal,
+                    _ => " ",
+                };
+                let is_border = x == area.left() || x == right || y == area.top() || y == bottom;
+                let style = if is_border {
+                    self.styles.border
+                } else {
+                    self.styles.bar
+                };
+                buf.get_mut(x, y).set_symbol(symbol).set_style(style);
+            }
+        }
+
+        let entries = &self.menus[menu].entries;
+        let width = right.saturating_sub(area.x + 1);
+        for (i, (y, entry)) in (area.top() + 1..bottom).zip(entries).enumerate() {
+            match entry {
+                MenuEntry::Separator => {
+                    buf.get_mut(area.left(), y).set_symbol(line.vertical_right);
+                    for x in area.left() + 1..right {
+                        buf.get_mut(x, y).set_symbol(line.horizontal);
+                    }
+                    buf.get_mut(right, y).set_symbol(line.vertical_left);
+                    for x in area.left()..area.right() {
+                        buf.get_mut(x, y).set_style(self.styles.border);
+                    }
+                }
+                MenuEntry::Item(item) => {
+                    let style = if !item.enabled {
+                        self.styles.disabled
+                    } else if highlighted == Some(i) {
+                        self.styles.selected
+                    } else {
+                        self.styles.bar
+                    };
+                    for x in area.left() + 1..right {
+                        buf.get_mut(x, y).set_symbol(" ").set_style(style);
+                    }
+                    let x = area.x + 2;
+                    let max_width = width.saturating_sub(2) as usize;
+                    buf.set_stringn(x, y, &item.label, max_width, style);
+                    if let Some(accelerator) = item.accelerator {
+                        // right aligned, with one space padding
+                        let x = right.saturating_sub(2);
+                        if x > area.x + 1 {
+                            buf.get_mut(x, y).set_char(accelerator).set_style(style);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Widget for MenuBar {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.content.paint(cx);
+
+        let rect = to_ratatui_rect(cx.rect());
+        let origin = cx.rect().origin().to_vec2();
+        let title_rects: Vec<_> = self.title_rects().collect();
+        let buf = cx.buffer_mut();
+        let bar = rect.intersection(buf.area);
+        if bar.area() == 0 {
+            return;
+        }
+        for x in bar.left()..bar.right() {
+            buf.get_mut(x, bar.y)
+                .set_symbol(" ")
+                .set_style(self.styles.bar);
+        }
+        for (i, (menu, title_rect)) in self.menus.iter().zip(title_rects).enumerate() {
+            let title_rect = to_ratatui_rect(title_rect + origin).intersection(bar);
+            if title_rect.area() == 0 {
+                continue;
+            }
+            let style = match self.open {
+                Some((open, _)) if open == i => self.styles.selected,
+                _ => self.styles.bar,
+            };
+            let title = format!(" {} ", menu.title);
+            buf.set_stringn(
+                title_rect.x,
+                title_rect.y,
+                title,
+                title_rect.width as usize,
+                style,
+            );
+        }
+
+        if let Some((menu, highlighted)) = self.open {
+            self.paint_dropdown(buf, origin, menu, highlighted);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // The top row is the menu bar
+        let content_size = self.content.layout(cx, &bc.shrink((0.0, 1.0)));
+        self.content.set_origin(cx, Point::new(0.0, 1.0));
+        let titles_width = self.title_rects().last().map_or(0.0, |r| r.x1);
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            content_size.width.max(titles_width)
+        };
+        self.size = bc.constrain(Size::new(width, content_size.height + 1.0));
+        self.size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        match event {
+            // The open menu traps mouse and key events
+            Event::Key(key) if self.open.is_some() => {
+                self.key_event(cx, key);
+                cx.set_handled(true);
+            }
+            Event::Mouse(mouse) if self.open.is_some() => {
+                self.mouse_event(cx, mouse);
+                cx.set_handled(true);
+            }
+            _ => {
+                self.content.event(cx, event);
+                if cx.is_handled() {
+                    return;
+                }
+                match event {
+                    Event::Key(KeyEvent {
+                        code: Key::Char(c),
+                        modifiers,
+                        ..
+                    }) if modifiers.contains(Modifiers::ALT) => {
+                        if let Some(menu) = self.menu_with_mnemonic(*c) {
+                            self.open(cx, menu);
+                            cx.set_handled(true);
+                        }
+                    }
+                    Event::Mouse(mouse) => self.mouse_event(cx, mouse),
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        if let LifeCycle::FocusChanged(false) = event {
+            // e.g. the focus was taken by a click elsewhere
+            if self.open.is_some() && !cx.is_focused() {
+                self.open = None;
+                self.previous_focus = None;
+                cx.request_paint();
+            }
+        }
+        self.content.lifecycle(cx, event);
+    }
+
+    fn children(&self) -> &[Pod] {
+        // While a menu is open, the content isn't hot and can't be focused
+        if self.open.is_some() {
+            &[]
+        } else {
+            std::slice::from_ref(&self.content)
+        }
+    }
+
+    fn accepts_focus(&self) -> bool {
+        self.open.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{test_key, test_mouse},
+        text_input, App, MenuItem, ViewExt,
+    };
+
+    fn menus() -> Vec<Menu> {
+        vec![Menu::new("File").item(MenuItem::new("quit", "Quit"))]
+    }
+
+    #[test]
+    fn next_enabled_skips_separators_and_disabled_items() {
+        let entries = [
+            MenuEntry::Item(MenuItem::new("new", "New")),
+            MenuEntry::Separator,
+            MenuEntry::Item(MenuItem::new("save", "Save").enabled(false)),
+            MenuEntry::Item(MenuItem::new("quit", "Quit")),
+        ];
+        assert_eq!(next_enabled(&entries, None, true), Some(0));
+        assert_eq!(next_enabled(&entries, None, false), Some(3));
+        assert_eq!(next_enabled(&entries, Some(0), true), Some(3));
+        // wraps around
+        assert_eq!(next_enabled(&entries, Some(3), true), Some(0));
+        assert_eq!(next_enabled(&entries, Some(0), false), Some(3));
+        assert_eq!(next_enabled(&[MenuEntry::Separator], None, true), None);
+    }
+
+    #[tokio::test]
+    async fn closing_a_menu_restores_the_previous_focus() {
+        let mut app = App::new(String::new(), |text: &mut String| {
+            text_input(text.clone(), |text: &mut String, new: String| *text = new)
+                .menu_bar(menus(), |_: &mut String, _: String| ())
+        })
+        .await;
+        app.render_once().await.unwrap();
+        // focuses the input
+        app.dispatch([test_key(Key::Tab)]).await;
+        app.dispatch([Event::Key(KeyEvent::new(Key::Char('f'), Modifiers::ALT))])
+            .await;
+        app.dispatch([test_key(Key::Esc)]).await;
+        app.dispatch([test_key(Key::Char('a'))]).await;
+        assert_eq!(app.screen_line(1), "a");
+    }
+
+    #[tokio::test]
+    async fn the_content_gets_no_mouse_events_while_a_menu_is_open() {
+        let mut app = App::new(0, |count: &mut i32| {
+            format!("{count}")
+                .fill_max_size(1.0)
+                .on_click(|count: &mut i32| *count += 1)
+                .menu_bar(menus(), |_: &mut i32, _: String| ())
+        })
+        .await;
+        let click = |column, row| {
+            [
+                test_mouse(MouseKind::Down(MouseButton::Left), column, row),
+                test_mouse(MouseKind::Up(MouseButton::Left), column, row),
+            ]
+        };
+        app.render_once().await.unwrap();
+        // opens the menu, the click on the content closes it again
+        app.dispatch(click(1, 0)).await;
+        app.dispatch(click(30, 5)).await;
+        assert_eq!(app.screen_line(1), "0");
+
+        app.dispatch(click(30, 5)).await;
+        assert_eq!(app.screen_line(1), "1");
+    }
+}
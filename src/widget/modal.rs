@@ -0,0 +1,78 @@
+use crate::geometry::{Point, Size};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, Event, LayoutCx, LifeCycle, Pod, Widget,
+};
+
+/// Shows an optional dialog centered on top of its content.
+///
+/// While the dialog is shown, it's the only child visible to hit testing and focus traversal,
+/// so the content neither receives mouse nor key events and loses the keyboard focus.
+pub struct Modal {
+    /// The content, followed by the dialog if it's shown
+    children: Vec<Pod>,
+}
+
+impl Modal {
+    pub(crate) fn new(content: impl Widget, dialog: Option<Pod>) -> Self {
+        Modal {
+            children: std::iter::once(Pod::new(content)).chain(dialog).collect(),
+        }
+    }
+
+    pub(crate) fn content(&mut self) -> &mut Pod {
+        &mut self.children[0]
+    }
+
+    pub(crate) fn dialog(&mut self) -> Option<&mut Pod> {
+        self.children.get_mut(1)
+    }
+
+    pub(crate) fn set_dialog(&mut self, dialog: Option<Pod>) {
+        self.children.truncate(1);
+        self.children.extend(dialog);
+    }
+}
+
+impl Widget for Modal {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        for child in &mut self.children {
+            child.paint(cx);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let size = self.children[0].layout(cx, bc);
+        self.children[0].set_origin(cx, Point::ORIGIN);
+        if let Some(dialog) = self.children.get_mut(1) {
+            let dialog_size = dialog.layout(cx, &BoxConstraints::tight(size).loosen());
+            let origin = ((size.to_vec2() - dialog_size.to_vec2()) / 2.0).round();
+            dialog.set_origin(cx, origin.to_point());
+        }
+        size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        if let Some(dialog) = self.children.get_mut(1) {
+            dialog.event(cx, event);
+            // Other events like resizes are still relevant for the content
+            if matches!(event, Event::Mouse(_) | Event::Key(_)) {
+                return;
+            }
+        }
+        self.children[0].event(cx, event)
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        // The content still needs e.g. view context and focus changes, while it's covered
+        for child in &mut self.children {
+            child.lifecycle(cx, event);
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        // The dialog traps hit testing and focus traversal
+        &self.children[self.children.len() - 1..]
+    }
+}
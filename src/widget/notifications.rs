@@ -0,0 +1,191 @@
+use ratatui::style::Style;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    geometry::{to_ratatui_rect, Point, Rect, Size},
+    ToastId,
+};
+
+use super::{
+    core::{EventCx, IdPath, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, Message, MouseButton, MouseKind, Pod,
+    RawMouseEvent, Widget,
+};
+
+/// Paints the toasts of [`Notifications`](crate::Notifications) on top of its content.
+///
+/// Every toast is a child of its own, so that it covers the content for hit testing, i.e. the
+/// content doesn't get hovered or clicked through a toast.
+pub struct NotificationOverlay {
+    id_path: IdPath,
+    /// The content, followed by one [`Toast`] per visible toast
+    children: Vec<Pod>,
+    /// The visible toasts, from the oldest to the newest
+    toasts: Vec<(ToastId, String)>,
+    size: Size,
+    style: Style,
+}
+
+impl NotificationOverlay {
+    pub(crate) fn new(
+        id_path: &IdPath,
+        content: impl Widget,
+        toasts: Vec<(ToastId, String)>,
+        style: Style,
+    ) -> Self {
+        let mut overlay = NotificationOverlay {
+            id_path: id_path.clone(),
+            children: vec![Pod::new(content)],
+            toasts: Vec::new(),
+            size: Size::ZERO,
+            style,
+        };
+        let _ = overlay.set_toasts(toasts);
+        overlay
+    }
+
+    pub(crate) fn content(&mut self) -> &mut Pod {
+        &mut self.children[0]
+    }
+
+    pub(crate) fn set_toasts(&mut self, toasts: Vec<(ToastId, String)>) -> ChangeFlags {
+        if self.toasts != toasts {
+            let style = self.style;
+            self.children.truncate(1);
+            self.children.extend(
+                toasts
+                    .iter()
+                    .map(|(_, message)| Pod::new(Toast::new(message.clone(), style))),
+            );
+            self.toasts = toasts;
+            ChangeFlags::tree_structure()
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) -> ChangeFlags {
+        if self.style != style {
+            self.style = style;
+            for toast in &mut self.children[1..] {
+                toast.downcast_mut::<Toast>().unwrap().style = style;
+            }
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// The local rects of the toasts which fit into the overlay (by their index), one row per toast.
+    ///
+    /// The newest toast is in the bottom right corner, older ones are stacked above it.
+    fn toast_rects(&self) -> impl Iterator<Item = (usize, Rect)> + '_ {
+        let Size { width, height } = self.size;
+        self.toasts
+            .iter()
+            .enumerate()
+            .rev()
+            .zip((0..height as usize).map(move |row| height - 1.0 - row as f64))
+            .map(move |((index, (_, message)), y)| {
+                // a space as padding on both sides
+                let toast_width = ((message.width() + 2) as f64).min(width);
+                (index, Rect::new(width - toast_width, y, width, y + 1.0))
+            })
+    }
+}
+
+impl Widget for NotificationOverlay {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        for child in &mut self.children {
+            child.paint(cx);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        self.size = self.children[0].layout(cx, bc);
+        self.children[0].set_origin(cx, Point::ORIGIN);
+
+        // Toasts which don't fit are laid out without a size, so they're neither painted nor hit
+        let mut rects = vec![Rect::ZERO; self.toasts.len()];
+        for (index, rect) in self.toast_rects() {
+            rects[index] = rect;
+        }
+        for (toast, rect) in self.children[1..].iter_mut().zip(rects) {
+            toast.layout(cx, &BoxConstraints::tight(rect.size()));
+            toast.set_origin(cx, rect.origin());
+        }
+        self.size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        if let Event::Mouse(RawMouseEvent {
+            kind: MouseKind::Down(MouseButton::Left),
+            column,
+            row,
+            ..
+        }) = event
+        {
+            // Mouse events are relative to the origin of the widget
+            let point = Point::new(*column as f64, *row as f64);
+            if let Some((index, _)) = self.toast_rects().find(|(_, rect)| rect.contains(point)) {
+                // A click dismisses the toast
+                let id = self.toasts[index].0;
+                cx.add_message(Message::new(self.id_path.clone(), id));
+                cx.set_handled(true);
+                return;
+            }
+        }
+        self.children[0].event(cx, event)
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        for child in &mut self.children {
+            child.lifecycle(cx, event);
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        // The toasts are on top of the content
+        &self.children
+    }
+}
+
+/// A single toast of a [`NotificationOverlay`], a message padded with a space on both sides.
+struct Toast {
+    message: String,
+    style: Style,
+}
+
+impl Toast {
+    fn new(message: String, style: Style) -> Self {
+        Toast { message, style }
+    }
+}
+
+impl Widget for Toast {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let style = self.style.patch(cx.override_style());
+        let rect = to_ratatui_rect(cx.rect());
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        if area.area() == 0 {
+            return;
+        }
+        for x in area.left()..area.right() {
+            buf.get_mut(x, area.y).set_symbol(" ").set_style(style);
+        }
+        let x = area.x + 1;
+        if x < area.right() {
+            let max_width = (area.right() - x).saturating_sub(1) as usize;
+            buf.set_stringn(x, area.y, &self.message, max_width, style);
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        bc.max()
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
+
+    fn lifecycle(&mut self, _cx: &mut LifeCycleCx, _event: &LifeCycle) {}
+}
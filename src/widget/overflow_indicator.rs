@@ -0,0 +1,52 @@
+use ratatui::{buffer::Buffer, layout::Rect};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{Alignment, OverflowIndicator};
+
+/// Draws the glyphs of `indicator` on the first and/or the last row of `rows`, the area of the
+/// scrollable content, whether there's hidden content `above` or `below` it.
+pub(crate) fn paint_overflow_indicator(
+    buf: &mut Buffer,
+    rows: Rect,
+    indicator: &OverflowIndicator,
+    above: bool,
+    below: bool,
+) {
+    let rows = rows.intersection(buf.area);
+    if rows.area() == 0 {
+        return;
+    }
+    let glyphs = [
+        (above, &indicator.above, rows.top()),
+        (below, &indicator.below, rows.bottom() - 1),
+    ];
+    for (_, glyph, y) in glyphs.into_iter().filter(|(shown, ..)| *shown) {
+        let width = (glyph.width() as u16).min(rows.width);
+        let free = rows.width - width;
+        let offset = match indicator.alignment {
+            Alignment::Start => 0,
+            Alignment::Center => free / 2,
+            Alignment::End => free,
+        };
+        // The style is patched onto the style of the row, e.g. the selection
+        buf.set_stringn(rows.x + offset, y, glyph, width as usize, indicator.style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyphs_are_placed_on_the_first_and_last_row() {
+        let area = Rect::new(0, 0, 5, 3);
+        let mut buf = Buffer::empty(area);
+        paint_overflow_indicator(&mut buf, area, &OverflowIndicator::default(), true, true);
+        assert_eq!(buf, Buffer::with_lines(vec!["    ▲", "     ", "    ▼"]));
+
+        let mut buf = Buffer::empty(area);
+        let indicator = OverflowIndicator::new("more", "…").alignment(Alignment::Center);
+        paint_overflow_indicator(&mut buf, area, &indicator, false, true);
+        assert_eq!(buf, Buffer::with_lines(vec!["     ", "     ", "  …  "]));
+    }
+}
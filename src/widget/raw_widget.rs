@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use ratatui::buffer::Buffer;
+
+use crate::{
+    geometry::{to_ratatui_rect, Size},
+    view::RenderFn,
+};
+
+use super::{
+    core::{LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, EventCx, LayoutCx, LifeCycle, Widget,
+};
+
+pub struct RawWidget {
+    render: RenderFn,
+    preferred_size: Option<(u16, u16)>,
+}
+
+impl RawWidget {
+    pub(crate) fn new(render: RenderFn, preferred_size: Option<(u16, u16)>) -> Self {
+        RawWidget {
+            render,
+            preferred_size,
+        }
+    }
+
+    /// The function is usually a new closure with every rebuild, so it's repainted every time.
+    pub(crate) fn set_render(&mut self, render: &RenderFn) -> ChangeFlags {
+        if Arc::ptr_eq(&self.render, render) {
+            ChangeFlags::empty()
+        } else {
+            self.render = render.clone();
+            ChangeFlags::PAINT
+        }
+    }
+
+    pub(crate) fn set_preferred_size(&mut self, preferred_size: Option<(u16, u16)>) -> ChangeFlags {
+        if self.preferred_size != preferred_size {
+            self.preferred_size = preferred_size;
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+impl Widget for RawWidget {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let buf = cx.buffer_mut();
+        let visible = rect.intersection(buf.area);
+        if visible.area() == 0 {
+            return;
+        }
+        // Rendered into a buffer of the whole area (so that the rendering doesn't depend on how much
+        // of it is visible), which starts with the current cells, only the visible cells are copied back
+        let mut scratch = Buffer::empty(rect);
+        for y in visible.top()..visible.bottom() {
+            for x in visible.left()..visible.right() {
+                *scratch.get_mut(x, y) = buf.get(x, y).clone();
+            }
+        }
+        (self.render)(rect, &mut scratch);
+        for y in visible.top()..visible.bottom() {
+            for x in visible.left()..visible.right() {
+                *buf.get_mut(x, y) = scratch.get(x, y).clone();
+            }
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        if let Some((width, height)) = self.preferred_size {
+            return bc.constrain(Size::new(width as f64, height as f64));
+        }
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            bc.min().width
+        };
+        let height = if bc.is_height_bounded() {
+            bc.max().height
+        } else {
+            bc.min().height
+        };
+        Size::new(width, height)
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
+
+    fn lifecycle(&mut self, _cx: &mut LifeCycleCx, _event: &LifeCycle) {}
+}
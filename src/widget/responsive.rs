@@ -0,0 +1,113 @@
+use crate::geometry::{Point, Size};
+
+use super::{
+    core::{EventCx, IdPath, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, Message, Pod, Widget,
+};
+
+/// Reports the space that's available for its content to its view, so that the content can be
+/// rebuilt for it.
+///
+/// The size is only reported when it crosses one of the width breakpoints
+/// (or whenever it changes if there are none), which keeps the number of rebuilds low
+/// and can't lead to a layout loop, as the available space doesn't depend on the content.
+pub struct Responsive {
+    id_path: IdPath,
+    pub(crate) content: Pod,
+    breakpoints: Vec<f64>,
+    /// The last size that was sent to the view
+    reported: Option<Size>,
+}
+
+/// Whether a change of the available size from `old` to `new` has to be reported.
+fn crosses_breakpoint(breakpoints: &[f64], old: Size, new: Size) -> bool {
+    if breakpoints.is_empty() {
+        old != new
+    } else {
+        let category = |size: Size| breakpoints.iter().filter(|b| size.width >= **b).count();
+        category(old) != category(new)
+    }
+}
+
+impl Responsive {
+    pub(crate) fn new(id_path: &IdPath, content: impl Widget, breakpoints: Vec<f64>) -> Self {
+        Responsive {
+            id_path: id_path.clone(),
+            content: Pod::new(content),
+            breakpoints,
+            reported: None,
+        }
+    }
+
+    pub(crate) fn set_breakpoints(&mut self, breakpoints: &[f64]) -> ChangeFlags {
+        if self.breakpoints != breakpoints {
+            self.breakpoints = breakpoints.to_vec();
+            // Forces reporting the current size again in the next layout
+            self.reported = None;
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+impl Widget for Responsive {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.content.paint(cx)
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let available = bc.max();
+        // Unbounded constraints, e.g. while a weighted layout measures the minimum size of its
+        // children, don't say anything about the available space
+        let report = available.is_finite()
+            && match self.reported {
+                Some(old) => crosses_breakpoint(&self.breakpoints, old, available),
+                None => true,
+            };
+        if report {
+            self.reported = Some(available);
+            cx.add_message(Message::new(self.id_path.clone(), available));
+        }
+        let size = self.content.layout(cx, bc);
+        self.content.set_origin(cx, Point::ORIGIN);
+        size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.content.event(cx, event)
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        self.content.lifecycle(cx, event)
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_only_when_a_breakpoint_is_crossed() {
+        let size = |width| Size::new(width, 20.0);
+        assert!(crosses_breakpoint(&[], size(50.0), size(51.0)));
+        assert!(!crosses_breakpoint(&[], size(50.0), size(50.0)));
+
+        let breakpoints = [60.0, 100.0];
+        assert!(!crosses_breakpoint(&breakpoints, size(40.0), size(59.0)));
+        assert!(crosses_breakpoint(&breakpoints, size(59.0), size(60.0)));
+        assert!(!crosses_breakpoint(&breakpoints, size(60.0), size(99.0)));
+        assert!(crosses_breakpoint(&breakpoints, size(99.0), size(120.0)));
+        assert!(crosses_breakpoint(&breakpoints, size(120.0), size(40.0)));
+        // Only the width is relevant for the breakpoints
+        assert!(!crosses_breakpoint(
+            &breakpoints,
+            size(70.0),
+            Size::new(70.0, 5.0)
+        ));
+    }
+}
@@ -0,0 +1,220 @@
+use crate::geometry::{Point, Size};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, Pod, Widget,
+};
+
+pub struct Scaffold {
+    /// The header children, followed by the body and the footer children
+    pub(crate) children: Vec<Pod>,
+    pub(crate) header_count: usize,
+    header_height: Option<f64>,
+    footer_height: Option<f64>,
+}
+
+impl Scaffold {
+    pub(crate) fn new(
+        children: Vec<Pod>,
+        header_count: usize,
+        header_height: Option<f64>,
+        footer_height: Option<f64>,
+    ) -> Self {
+        Scaffold {
+            children,
+            header_count,
+            header_height,
+            footer_height,
+        }
+    }
+
+    pub(crate) fn set_heights(
+        &mut self,
+        header_height: Option<f64>,
+        footer_height: Option<f64>,
+    ) -> ChangeFlags {
+        if self.header_height != header_height || self.footer_height != footer_height {
+            self.header_height = header_height;
+            self.footer_height = footer_height;
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+/// Lays out the children of the header or footer below each other and returns the height of the region.
+///
+/// A fixed `height` is reserved completely, even if the children don't need it.
+fn layout_region(
+    children: &mut [Pod],
+    cx: &mut LayoutCx,
+    bc: &BoxConstraints,
+    height: Option<f64>,
+    available: f64,
+) -> f64 {
+    let max_height = height.unwrap_or(available).min(available);
+    let mut used: f64 = 0.0;
+    for child in children {
+        let size = child.layout(cx, &bc.shrink_max_height_to(max_height - used));
+        used += size.height;
+    }
+    if height.is_some() {
+        max_height
+    } else {
+        used.min(max_height)
+    }
+}
+
+fn stack_children(children: &mut [Pod], cx: &mut LayoutCx, mut y: f64) {
+    for child in children {
+        child.set_origin(cx, Point::new(0.0, y));
+        y += child.size().height;
+    }
+}
+
+impl Widget for Scaffold {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        for child in self.children.iter_mut() {
+            child.paint(cx);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let bc = bc.loosen();
+        let (header, rest) = self.children.split_at_mut(self.header_count);
+        let (body, footer) = rest.split_at_mut(1);
+        let body = &mut body[0];
+
+        // The fixed regions first, the body gets the remaining space
+        let max_height = bc.max().height;
+        let header_height = layout_region(header, cx, &bc, self.header_height, max_height);
+        let footer_height = layout_region(
+            footer,
+            cx,
+            &bc,
+            self.footer_height,
+            max_height - header_height,
+        );
+        let body_bc = bc.shrink_max_height_to(max_height - header_height - footer_height);
+        let body_size = body.layout(cx, &body_bc);
+
+        let height = if bc.is_height_bounded() {
+            max_height
+        } else {
+            header_height + body_size.height + footer_height
+        };
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            header
+                .iter()
+                .chain(footer.iter())
+                .map(|child| child.size().width)
+                .fold(body_size.width, f64::max)
+        };
+
+        stack_children(header, cx, 0.0);
+        body.set_origin(cx, Point::new(0.0, header_height));
+        stack_children(footer, cx, height - footer_height);
+
+        Size::new(width, height)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        for child in &mut self.children {
+            child.event(cx, event);
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        for child in &mut self.children {
+            child.lifecycle(cx, event);
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        &self.children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{App, BorderKind, View, ViewExt};
+
+    fn screen_lines<T: Send + 'static, V: View<T> + 'static>(
+        app: &App<T, V>,
+        height: u16,
+    ) -> Vec<String> {
+        (0..height).map(|y| app.screen_line(y)).collect()
+    }
+
+    #[tokio::test]
+    async fn the_body_gets_the_space_which_is_left_by_the_header_and_footer() {
+        let mut app = App::new((), |_: &mut ()| {
+            // a bordered box which fills the space it gets
+            let body = "body".border(BorderKind::Straight).fill_max_size(1.0);
+            crate::scaffold(body)
+                .header("title")
+                .footer(("status", "keys"))
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(10, 6);
+        app.render_once().await.unwrap();
+        assert_eq!(
+            screen_lines(&app, 6),
+            [
+                "title",
+                "┌────────┐",
+                "│body    │",
+                "└────────┘",
+                "status",
+                "keys"
+            ]
+        );
+
+        // the footer stays at the bottom, the body grows
+        app.config.terminal.backend_mut().resize(10, 8);
+        app.render_once().await.unwrap();
+        assert_eq!(
+            screen_lines(&app, 8),
+            [
+                "title",
+                "┌────────┐",
+                "│body    │",
+                "│        │",
+                "│        │",
+                "└────────┘",
+                "status",
+                "keys"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn fixed_heights_are_reserved_even_if_they_are_not_needed() {
+        let mut app = App::new((), |_: &mut ()| {
+            // a bordered box which fills the space it gets
+            let body = "body".border(BorderKind::Straight).fill_max_size(1.0);
+            crate::scaffold(body)
+                .header("title")
+                .header_height(2)
+                .footer("status")
+                .footer_height(1)
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(10, 6);
+        app.render_once().await.unwrap();
+        assert_eq!(
+            screen_lines(&app, 6),
+            [
+                "title",
+                "",
+                "┌────────┐",
+                "│body    │",
+                "└────────┘",
+                "status"
+            ]
+        );
+    }
+}
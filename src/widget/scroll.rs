@@ -0,0 +1,558 @@
+use std::time::{Duration, Instant};
+
+use ratatui::buffer::Buffer;
+use xilem_core::Id;
+
+use crate::{
+    geometry::{to_ratatui_rect, Point, Rect, Size, Vec2},
+    OverflowIndicator, ScrollAxes,
+};
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    paint_overflow_indicator, BoxConstraints, ChangeFlags, Event, Key, KeyEvent, LayoutCx,
+    LifeCycle, Modifiers, MouseKind, Pod, RawMouseEvent, Widget,
+};
+
+/// Lines (or columns) that are scrolled with a notch of the mouse wheel by default
+pub(crate) const SCROLL_STEP: u16 = 3;
+
+/// Wheel notches in the same direction within this time accelerate the scrolling,
+/// after a longer pause the step is reset.
+const WHEEL_ACCELERATION_TIMEOUT: Duration = Duration::from_millis(150);
+
+/// The accelerated step is at most this multiple of the scroll step
+const MAX_WHEEL_ACCELERATION: f64 = 4.0;
+
+/// Shows a section of its content, which may be larger than the scroll view along the scrolled axes.
+pub struct Scroll {
+    content: Pod,
+    axes: ScrollAxes,
+    /// The position of the section of the content that is shown
+    offset: Vec2,
+    content_size: Size,
+    viewport: Size,
+    /// Requests of the app logic, which are applied in the next layout pass
+    requests: Vec<ScrollRequest>,
+    /// Lines (or columns) that are scrolled with a notch of the mouse wheel
+    wheel_step: f64,
+    wheel_acceleration: bool,
+    /// The direction and time of the last wheel notch and how many notches quickly followed each other
+    wheel_streak: Option<(MouseKind, Instant, u32)>,
+    overflow_indicator: Option<OverflowIndicator>,
+}
+
+/// A programmatic change of the offset of a [`Scroll`], see [`ScrollController`](crate::ScrollController).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ScrollRequest {
+    /// Scrolls to a fraction (`0.0` is the start, `1.0` the end) of the scrollable range of an axis
+    Fraction { x: Option<f64>, y: Option<f64> },
+    /// Scrolls as little as possible to show the child with this index of the content
+    RevealChild(usize),
+    /// Scrolls as little as possible to show the descendant with this id
+    Reveal(Id),
+}
+
+impl Scroll {
+    pub(crate) fn new(content: impl Widget, axes: ScrollAxes) -> Self {
+        Scroll {
+            content: Pod::new(content),
+            axes,
+            offset: Vec2::ZERO,
+            content_size: Size::ZERO,
+            viewport: Size::ZERO,
+            requests: Vec::new(),
+            wheel_step: SCROLL_STEP as f64,
+            wheel_acceleration: false,
+            wheel_streak: None,
+            overflow_indicator: None,
+        }
+    }
+
+    pub(crate) fn content(&mut self) -> &mut Pod {
+        &mut self.content
+    }
+
+    pub(crate) fn set_axes(&mut self, axes: ScrollAxes) -> ChangeFlags {
+        if self.axes != axes {
+            self.axes = axes;
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// Only affects the next wheel events, nothing has to be repainted.
+    pub(crate) fn set_wheel_step(&mut self, step: u16, acceleration: bool) {
+        self.wheel_step = step.max(1) as f64;
+        self.wheel_acceleration = acceleration;
+        if !acceleration {
+            self.wheel_streak = None;
+        }
+    }
+
+    pub(crate) fn set_overflow_indicator(
+        &mut self,
+        overflow_indicator: Option<OverflowIndicator>,
+    ) -> ChangeFlags {
+        if self.overflow_indicator != overflow_indicator {
+            self.overflow_indicator = overflow_indicator;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn request_scroll(&mut self, requests: Vec<ScrollRequest>) -> ChangeFlags {
+        if requests.is_empty() {
+            return ChangeFlags::empty();
+        }
+        self.requests.extend(requests);
+        ChangeFlags::LAYOUT
+    }
+
+    /// The offset after `request`, which is clamped afterwards, `None` if it refers to a widget
+    /// which isn't in the content.
+    fn requested_offset(&self, request: ScrollRequest, viewport: Size) -> Option<Vec2> {
+        let range = self.content_size - viewport;
+        let rect = match request {
+            ScrollRequest::Fraction { x, y } => {
+                return Some(Vec2::new(
+                    x.map_or(self.offset.x, |x| x.clamp(0.0, 1.0) * range.width),
+                    y.map_or(self.offset.y, |y| y.clamp(0.0, 1.0) * range.height),
+                ))
+            }
+            ScrollRequest::RevealChild(index) => {
+                let child = self.content.widget.children().get(index)?;
+                Rect::from_origin_size(child.state.origin, child.state.size)
+            }
+            ScrollRequest::Reveal(id) => descendant_rect(&self.content, id, Point::ORIGIN)?,
+        };
+        Some(Vec2::new(
+            reveal_offset(self.offset.x, viewport.width, rect.x0, rect.x1),
+            reveal_offset(self.offset.y, viewport.height, rect.y0, rect.y1),
+        ))
+    }
+
+    /// Scrolls by `delta` along the enabled axes, returns whether the offset changed.
+    fn scroll_by(&mut self, viewport: Size, delta: Vec2) -> bool {
+        let mut offset = self.offset;
+        if self.axes.contains(ScrollAxes::HORIZONTAL) {
+            offset.x += delta.x;
+        }
+        if self.axes.contains(ScrollAxes::VERTICAL) {
+            offset.y += delta.y;
+        }
+        let offset = clamp_offset(offset, self.content_size, viewport);
+        let changed = offset != self.offset;
+        self.offset = offset;
+        changed
+    }
+
+    /// Whether the content is larger than the viewport along one of the scrolled axes.
+    fn overflows(&self) -> bool {
+        (self.axes.contains(ScrollAxes::HORIZONTAL)
+            && self.content_size.width > self.viewport.width)
+            || (self.axes.contains(ScrollAxes::VERTICAL)
+                && self.content_size.height > self.viewport.height)
+    }
+
+    /// The step of a wheel notch at `now`, which grows while notches in the same direction
+    /// quickly follow each other, if the acceleration is enabled.
+    fn wheel_step(&mut self, kind: MouseKind, now: Instant) -> f64 {
+        if !self.wheel_acceleration {
+            return self.wheel_step;
+        }
+        let streak = match self.wheel_streak {
+            Some((last_kind, last, streak))
+                if last_kind == kind
+                    && now.saturating_duration_since(last) <= WHEEL_ACCELERATION_TIMEOUT =>
+            {
+                streak + 1
+            }
+            _ => 0,
+        };
+        self.wheel_streak = Some((kind, now, streak));
+        let acceleration = (1.0 + streak as f64 * 0.5).min(MAX_WHEEL_ACCELERATION);
+        (self.wheel_step * acceleration).round()
+    }
+
+    /// The delta of a scroll wheel event, which scrolls horizontally with `Shift`
+    /// or if only the horizontal axis is enabled.
+    fn wheel_delta(&mut self, kind: MouseKind, modifiers: Modifiers, now: Instant) -> Option<Vec2> {
+        let horizontal =
+            modifiers.contains(Modifiers::SHIFT) || !self.axes.contains(ScrollAxes::VERTICAL);
+        if !matches!(
+            kind,
+            MouseKind::ScrollDown
+                | MouseKind::ScrollUp
+                | MouseKind::ScrollRight
+                | MouseKind::ScrollLeft
+        ) {
+            return None;
+        }
+        let step = self.wheel_step(kind, now);
+        let step = match kind {
+            MouseKind::ScrollDown => step,
+            MouseKind::ScrollUp => -step,
+            MouseKind::ScrollRight => return Some(Vec2::new(step, 0.0)),
+            _ => return Some(Vec2::new(-step, 0.0)),
+        };
+        Some(if horizontal {
+            Vec2::new(step, 0.0)
+        } else {
+            Vec2::new(0.0, step)
+        })
+    }
+}
+
+/// The delta of a navigation key, `Home`/`End` jump to the start/end and `PageUp`/`PageDown`
+/// scroll by the `viewport`, keeping one line (or column) of the previous page as context.
+///
+/// These keys scroll vertically, unless only the horizontal axis is enabled.
+fn key_delta(axes: ScrollAxes, code: Key, content: Size, viewport: Size) -> Option<Vec2> {
+    let horizontal = !axes.contains(ScrollAxes::VERTICAL);
+    let (content, viewport) = if horizontal {
+        (content.width, viewport.width)
+    } else {
+        (content.height, viewport.height)
+    };
+    let page = (viewport - 1.0).max(1.0);
+    let delta = match code {
+        Key::Up if !horizontal => -1.0,
+        Key::Down if !horizontal => 1.0,
+        Key::Left => return Some(Vec2::new(-1.0, 0.0)),
+        Key::Right => return Some(Vec2::new(1.0, 0.0)),
+        Key::PageUp => -page,
+        Key::PageDown => page,
+        // The offset is clamped afterwards
+        Key::Home => -content,
+        Key::End => content,
+        _ => return None,
+    };
+    Some(if horizontal {
+        Vec2::new(delta, 0.0)
+    } else {
+        Vec2::new(0.0, delta)
+    })
+}
+
+/// The rect of the widget `id` within `pod` (which is at `origin`) relative to the origin of the content.
+fn descendant_rect(pod: &Pod, id: Id, origin: Point) -> Option<Rect> {
+    if pod.id() == id {
+        return Some(Rect::from_origin_size(origin, pod.state.size));
+    }
+    pod.widget
+        .children()
+        .iter()
+        .find_map(|child| descendant_rect(child, id, origin + child.state.origin.to_vec2()))
+}
+
+/// The offset along an axis, which moves the range `start..end` into the viewport with as little
+/// scrolling as possible. If it's larger than the viewport, its start is shown.
+fn reveal_offset(offset: f64, viewport: f64, start: f64, end: f64) -> f64 {
+    if start < offset {
+        start
+    } else if end > offset + viewport {
+        (end - viewport).min(start)
+    } else {
+        offset
+    }
+}
+
+/// Clamps each axis of `offset` independently, so that the `viewport` is within the content,
+/// the offset of an axis along which the content fits in the viewport is zero.
+fn clamp_offset(offset: Vec2, content: Size, viewport: Size) -> Vec2 {
+    let max_x = (content.width - viewport.width).max(0.0);
+    let max_y = (content.height - viewport.height).max(0.0);
+    Vec2::new(
+        offset.x.clamp(0.0, max_x).round(),
+        offset.y.clamp(0.0, max_y).round(),
+    )
+}
+
+impl Widget for Scroll {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let fits = self.content_size.width <= rect.width as f64
+            && self.content_size.height <= rect.height as f64;
+        if fits {
+            self.content.paint(cx);
+            return;
+        }
+
+        let viewport = rect.intersection(cx.buffer.area);
+        if viewport.area() == 0 {
+            return;
+        }
+        // The content is painted into a buffer of the size of the viewport, which is placed at the
+        // visible section of the content, so that everything outside of it is clipped.
+        // Content beyond the range of the buffer coordinates is moved into it.
+        let visible = Point::new(
+            self.offset.x + (viewport.x - rect.x) as f64,
+            self.offset.y + (viewport.y - rect.y) as f64,
+        );
+        let mut offscreen = Buffer::empty(ratatui::layout::Rect {
+            x: visible.x.min((u16::MAX - viewport.width) as f64) as u16,
+            y: visible.y.min((u16::MAX - viewport.height) as f64) as u16,
+            ..viewport
+        });
+        let cells = |area: ratatui::layout::Rect| {
+            (area.top()..area.bottom())
+                .flat_map(move |y| (area.left()..area.right()).map(move |x| (x, y)))
+        };
+        // Keep what's painted below the scroll view, e.g. a background
+        for ((x, y), (ox, oy)) in cells(viewport).zip(cells(offscreen.area)) {
+            *offscreen.get_mut(ox, oy) = cx.buffer.get(x, y).clone();
+        }
+
+        let translation = Vec2::new(
+            offscreen.area.x as f64 - viewport.x as f64,
+            offscreen.area.y as f64 - viewport.y as f64,
+        );
+        cx.paint_offscreen(&mut self.content, &mut offscreen, translation, viewport);
+
+        for ((x, y), (ox, oy)) in cells(viewport).zip(cells(offscreen.area)) {
+            *cx.buffer.get_mut(x, y) = offscreen.get(ox, oy).clone();
+        }
+
+        if let Some(indicator) = &self.overflow_indicator {
+            let above = self.offset.y > 0.0;
+            let below = self.offset.y + (rect.height as f64) < self.content_size.height;
+            paint_overflow_indicator(cx.buffer, rect, indicator, above, below);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // The content isn't limited along the scrolled axes
+        let mut content_bc = *bc;
+        if self.axes.contains(ScrollAxes::HORIZONTAL) {
+            content_bc = content_bc.unbound_max_width();
+        }
+        if self.axes.contains(ScrollAxes::VERTICAL) {
+            content_bc = content_bc.unbound_max_height();
+        }
+        self.content_size = self.content.layout(cx, &content_bc);
+        let size = bc.constrain(self.content_size);
+
+        for request in std::mem::take(&mut self.requests) {
+            if let Some(offset) = self.requested_offset(request, size) {
+                // Only the enabled axes are changed, like when scrolling with the mouse or keyboard
+                if self.axes.contains(ScrollAxes::HORIZONTAL) {
+                    self.offset.x = offset.x;
+                }
+                if self.axes.contains(ScrollAxes::VERTICAL) {
+                    self.offset.y = offset.y;
+                }
+                self.offset = clamp_offset(self.offset, self.content_size, size);
+            }
+        }
+        self.offset = clamp_offset(self.offset, self.content_size, size);
+        self.viewport = size;
+        self.content
+            .set_origin(cx, Point::new(-self.offset.x, -self.offset.y));
+        size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.content.event(cx, event);
+        if cx.is_handled() {
+            return;
+        }
+
+        let viewport = cx.rect().size();
+        let delta = match event {
+            Event::Mouse(RawMouseEvent {
+                kind, modifiers, ..
+            }) if cx.is_hot() => self.wheel_delta(*kind, *modifiers, Instant::now()),
+            // Navigation keys which aren't handled by a focused descendant
+            Event::Key(KeyEvent { code, .. }) if cx.has_focus() => {
+                key_delta(self.axes, *code, self.content_size, viewport)
+            }
+            _ => None,
+        };
+        if let Some(delta) = delta {
+            if self.scroll_by(viewport, delta) {
+                cx.request_layout();
+                // Nested scroll views don't scroll at the same time
+                cx.set_handled(true);
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        self.content.lifecycle(cx, event);
+    }
+
+    fn accepts_focus(&self) -> bool {
+        // So that content without focusable widgets can be scrolled with the keyboard,
+        // content which fits doesn't need to be an additional tab stop
+        self.overflows()
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{test_key, test_mouse},
+        App, ViewExt,
+    };
+
+    #[test]
+    fn axes_are_clamped_independently() {
+        let viewport = Size::new(10.0, 5.0);
+        // only the height overflows
+        let content = Size::new(8.0, 20.0);
+        assert_eq!(
+            clamp_offset(Vec2::new(3.0, 30.0), content, viewport),
+            Vec2::new(0.0, 15.0)
+        );
+        assert_eq!(
+            clamp_offset(Vec2::new(-1.0, -1.0), content, viewport),
+            Vec2::ZERO
+        );
+        // only the width overflows
+        let content = Size::new(25.0, 5.0);
+        assert_eq!(
+            clamp_offset(Vec2::new(12.0, 2.0), content, viewport),
+            Vec2::new(12.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn pages_keep_a_line_of_context() {
+        let content = Size::new(10.0, 50.0);
+        let viewport = Size::new(10.0, 10.0);
+        let delta = |axes, code| key_delta(axes, code, content, viewport);
+        assert_eq!(
+            delta(ScrollAxes::VERTICAL, Key::PageDown),
+            Some(Vec2::new(0.0, 9.0))
+        );
+        assert_eq!(
+            delta(ScrollAxes::HORIZONTAL, Key::PageUp),
+            Some(Vec2::new(-9.0, 0.0))
+        );
+        assert_eq!(
+            delta(ScrollAxes::VERTICAL, Key::End),
+            Some(Vec2::new(0.0, 50.0))
+        );
+        assert_eq!(delta(ScrollAxes::HORIZONTAL, Key::Down), None);
+
+        // jumping to the end scrolls as far as possible
+        let end = delta(ScrollAxes::VERTICAL, Key::End).unwrap();
+        assert_eq!(clamp_offset(end, content, viewport), Vec2::new(0.0, 40.0));
+    }
+
+    #[test]
+    fn fast_wheel_scrolling_accelerates_until_a_pause() {
+        let mut scroll = Scroll::new(
+            crate::widget::WrappedText::new(Vec::new()),
+            ScrollAxes::VERTICAL,
+        );
+        scroll.set_wheel_step(2, true);
+        let now = Instant::now();
+        let step = |scroll: &mut Scroll, kind, ms| {
+            scroll.wheel_step(kind, now + Duration::from_millis(ms))
+        };
+        assert_eq!(step(&mut scroll, MouseKind::ScrollDown, 0), 2.0);
+        assert_eq!(step(&mut scroll, MouseKind::ScrollDown, 50), 3.0);
+        assert_eq!(step(&mut scroll, MouseKind::ScrollDown, 100), 4.0);
+        // the acceleration is limited
+        for ms in 1..20 {
+            step(&mut scroll, MouseKind::ScrollDown, 100 + ms * 10);
+        }
+        assert_eq!(step(&mut scroll, MouseKind::ScrollDown, 300), 8.0);
+        // changing the direction or pausing resets it
+        assert_eq!(step(&mut scroll, MouseKind::ScrollUp, 310), 2.0);
+        assert_eq!(step(&mut scroll, MouseKind::ScrollUp, 320), 3.0);
+        assert_eq!(step(&mut scroll, MouseKind::ScrollUp, 600), 2.0);
+    }
+
+    #[test]
+    fn reveals_with_as_little_scrolling_as_possible() {
+        // above the viewport
+        assert_eq!(reveal_offset(10.0, 5.0, 7.0, 8.0), 7.0);
+        // below the viewport
+        assert_eq!(reveal_offset(10.0, 5.0, 16.0, 18.0), 13.0);
+        // already visible
+        assert_eq!(reveal_offset(10.0, 5.0, 11.0, 14.0), 10.0);
+        // larger than the viewport
+        assert_eq!(reveal_offset(0.0, 5.0, 20.0, 30.0), 20.0);
+    }
+
+    #[tokio::test]
+    async fn only_the_section_of_the_content_in_the_viewport_is_painted() {
+        let mut app = App::new((), |_: &mut ()| {
+            crate::v_stack(
+                (0..10)
+                    .map(|i| format!("abc{i}defghij"))
+                    .collect::<Vec<_>>(),
+            )
+            .scroll(ScrollAxes::BOTH)
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(6, 4);
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "abc0de");
+
+        app.dispatch([
+            test_mouse(MouseKind::ScrollDown, 0, 1),
+            test_mouse(MouseKind::ScrollRight, 0, 1),
+        ])
+        .await;
+        let lines: Vec<_> = (0..4).map(|y| app.screen_line(y)).collect();
+        assert_eq!(lines, ["3defgh", "4defgh", "5defgh", "6defgh"]);
+    }
+
+    #[tokio::test]
+    async fn only_overflowing_scroll_views_are_focusable() {
+        let mut app = App::new((), |_: &mut ()| {
+            crate::v_stack((
+                "fits".scroll(ScrollAxes::VERTICAL),
+                crate::v_stack((0..10).map(|i| format!("Line {i}")).collect::<Vec<_>>())
+                    .scroll(ScrollAxes::VERTICAL),
+            ))
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(10, 4);
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(1), "Line 0");
+
+        // the first scroll view is skipped
+        app.dispatch([test_key(Key::Tab), test_key(Key::Down)])
+            .await;
+        assert_eq!(app.screen_line(0), "fits");
+        assert_eq!(app.screen_line(1), "Line 1");
+    }
+
+    #[tokio::test]
+    async fn the_overflow_indicator_shows_content_hidden_above_and_below() {
+        let mut app = App::new((), |_: &mut ()| {
+            crate::v_stack((0..10).map(|i| format!("Line {i}")).collect::<Vec<_>>())
+                .scroll(ScrollAxes::VERTICAL)
+                .overflow_indicator(OverflowIndicator::default())
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(10, 4);
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "Line 0");
+        assert_eq!(app.screen_line(3), "Line ▼");
+
+        app.dispatch([test_mouse(MouseKind::ScrollDown, 0, 1)])
+            .await;
+        assert_eq!(app.screen_line(0), "Line ▲");
+        assert_eq!(app.screen_line(1), "Line 4");
+        assert_eq!(app.screen_line(3), "Line ▼");
+
+        // the end of the content is reached
+        app.dispatch([test_mouse(MouseKind::ScrollDown, 0, 1)])
+            .await;
+        assert_eq!(app.screen_line(0), "Line ▲");
+        assert_eq!(app.screen_line(3), "Line 9");
+    }
+}
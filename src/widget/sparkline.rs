@@ -0,0 +1,134 @@
+use ratatui::{style::Style, symbols::bar};
+
+use crate::geometry::{to_ratatui_rect, Size};
+
+use super::{
+    core::{LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, EventCx, LayoutCx, LifeCycle, Widget,
+};
+
+pub struct Sparkline {
+    data: Vec<u64>,
+    max: Option<u64>,
+    style: Style,
+}
+
+impl Sparkline {
+    pub(crate) fn new(data: Vec<u64>, max: Option<u64>, style: Style) -> Self {
+        Sparkline { data, max, style }
+    }
+
+    pub(crate) fn set_data(&mut self, data: &[u64]) -> ChangeFlags {
+        if self.data != data {
+            // The width depends on the amount of data
+            let changeflags = if self.data.len() != data.len() {
+                ChangeFlags::LAYOUT | ChangeFlags::PAINT
+            } else {
+                ChangeFlags::PAINT
+            };
+            self.data = data.to_vec();
+            changeflags
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_max(&mut self, max: Option<u64>) -> ChangeFlags {
+        if self.max != max {
+            self.max = max;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) -> ChangeFlags {
+        if self.style != style {
+            self.style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+/// The length of a bar in eighths of a cell, when `max` fills `height` cells.
+pub(super) fn bar_height(value: u64, max: u64, height: u16) -> u64 {
+    if max == 0 {
+        return 0;
+    }
+    let ratio = value.min(max) as f64 / max as f64;
+    (ratio * height as f64 * 8.0).round() as u64
+}
+
+/// The glyph of a vertical bar which fills `eighths` of a cell (from the bottom).
+pub(super) fn bar_symbol(eighths: u64) -> &'static str {
+    match eighths {
+        0 => bar::NINE_LEVELS.empty,
+        1 => bar::NINE_LEVELS.one_eighth,
+        2 => bar::NINE_LEVELS.one_quarter,
+        3 => bar::NINE_LEVELS.three_eighths,
+        4 => bar::NINE_LEVELS.half,
+        5 => bar::NINE_LEVELS.five_eighths,
+        6 => bar::NINE_LEVELS.three_quarters,
+        7 => bar::NINE_LEVELS.seven_eighths,
+        _ => bar::NINE_LEVELS.full,
+    }
+}
+
+impl Widget for Sparkline {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let style = self.style.patch(cx.override_style());
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        if area.area() == 0 {
+            return;
+        }
+
+        // Only the most recent values are shown, when there's more data than space
+        let data = &self.data[self.data.len().saturating_sub(rect.width as usize)..];
+        let max = self
+            .max
+            .unwrap_or_else(|| data.iter().copied().max().unwrap_or(0));
+
+        for (x, value) in (rect.left()..).zip(data) {
+            let mut eighths = bar_height(*value, max, rect.height);
+            for y in (rect.top()..rect.bottom()).rev() {
+                if area.left() <= x && x < area.right() && area.top() <= y && y < area.bottom() {
+                    buf.get_mut(x, y)
+                        .set_symbol(bar_symbol(eighths))
+                        .set_style(style);
+                }
+                eighths = eighths.saturating_sub(8);
+            }
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // One column per value, the data is truncated in paint when there's not enough space
+        bc.constrain(Size::new(self.data.len() as f64, 1.0))
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
+
+    fn lifecycle(&mut self, _cx: &mut LifeCycleCx, _event: &LifeCycle) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars_are_scaled_to_max() {
+        assert_eq!(bar_height(0, 8, 1), 0);
+        assert_eq!(bar_height(4, 8, 1), 4);
+        assert_eq!(bar_height(8, 8, 1), 8);
+        // Values above the max are clamped
+        assert_eq!(bar_height(16, 8, 1), 8);
+        assert_eq!(bar_height(8, 8, 3), 24);
+        assert_eq!(bar_height(1, 0, 1), 0);
+        assert_eq!(bar_symbol(bar_height(2, 8, 1)), "▂");
+        assert_eq!(bar_symbol(bar_height(8, 8, 1)), "█");
+    }
+}
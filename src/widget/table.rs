@@ -0,0 +1,793 @@
+use std::{collections::HashSet, sync::Arc};
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Modifier, Style},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    geometry::{to_ratatui_rect, Size},
+    Alignment, Column, ColumnWidth, OverflowIndicator,
+};
+
+use super::{
+    core::{IdPath, LifeCycleCx, PaintCx},
+    paint_overflow_indicator, set_stringn_clipped, BoxConstraints, ChangeFlags, Event, EventCx,
+    Key, KeyEvent, LayoutCx, LifeCycle, Message, Modifiers, MouseButton, MouseKind, RawMouseEvent,
+    Widget,
+};
+
+/// Columns between two table columns
+const COLUMN_GAP: u16 = 1;
+/// Rows that are scrolled with the mouse wheel
+const SCROLL_STEP: usize = 3;
+
+/// The messages a [`Table`] sends to its view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TableMessage {
+    Select(usize),
+    Sort {
+        column: usize,
+        ascending: bool,
+    },
+    /// The set of selected rows in the multi-select mode
+    SelectionChange(HashSet<usize>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TableStyles {
+    pub(crate) header: Style,
+    pub(crate) text: Style,
+    pub(crate) selected: Style,
+}
+
+pub struct Table {
+    id_path: IdPath,
+    columns: Vec<Column>,
+    rows: Arc<Vec<Vec<String>>>,
+    /// The selected row, in the multi-select mode it's the row the keyboard cursor is on
+    selected: Option<usize>,
+    multi_select: bool,
+    /// The selected rows in the multi-select mode
+    selection: HashSet<usize>,
+    /// Where a range selection (with `Shift`) starts, with the selection before the range
+    anchor: Option<(usize, HashSet<usize>)>,
+    /// Whether selected rows were dropped, because the rows were removed
+    selection_pruned: bool,
+    /// The sorted column and whether it's sorted ascending
+    sort: Option<(usize, bool)>,
+    styles: TableStyles,
+    overflow_indicator: Option<OverflowIndicator>,
+    /// The index of the first visible row
+    first_row: usize,
+    /// The index of the first visible column, when the columns are wider than the table
+    first_column: usize,
+    widths: Vec<u16>,
+    size: Size,
+}
+
+/// The minimum width of a column with a relative width, its title and a sort indicator
+fn min_width(column: &Column) -> u16 {
+    column.title.width() as u16 + 2
+}
+
+/// The widths of `columns` in a table which is `available` cells wide.
+///
+/// If `available` is infinite, columns with a relative width get their minimum width.
+fn column_widths(columns: &[Column], available: f64) -> Vec<u16> {
+    if !available.is_finite() {
+        return columns
+            .iter()
+            .map(|column| match column.width {
+                ColumnWidth::Fixed(width) => width,
+                _ => min_width(column),
+            })
+            .collect();
+    }
+    let gaps = COLUMN_GAP as u32 * (columns.len() as u32).saturating_sub(1);
+    let usable = (available.max(0.0) as u32).saturating_sub(gaps);
+    let mut widths: Vec<u32> = columns
+        .iter()
+        .map(|column| match column.width {
+            ColumnWidth::Fixed(width) => width as u32,
+            ColumnWidth::Percent(percent) => usable * percent.min(100) as u32 / 100,
+            ColumnWidth::Weight(_) => 0,
+        })
+        .collect();
+    let remaining = usable.saturating_sub(widths.iter().sum());
+    let total_weight: u32 = columns
+        .iter()
+        .filter_map(|column| match column.width {
+            ColumnWidth::Weight(weight) => Some(weight as u32),
+            _ => None,
+        })
+        .sum();
+    // The shares are computed cumulatively, so that they add up to the remaining width exactly
+    let mut cumulative_weight = 0;
+    let mut assigned = 0;
+    for (width, column) in widths.iter_mut().zip(columns) {
+        if let ColumnWidth::Weight(weight) = column.width {
+            cumulative_weight += weight as u32;
+            let share = (remaining * cumulative_weight)
+                .checked_div(total_weight)
+                .unwrap_or(0)
+                - assigned;
+            assigned += share;
+            *width = share.max(min_width(column) as u32);
+        }
+    }
+    widths
+        .into_iter()
+        .map(|width| width.min(u16::MAX as u32) as u16)
+        .collect()
+}
+
+/// The rows in `base` and all rows between `anchor` and `row` (inclusive).
+fn extend_selection(base: &HashSet<usize>, anchor: usize, row: usize) -> HashSet<usize> {
+    let range = anchor.min(row)..=anchor.max(row);
+    base.iter().copied().chain(range).collect()
+}
+
+/// Draws `text` into the `width` cells at `x`, `y`, truncated if it doesn't fit.
+fn draw_cell(buf: &mut Buffer, x: u16, y: u16, width: u16, text: &str, alignment: Alignment) {
+    let free = width.saturating_sub(text.width() as u16);
+    let offset = match alignment {
+        Alignment::Start => 0,
+        Alignment::Center => free / 2,
+        Alignment::End => free,
+    };
+    set_stringn_clipped(
+        buf,
+        x + offset,
+        y,
+        text,
+        (width - offset) as usize,
+        Style::default(),
+    );
+}
+
+impl Table {
+    pub(crate) fn new(
+        id_path: &IdPath,
+        columns: Vec<Column>,
+        rows: Arc<Vec<Vec<String>>>,
+        selected: Option<usize>,
+        sort: Option<(usize, bool)>,
+        styles: TableStyles,
+    ) -> Self {
+        Table {
+            id_path: id_path.clone(),
+            columns,
+            rows,
+            selected,
+            multi_select: false,
+            selection: HashSet::new(),
+            anchor: None,
+            selection_pruned: false,
+            sort,
+            styles,
+            overflow_indicator: None,
+            first_row: 0,
+            first_column: 0,
+            widths: Vec::new(),
+            size: Size::ZERO,
+        }
+    }
+
+    pub(crate) fn set_columns(&mut self, columns: &[Column]) -> ChangeFlags {
+        if self.columns != columns {
+            self.columns = columns.to_vec();
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_rows(&mut self, rows: &Arc<Vec<Vec<String>>>) -> ChangeFlags {
+        if !Arc::ptr_eq(&self.rows, rows) && self.rows != *rows {
+            self.rows = rows.clone();
+            // The change is reported in the layout pass
+            let len = self.selection.len();
+            self.selection.retain(|row| *row < rows.len());
+            self.selection_pruned |= self.selection.len() != len;
+            if matches!(self.anchor, Some((anchor, _)) if anchor >= rows.len()) {
+                self.anchor = None;
+            }
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_selected(&mut self, selected: Option<usize>) -> ChangeFlags {
+        if self.selected != selected {
+            self.selected = selected;
+            // The layout scrolls to the selected row
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_multi_select(&mut self, multi_select: bool) -> ChangeFlags {
+        if self.multi_select != multi_select {
+            self.multi_select = multi_select;
+            self.anchor = None;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_selection(&mut self, mut selection: HashSet<usize>) -> ChangeFlags {
+        selection.retain(|row| *row < self.rows.len());
+        if self.selection != selection {
+            self.selection = selection;
+            self.anchor = None;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_sort(&mut self, sort: Option<(usize, bool)>) -> ChangeFlags {
+        if self.sort != sort {
+            self.sort = sort;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_styles(&mut self, styles: TableStyles) -> ChangeFlags {
+        if self.styles != styles {
+            self.styles = styles;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_overflow_indicator(
+        &mut self,
+        overflow_indicator: Option<OverflowIndicator>,
+    ) -> ChangeFlags {
+        if self.overflow_indicator != overflow_indicator {
+            self.overflow_indicator = overflow_indicator;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// The number of rows below the header, that fit into the table
+    fn visible_rows(&self) -> usize {
+        (self.size.height as usize).saturating_sub(1)
+    }
+
+    /// Clamps the scroll offsets and scrolls the selected row into view.
+    fn scroll_to_selected(&mut self) {
+        let visible = self.visible_rows().max(1);
+        if let Some(selected) = self.selected {
+            if selected < self.first_row {
+                self.first_row = selected;
+            } else if selected >= self.first_row + visible {
+                self.first_row = selected + 1 - visible;
+            }
+        }
+        self.clamp_first_row();
+        self.first_column = self.first_column.min(self.columns.len().saturating_sub(1));
+    }
+
+    /// Whether the visible columns, starting at the first visible one, are wider than the table
+    fn overflows(&self) -> bool {
+        let width: u32 = self.widths[self.first_column.min(self.widths.len())..]
+            .iter()
+            .map(|w| *w as u32 + COLUMN_GAP as u32)
+            .sum();
+        width.saturating_sub(COLUMN_GAP as u32) as f64 > self.size.width
+    }
+
+    /// The visible columns with their (local) x positions and visible widths.
+    fn visible_columns(&self) -> impl Iterator<Item = (usize, u16, u16)> + '_ {
+        let table_width = self.size.width as u16;
+        let mut x = 0u16;
+        // The widths are only updated in the layout, after the columns changed
+        (self.first_column..self.columns.len().min(self.widths.len()))
+            .map(move |column| {
+                let start = x;
+                let width = self.widths[column].min(table_width.saturating_sub(start));
+                x = x
+                    .saturating_add(self.widths[column])
+                    .saturating_add(COLUMN_GAP);
+                (column, start, width)
+            })
+            .take_while(move |(_, start, _)| *start < table_width)
+    }
+
+    fn select(&mut self, cx: &mut EventCx, row: usize) {
+        if self.selected != Some(row) {
+            self.selected = Some(row);
+            self.scroll_to_selected();
+            cx.add_message(Message::new(
+                self.id_path.clone(),
+                TableMessage::Select(row),
+            ));
+            cx.request_paint();
+        }
+    }
+
+    fn set_selection_and_notify(&mut self, cx: &mut EventCx, selection: HashSet<usize>) {
+        if self.selection != selection {
+            self.selection = selection;
+            cx.add_message(Message::new(
+                self.id_path.clone(),
+                TableMessage::SelectionChange(self.selection.clone()),
+            ));
+            cx.request_paint();
+        }
+    }
+
+    /// Selects the rows from the anchor (or the current row) to `row` in the multi-select mode.
+    fn extend_selection_to(&mut self, cx: &mut EventCx, row: usize) {
+        if self.anchor.is_none() {
+            self.anchor = Some((self.selected.unwrap_or(row), self.selection.clone()));
+        }
+        let Some((anchor, base)) = &self.anchor else {
+            return;
+        };
+        let selection = extend_selection(base, *anchor, row);
+        self.select(cx, row);
+        self.set_selection_and_notify(cx, selection);
+    }
+
+    /// Toggles whether `row` is selected in the multi-select mode, a following range starts there.
+    fn toggle_selection(&mut self, cx: &mut EventCx, row: usize) {
+        let mut selection = self.selection.clone();
+        if !selection.remove(&row) {
+            selection.insert(row);
+        }
+        self.anchor = Some((row, selection.clone()));
+        self.set_selection_and_notify(cx, selection);
+    }
+
+    /// The row that's selected with `code`, if it's a navigation key.
+    fn navigate(&self, code: Key) -> Option<usize> {
+        let last = self.rows.len().checked_sub(1)?;
+        let page = self.visible_rows().max(1);
+        let row = match (code, self.selected) {
+            (Key::Home, _) => 0,
+            (Key::End, _) => last,
+            (Key::Up | Key::Down | Key::PageUp | Key::PageDown, None) => 0,
+            (Key::Up, Some(row)) => row.saturating_sub(1),
+            (Key::Down, Some(row)) => row + 1,
+            (Key::PageUp, Some(row)) => row.saturating_sub(page),
+            (Key::PageDown, Some(row)) => row + page,
+            _ => return None,
+        };
+        Some(row.min(last))
+    }
+
+    fn scroll_columns(&mut self, cx: &mut EventCx, forward: bool) -> bool {
+        if forward && self.overflows() {
+            self.first_column += 1;
+        } else if !forward && self.first_column > 0 {
+            self.first_column -= 1;
+        } else {
+            return false;
+        }
+        cx.request_paint();
+        true
+    }
+
+    fn sort_by(&mut self, cx: &mut EventCx, column: usize) {
+        // The first click sorts ascending, the next one toggles the order
+        let ascending = !matches!(self.sort, Some((sorted, true)) if sorted == column);
+        self.sort = Some((column, ascending));
+        cx.add_message(Message::new(
+            self.id_path.clone(),
+            TableMessage::Sort { column, ascending },
+        ));
+        cx.request_paint();
+    }
+
+    fn mouse_event(&mut self, cx: &mut EventCx, mouse: &RawMouseEvent) {
+        // The table also gets the event which moves the mouse out of it, e.g. a click elsewhere
+        if !cx.is_hot() {
+            return;
+        }
+        // Mouse events are relative to the origin of the widget
+        let (Ok(x), Ok(y)) = (u16::try_from(mouse.column), usize::try_from(mouse.row)) else {
+            return;
+        };
+        match mouse.kind {
+            MouseKind::Down(MouseButton::Left) if y == 0 => {
+                cx.request_focus();
+                let column = self
+                    .visible_columns()
+                    .find(|(_, start, width)| (*start..start + width).contains(&x))
+                    .map(|(column, ..)| column);
+                if let Some(column) = column.filter(|c| self.columns[*c].sortable) {
+                    self.sort_by(cx, column);
+                }
+            }
+            MouseKind::Down(MouseButton::Left) => {
+                cx.request_focus();
+                // `y` is at least 1 here, the header is in the row 0
+                let row = self.first_row + (y - 1);
+                if row >= self.rows.len() {
+                    return;
+                }
+                if !self.multi_select {
+                    self.select(cx, row);
+                } else if mouse.modifiers.contains(Modifiers::SHIFT) {
+                    self.extend_selection_to(cx, row);
+                } else if mouse.modifiers.contains(Modifiers::CONTROL) {
+                    self.select(cx, row);
+                    self.toggle_selection(cx, row);
+                } else {
+                    self.select(cx, row);
+                    let selection = HashSet::from([row]);
+                    self.anchor = Some((row, HashSet::new()));
+                    self.set_selection_and_notify(cx, selection);
+                }
+            }
+            MouseKind::ScrollDown => {
+                self.first_row += SCROLL_STEP;
+                self.clamp_first_row();
+                cx.request_paint();
+            }
+            MouseKind::ScrollUp => {
+                self.first_row = self.first_row.saturating_sub(SCROLL_STEP);
+                cx.request_paint();
+            }
+            MouseKind::ScrollRight => {
+                self.scroll_columns(cx, true);
+            }
+            MouseKind::ScrollLeft => {
+                self.scroll_columns(cx, false);
+            }
+            _ => (),
+        }
+    }
+
+    /// Clamps the first visible row, so that there are no empty rows below the last one.
+    fn clamp_first_row(&mut self) {
+        self.first_row = self
+            .first_row
+            .min(self.rows.len().saturating_sub(self.visible_rows()));
+    }
+}
+
+impl Widget for Table {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let columns: Vec<_> = self.visible_columns().collect();
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        if area.area() == 0 {
+            return;
+        }
+
+        buf.set_style(area, self.styles.text);
+        // The header is hidden when the table is partly scrolled out of view
+        if area.top() == rect.y {
+            let header = ratatui::layout::Rect { height: 1, ..area };
+            buf.set_style(header, self.styles.header);
+            for &(column, x, width) in &columns {
+                let title = &self.columns[column].title;
+                let title = match self.sort {
+                    Some((sorted, ascending)) if sorted == column => {
+                        format!("{title} {}", if ascending { "▲" } else { "▼" })
+                    }
+                    _ => title.to_string(),
+                };
+                draw_cell(buf, rect.x + x, rect.y, width, &title, Alignment::Start);
+            }
+        }
+
+        let hidden_rows = area.top().saturating_sub(rect.y + 1) as usize;
+        let rows = (self.first_row..self.rows.len()).zip(rect.y + 1..area.bottom());
+        for (row, y) in rows.skip(hidden_rows) {
+            let line = ratatui::layout::Rect {
+                y,
+                height: 1,
+                ..area
+            };
+            if !self.multi_select && Some(row) == self.selected {
+                buf.set_style(line, self.styles.selected);
+            } else if self.multi_select {
+                if self.selection.contains(&row) {
+                    buf.set_style(line, self.styles.selected);
+                }
+                // The row of the keyboard cursor
+                if Some(row) == self.selected {
+                    buf.set_style(line, Style::default().add_modifier(Modifier::UNDERLINED));
+                }
+            }
+            for &(column, x, width) in &columns {
+                if let Some(cell) = self.rows[row].get(column) {
+                    let alignment = self.columns[column].alignment;
+                    draw_cell(buf, rect.x + x, y, width, cell, alignment);
+                }
+            }
+        }
+
+        if let Some(indicator) = &self.overflow_indicator {
+            let rows = ratatui::layout::Rect {
+                y: rect.y + 1,
+                height: rect.height - 1,
+                ..rect
+            };
+            let below = self.first_row + (rows.height as usize) < self.rows.len();
+            paint_overflow_indicator(buf, rows, indicator, self.first_row > 0, below);
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        if std::mem::take(&mut self.selection_pruned) {
+            cx.add_message(Message::new(
+                self.id_path.clone(),
+                TableMessage::SelectionChange(self.selection.clone()),
+            ));
+        }
+        self.widths = column_widths(&self.columns, bc.max().width);
+        let width = if bc.max().width.is_finite() {
+            bc.max().width
+        } else {
+            let widths: u32 = self.widths.iter().map(|w| *w as u32).sum();
+            (widths + COLUMN_GAP as u32 * (self.widths.len() as u32).saturating_sub(1)) as f64
+        };
+        // Only the number of rows is relevant for the layout, not their content
+        self.size = bc.constrain(Size::new(width, (self.rows.len() + 1) as f64));
+        self.scroll_to_selected();
+        self.size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        match event {
+            Event::Key(KeyEvent {
+                code, modifiers, ..
+            }) if cx.is_focused() => {
+                let handled = match code {
+                    Key::Left => self.scroll_columns(cx, false),
+                    Key::Right => self.scroll_columns(cx, true),
+                    Key::Char(' ') if self.multi_select => match self.selected {
+                        Some(row) => {
+                            self.toggle_selection(cx, row);
+                            true
+                        }
+                        None => false,
+                    },
+                    code => match self.navigate(*code) {
+                        Some(row) if self.multi_select && modifiers.contains(Modifiers::SHIFT) => {
+                            self.extend_selection_to(cx, row);
+                            true
+                        }
+                        Some(row) => {
+                            self.select(cx, row);
+                            true
+                        }
+                        None => false,
+                    },
+                };
+                if handled {
+                    cx.set_handled(true);
+                }
+            }
+            Event::Mouse(mouse) => self.mouse_event(cx, mouse),
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, _cx: &mut LifeCycleCx, _event: &LifeCycle) {}
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        app::{test_key, test_mouse},
+        widget::{CxState, FocusState, WidgetState},
+        App,
+    };
+
+    #[test]
+    fn column_widths_follow_the_width_rules() {
+        let columns = [
+            Column::new("id").width(ColumnWidth::Fixed(4)),
+            Column::new("name").width(ColumnWidth::Percent(50)),
+            Column::new("a").width(ColumnWidth::Weight(1)),
+            Column::new("b").width(ColumnWidth::Weight(2)),
+        ];
+        // 3 gaps, 50% of 100 usable cells, the remaining 46 are shared 1:2
+        assert_eq!(column_widths(&columns, 103.0), vec![4, 50, 15, 31]);
+        // Columns with a weight are at least as wide as their title with a sort indicator
+        assert_eq!(column_widths(&columns, 10.0), vec![4, 3, 3, 3]);
+        assert_eq!(column_widths(&columns, f64::INFINITY), vec![4, 6, 3, 3]);
+    }
+
+    #[test]
+    fn ranges_extend_the_previous_selection() {
+        let base = HashSet::from([1]);
+        assert_eq!(extend_selection(&base, 5, 3), HashSet::from([1, 3, 4, 5]));
+        assert_eq!(extend_selection(&base, 3, 3), HashSet::from([1, 3]));
+        assert_eq!(
+            extend_selection(&HashSet::new(), 2, 4),
+            HashSet::from([2, 3, 4])
+        );
+    }
+
+    /// `count` rows with a name and a size column
+    fn rows(count: usize) -> Vec<Vec<String>> {
+        (0..count)
+            .map(|row| vec![format!("row {row}"), format!("{}", count - row)])
+            .collect()
+    }
+
+    /// A press and release of the left mouse button at `column` and `row`
+    fn click(column: i16, row: i16) -> [Event; 2] {
+        let left = MouseButton::Left;
+        [
+            test_mouse(MouseKind::Down(left), column, row),
+            test_mouse(MouseKind::Up(left), column, row),
+        ]
+    }
+
+    #[tokio::test]
+    async fn rows_are_selected_with_the_keyboard_and_the_mouse() {
+        let mut app = App::new(None, |selected: &mut Option<usize>| {
+            crate::v_stack((
+                format!("selected {selected:?}"),
+                crate::table([Column::new("name"), Column::new("size")], rows(10))
+                    .on_select(|selected: &mut Option<usize>, row| *selected = Some(row)),
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        app.dispatch([test_key(Key::Tab), test_key(Key::Down), test_key(Key::Down)])
+            .await;
+        assert_eq!(app.screen_line(0), "selected Some(1)");
+        app.dispatch([test_key(Key::End)]).await;
+        assert_eq!(app.screen_line(0), "selected Some(9)");
+
+        // the header is in the row 1 of the screen
+        app.dispatch(click(2, 5)).await;
+        assert_eq!(app.screen_line(0), "selected Some(3)");
+        // below the last row
+        app.dispatch(click(2, 20)).await;
+        assert_eq!(app.screen_line(0), "selected Some(3)");
+    }
+
+    #[tokio::test]
+    async fn clicks_on_a_sortable_header_report_the_sort_order() {
+        let mut app = App::new(Vec::new(), |sorts: &mut Vec<(usize, bool)>| {
+            crate::v_stack((
+                format!("{sorts:?}"),
+                crate::table(
+                    [Column::new("name"), Column::new("size").sortable()],
+                    rows(3),
+                )
+                .on_sort(|sorts: &mut Vec<(usize, bool)>, sort| sorts.push(sort)),
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        // the name column isn't sortable
+        app.dispatch(click(0, 1)).await;
+        assert_eq!(app.screen_line(0), "[]");
+
+        // the 79 usable cells are shared 39:40, the size column starts at 40
+        app.dispatch(click(41, 1)).await;
+        app.dispatch(click(41, 1)).await;
+        assert_eq!(app.screen_line(0), "[(1, true), (1, false)]");
+        assert!(app.screen_line(1).ends_with("size ▼"));
+    }
+
+    #[tokio::test]
+    async fn overflowing_columns_are_scrolled_horizontally() {
+        let mut app = App::new((), |_: &mut ()| {
+            crate::table(
+                ["a", "b", "c"].map(|title| Column::new(title).width(ColumnWidth::Fixed(10))),
+                vec![vec!["a0".to_string(), "b0".to_string(), "c0".to_string()]],
+            )
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(15, 5);
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(1), "a0         b0");
+
+        app.dispatch([test_key(Key::Tab), test_key(Key::Right)])
+            .await;
+        assert_eq!(app.screen_line(1), "b0         c0");
+        // the last column fits, so there's nothing left to scroll
+        app.dispatch([test_key(Key::Right), test_key(Key::Right)])
+            .await;
+        assert_eq!(app.screen_line(1), "c0");
+
+        app.dispatch([test_mouse(MouseKind::ScrollLeft, 2, 1)])
+            .await;
+        assert_eq!(app.screen_line(1), "b0         c0");
+    }
+
+    #[tokio::test]
+    async fn mouse_events_outside_of_the_table_are_ignored() {
+        let mut app = App::new(None, |selected: &mut Option<usize>| {
+            crate::v_stack((
+                format!("selected {selected:?}"),
+                crate::table([Column::new("name"), Column::new("size")], rows(10))
+                    .on_select(|selected: &mut Option<usize>, row| *selected = Some(row)),
+            ))
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(15, 5);
+        app.render_once().await.unwrap();
+        // the table shows the header and 3 rows, scrolled to the last one
+        app.dispatch([test_key(Key::Tab), test_key(Key::End)]).await;
+        assert_eq!(app.screen_line(0), "selected Some(9)");
+        assert!(app.screen_line(2).starts_with("row 7"));
+
+        // the table isn't hot anymore, but gets the events which are above it
+        let left = MouseButton::Left;
+        app.dispatch([test_mouse(MouseKind::Moved, 2, 3)]).await;
+        app.dispatch(click(2, 0)).await;
+        app.dispatch([test_mouse(MouseKind::Moved, 2, 3)]).await;
+        app.dispatch([test_mouse(MouseKind::ScrollUp, 2, 0)]).await;
+        app.dispatch([test_mouse(MouseKind::Moved, 2, 3)]).await;
+        app.dispatch([test_mouse(MouseKind::Down(left), -1, -1)])
+            .await;
+        assert_eq!(app.screen_line(0), "selected Some(9)");
+        assert!(app.screen_line(2).starts_with("row 7"));
+
+        // but still within it
+        app.dispatch([test_mouse(MouseKind::ScrollUp, 2, 3)]).await;
+        assert!(app.screen_line(2).starts_with("row 4"));
+    }
+
+    #[test]
+    fn only_the_visible_rows_are_painted() {
+        let styles = TableStyles {
+            header: Style::default(),
+            text: Style::default(),
+            selected: Style::default(),
+        };
+        let rows = Arc::new(rows(100));
+        let columns = vec![Column::new("name")];
+        let mut table = Table::new(&Vec::new(), columns, rows, Some(50), None, styles);
+        let (mut messages, mut focus) = (Vec::new(), FocusState::default());
+        let cx_state = &mut CxState::new(&mut messages, &mut focus, Default::default());
+        let mut widget_state = WidgetState::new();
+        // a header and 3 rows, scrolled to the selected row
+        let bc = BoxConstraints::tight(Size::new(10.0, 4.0));
+        widget_state.size = table.layout(
+            &mut LayoutCx {
+                cx_state,
+                widget_state: &mut widget_state,
+            },
+            &bc,
+        );
+
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 10, 8));
+        table.paint(&mut PaintCx {
+            cx_state,
+            widget_state: &mut widget_state,
+            buffer: &mut buffer,
+            override_style: Style::default(),
+        });
+        let lines: Vec<String> = (0..8)
+            .map(|y| {
+                let line: String = (0..10).map(|x| buffer.get(x, y).symbol()).collect();
+                line.trim_end().to_string()
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            ["name", "row 48", "row 49", "row 50", "", "", "", ""]
+        );
+    }
+}
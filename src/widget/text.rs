@@ -1,15 +1,32 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, ops::Range};
 
-use ratatui::style::Style;
+use ratatui::style::{Color, Modifier, Style};
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
-use crate::geometry::{to_ratatui_rect, Size};
+use crate::{
+    geometry::{to_ratatui_rect, Size},
+    view::{ControlChars, TruncateMode},
+};
 
-use super::{core::EventCx, BoxConstraints, ChangeFlags, Event, LayoutCx, PaintCx, Widget};
+use super::{
+    core::EventCx, set_stringn_clipped, BoxConstraints, ChangeFlags, Event, LayoutCx, PaintCx,
+    Widget,
+};
 
 pub struct Text {
     pub(crate) text: Cow<'static, str>,
     pub(crate) style: Style,
+    pub(crate) truncate: Option<TruncateMode>,
+    pub(crate) ellipsis: Cow<'static, str>,
+    pub(crate) tab_width: u16,
+    pub(crate) control_chars: ControlChars,
+    /// Patched below `style` while the pointer is over the text
+    pub(crate) hover_style: Style,
+    /// Byte ranges of `text`, which are painted with `highlight_style` or the one of the theme
+    pub(crate) highlights: Vec<Range<usize>>,
+    pub(crate) highlight_style: Option<Style>,
+    pub(crate) theme_highlight_style: Style,
 }
 
 // TODO maybe a generic macro for stuff like below?
@@ -17,7 +34,11 @@ impl Text {
     pub fn set_text(&mut self, text: Cow<'static, str>) -> ChangeFlags {
         let mut changeflags = ChangeFlags::empty();
         if self.text != text {
-            changeflags.set(ChangeFlags::LAYOUT, self.text.width() != text.width());
+            changeflags.set(
+                ChangeFlags::LAYOUT,
+                text_size(&self.text, self.tab_width, self.control_chars)
+                    != text_size(&text, self.tab_width, self.control_chars),
+            );
             changeflags |= ChangeFlags::PAINT;
             self.text = text;
         }
@@ -31,44 +52,469 @@ impl Text {
             ChangeFlags::empty()
         }
     }
+    pub(crate) fn set_truncation(
+        &mut self,
+        truncate: Option<TruncateMode>,
+        ellipsis: &Cow<'static, str>,
+    ) -> ChangeFlags {
+        if self.truncate != truncate || self.ellipsis != *ellipsis {
+            self.truncate = truncate;
+            self.ellipsis = ellipsis.clone();
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+    pub(crate) fn set_tab_width(&mut self, tab_width: u16) -> ChangeFlags {
+        if tab_width != self.tab_width {
+            self.tab_width = tab_width;
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+    pub(crate) fn set_hover_style(&mut self, hover_style: Style) -> ChangeFlags {
+        if hover_style != self.hover_style {
+            self.hover_style = hover_style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+    pub(crate) fn set_highlights(
+        &mut self,
+        highlights: &[Range<usize>],
+        highlight_style: Option<Style>,
+    ) -> ChangeFlags {
+        if self.highlights != highlights || self.highlight_style != highlight_style {
+            self.highlights = highlights.to_vec();
+            self.highlight_style = highlight_style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+    pub(crate) fn set_theme_highlight_style(&mut self, style: Style) -> ChangeFlags {
+        if style != self.theme_highlight_style {
+            self.theme_highlight_style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+    pub(crate) fn set_control_chars(&mut self, control_chars: ControlChars) -> ChangeFlags {
+        if control_chars != self.control_chars {
+            self.control_chars = control_chars;
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+}
+
+/// A single line of text as it's displayed, see [`display_line`].
+#[derive(Debug, PartialEq)]
+struct DisplayLine {
+    text: String,
+    /// The style of `text[start..]` up to the start of the next style, which is patched onto the
+    /// style of the widget, the first one always starts at `0`
+    styles: Vec<(usize, Style)>,
+    /// The parts of the source line which are copied as-is, as `(source start, start, len)`
+    runs: Vec<(usize, usize, usize)>,
+}
+
+impl DisplayLine {
+    /// The styled parts of `text[range]`.
+    fn spans(&self, range: Range<usize>) -> impl Iterator<Item = (&str, Style)> {
+        self.styles
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, &(start, style))| {
+                let end = self.styles.get(i + 1).map_or(self.text.len(), |(s, _)| *s);
+                let (start, end) = (start.max(range.start), end.min(range.end));
+                (start < end).then(|| (&self.text[start..end], style))
+            })
+    }
+
+    /// The byte offset in `text` of the byte `offset` of the source line, an offset within a
+    /// control character or escape sequence is moved before its replacement.
+    fn display_offset(&self, offset: usize) -> usize {
+        let i = self
+            .runs
+            .partition_point(|(source_start, ..)| *source_start <= offset);
+        match i.checked_sub(1).map(|i| self.runs[i]) {
+            Some((source_start, start, len)) => start + (offset - source_start).min(len),
+            None => 0,
+        }
+    }
+
+    /// Patches the styles of `text[range]` with `style`.
+    fn patch_styles(&mut self, range: Range<usize>, style: Style) {
+        if range.is_empty() {
+            return;
+        }
+        for offset in [range.start, range.end] {
+            let i = self.styles.partition_point(|(start, _)| *start <= offset);
+            if offset < self.text.len() && self.styles[i - 1].0 != offset {
+                let split = self.styles[i - 1].1;
+                self.styles.insert(i, (offset, split));
+            }
+        }
+        for (start, run_style) in &mut self.styles {
+            if range.contains(start) {
+                *run_style = run_style.patch(style);
+            }
+        }
+    }
+}
+
+/// Extends `range` of `text` to the boundaries of the graphemes, which it (partially) contains.
+fn grapheme_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let mut start = 0;
+    let mut end = text.len();
+    for (i, grapheme) in text.grapheme_indices(true) {
+        if i <= range.start {
+            start = i;
+        }
+        if i + grapheme.len() >= range.end {
+            end = i + grapheme.len();
+            break;
+        }
+    }
+    start..end.max(start)
+}
+
+/// Prepares a single `line` to be written into the buffer: tabs are replaced with spaces up to
+/// the next tab stop (every `tab_width` cells) and control characters are handled as configured
+/// with `control_chars`.
+fn display_line(line: &str, tab_width: u16, control_chars: ControlChars) -> DisplayLine {
+    let tab_width = tab_width.max(1) as usize;
+    let mut text = String::with_capacity(line.len());
+    let mut styles = vec![(0, Style::default())];
+    let mut runs = Vec::new();
+    let mut column = 0;
+    // Text without control characters is copied as a whole
+    let mut run_start = 0;
+    let mut i = 0;
+    while let Some(c) = line[i..].chars().next() {
+        if !c.is_control() {
+            i += c.len_utf8();
+            continue;
+        }
+        let run = &line[run_start..i];
+        runs.push((run_start, text.len(), run.len()));
+        text.push_str(run);
+        column += run.width();
+
+        let mut len = c.len_utf8();
+        match c {
+            '\t' => {
+                let spaces = tab_width - column % tab_width;
+                text.push_str(&" ".repeat(spaces));
+                column += spaces;
+            }
+            '\x1b' => {
+                let (sequence_len, sgr) = escape_sequence(&line[i..]);
+                len = sequence_len;
+                if let (ControlChars::Ansi, Some(params)) = (control_chars, sgr) {
+                    let style = apply_sgr(styles[styles.len() - 1].1, params);
+                    match styles.last_mut() {
+                        Some(last) if last.0 == text.len() => last.1 = style,
+                        _ => styles.push((text.len(), style)),
+                    }
+                }
+            }
+            _ if control_chars == ControlChars::Strip => {}
+            _ => {
+                let visible = visible_control_char(c);
+                text.push_str(&visible);
+                column += visible.width();
+            }
+        }
+        i += len;
+        run_start = i;
+    }
+    runs.push((run_start, text.len(), line.len() - run_start));
+    text.push_str(&line[run_start..]);
+    DisplayLine { text, styles, runs }
+}
+
+/// Caret notation (e.g. `^C`) for C0 control characters and `DEL`, `�` for others.
+fn visible_control_char(c: char) -> String {
+    match c as u32 {
+        0x00..=0x1f => format!("^{}", char::from(c as u8 + 0x40)),
+        0x7f => "^?".to_string(),
+        _ => char::REPLACEMENT_CHARACTER.to_string(),
+    }
+}
+
+/// The length in bytes of the escape sequence at the start of `text`, which starts with `ESC`,
+/// and its parameters if it's an SGR sequence (`ESC [ <params> m`), which sets colors and modifiers.
+pub(super) fn escape_sequence(text: &str) -> (usize, Option<&str>) {
+    let bytes = text.as_bytes();
+    match bytes.get(1) {
+        // CSI, parameter and intermediate bytes followed by a final byte
+        Some(b'[') => {
+            let end = bytes[2..]
+                .iter()
+                .position(|b| !(0x20..=0x3f).contains(b))
+                .map_or(bytes.len(), |p| p + 2);
+            match bytes.get(end) {
+                Some(b) if (0x40..=0x7e).contains(b) => {
+                    (end + 1, (*b == b'm').then(|| &text[2..end]))
+                }
+                // Malformed, everything up to the unexpected char is removed
+                _ => (end, None),
+            }
+        }
+        // OSC (e.g. window titles or hyperlinks), terminated by `BEL` or `ESC \`
+        Some(b']') => match text[2..].find(|c: char| c == '\x07' || c == '\x1b') {
+            Some(p) if bytes[p + 2] == 0x07 => (p + 3, None),
+            Some(p) if bytes.get(p + 3) == Some(&b'\\') => (p + 4, None),
+            Some(p) => (p + 2, None),
+            None => (text.len(), None),
+        },
+        Some(b) if (0x20..=0x7e).contains(b) => (2, None),
+        _ => (1, None),
+    }
+}
+
+/// The 16 colors of SGR `30..=37` and `90..=97` (or `40..=47` and `100..=107` for the background).
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// Applies the `;` separated parameters of an SGR escape sequence to `style`.
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    // An empty parameter means `0`, which resets the style
+    let mut params = params
+        .split([';', ':'])
+        .map(|p| p.parse::<u16>().unwrap_or(0));
+    while let Some(param) = params.next() {
+        style = match param {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 => style.add_modifier(Modifier::SLOW_BLINK),
+            6 => style.add_modifier(Modifier::RAPID_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            8 => style.add_modifier(Modifier::HIDDEN),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            25 => style.remove_modifier(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            28 => style.remove_modifier(Modifier::HIDDEN),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(ANSI_COLORS[param as usize - 30]),
+            90..=97 => style.fg(ANSI_COLORS[param as usize - 90 + 8]),
+            40..=47 => style.bg(ANSI_COLORS[param as usize - 40]),
+            100..=107 => style.bg(ANSI_COLORS[param as usize - 100 + 8]),
+            38 => match extended_color(&mut params) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            48 => match extended_color(&mut params) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            39 => Style { fg: None, ..style },
+            49 => Style { bg: None, ..style },
+            _ => style,
+        };
+    }
+    style
+}
+
+/// The color of SGR `38` or `48`, either `5;<index>` or `2;<r>;<g>;<b>`.
+fn extended_color(params: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    let mut next = || params.next().and_then(|p| u8::try_from(p).ok());
+    match next()? {
+        5 => Some(Color::Indexed(next()?)),
+        2 => Some(Color::Rgb(next()?, next()?, next()?)),
+        _ => None,
+    }
+}
+
+/// The size of (multiline) text in terminal cells, i.e. wide characters (e.g. CJK) take two cells,
+/// combining characters none and tabs and control characters are handled like in [`display_line`].
+fn text_size(text: &str, tab_width: u16, control_chars: ControlChars) -> Size {
+    let mut width = 0;
+    let mut height = 0;
+
+    for l in text.lines() {
+        width = width.max(display_line(l, tab_width, control_chars).text.width());
+        height += 1;
+    }
+
+    Size {
+        width: width as f64,
+        height: height as f64,
+    }
+}
+
+/// The byte index up to which `text` fits into `width` cells, without splitting graphemes.
+fn prefix_end(text: &str, width: usize) -> usize {
+    let mut current_width = 0;
+    for (i, grapheme) in text.grapheme_indices(true) {
+        current_width += grapheme.width();
+        if current_width > width {
+            return i;
+        }
+    }
+    text.len()
+}
+
+/// The byte index from which the rest of `text` fits into `width` cells, without splitting graphemes.
+fn suffix_start(text: &str, width: usize) -> usize {
+    let mut current_width = 0;
+    for (i, grapheme) in text.grapheme_indices(true).rev() {
+        current_width += grapheme.width();
+        if current_width > width {
+            return i + grapheme.len();
+        }
+    }
+    0
+}
+
+/// What's shown of a single line of text, which is shortened to at most a width, see [`truncation`].
+#[derive(Debug, PartialEq)]
+enum Truncation {
+    /// The whole text fits
+    None,
+    /// Not even the ellipsis fits, only its first `.0` bytes are shown
+    Ellipsis(usize),
+    /// `text[..head]`, the ellipsis and `text[tail..]` are shown
+    Cut { head: usize, tail: usize },
+}
+
+/// How a single line of `text` is shortened to at most `width` cells, the cut off part is replaced with `ellipsis`.
+fn truncation(text: &str, width: usize, mode: TruncateMode, ellipsis: &str) -> Truncation {
+    if text.width() <= width {
+        return Truncation::None;
+    }
+    let ellipsis_width = ellipsis.width();
+    if ellipsis_width >= width {
+        return Truncation::Ellipsis(prefix_end(ellipsis, width));
+    }
+    let available = width - ellipsis_width;
+    match mode {
+        TruncateMode::End => Truncation::Cut {
+            head: prefix_end(text, available),
+            tail: text.len(),
+        },
+        TruncateMode::Start => Truncation::Cut {
+            head: 0,
+            tail: suffix_start(text, available),
+        },
+        TruncateMode::Middle => {
+            // The start gets the extra cell, if `available` is odd
+            let head = prefix_end(text, available.div_ceil(2));
+            let tail = suffix_start(text, available - text[..head].width());
+            Truncation::Cut { head, tail }
+        }
+    }
 }
 
 impl Widget for Text {
     fn paint(&mut self, cx: &mut PaintCx) {
         let rect = to_ratatui_rect(cx.rect());
 
-        let style = self.style.patch(cx.override_style);
+        let area = rect.intersection(cx.buffer.area);
+        let override_style = cx.override_style;
+        // An explicit style of the text takes precedence over the hover style of the theme
+        let style = if cx.is_hot() {
+            self.hover_style.patch(self.style)
+        } else {
+            self.style
+        };
 
-        let term_size = cx.terminal.size().unwrap();
+        if area.area() > 0 {
+            // The text is truncated at the right edge of the buffer, e.g. of the terminal
+            let max_width = (area.right() - rect.x) as usize;
+            let hidden_rows = (area.top() - rect.y) as usize;
+            let buf = cx.buffer_mut();
+            // `set_stringn` doesn't split wide graphemes, so they are either painted completely or not at all
+            let rows = (rect.y..area.bottom()).zip(self.text.lines());
+            for (y, line) in rows.skip(hidden_rows) {
+                let line_start = line.as_ptr() as usize - self.text.as_ptr() as usize;
+                let line_range = line_start..line_start + line.len();
+                let source_line = line;
+                // Raw tabs and control characters would corrupt the display
+                let mut line = display_line(line, self.tab_width, self.control_chars);
+                // The highlighted ranges refer to the whole (source) text
+                let highlight_style = self.highlight_style.unwrap_or(self.theme_highlight_style);
+                for highlight in &self.highlights {
+                    let start = highlight.start.max(line_range.start) - line_start;
+                    let end = highlight.end.min(line_range.end).saturating_sub(line_start);
+                    if start < end {
+                        let range = grapheme_range(source_line, start..end);
+                        line.patch_styles(
+                            line.display_offset(range.start)..line.display_offset(range.end),
+                            highlight_style,
+                        );
+                    }
+                }
+                let truncation = match self.truncate {
+                    Some(mode) => truncation(&line.text, max_width, mode, &self.ellipsis),
+                    None => Truncation::None,
+                };
+                let spans: Vec<_> = match truncation {
+                    Truncation::None => line.spans(0..line.text.len()).collect(),
+                    Truncation::Ellipsis(end) => vec![(&self.ellipsis[..end], Style::default())],
+                    Truncation::Cut { head, tail } => line
+                        .spans(0..head)
+                        .chain(std::iter::once((self.ellipsis.as_ref(), Style::default())))
+                        .chain(line.spans(tail..line.text.len()))
+                        .collect(),
+                };
 
-        let max_width = rect.width.min(term_size.width.saturating_sub(rect.x)) as usize;
-        if rect.height > 0 && max_width > 0 && rect.y < term_size.height {
-            // TODO cut the text off, when it is out of bounds (rect.height is not respected)
-            // likely with a custom implementation to render the text, instead of `set_stringn`
-            cx.terminal
-                .current_buffer_mut()
-                .set_stringn(rect.x, rect.y, &self.text, max_width, style);
+                let end = rect.x + max_width as u16;
+                let mut x = rect.x;
+                for (text, span_style) in spans {
+                    // The style of the text (e.g. ANSI colors) is overridden e.g. when it's selected
+                    let style = style.patch(span_style).patch(override_style);
+                    let start = x;
+                    (x, _) = set_stringn_clipped(buf, x, y, text, (end - x) as usize, style);
+                    // A wide grapheme didn't fit
+                    if x >= end || ((x - start) as usize) < text.width() {
+                        break;
+                    }
+                }
+            }
         }
     }
 
     fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
-        let mut width = 0;
-        let mut height = 0;
-
-        for l in self.text.lines() {
-            width = width.max(l.width());
-            height += 1;
-        }
-
-        bc.constrain(Size {
-            width: width as f64,
-            height: height as f64,
-        })
+        bc.constrain(text_size(&self.text, self.tab_width, self.control_chars))
     }
 
     fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
 
-    fn lifecycle(&mut self, _cx: &mut super::core::LifeCycleCx, _event: &super::LifeCycle) {}
+    fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &super::LifeCycle) {
+        if matches!(event, super::LifeCycle::HotChanged(_)) && self.hover_style != Style::default()
+        {
+            cx.request_paint();
+        }
+    }
 }
 
 // TODO relatively hacky naive implementation of wrapping text via flexbox
@@ -129,3 +575,539 @@ impl Widget for WrappedText {
 
     fn lifecycle(&mut self, _cx: &mut super::core::LifeCycleCx, _event: &super::LifeCycle) {}
 }
+
+/// The space between the end and the start of the looped text in a [`MarqueeText`].
+const MARQUEE_GAP: &str = "   ";
+
+pub struct MarqueeText {
+    text: Cow<'static, str>,
+    style: Style,
+    tab_width: u16,
+    control_chars: ControlChars,
+    /// `text` as it's displayed, see [`display_line`]
+    line: DisplayLine,
+    /// In cells per second
+    speed: f64,
+    /// The current horizontal scroll offset in cells
+    offset: f64,
+    overflows: bool,
+}
+
+impl MarqueeText {
+    pub(crate) fn new(
+        text: Cow<'static, str>,
+        style: Style,
+        tab_width: u16,
+        control_chars: ControlChars,
+        speed: f64,
+    ) -> Self {
+        MarqueeText {
+            line: display_line(&text, tab_width, control_chars),
+            text,
+            style,
+            tab_width,
+            control_chars,
+            speed,
+            offset: 0.0,
+            overflows: false,
+        }
+    }
+
+    pub(crate) fn set_text(&mut self, text: &Cow<'static, str>) -> ChangeFlags {
+        if self.text != *text {
+            self.text = text.clone();
+            self.offset = 0.0;
+            self.update_line()
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) -> ChangeFlags {
+        if style != self.style {
+            self.style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_tab_width(&mut self, tab_width: u16) -> ChangeFlags {
+        if tab_width != self.tab_width {
+            self.tab_width = tab_width;
+            self.update_line()
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_control_chars(&mut self, control_chars: ControlChars) -> ChangeFlags {
+        if control_chars != self.control_chars {
+            self.control_chars = control_chars;
+            self.update_line()
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_speed(&mut self, speed: f64) -> ChangeFlags {
+        let changed = speed != self.speed;
+        self.speed = speed;
+        // A text that fits isn't animated, regardless of the speed
+        if changed && self.overflows {
+            ChangeFlags::ANIMATION
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn update_line(&mut self) -> ChangeFlags {
+        self.line = display_line(&self.text, self.tab_width, self.control_chars);
+        ChangeFlags::LAYOUT | ChangeFlags::PAINT
+    }
+}
+
+impl Widget for MarqueeText {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let override_style = cx.override_style();
+        let buf = cx.buffer_mut();
+        if rect.intersection(buf.area).area() == 0 {
+            return;
+        }
+
+        // The text followed by the gap, the graphemes of the gap are offset by the length of the text
+        let text_len = self.line.text.len();
+        let looped = self.line.text.grapheme_indices(true).chain(
+            MARQUEE_GAP
+                .grapheme_indices(true)
+                .map(move |(i, grapheme)| (text_len + i, grapheme)),
+        );
+        // Starting at the current offset, the (looped) text is painted as far as it fits
+        let skip = self.offset.floor() as usize;
+        let mut skipped = 0;
+        let mut x = rect.x as usize;
+        for (i, grapheme) in looped.cycle() {
+            if !self.overflows && i >= text_len {
+                break;
+            }
+            let width = grapheme.width();
+            if skipped < skip {
+                skipped += width;
+                continue;
+            }
+            if x + width > rect.right() as usize {
+                break;
+            }
+            // e.g. ANSI colors, the gap has no style of its own
+            let span_style = self
+                .line
+                .spans(i..i + grapheme.len())
+                .next()
+                .map_or(Style::default(), |(_, style)| style);
+            let style = self.style.patch(span_style).patch(override_style);
+            set_stringn_clipped(buf, x as u16, rect.y, grapheme, width, style);
+            x += width;
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let width = self.line.text.width() as f64;
+        let size = bc.constrain(Size::new(width, 1.0));
+        self.overflows = width > size.width;
+        if self.overflows {
+            cx.request_animation_update();
+        } else {
+            self.offset = 0.0;
+        }
+        size
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _event: &Event) {}
+
+    fn lifecycle(&mut self, cx: &mut super::core::LifeCycleCx, event: &super::LifeCycle) {
+        // The animation pauses as long as the text fits, it's resumed in layout
+        if matches!(event, super::LifeCycle::Animate) && self.overflows {
+            let loop_width = (self.line.text.width() + MARQUEE_GAP.width()) as f64;
+            let prev_offset = self.offset.floor();
+            let delta = self.speed * cx.time_since_last_render_request().as_secs_f64();
+            self.offset = (self.offset + delta) % loop_width;
+            if self.offset.floor() != prev_offset {
+                cx.request_paint();
+            }
+            cx.request_animation_update();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ratatui::buffer::Buffer;
+
+    use super::*;
+    use crate::{
+        app::test_mouse,
+        v_stack,
+        widget::{CxState, FocusState, MouseButton, MouseKind, PodFlags, WidgetState},
+        App, ToMarqueeText, ViewExt,
+    };
+
+    fn truncate(text: &str, width: usize, mode: TruncateMode, ellipsis: &str) -> Cow<'static, str> {
+        match truncation(text, width, mode, ellipsis) {
+            Truncation::None => Cow::Owned(text.to_string()),
+            Truncation::Ellipsis(end) => Cow::Owned(ellipsis[..end].to_string()),
+            Truncation::Cut { head, tail } => {
+                Cow::Owned(format!("{}{ellipsis}{}", &text[..head], &text[tail..]))
+            }
+        }
+    }
+
+    fn size(text: &str) -> Size {
+        text_size(text, 4, ControlChars::Visible)
+    }
+
+    fn displayed(line: &str, control_chars: ControlChars) -> String {
+        display_line(line, 4, control_chars).text
+    }
+
+    /// Paints `text` into a 10x1 buffer, as if the pointer is over it when `hot`.
+    fn paint(text: &mut Text, hot: bool) -> Buffer {
+        let area = ratatui::layout::Rect::new(0, 0, 10, 1);
+        let mut buffer = Buffer::empty(area);
+        let (mut messages, mut focus) = (Vec::new(), FocusState::default());
+        let mut widget_state = WidgetState::new();
+        widget_state.size = Size::new(10.0, 1.0);
+        widget_state.flags.set(PodFlags::IS_HOT, hot);
+        text.paint(&mut PaintCx {
+            cx_state: &mut CxState::new(&mut messages, &mut focus, Default::default()),
+            widget_state: &mut widget_state,
+            buffer: &mut buffer,
+            override_style: Style::default(),
+        });
+        buffer
+    }
+
+    #[test]
+    fn text_size_is_measured_in_cells() {
+        assert_eq!(size("abc"), Size::new(3.0, 1.0));
+        // CJK characters are twice as wide
+        assert_eq!(size("日本語"), Size::new(6.0, 1.0));
+        assert_eq!(size("ab😀"), Size::new(4.0, 1.0));
+        // combining characters don't take any space
+        assert_eq!(size("e\u{301}e\u{301}"), Size::new(2.0, 1.0));
+        assert_eq!(size("abc\n日本語\nd"), Size::new(6.0, 3.0));
+        assert_eq!(size(""), Size::ZERO);
+        assert_eq!(size("a\tb\nabcde\tb"), Size::new(9.0, 2.0));
+    }
+
+    #[test]
+    fn hovered_text_uses_the_hover_style() {
+        let theme = crate::Theme::default();
+        let mut text = Text {
+            text: "hover".into(),
+            style: Style::default().add_modifier(Modifier::ITALIC),
+            truncate: None,
+            ellipsis: "…".into(),
+            tab_width: 4,
+            control_chars: ControlChars::Visible,
+            hover_style: theme.text.hover,
+            highlights: Vec::new(),
+            highlight_style: None,
+            theme_highlight_style: theme.text.highlight,
+        };
+        assert_ne!(theme.text.hover, theme.text.default);
+
+        let buffer = paint(&mut text, false);
+        assert_eq!(buffer.get(0, 0).fg, Color::Reset);
+        let buffer = paint(&mut text, true);
+        assert_eq!(buffer.get(0, 0).symbol(), "h");
+        assert_eq!(Some(buffer.get(0, 0).fg), theme.text.hover.fg);
+        assert!(buffer.get(4, 0).modifier.contains(Modifier::ITALIC));
+
+        // an explicit color of the text wins
+        text.style = text.style.fg(Color::Red);
+        assert_eq!(paint(&mut text, true).get(0, 0).fg, Color::Red);
+    }
+
+    #[test]
+    fn highlights_whole_graphemes_of_multiple_ranges() {
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+        let mut text = Text {
+            text: "ab\tcd\ne\u{301}fg".into(),
+            style: Style::default(),
+            truncate: None,
+            ellipsis: "…".into(),
+            tab_width: 4,
+            control_chars: ControlChars::Visible,
+            hover_style: Style::default(),
+            // "a", "c" after the tab, and the first byte of "é" in the second line
+            highlights: vec![0..1, 3..4, 6..7],
+            highlight_style: Some(highlight),
+            theme_highlight_style: Style::default(),
+        };
+        let mut widget_state = WidgetState::new();
+        widget_state.size = Size::new(10.0, 2.0);
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 10, 2));
+        let (mut messages, mut focus) = (Vec::new(), FocusState::default());
+        text.paint(&mut PaintCx {
+            cx_state: &mut CxState::new(&mut messages, &mut focus, Default::default()),
+            widget_state: &mut widget_state,
+            buffer: &mut buffer,
+            override_style: Style::default(),
+        });
+
+        let reversed = |x, y| buffer.get(x, y).modifier.contains(Modifier::REVERSED);
+        let row = |y| (0..6).map(|x| reversed(x, y)).collect::<Vec<_>>();
+        // "ab  cd"
+        assert_eq!(row(0), vec![true, false, false, false, true, false]);
+        // "éfg"
+        assert_eq!(row(1), vec![true, false, false, false, false, false]);
+        assert_eq!(buffer.get(0, 1).symbol(), "e\u{301}");
+    }
+
+    #[test]
+    fn maps_source_offsets_to_displayed_offsets() {
+        let line = display_line("a\tb\x1b[31mc\x03d", 4, ControlChars::Visible);
+        assert_eq!(line.text, "a   bc^Cd");
+        assert_eq!(line.display_offset(0), 0);
+        // "b" after the tab
+        assert_eq!(line.display_offset(2), 4);
+        // "c" after the escape sequence, and offsets within it
+        assert_eq!(line.display_offset(8), 5);
+        assert_eq!(line.display_offset(5), 5);
+        // "d" after the caret notation
+        assert_eq!(line.display_offset(10), 8);
+        assert_eq!(line.display_offset(11), 9);
+
+        assert_eq!(grapheme_range("e\u{301}x", 0..1), 0..3);
+        assert_eq!(grapheme_range("e\u{301}x", 1..4), 0..4);
+        assert_eq!(grapheme_range("e\u{301}x", 3..4), 3..4);
+    }
+
+    #[test]
+    fn tabs_are_aligned_to_tab_stops() {
+        let column_of_b = |line: &str, tab_width| {
+            let expanded = display_line(line, tab_width, ControlChars::Visible).text;
+            expanded[..expanded.find('b').unwrap()].width()
+        };
+        assert_eq!(column_of_b("a\tb", 4), 4);
+        assert_eq!(column_of_b("abc\tb", 4), 4);
+        // a tab at a tab stop moves to the next one
+        assert_eq!(column_of_b("aaaa\tb", 4), 8);
+        assert_eq!(column_of_b("\t\tb", 4), 8);
+        // wide characters take two columns
+        assert_eq!(column_of_b("日本\tb", 4), 8);
+        assert_eq!(column_of_b("日\tb", 4), 4);
+        // combining characters don't take any
+        assert_eq!(column_of_b("e\u{301}e\u{301}e\u{301}\tb", 4), 4);
+        assert_eq!(column_of_b("a\tb", 8), 8);
+        assert_eq!(displayed("a\tb", ControlChars::Visible), "a   b");
+        // escape sequences don't take any space
+        assert_eq!(column_of_b("\x1b[31mabc\tb", 4), 4);
+    }
+
+    #[test]
+    fn escape_sequences_are_removed_by_default() {
+        let line = "\x1b[31merror\x1b[0m: failed";
+        assert_eq!(displayed(line, ControlChars::Visible), "error: failed");
+        assert_eq!(displayed(line, ControlChars::Strip), "error: failed");
+        assert_eq!(size(line), Size::new(13.0, 1.0));
+        assert_eq!(
+            display_line(line, 4, ControlChars::Visible).styles,
+            vec![(0, Style::default())]
+        );
+        // other sequences, e.g. cursor movement, clearing the screen or setting the window title
+        assert_eq!(
+            displayed(
+                "a\x1b[2J\x1b[1;1Hb\x1b]0;title\x07c\x1b]8;;url\x1b\\d",
+                ControlChars::Visible
+            ),
+            "abcd"
+        );
+        // unterminated sequences are removed up to the end of the line
+        assert_eq!(displayed("a\x1b[31", ControlChars::Visible), "a");
+        assert_eq!(displayed("a\x1b]0;title", ControlChars::Visible), "a");
+    }
+
+    #[test]
+    fn control_chars_are_visible_or_stripped() {
+        assert_eq!(
+            displayed("a\x03b\x7fc\rd", ControlChars::Visible),
+            "a^Cb^?c^Md"
+        );
+        assert_eq!(displayed("a\x00b\u{9b}c", ControlChars::Visible), "a^@b�c");
+        assert_eq!(displayed("a\x03b\x7fc\rd", ControlChars::Strip), "abcd");
+        assert_eq!(size("a\x03b"), Size::new(4.0, 1.0));
+    }
+
+    #[test]
+    fn ansi_colors_style_the_following_text() {
+        let line = display_line(
+            "\x1b[31merror\x1b[0m: \x1b[1;38;5;208mfailed\x1b[22;39m!",
+            4,
+            ControlChars::Ansi,
+        );
+        assert_eq!(line.text, "error: failed!");
+        assert_eq!(
+            line.styles,
+            vec![
+                (0, Style::default().fg(Color::Red)),
+                (5, Style::default()),
+                (
+                    7,
+                    Style::default()
+                        .fg(Color::Indexed(208))
+                        .add_modifier(Modifier::BOLD)
+                ),
+                (
+                    13,
+                    Style::default().remove_modifier(Modifier::BOLD | Modifier::DIM)
+                ),
+            ]
+        );
+        let spans: Vec<_> = line.spans(3..9).collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("or", Style::default().fg(Color::Red)),
+                (": ", Style::default()),
+                ("fa", line.styles[2].1),
+            ]
+        );
+
+        let line = display_line("\x1b[38;2;1;2;3;104mrgb", 4, ControlChars::Ansi);
+        assert_eq!(
+            line.styles,
+            vec![(
+                0,
+                Style::default()
+                    .fg(Color::Rgb(1, 2, 3))
+                    .bg(Color::LightBlue)
+            )]
+        );
+        // other escape sequences are still removed
+        assert_eq!(displayed("a\x1b[2Jb\x03", ControlChars::Ansi), "ab^C");
+    }
+
+    #[test]
+    fn invalid_ansi_sequences_are_ignored() {
+        for line in [
+            // incomplete or out of range extended colors, unknown parameters and other sequences
+            "\x1b[38;5mok",
+            "\x1b[38;2;300;0;0mok",
+            "\x1b[48;7mok",
+            "\x1b[99999mok",
+            "\x1b[1;2xok",
+            // unterminated
+            "ok\x1b[31",
+            "ok\x1b",
+            "ok\x1b[",
+            "ok\x1b]",
+        ] {
+            let line = display_line(line, 4, ControlChars::Ansi);
+            assert_eq!(line.text, "ok", "{line:?}");
+            assert_eq!(line.styles.len(), 1, "{line:?}");
+        }
+        // a sequence which is cut off by an unexpected char is removed up to that char
+        assert_eq!(displayed("\x1b[3\u{e4}ok", ControlChars::Ansi), "\u{e4}ok");
+    }
+
+    #[test]
+    fn text_that_fits_is_not_truncated() {
+        assert_eq!(truncate("日本語", 6, TruncateMode::End, "…"), "日本語");
+        assert_eq!(
+            truncation("abc", 3, TruncateMode::Middle, "…"),
+            Truncation::None
+        );
+    }
+
+    #[test]
+    fn truncates_cjk_without_splitting_wide_characters() {
+        let text = "日本語のテキスト";
+        assert_eq!(truncate(text, 7, TruncateMode::End, "…"), "日本語…");
+        // A wide character doesn't fit into the last remaining cell
+        assert_eq!(truncate(text, 6, TruncateMode::End, "…"), "日本…");
+        assert_eq!(truncate(text, 6, TruncateMode::Start, "…"), "…スト");
+        assert_eq!(truncate(text, 9, TruncateMode::Middle, "…"), "日本…スト");
+    }
+
+    #[test]
+    fn truncates_emoji_as_graphemes() {
+        let text = "ab😀cd😀ef";
+        assert_eq!(truncate(text, 7, TruncateMode::Middle, "…"), "ab…😀ef");
+        assert_eq!(truncate(text, 4, TruncateMode::End, "…"), "ab…");
+        assert_eq!(truncate(text, 5, TruncateMode::Start, "…"), "…😀ef");
+        // combining characters stay with their base character
+        assert_eq!(
+            truncate("e\u{301}e\u{301}e\u{301}", 2, TruncateMode::End, "…"),
+            "e\u{301}…"
+        );
+    }
+
+    #[test]
+    fn uses_custom_ellipsis() {
+        assert_eq!(
+            truncate("hello world", 8, TruncateMode::End, "..."),
+            "hello..."
+        );
+        // Only the ellipsis is shown, when there's not even enough space for it
+        assert_eq!(truncate("hello world", 2, TruncateMode::End, "..."), "..");
+    }
+
+    #[tokio::test]
+    async fn marquee_text_scrolls_in_a_loop() {
+        // The escape sequence is removed, so the text is 6 cells wide
+        let mut app = App::new((), |_: &mut ()| "\x1b[1mabcdef".marquee().speed(1.0)).await;
+        app.config.terminal.backend_mut().resize(4, 1);
+        assert!(app.render_frame(Duration::ZERO).await);
+        assert_eq!(app.screen_line(0), "abcd");
+        assert!(app.render_frame(Duration::from_secs(1)).await);
+        assert_eq!(app.screen_line(0), "bcde");
+        // followed by the gap
+        app.render_frame(Duration::from_secs(2)).await;
+        assert_eq!(app.screen_line(0), "def");
+        app.render_frame(Duration::from_secs(5)).await;
+        assert_eq!(app.screen_line(0), " abc");
+        // wrapped around to the start
+        app.render_frame(Duration::from_secs(1)).await;
+        assert_eq!(app.screen_line(0), "abcd");
+    }
+
+    #[tokio::test]
+    async fn marquee_text_that_fits_is_not_animated() {
+        let mut app = App::new((), |_: &mut ()| "a\tb".marquee()).await;
+        assert!(!app.render_frame(Duration::ZERO).await);
+        assert_eq!(app.screen_line(0), "a   b");
+
+        let mut marquee = MarqueeText::new(
+            "abc".into(),
+            Style::default(),
+            4,
+            ControlChars::Visible,
+            8.0,
+        );
+        assert_eq!(marquee.set_speed(1.0), ChangeFlags::empty());
+    }
+
+    #[tokio::test]
+    async fn the_animation_stops_when_the_marquee_text_is_removed() {
+        let mut app = App::new(true, |shown: &mut bool| {
+            v_stack((
+                shown.then(|| "abcdefgh".marquee()),
+                "hide".on_click(|shown: &mut bool| *shown = false),
+            ))
+        })
+        .await;
+        app.config.terminal.backend_mut().resize(4, 2);
+        assert!(app.render_frame(Duration::ZERO).await);
+        app.dispatch([
+            test_mouse(MouseKind::Down(MouseButton::Left), 0, 1),
+            test_mouse(MouseKind::Up(MouseButton::Left), 0, 1),
+        ])
+        .await;
+        assert_eq!(app.screen_line(0), "hide");
+        assert!(!app.render_frame(Duration::from_secs(1)).await);
+    }
+}
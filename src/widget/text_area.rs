@@ -0,0 +1,614 @@
+use ratatui::style::Style;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    geometry::{to_ratatui_rect, Size},
+    OverflowIndicator,
+};
+
+use super::{
+    core::{IdPath, LifeCycleCx, PaintCx},
+    paint_overflow_indicator, set_stringn_clipped,
+    text_input::{
+        cursor_at_column, is_boundary, next_boundary, next_word_end, prev_boundary, prev_word_start,
+    },
+    BoxConstraints, ChangeFlags, Event, EventCx, Key, KeyEvent, LayoutCx, LifeCycle, Message,
+    Modifiers, MouseButton, MouseKind, RawMouseEvent, Widget,
+};
+
+/// A position in the text, `index` is the byte index in the line, which is always at a grapheme boundary.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Cursor {
+    line: usize,
+    index: usize,
+}
+
+/// A visual row, i.e. a part of a (wrapped) line.
+type Row = (usize, usize);
+
+pub struct TextArea {
+    id_path: IdPath,
+    /// The text as it was set by the view or sent to it the last time
+    text: String,
+    lines: Vec<String>,
+    cursor: Cursor,
+    /// The column the cursor tries to keep when moving up and down
+    desired_column: Option<usize>,
+    /// The first visible row
+    scroll: Row,
+    /// The width at which lines are wrapped
+    width: usize,
+    height: usize,
+    style: Style,
+    overflow_indicator: Option<OverflowIndicator>,
+}
+
+impl TextArea {
+    pub(crate) fn new(id_path: &IdPath, text: String, style: Style) -> Self {
+        TextArea {
+            id_path: id_path.clone(),
+            lines: text.split('\n').map(String::from).collect(),
+            text,
+            cursor: Cursor::default(),
+            desired_column: None,
+            scroll: (0, 0),
+            width: 1,
+            height: 1,
+            style,
+            overflow_indicator: None,
+        }
+    }
+
+    pub(crate) fn set_text(&mut self, text: &str) -> ChangeFlags {
+        if self.text != text {
+            self.text = text.to_string();
+            self.lines = text.split('\n').map(String::from).collect();
+            let line = self.cursor.line.min(self.lines.len() - 1);
+            let index = if is_boundary(&self.lines[line], self.cursor.index) {
+                self.cursor.index
+            } else {
+                self.lines[line].len()
+            };
+            self.cursor = Cursor { line, index };
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) -> ChangeFlags {
+        if self.style != style {
+            self.style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_overflow_indicator(
+        &mut self,
+        overflow_indicator: Option<OverflowIndicator>,
+    ) -> ChangeFlags {
+        if self.overflow_indicator != overflow_indicator {
+            self.overflow_indicator = overflow_indicator;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn line(&self) -> &String {
+        &self.lines[self.cursor.line]
+    }
+
+    fn row_starts(&self, line: usize) -> Vec<usize> {
+        row_starts(&self.lines[line], self.width)
+    }
+
+    /// The row of the cursor and its column within that row.
+    fn cursor_row(&self) -> (Row, usize) {
+        let starts = self.row_starts(self.cursor.line);
+        let row = starts.partition_point(|start| *start <= self.cursor.index) - 1;
+        let column = self.line()[starts[row]..self.cursor.index].width();
+        ((self.cursor.line, row), column)
+    }
+
+    fn next_row(&self, (line, row): Row) -> Option<Row> {
+        if row + 1 < self.row_starts(line).len() {
+            Some((line, row + 1))
+        } else if line + 1 < self.lines.len() {
+            Some((line + 1, 0))
+        } else {
+            None
+        }
+    }
+
+    fn prev_row(&self, (line, row): Row) -> Option<Row> {
+        if row > 0 {
+            Some((line, row - 1))
+        } else if line > 0 {
+            Some((line - 1, self.row_starts(line - 1).len() - 1))
+        } else {
+            None
+        }
+    }
+
+    /// The text of a row, `None` if the row doesn't exist (anymore).
+    fn row_text(&self, (line, row): Row) -> Option<&str> {
+        let text = self.lines.get(line)?;
+        let starts = row_starts(text, self.width);
+        let start = *starts.get(row)?;
+        let end = starts.get(row + 1).copied().unwrap_or(text.len());
+        Some(&text[start..end])
+    }
+
+    /// Moves the cursor to the grapheme closest to `column` in `row`.
+    fn move_to_row(&mut self, (line, row): Row, column: usize) {
+        let text = &self.lines[line];
+        let starts = row_starts(text, self.width);
+        let start = starts[row];
+        let end = starts.get(row + 1).copied().unwrap_or(text.len());
+        let mut index = start + cursor_at_column(&text[start..end], column);
+        // The end of a wrapped row is the start of the next row, so stay in front of the last grapheme
+        if index == end && row + 1 < starts.len() {
+            index = prev_boundary(text, end).max(start);
+        }
+        self.cursor = Cursor { line, index };
+    }
+
+    fn move_vertically(&mut self, rows: usize, up: bool) {
+        let (mut row, column) = self.cursor_row();
+        let column = *self.desired_column.get_or_insert(column);
+        for _ in 0..rows {
+            let next = if up {
+                self.prev_row(row)
+            } else {
+                self.next_row(row)
+            };
+            match next {
+                Some(next) => row = next,
+                None => break,
+            }
+        }
+        self.move_to_row(row, column);
+    }
+
+    /// Applies a key press to the text and cursor, returns whether the text has changed.
+    ///
+    /// Returns `None` if the key isn't used by the text area.
+    fn edit(&mut self, key: &KeyEvent) -> Option<bool> {
+        let Cursor { line, index } = self.cursor;
+        let ctrl = key.modifiers.contains(Modifiers::CONTROL);
+        if key.modifiers.contains(Modifiers::ALT)
+            || (ctrl
+                && !matches!(
+                    key.code,
+                    Key::Home | Key::End | Key::Left | Key::Right | Key::Backspace | Key::Delete
+                ))
+        {
+            return None;
+        }
+        let line_len = self.lines[line].len();
+        let last_line = self.lines.len() - 1;
+        // Vertical movement keeps the desired column, everything else resets it
+        let vertical_movement = match key.code {
+            Key::Up => Some((1, true)),
+            Key::Down => Some((1, false)),
+            Key::PageUp => Some((self.height.max(1), true)),
+            Key::PageDown => Some((self.height.max(1), false)),
+            _ => None,
+        };
+        if let Some((rows, up)) = vertical_movement {
+            self.move_vertically(rows, up);
+            return Some(false);
+        }
+        let mut text_changed = true;
+        match key.code {
+            Key::Char(c) => {
+                self.lines[line].insert(index, c);
+                // e.g. a combining character could have been merged with the previous grapheme
+                self.cursor.index = next_boundary(&self.lines[line], index);
+            }
+            Key::Enter => {
+                let rest = self.lines[line].split_off(index);
+                self.lines.insert(line + 1, rest);
+                self.cursor = Cursor {
+                    line: line + 1,
+                    index: 0,
+                };
+            }
+            // Word-wise within the line, at its start or end like without `Ctrl`
+            Key::Backspace if ctrl && index > 0 => {
+                let start = prev_word_start(&self.lines[line], index);
+                self.lines[line].replace_range(start..index, "");
+                self.cursor.index = start;
+            }
+            Key::Delete if ctrl && index < line_len => {
+                let end = next_word_end(&self.lines[line], index);
+                self.lines[line].replace_range(index..end, "");
+            }
+            Key::Backspace if index > 0 => {
+                let start = prev_boundary(&self.lines[line], index);
+                self.lines[line].replace_range(start..index, "");
+                self.cursor.index = start;
+            }
+            Key::Backspace if line > 0 => {
+                let removed = self.lines.remove(line);
+                let index = self.lines[line - 1].len();
+                self.lines[line - 1].push_str(&removed);
+                self.cursor = Cursor {
+                    line: line - 1,
+                    index,
+                };
+            }
+            Key::Delete if index < line_len => {
+                let end = next_boundary(&self.lines[line], index);
+                self.lines[line].replace_range(index..end, "");
+            }
+            Key::Delete if line < last_line => {
+                let removed = self.lines.remove(line + 1);
+                self.lines[line].push_str(&removed);
+            }
+            _ => {
+                text_changed = false;
+                match key.code {
+                    Key::Left if ctrl && index > 0 => {
+                        self.cursor.index = prev_word_start(&self.lines[line], index);
+                    }
+                    Key::Right if ctrl && index < line_len => {
+                        self.cursor.index = next_word_end(&self.lines[line], index);
+                    }
+                    Key::Left if index > 0 => {
+                        self.cursor.index = prev_boundary(&self.lines[line], index);
+                    }
+                    Key::Left if line > 0 => {
+                        self.cursor = Cursor {
+                            line: line - 1,
+                            index: self.lines[line - 1].len(),
+                        };
+                    }
+                    Key::Right if index < line_len => {
+                        self.cursor.index = next_boundary(&self.lines[line], index);
+                    }
+                    Key::Right if line < last_line => {
+                        self.cursor = Cursor {
+                            line: line + 1,
+                            index: 0,
+                        };
+                    }
+                    Key::Home if ctrl => self.cursor = Cursor::default(),
+                    Key::End if ctrl => {
+                        self.cursor = Cursor {
+                            line: last_line,
+                            index: self.lines[last_line].len(),
+                        };
+                    }
+                    Key::Home => self.cursor.index = 0,
+                    Key::End => self.cursor.index = line_len,
+                    // At the start or end of the text
+                    Key::Left | Key::Right | Key::Backspace | Key::Delete => (),
+                    _ => return None,
+                }
+            }
+        }
+        self.desired_column = None;
+        Some(text_changed)
+    }
+
+    /// Inserts pasted `text` at the cursor as a single edit, returns whether the text has changed.
+    fn paste(&mut self, text: &str) -> bool {
+        if text.is_empty() {
+            return false;
+        }
+        let Cursor { line, index } = self.cursor;
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let rest = self.lines[line].split_off(index);
+        let mut pasted = text.split('\n');
+        // `split` yields at least one (maybe empty) line
+        self.lines[line].push_str(pasted.next().unwrap_or_default());
+        let mut last = line;
+        for pasted_line in pasted {
+            last += 1;
+            self.lines.insert(last, pasted_line.to_string());
+        }
+        let mut index = self.lines[last].len();
+        self.lines[last].push_str(&rest);
+        // e.g. the rest of the line could start with a combining character
+        if !is_boundary(&self.lines[last], index) {
+            index = next_boundary(&self.lines[last], index);
+        }
+        self.cursor = Cursor { line: last, index };
+        self.desired_column = None;
+        true
+    }
+
+    /// Scrolls as little as possible, so that the cursor is visible.
+    fn scroll_to_cursor(&mut self) {
+        // The scrolled to row may have been removed by an edit
+        if self.row_text(self.scroll).is_none() {
+            let line = self.scroll.0.min(self.lines.len() - 1);
+            self.scroll = (line, self.row_starts(line).len() - 1);
+        }
+        let (cursor_row, _) = self.cursor_row();
+        if cursor_row < self.scroll {
+            self.scroll = cursor_row;
+            return;
+        }
+        let mut row = self.scroll;
+        for _ in 1..self.height {
+            if row == cursor_row {
+                return;
+            }
+            match self.next_row(row) {
+                Some(next) => row = next,
+                None => return,
+            }
+        }
+        if row != cursor_row {
+            // The cursor is below the visible rows, so it's shown in the last row
+            let mut row = cursor_row;
+            for _ in 1..self.height {
+                match self.prev_row(row) {
+                    Some(prev) => row = prev,
+                    None => break,
+                }
+            }
+            self.scroll = row;
+        }
+    }
+
+    fn emit_text(&mut self, cx: &mut EventCx) {
+        self.text = self.lines.join("\n");
+        cx.add_message(Message::new(self.id_path.clone(), self.text.clone()));
+    }
+}
+
+/// The byte indices at which the visual rows of `line` start, when it's wrapped at `width` cells.
+fn row_starts(line: &str, width: usize) -> Vec<usize> {
+    let width = width.max(1);
+    let mut starts = vec![0];
+    let mut row_width = 0;
+    for (i, grapheme) in line.grapheme_indices(true) {
+        let grapheme_width = grapheme.width();
+        if row_width + grapheme_width > width && row_width > 0 {
+            starts.push(i);
+            row_width = 0;
+        }
+        row_width += grapheme_width;
+    }
+    // The cursor at the end of a completely filled row is shown at the start of the next row
+    if row_width >= width {
+        starts.push(line.len());
+    }
+    starts
+}
+
+impl Widget for TextArea {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let style = self.style.patch(cx.override_style());
+        let is_focused = cx.is_focused();
+        if rect.width == 0 || rect.height == 0 {
+            return;
+        }
+        self.scroll_to_cursor();
+        let (cursor_row, cursor_column) = self.cursor_row();
+
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        if area.area() == 0 {
+            return;
+        }
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf.get_mut(x, y).set_symbol(" ").set_style(style);
+            }
+        }
+
+        let mut cursor_position = None;
+        let mut row = Some(self.scroll);
+        for y in rect.top()..rect.bottom() {
+            let Some(current_row) = row else {
+                break;
+            };
+            if current_row == cursor_row {
+                cursor_position = Some((rect.x + cursor_column as u16, y));
+            }
+            if y >= area.top() && y < area.bottom() {
+                let text = self.row_text(current_row).unwrap_or_default();
+                let max_width = (area.right() - rect.x) as usize;
+                set_stringn_clipped(buf, rect.x, y, text, max_width, style);
+            }
+            row = self.next_row(current_row);
+        }
+
+        if let Some(indicator) = &self.overflow_indicator {
+            // `row` is the first row below the text area, if there is one
+            let above = self.scroll != (0, 0);
+            paint_overflow_indicator(buf, rect, indicator, above, row.is_some());
+        }
+
+        if let Some((x, y)) = cursor_position {
+            let visible =
+                area.left() <= x && x < area.right() && area.top() <= y && y < area.bottom();
+            if is_focused && visible {
+                cx.set_cursor_position(x, y);
+            }
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // The text area fills the available space, so that edits don't require a new layout
+        let width = if bc.is_width_bounded() {
+            bc.max().width
+        } else {
+            // An additional cell for the cursor at the end of the lines
+            self.lines
+                .iter()
+                .map(|line| line.width())
+                .max()
+                .unwrap_or(0) as f64
+                + 1.0
+        };
+        self.width = width as usize;
+        let height = if bc.is_height_bounded() {
+            bc.max().height
+        } else {
+            let rows: usize = (0..self.lines.len())
+                .map(|line| self.row_starts(line).len())
+                .sum();
+            rows as f64
+        };
+        let size = bc.constrain(Size::new(width, height));
+        self.width = size.width as usize;
+        self.height = size.height as usize;
+        size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        match event {
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Down(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) if cx.is_hot() => {
+                // Mouse events are relative to the origin of the widget
+                let mut clicked_row = self.scroll;
+                for _ in 0..(*row).max(0) {
+                    match self.next_row(clicked_row) {
+                        Some(next) => clicked_row = next,
+                        None => break,
+                    }
+                }
+                self.move_to_row(clicked_row, (*column).max(0) as usize);
+                self.desired_column = None;
+                cx.request_focus();
+                cx.request_paint();
+            }
+            Event::Key(key) if cx.is_focused() => {
+                if let Some(text_changed) = self.edit(key) {
+                    if text_changed {
+                        self.emit_text(cx);
+                    }
+                    cx.request_paint();
+                    cx.set_handled(true);
+                }
+            }
+            // Line breaks are kept, the whole text is a single edit
+            Event::Paste(text) if cx.is_focused() => {
+                if self.paste(text) {
+                    self.emit_text(cx);
+                }
+                cx.request_paint();
+                cx.set_handled(true);
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        if matches!(event, LifeCycle::FocusChanged(_)) {
+            // show or hide the cursor
+            cx.request_paint();
+        }
+    }
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_area(text: &str, width: usize) -> TextArea {
+        let mut text_area = TextArea::new(&IdPath::new(), text.to_string(), Style::default());
+        text_area.width = width;
+        text_area.height = 3;
+        text_area
+    }
+
+    fn press(text_area: &mut TextArea, code: Key) -> Option<bool> {
+        text_area.edit(&KeyEvent::from(code))
+    }
+
+    #[test]
+    fn lines_are_wrapped_at_the_width() {
+        assert_eq!(row_starts("", 4), vec![0]);
+        assert_eq!(row_starts("abc", 4), vec![0]);
+        assert_eq!(row_starts("abcdefghij", 4), vec![0, 4, 8]);
+        // The cursor behind a full row needs an additional row
+        assert_eq!(row_starts("abcd", 4), vec![0, 4]);
+        // Wide characters aren't split
+        assert_eq!(row_starts("日本語", 5), vec![0, "日本".len()]);
+    }
+
+    #[test]
+    fn enter_and_backspace_split_and_join_lines() {
+        let mut text_area = text_area("hello world", 20);
+        text_area.cursor.index = 5;
+        assert_eq!(press(&mut text_area, Key::Enter), Some(true));
+        assert_eq!(text_area.lines, vec!["hello", " world"]);
+        assert_eq!(text_area.cursor, Cursor { line: 1, index: 0 });
+        assert_eq!(press(&mut text_area, Key::Backspace), Some(true));
+        assert_eq!(text_area.lines, vec!["hello world"]);
+        assert_eq!(text_area.cursor, Cursor { line: 0, index: 5 });
+        press(&mut text_area, Key::End);
+        assert_eq!(press(&mut text_area, Key::Delete), Some(false));
+    }
+
+    #[test]
+    fn pasted_lines_are_inserted_at_the_cursor() {
+        let mut text_area = text_area("<>", 20);
+        text_area.cursor.index = 1;
+        assert!(text_area.paste("a\r\nb\nc"));
+        assert_eq!(text_area.lines, vec!["<a", "b", "c>"]);
+        assert_eq!(text_area.cursor, Cursor { line: 2, index: 1 });
+    }
+
+    #[test]
+    fn cursor_keeps_its_column_when_moving_across_lines() {
+        let mut text_area = text_area("abcdef\nab\nabcdef", 20);
+        text_area.cursor.index = 5;
+        press(&mut text_area, Key::Down);
+        assert_eq!(text_area.cursor, Cursor { line: 1, index: 2 });
+        press(&mut text_area, Key::Down);
+        assert_eq!(text_area.cursor, Cursor { line: 2, index: 5 });
+        press(&mut text_area, Key::Down);
+        assert_eq!(text_area.cursor, Cursor { line: 2, index: 5 });
+        press(&mut text_area, Key::PageUp);
+        assert_eq!(text_area.cursor, Cursor { line: 0, index: 5 });
+    }
+
+    #[test]
+    fn cursor_moves_through_wrapped_rows() {
+        let mut text_area = text_area("abcdefghij\nxy", 4);
+        text_area.cursor.index = 1;
+        press(&mut text_area, Key::Down);
+        assert_eq!(text_area.cursor, Cursor { line: 0, index: 5 });
+        press(&mut text_area, Key::Down);
+        assert_eq!(text_area.cursor, Cursor { line: 0, index: 9 });
+        press(&mut text_area, Key::Down);
+        assert_eq!(text_area.cursor, Cursor { line: 1, index: 1 });
+        press(&mut text_area, Key::Left);
+        press(&mut text_area, Key::Left);
+        assert_eq!(text_area.cursor, Cursor { line: 0, index: 10 });
+    }
+
+    #[test]
+    fn scrolls_to_the_cursor() {
+        let mut text_area = text_area("1\n2\n3\n4\n5", 4);
+        press(&mut text_area, Key::PageDown);
+        press(&mut text_area, Key::PageDown);
+        text_area.scroll_to_cursor();
+        assert_eq!(text_area.scroll, (2, 0));
+        press(&mut text_area, Key::Up);
+        press(&mut text_area, Key::Up);
+        press(&mut text_area, Key::Up);
+        text_area.scroll_to_cursor();
+        assert_eq!(text_area.scroll, (1, 0));
+    }
+}
@@ -0,0 +1,1065 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    geometry::{to_ratatui_rect, Size},
+    view::{PasteNewlines, Validation},
+};
+
+use super::{
+    core::{IdPath, LifeCycleCx, PaintCx},
+    set_stringn_clipped,
+    text::escape_sequence,
+    BoxConstraints, ChangeFlags, Event, EventCx, Key, KeyEvent, LayoutCx, LifeCycle, Message,
+    Modifiers, MouseButton, MouseKind, RawMouseEvent, Widget,
+};
+
+pub struct TextInput {
+    id_path: IdPath,
+    text: String,
+    /// The byte index of the cursor, which is always at a grapheme boundary.
+    cursor: usize,
+    /// The amount of cells the text is scrolled to the left, so that the cursor stays visible.
+    scroll: usize,
+    style: Style,
+    /// Shown while the text is empty
+    placeholder: String,
+    placeholder_style: Style,
+    /// Painted instead of each grapheme of the text, e.g. for passwords
+    mask: Option<String>,
+    validator: Option<Validator>,
+    validation_delay: Duration,
+    /// The validation is pending until then, it's delayed while the user is still typing
+    validation_deadline: Option<Instant>,
+    /// The message of the last failed validation
+    error: Option<String>,
+    error_style: Style,
+    show_error_message: bool,
+    /// Candidates for the text, shown in a dropdown while the input is focused
+    suggestions: Vec<String>,
+    suggestion_style: Style,
+    selected_suggestion_style: Style,
+    selected_suggestion: Option<usize>,
+    /// The dropdown is closed with `Esc` or by accepting a suggestion, until the next edit
+    suggestions_open: bool,
+    /// The time between toggling the cursor on and off while focused, it doesn't blink if `None`
+    cursor_blink: Option<Duration>,
+    /// The blink starts with a visible cursor then, it's reset with every key press
+    blink_start: Instant,
+    cursor_visible: bool,
+    paste_newlines: PasteNewlines,
+}
+
+/// The maximum amount of suggestions, which are shown at once in the dropdown
+const MAX_VISIBLE_SUGGESTIONS: usize = 8;
+
+/// Checks the text of a [`TextInput`], returns a message describing the problem if it's invalid.
+pub(crate) type Validator = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+impl TextInput {
+    pub(crate) fn new(id_path: &IdPath, text: String, style: Style) -> Self {
+        TextInput {
+            id_path: id_path.clone(),
+            cursor: text.len(),
+            text,
+            scroll: 0,
+            style,
+            placeholder: String::new(),
+            placeholder_style: Style::default(),
+            mask: None,
+            validator: None,
+            validation_delay: Duration::ZERO,
+            validation_deadline: None,
+            error: None,
+            error_style: Style::default(),
+            show_error_message: true,
+            suggestions: Vec::new(),
+            suggestion_style: Style::default(),
+            selected_suggestion_style: Style::default(),
+            selected_suggestion: None,
+            suggestions_open: true,
+            cursor_blink: None,
+            blink_start: Instant::now(),
+            cursor_visible: true,
+            paste_newlines: PasteNewlines::default(),
+        }
+    }
+
+    pub(crate) fn set_text(&mut self, text: &str) -> ChangeFlags {
+        if self.text != text {
+            self.text = text.to_string();
+            if !is_boundary(&self.text, self.cursor) {
+                self.cursor = self.text.len();
+            }
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) -> ChangeFlags {
+        if self.style != style {
+            self.style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_placeholder(&mut self, placeholder: &str, style: Style) -> ChangeFlags {
+        let mut changeflags = ChangeFlags::empty();
+        if self.placeholder != placeholder {
+            self.placeholder = placeholder.to_string();
+            changeflags |= ChangeFlags::LAYOUT | ChangeFlags::PAINT;
+        }
+        if self.placeholder_style != style {
+            self.placeholder_style = style;
+            changeflags |= ChangeFlags::PAINT;
+        }
+        changeflags
+    }
+
+    pub(crate) fn set_mask(&mut self, mask: Option<char>) -> ChangeFlags {
+        let mask = mask.map(String::from);
+        if self.mask != mask {
+            self.mask = mask;
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// The validator isn't compared, as it's usually a new closure with every rebuild,
+    /// a new validator is only used with the next edit.
+    pub(crate) fn set_validator(&mut self, validator: Option<Validator>, delay: Duration) {
+        if validator.is_none() {
+            self.validation_deadline = None;
+            self.error = None;
+        }
+        self.validator = validator;
+        self.validation_delay = delay;
+    }
+
+    pub(crate) fn set_error_style(&mut self, style: Style, show_message: bool) -> ChangeFlags {
+        let mut changeflags = ChangeFlags::empty();
+        if self.error_style != style {
+            self.error_style = style;
+            changeflags |= ChangeFlags::PAINT;
+        }
+        if self.show_error_message != show_message {
+            self.show_error_message = show_message;
+            changeflags |= ChangeFlags::LAYOUT | ChangeFlags::PAINT;
+        }
+        changeflags
+    }
+
+    pub(crate) fn set_suggestions(&mut self, suggestions: &[String]) -> ChangeFlags {
+        if self.suggestions != suggestions {
+            self.suggestions = suggestions.to_vec();
+            self.selected_suggestion = None;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// The styles of the suggestions and of the selected suggestion.
+    pub(crate) fn set_suggestion_styles(
+        &mut self,
+        (style, selected): (Style, Style),
+    ) -> ChangeFlags {
+        if self.suggestion_style != style || self.selected_suggestion_style != selected {
+            self.suggestion_style = style;
+            self.selected_suggestion_style = selected;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_cursor_blink(&mut self, interval: Option<Duration>) -> ChangeFlags {
+        let interval = interval.filter(|interval| !interval.is_zero());
+        if self.cursor_blink != interval {
+            self.cursor_blink = interval;
+            self.cursor_visible = true;
+            // The animation is only continued while the input is focused
+            ChangeFlags::PAINT | ChangeFlags::ANIMATION
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// Only affects the next paste, nothing has to be repainted.
+    pub(crate) fn set_paste_newlines(&mut self, paste_newlines: PasteNewlines) {
+        self.paste_newlines = paste_newlines;
+    }
+
+    /// Inserts pasted `text` at the cursor as a single edit, returns whether the text has changed.
+    fn paste(&mut self, text: &str) -> bool {
+        // Terminals may send a lone `\r` as line break, e.g. in bracketed paste mode
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let text = text.strip_suffix('\n').unwrap_or(&text);
+        let text = if text.contains('\n') {
+            match self.paste_newlines {
+                PasteNewlines::Join => text.split('\n').collect::<Vec<_>>().join(" "),
+                PasteNewlines::Reject => return false,
+            }
+        } else {
+            text.to_string()
+        };
+        let text = strip_control_chars(&text);
+        if text.is_empty() {
+            return false;
+        }
+        self.text.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+        // e.g. the pasted text could start with a combining character
+        if !is_boundary(&self.text, self.cursor) {
+            self.cursor = next_boundary(&self.text, self.cursor);
+        }
+        true
+    }
+
+    /// Shows the cursor and starts blinking again from there, so that it doesn't blink while typing.
+    fn reset_blink(&mut self, now: Instant) {
+        self.blink_start = now;
+        self.cursor_visible = true;
+    }
+
+    /// Whether the blinking cursor is in its visible phase at `now`.
+    fn cursor_visible_at(&self, now: Instant) -> bool {
+        let Some(interval) = self.cursor_blink else {
+            return true;
+        };
+        let toggles =
+            now.saturating_duration_since(self.blink_start).as_nanos() / interval.as_nanos();
+        toggles % 2 == 0
+    }
+
+    /// Suggestions of a masked input (e.g. of a password) would reveal what's typed.
+    fn shows_suggestions(&self) -> bool {
+        self.suggestions_open && !self.suggestions.is_empty() && self.mask.is_none()
+    }
+
+    /// Navigates the open suggestion dropdown, returns whether the text has changed.
+    ///
+    /// `Tab` and `Enter` replace the text with the selected (or first) suggestion.
+    /// Returns `None` if the key isn't used by the dropdown.
+    fn navigate_suggestions(&mut self, key: &KeyEvent) -> Option<bool> {
+        if !self.shows_suggestions() || !key.modifiers.is_empty() {
+            return None;
+        }
+        let len = self.suggestions.len();
+        let selected = self.selected_suggestion;
+        match key.code {
+            Key::Down => self.selected_suggestion = Some(selected.map_or(0, |i| (i + 1) % len)),
+            Key::Up => {
+                self.selected_suggestion = Some(selected.map_or(len - 1, |i| (i + len - 1) % len))
+            }
+            Key::Tab | Key::Enter => {
+                let suggestion = &self.suggestions[selected.unwrap_or(0)];
+                let changed = self.text != *suggestion;
+                self.text = suggestion.clone();
+                self.cursor = self.text.len();
+                self.suggestions_open = false;
+                return Some(changed);
+            }
+            // The typed text is kept
+            Key::Esc => self.suggestions_open = false,
+            _ => return None,
+        }
+        Some(false)
+    }
+
+    /// Sends the changed text (and the result of its validation) to the view.
+    fn send_text(&mut self, cx: &mut EventCx) {
+        cx.add_message(Message::new(self.id_path.clone(), self.text.clone()));
+        match self.text_changed(Instant::now()) {
+            Some(validation) => cx.add_message(Message::new(self.id_path.clone(), validation)),
+            None if self.validation_deadline.is_some() => cx.request_animation_update(),
+            None => (),
+        }
+        cx.request_layout();
+    }
+
+    /// Paints the open suggestion dropdown below `anchor` (or above it, if there's no room below)
+    /// as overlay, so that it isn't clipped or painted over by the following widgets.
+    fn paint_suggestions(&self, cx: &mut PaintCx, anchor: Rect) {
+        let visible = self.suggestions.len().min(MAX_VISIBLE_SUGGESTIONS);
+        // Scrolls the suggestions, so that the selected one is visible
+        let first = self
+            .selected_suggestion
+            .map_or(0, |i| (i + 1).saturating_sub(visible));
+        let suggestions = &self.suggestions[first..first + visible];
+        let width = suggestions.iter().map(|s| s.width()).max().unwrap_or(0) + 2;
+        let screen = cx.buffer_mut().area;
+        let rect = dropdown_rect(anchor, (width as u16, visible as u16), screen);
+        if rect.area() == 0 {
+            return;
+        }
+        let mut overlay = Buffer::empty(rect);
+        for (i, (y, suggestion)) in (rect.top()..rect.bottom()).zip(suggestions).enumerate() {
+            let style = if self.selected_suggestion == Some(first + i) {
+                self.selected_suggestion_style
+            } else {
+                self.suggestion_style
+            };
+            overlay.set_style(Rect::new(rect.x, y, rect.width, 1), style);
+            let max_width = rect.width.saturating_sub(2) as usize;
+            overlay.set_stringn(rect.x + 1, y, suggestion, max_width, style);
+        }
+        cx.paint_overlay(overlay);
+    }
+
+    /// Validates the changed text right away, or schedules the validation when it's delayed.
+    fn text_changed(&mut self, now: Instant) -> Option<Validation> {
+        self.validator.as_ref()?;
+        if self.validation_delay.is_zero() {
+            self.validate()
+        } else {
+            self.validation_deadline = Some(now + self.validation_delay);
+            None
+        }
+    }
+
+    /// Validates the text if the pending validation is due.
+    fn validate_pending(&mut self, now: Instant) -> Option<Validation> {
+        if now < self.validation_deadline? {
+            return None;
+        }
+        self.validation_deadline = None;
+        self.validate()
+    }
+
+    fn validate(&mut self) -> Option<Validation> {
+        let result = (self.validator.as_ref()?)(&self.text);
+        self.error = result.clone().err();
+        Some(match result {
+            Ok(()) => Validation::Valid,
+            Err(message) => Validation::Invalid(message),
+        })
+    }
+
+    /// The graphemes of the text with their byte index as they are painted, i.e. masked if there's a mask.
+    fn displayed_graphemes(&self) -> impl Iterator<Item = (usize, &str)> {
+        self.text
+            .grapheme_indices(true)
+            .map(|(i, grapheme)| (i, self.mask.as_deref().unwrap_or(grapheme)))
+    }
+
+    /// The width of the painted text up to the byte index `end`.
+    fn displayed_width(&self, end: usize) -> usize {
+        self.displayed_graphemes()
+            .take_while(|(i, _)| *i < end)
+            .map(|(_, grapheme)| grapheme.width())
+            .sum()
+    }
+
+    /// Applies a key press to the text and cursor, returns whether the text has changed.
+    ///
+    /// Returns `None` if the key isn't used by the text input.
+    fn edit(&mut self, key: &KeyEvent) -> Option<bool> {
+        edit_line(&mut self.text, &mut self.cursor, key)
+    }
+}
+
+/// The area of a dropdown of `size`, which is placed below `anchor` (or above it, if there's not
+/// enough space below) and is at least as wide as `anchor`, within `screen`.
+fn dropdown_rect(anchor: Rect, (width, height): (u16, u16), screen: Rect) -> Rect {
+    let width = width.max(anchor.width).min(screen.width);
+    let x = anchor.x.min(screen.right() - width).max(screen.left());
+    let space_below = screen.bottom().saturating_sub(anchor.bottom());
+    let space_above = anchor.top().saturating_sub(screen.top());
+    if height <= space_below || space_below >= space_above {
+        Rect::new(x, anchor.bottom(), width, height.min(space_below))
+    } else {
+        let height = height.min(space_above);
+        Rect::new(x, anchor.top() - height, width, height)
+    }
+}
+
+/// Applies a key press to a single line `text` with the byte index `cursor`, returns whether the text
+/// has changed.
+///
+/// Returns `None` if the key isn't used for editing.
+pub(super) fn edit_line(text: &mut String, cursor: &mut usize, key: &KeyEvent) -> Option<bool> {
+    if key.modifiers.contains(Modifiers::ALT) {
+        return None;
+    }
+    if key.modifiers.contains(Modifiers::CONTROL) {
+        return edit_line_by_word(text, cursor, key.code);
+    }
+    match key.code {
+        Key::Char(c) => {
+            text.insert(*cursor, c);
+            // e.g. a combining character could have been merged with the previous grapheme
+            *cursor = next_boundary(text, *cursor);
+            Some(true)
+        }
+        Key::Backspace => {
+            let start = prev_boundary(text, *cursor);
+            text.replace_range(start..*cursor, "");
+            let changed = start != *cursor;
+            *cursor = start;
+            Some(changed)
+        }
+        Key::Delete => {
+            let end = next_boundary(text, *cursor);
+            text.replace_range(*cursor..end, "");
+            Some(end != *cursor)
+        }
+        Key::Left => {
+            *cursor = prev_boundary(text, *cursor);
+            Some(false)
+        }
+        Key::Right => {
+            *cursor = next_boundary(text, *cursor);
+            Some(false)
+        }
+        Key::Home => {
+            *cursor = 0;
+            Some(false)
+        }
+        Key::End => {
+            *cursor = text.len();
+            Some(false)
+        }
+        _ => None,
+    }
+}
+
+/// Word-wise editing with `Ctrl`, which moves or deletes up to the start or end of a word,
+/// see [`prev_word_start`] and [`next_word_end`].
+fn edit_line_by_word(text: &mut String, cursor: &mut usize, code: Key) -> Option<bool> {
+    match code {
+        Key::Backspace => {
+            let start = prev_word_start(text, *cursor);
+            text.replace_range(start..*cursor, "");
+            let changed = start != *cursor;
+            *cursor = start;
+            Some(changed)
+        }
+        Key::Delete => {
+            let end = next_word_end(text, *cursor);
+            text.replace_range(*cursor..end, "");
+            Some(end != *cursor)
+        }
+        Key::Left => {
+            *cursor = prev_word_start(text, *cursor);
+            Some(false)
+        }
+        Key::Right => {
+            *cursor = next_word_end(text, *cursor);
+            Some(false)
+        }
+        _ => None,
+    }
+}
+
+/// The byte index of the start of the word before `cursor`, whitespace between is skipped.
+///
+/// Words are segmented by the Unicode word boundaries, i.e. `fooBar` or `foo.bar` are a single word
+/// and a run of other punctuation like `,` or `->` is a word of its own.
+pub(super) fn prev_word_start(text: &str, cursor: usize) -> usize {
+    text.split_word_bound_indices()
+        .rev()
+        .find(|(i, word)| *i < cursor && !word.trim().is_empty())
+        .map_or(0, |(i, _)| i)
+}
+
+/// The byte index of the end of the word after `cursor`, whitespace between is skipped,
+/// see [`prev_word_start`].
+pub(super) fn next_word_end(text: &str, cursor: usize) -> usize {
+    text.split_word_bound_indices()
+        .find(|(i, word)| i + word.len() > cursor && !word.trim().is_empty())
+        .map_or(text.len(), |(i, word)| i + word.len())
+}
+
+/// Replaces the tabs of pasted `text` with a space and removes other control characters,
+/// including whole escape sequences (e.g. colors of copied terminal output), which would corrupt
+/// the display.
+fn strip_control_chars(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut i = 0;
+    while let Some(c) = text[i..].chars().next() {
+        let mut len = c.len_utf8();
+        match c {
+            '\t' => stripped.push(' '),
+            '\x1b' => len = escape_sequence(&text[i..]).0,
+            _ if c.is_control() => {}
+            _ => stripped.push(c),
+        }
+        i += len;
+    }
+    stripped
+}
+
+/// Whether `cursor` is a byte index at a grapheme boundary of `text`.
+pub(super) fn is_boundary(text: &str, cursor: usize) -> bool {
+    cursor <= text.len()
+        && GraphemeCursor::new(cursor, text.len(), true)
+            .is_boundary(text, 0)
+            .unwrap_or(false)
+}
+
+/// The byte index of the grapheme boundary before `cursor`.
+pub(super) fn prev_boundary(text: &str, cursor: usize) -> usize {
+    GraphemeCursor::new(cursor, text.len(), true)
+        .prev_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(0)
+}
+
+/// The byte index of the grapheme boundary after `cursor`.
+pub(super) fn next_boundary(text: &str, cursor: usize) -> usize {
+    GraphemeCursor::new(cursor, text.len(), true)
+        .next_boundary(text, 0)
+        .ok()
+        .flatten()
+        .unwrap_or(text.len())
+}
+
+/// The byte index of the grapheme boundary, which is the closest to the cell `column`.
+pub(super) fn cursor_at_column(text: &str, column: usize) -> usize {
+    column_to_cursor(text.grapheme_indices(true), text.len(), column)
+}
+
+/// See [`cursor_at_column`], for `graphemes` with their byte index as they are painted.
+fn column_to_cursor<'a>(
+    graphemes: impl Iterator<Item = (usize, &'a str)>,
+    len: usize,
+    column: usize,
+) -> usize {
+    let mut width = 0;
+    for (i, grapheme) in graphemes {
+        let grapheme_width = grapheme.width();
+        // Clicking on the right half of a wide grapheme places the cursor after it
+        if column < width + grapheme_width.div_ceil(2) {
+            return i;
+        }
+        width += grapheme_width;
+    }
+    len
+}
+
+impl Widget for TextInput {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let mut style = self.style.patch(cx.override_style());
+        let error_style = cx.override_style().patch(self.error_style);
+        if self.error.is_some() {
+            style = style.patch(self.error_style);
+        }
+        let is_focused = cx.is_focused();
+
+        let width = rect.width as usize;
+        if width == 0 || rect.height == 0 {
+            return;
+        }
+        // The cursor takes a cell at the end of the text
+        self.scroll = self
+            .scroll
+            .min((self.displayed_width(self.text.len()) + 1).saturating_sub(width));
+        let cursor_column = self.displayed_width(self.cursor);
+        if cursor_column < self.scroll {
+            self.scroll = cursor_column;
+        } else if cursor_column >= self.scroll + width {
+            self.scroll = cursor_column + 1 - width;
+        }
+
+        let buf = cx.buffer_mut();
+        if rect.intersection(buf.area).area() == 0 {
+            return;
+        }
+        // The text is on the first row, an error message on the second one
+        let line = Rect { height: 1, ..rect }.intersection(buf.area);
+        if line.area() > 0 {
+            for x in line.left()..line.right() {
+                buf.get_mut(x, line.y).set_symbol(" ").set_style(style);
+            }
+        }
+        if self.text.is_empty() {
+            // The cursor is still at the start, on top of the placeholder
+            let placeholder_style = style.patch(self.placeholder_style);
+            let max_width = line.right().saturating_sub(rect.x) as usize;
+            set_stringn_clipped(
+                buf,
+                rect.x,
+                rect.y,
+                &self.placeholder,
+                max_width,
+                placeholder_style,
+            );
+        }
+        let mut column = 0;
+        // With a mask, the actual text is never painted
+        for (_, grapheme) in self.displayed_graphemes() {
+            let grapheme_width = grapheme.width();
+            // Wide graphemes which are only partly visible are left out
+            if column >= self.scroll && column + grapheme_width <= self.scroll + width {
+                let x = rect.x + (column - self.scroll) as u16;
+                if line.left() <= x && x < line.right() {
+                    buf.set_stringn(x, line.y, grapheme, (line.right() - x) as usize, style);
+                }
+            }
+            column += grapheme_width;
+        }
+        if let Some(error) = self.error.as_ref().filter(|_| self.show_error_message) {
+            if rect.height > 1 {
+                let max_width = rect.right().min(buf.area.right()).saturating_sub(rect.x);
+                let y = rect.y + 1;
+                set_stringn_clipped(buf, rect.x, y, error, max_width as usize, error_style);
+            }
+        }
+
+        if is_focused && self.shows_suggestions() {
+            self.paint_suggestions(cx, rect);
+        }
+        if is_focused && self.cursor_visible {
+            let x = rect.x + (cursor_column - self.scroll) as u16;
+            if line.left() <= x && x < line.right() {
+                cx.set_cursor_position(x, line.y);
+            }
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        // An additional cell for the cursor at the end of the text, the size doesn't change
+        // when the placeholder is replaced with the first typed character
+        let mut width = (self.displayed_width(self.text.len()) + 1).max(self.placeholder.width());
+        // The message of a failed validation is shown in the line below
+        let mut height = 1.0;
+        if let Some(error) = self.error.as_ref().filter(|_| self.show_error_message) {
+            width = width.max(error.width());
+            height = 2.0;
+        }
+        bc.constrain(Size::new(width as f64, height))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        match event {
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Down(MouseButton::Left),
+                column,
+                ..
+            }) if cx.is_hot() => {
+                // Mouse events are relative to the origin of the widget
+                let column = (*column).max(0) as usize;
+                self.cursor = column_to_cursor(
+                    self.displayed_graphemes(),
+                    self.text.len(),
+                    column + self.scroll,
+                );
+                self.reset_blink(Instant::now());
+                cx.request_focus();
+                cx.request_paint();
+            }
+            Event::Key(key) if cx.is_focused() => {
+                if let Some(text_changed) = self.navigate_suggestions(key) {
+                    if text_changed {
+                        self.send_text(cx);
+                    }
+                    cx.request_paint();
+                    cx.set_handled(true);
+                } else if let Some(text_changed) = self.edit(key) {
+                    if text_changed {
+                        self.suggestions_open = true;
+                        self.send_text(cx);
+                    }
+                    self.reset_blink(Instant::now());
+                    cx.request_paint();
+                    cx.set_handled(true);
+                }
+            }
+            Event::Paste(text) if cx.is_focused() => {
+                if self.paste(text) {
+                    self.suggestions_open = true;
+                    self.send_text(cx);
+                }
+                self.reset_blink(Instant::now());
+                cx.request_paint();
+                cx.set_handled(true);
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        match event {
+            // show or hide the cursor and the suggestions
+            LifeCycle::FocusChanged(_) => {
+                if cx.is_focused() {
+                    self.reset_blink(Instant::now());
+                    if self.cursor_blink.is_some() {
+                        cx.request_animation_update();
+                    }
+                }
+                cx.request_paint();
+            }
+            LifeCycle::Animate => {
+                let now = Instant::now();
+                // The deadline is checked in every frame while the validation is pending
+                if self.validation_deadline.is_some() {
+                    match self.validate_pending(now) {
+                        Some(validation) => {
+                            cx.add_message(Message::new(self.id_path.clone(), validation));
+                            cx.request_layout();
+                        }
+                        None => cx.request_animation_update(),
+                    }
+                }
+                // Without the focus, the blinking stops and nothing is repainted anymore
+                if self.cursor_blink.is_some() && cx.is_focused() {
+                    let cursor_visible = self.cursor_visible_at(now);
+                    if self.cursor_visible != cursor_visible {
+                        self.cursor_visible = cursor_visible;
+                        cx.request_paint();
+                    }
+                    cx.request_animation_update();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{buffer::Buffer, style::Color};
+
+    use super::*;
+    use crate::widget::{CxState, FocusState, WidgetState};
+
+    const FAMILY: &str = "👨‍👩‍👧‍👦";
+
+    /// Paints `input` into a 10x1 buffer.
+    fn paint(input: &mut TextInput) -> Buffer {
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 10, 1));
+        let (mut messages, mut focus) = (Vec::new(), FocusState::default());
+        let mut widget_state = WidgetState::new();
+        widget_state.size = Size::new(10.0, 1.0);
+        input.paint(&mut PaintCx {
+            cx_state: &mut CxState::new(&mut messages, &mut focus, Default::default()),
+            widget_state: &mut widget_state,
+            buffer: &mut buffer,
+            override_style: Style::default(),
+        });
+        buffer
+    }
+
+    #[test]
+    fn cursor_moves_over_graphemes() {
+        let text = format!("a{FAMILY}e\u{301}");
+        let after_family = 1 + FAMILY.len();
+        assert_eq!(next_boundary(&text, 0), 1);
+        assert_eq!(next_boundary(&text, 1), after_family);
+        assert_eq!(next_boundary(&text, after_family), text.len());
+        assert_eq!(next_boundary(&text, text.len()), text.len());
+        assert_eq!(prev_boundary(&text, text.len()), after_family);
+        assert_eq!(prev_boundary(&text, after_family), 1);
+        assert_eq!(prev_boundary(&text, 0), 0);
+    }
+
+    #[test]
+    fn backspace_deletes_a_family_emoji_as_one_unit() {
+        let mut input = TextInput::new(&IdPath::new(), format!("a{FAMILY}"), Style::default());
+        let backspace = KeyEvent::from(Key::Backspace);
+        assert_eq!(input.edit(&backspace), Some(true));
+        assert_eq!(input.text, "a");
+        assert_eq!(input.cursor, 1);
+        assert_eq!(input.edit(&backspace), Some(true));
+        assert_eq!(input.text, "");
+        assert_eq!(input.edit(&backspace), Some(false));
+    }
+
+    #[test]
+    fn delete_removes_the_grapheme_after_the_cursor() {
+        let mut input = TextInput::new(
+            &IdPath::new(),
+            format!("{FAMILY}e\u{301}"),
+            Style::default(),
+        );
+        input.edit(&KeyEvent::from(Key::Home));
+        assert_eq!(input.edit(&KeyEvent::from(Key::Delete)), Some(true));
+        assert_eq!(input.text, "e\u{301}");
+        assert_eq!(input.edit(&KeyEvent::from(Key::Delete)), Some(true));
+        assert_eq!(input.text, "");
+    }
+
+    #[test]
+    fn combining_characters_are_merged_with_the_previous_grapheme() {
+        let mut input = TextInput::new(&IdPath::new(), "e".to_string(), Style::default());
+        input.edit(&KeyEvent::from(Key::Char('\u{301}')));
+        assert_eq!(input.cursor, input.text.len());
+        input.edit(&KeyEvent::from(Key::Left));
+        assert_eq!(input.cursor, 0);
+    }
+
+    #[test]
+    fn words_skip_leading_whitespace() {
+        let text = "let  value = 1";
+        assert_eq!(next_word_end(text, 0), 3);
+        assert_eq!(next_word_end(text, 3), 10);
+        // from within a word
+        assert_eq!(next_word_end(text, 6), 10);
+        assert_eq!(next_word_end(text, text.len()), text.len());
+        assert_eq!(prev_word_start(text, text.len()), 13);
+        assert_eq!(prev_word_start(text, 13), 11);
+        assert_eq!(prev_word_start(text, 11), 5);
+        assert_eq!(prev_word_start(text, 5), 0);
+        assert_eq!(prev_word_start(text, 0), 0);
+    }
+
+    #[test]
+    fn camel_case_is_a_single_word_and_punctuation_separates_words() {
+        let text = "parseJsonValue(a, b)";
+        assert_eq!(next_word_end(text, 0), "parseJsonValue".len());
+        assert_eq!(prev_word_start(text, "parseJsonValue".len()), 0);
+        // "(" and "," are words of their own
+        assert_eq!(next_word_end(text, 14), 15);
+        assert_eq!(next_word_end(text, 16), 17);
+        assert_eq!(prev_word_start(text, 18), 16);
+        // "." between letters doesn't separate words
+        assert_eq!(next_word_end("self.value x", 0), "self.value".len());
+        // each non-whitespace char of non-letters is a word
+        assert_eq!(next_word_end("->next", 0), 1);
+    }
+
+    #[test]
+    fn ctrl_edits_by_word() {
+        let ctrl = |code| KeyEvent::new(code, Modifiers::CONTROL);
+        let mut input = TextInput::new(
+            &IdPath::new(),
+            "hello big world".to_string(),
+            Style::default(),
+        );
+        assert_eq!(input.edit(&ctrl(Key::Left)), Some(false));
+        assert_eq!(input.cursor, 10);
+        assert_eq!(input.edit(&ctrl(Key::Backspace)), Some(true));
+        assert_eq!(input.text, "hello world");
+        assert_eq!(input.cursor, 6);
+        assert_eq!(input.edit(&ctrl(Key::Delete)), Some(true));
+        assert_eq!(input.text, "hello ");
+        assert_eq!(input.edit(&ctrl(Key::Delete)), Some(false));
+        assert_eq!(input.edit(&ctrl(Key::Char('a'))), None);
+    }
+
+    #[test]
+    fn placeholder_is_shown_while_the_text_is_empty() {
+        let placeholder_style = Style::default().fg(Color::DarkGray);
+        let mut input = TextInput::new(&IdPath::new(), String::new(), Style::default());
+        assert_eq!(
+            input.set_placeholder("Search…", placeholder_style),
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        );
+        assert_eq!(
+            input.set_placeholder("Search…", placeholder_style),
+            ChangeFlags::empty()
+        );
+        let buffer = paint(&mut input);
+        assert_eq!(buffer.get(0, 0).symbol(), "S");
+        assert_eq!(buffer.get(6, 0).symbol(), "…");
+        assert_eq!(buffer.get(0, 0).fg, Color::DarkGray);
+
+        // the placeholder isn't part of the text and the cursor is still at the start
+        assert_eq!(input.text, "");
+        assert_eq!(input.cursor, 0);
+        assert_eq!(input.edit(&KeyEvent::from(Key::Char('a'))), Some(true));
+        assert_eq!(input.text, "a");
+        let buffer = paint(&mut input);
+        assert_eq!(buffer.get(0, 0).symbol(), "a");
+        assert_eq!(buffer.get(1, 0).symbol(), " ");
+        assert_eq!(buffer.get(0, 0).fg, Color::Reset);
+    }
+
+    #[test]
+    fn masked_text_is_never_painted() {
+        let mut input = TextInput::new(&IdPath::new(), "日e\u{301}x".to_string(), Style::default());
+        assert_eq!(
+            input.set_mask(Some('•')),
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        );
+        assert_eq!(input.set_mask(Some('•')), ChangeFlags::empty());
+        let buffer = paint(&mut input);
+        let painted: String = (0..4).map(|x| buffer.get(x, 0).symbol()).collect();
+        assert_eq!(painted, "••• ");
+        // one cell per grapheme, regardless of the width of the actual text
+        assert_eq!(input.displayed_width(input.text.len()), 3);
+        assert_eq!(input.displayed_width("日".len()), 1);
+        assert_eq!(
+            column_to_cursor(input.displayed_graphemes(), input.text.len(), 1),
+            "日".len()
+        );
+
+        // the actual text is edited
+        input.edit(&KeyEvent::from(Key::Char('y')));
+        assert_eq!(input.text, "日e\u{301}xy");
+        assert_eq!(
+            input.set_mask(None),
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        );
+        assert_eq!(paint(&mut input).get(0, 0).symbol(), "日");
+    }
+
+    #[test]
+    fn failed_validation_is_shown_until_the_text_is_valid() {
+        let mut input = TextInput::new(&IdPath::new(), "1".to_string(), Style::default());
+        let digits: Validator = Arc::new(|text: &str| {
+            if text.chars().all(char::is_numeric) {
+                Ok(())
+            } else {
+                Err("Only digits".to_string())
+            }
+        });
+        input.set_validator(Some(digits.clone()), Duration::ZERO);
+        let _ = input.set_error_style(Style::default().fg(Color::Red), true);
+        let now = Instant::now();
+
+        input.edit(&KeyEvent::from(Key::Char('x')));
+        assert_eq!(
+            input.text_changed(now),
+            Some(Validation::Invalid("Only digits".to_string()))
+        );
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 12, 2));
+        let (mut messages, mut focus) = (Vec::new(), FocusState::default());
+        let mut widget_state = WidgetState::new();
+        widget_state.size = Size::new(12.0, 2.0);
+        input.paint(&mut PaintCx {
+            cx_state: &mut CxState::new(&mut messages, &mut focus, Default::default()),
+            widget_state: &mut widget_state,
+            buffer: &mut buffer,
+            override_style: Style::default(),
+        });
+        assert_eq!(buffer.get(0, 0).fg, Color::Red);
+        assert_eq!(buffer.get(0, 1).symbol(), "O");
+
+        input.edit(&KeyEvent::from(Key::Backspace));
+        assert_eq!(input.text_changed(now), Some(Validation::Valid));
+        assert_eq!(input.error, None);
+
+        // a delayed validation is only done after the delay since the last edit
+        let delay = Duration::from_millis(300);
+        input.set_validator(Some(digits), delay);
+        input.edit(&KeyEvent::from(Key::Char('x')));
+        assert_eq!(input.text_changed(now), None);
+        assert_eq!(input.validate_pending(now + delay / 2), None);
+        assert_eq!(
+            input.validate_pending(now + delay),
+            Some(Validation::Invalid("Only digits".to_string()))
+        );
+        assert_eq!(input.validate_pending(now + delay * 2), None);
+    }
+
+    #[test]
+    fn suggestions_are_selected_and_accepted_with_the_keyboard() {
+        let mut input = TextInput::new(&IdPath::new(), "r".to_string(), Style::default());
+        let _ = input.set_suggestions(&["Ruby".to_string(), "Rust".to_string()]);
+        let key = KeyEvent::from;
+        assert_eq!(input.navigate_suggestions(&key(Key::Up)), Some(false));
+        assert_eq!(input.selected_suggestion, Some(1));
+        assert_eq!(input.navigate_suggestions(&key(Key::Down)), Some(false));
+        assert_eq!(input.selected_suggestion, Some(0));
+        assert_eq!(input.navigate_suggestions(&key(Key::Char('u'))), None);
+        assert_eq!(input.navigate_suggestions(&key(Key::Tab)), Some(true));
+        assert_eq!(input.text, "Ruby");
+        assert_eq!(input.cursor, 4);
+        // closed until the next edit, so that `Tab` moves the focus again
+        assert_eq!(input.navigate_suggestions(&key(Key::Tab)), None);
+
+        input.suggestions_open = true;
+        assert_eq!(input.navigate_suggestions(&key(Key::Esc)), Some(false));
+        assert_eq!(input.text, "Ruby");
+        assert!(!input.shows_suggestions());
+    }
+
+    #[test]
+    fn masked_input_shows_no_suggestions() {
+        let mut input = TextInput::new(&IdPath::new(), "h".to_string(), Style::default());
+        let _ = input.set_suggestions(&["hunter2".to_string()]);
+        assert!(input.shows_suggestions());
+        let _ = input.set_mask(Some('•'));
+        assert!(!input.shows_suggestions());
+        // `Tab` moves the focus instead of accepting the suggestion
+        assert_eq!(input.navigate_suggestions(&KeyEvent::from(Key::Tab)), None);
+        assert_eq!(input.text, "h");
+    }
+
+    #[test]
+    fn typing_restarts_the_cursor_blink() {
+        let mut input = TextInput::new(&IdPath::new(), String::new(), Style::default());
+        let now = Instant::now();
+        assert!(input.cursor_visible_at(now + Duration::from_millis(700)));
+
+        let _ = input.set_cursor_blink(Some(Duration::from_millis(500)));
+        input.reset_blink(now);
+        assert!(input.cursor_visible_at(now + Duration::from_millis(400)));
+        assert!(!input.cursor_visible_at(now + Duration::from_millis(700)));
+        assert!(input.cursor_visible_at(now + Duration::from_millis(1200)));
+
+        input.reset_blink(now + Duration::from_millis(600));
+        assert!(input.cursor_visible_at(now + Duration::from_millis(700)));
+    }
+
+    #[test]
+    fn pasted_line_breaks_are_joined_or_rejected() {
+        let mut input = TextInput::new(&IdPath::new(), "<>".to_string(), Style::default());
+        input.cursor = 1;
+        assert!(input.paste("a\nb"));
+        assert_eq!(input.text, "<a b>");
+        assert_eq!(input.cursor, 4);
+        assert!(input.paste("c\r\n"));
+        assert_eq!(input.text, "<a bc>");
+        assert!(input.paste("a\rb"));
+        assert_eq!(input.text, "<a bca b>");
+
+        input.set_paste_newlines(PasteNewlines::Reject);
+        assert!(!input.paste("a\nb"));
+        assert!(!input.paste("a\rb"));
+        assert_eq!(input.text, "<a bca b>");
+    }
+
+    #[test]
+    fn pasted_control_characters_are_removed() {
+        let mut input = TextInput::new(&IdPath::new(), String::new(), Style::default());
+        // tabs are replaced with a space, escape sequences are removed as a whole
+        assert!(input.paste("a\tb \x1b[31mred\x1b[0m\x07\x1b"));
+        assert_eq!(input.text, "a b red");
+        assert_eq!(input.cursor, input.text.len());
+        assert!(!input.paste("\x1b]0;title\x07\x00"));
+        assert_eq!(input.text, "a b red");
+    }
+
+    #[test]
+    fn dropdown_flips_above_the_anchor_without_room_below() {
+        let screen = Rect::new(0, 0, 20, 10);
+        let below = dropdown_rect(Rect::new(2, 1, 5, 1), (8, 3), screen);
+        assert_eq!(below, Rect::new(2, 2, 8, 3));
+        let above = dropdown_rect(Rect::new(15, 8, 5, 1), (8, 3), screen);
+        assert_eq!(above, Rect::new(12, 5, 8, 3));
+        // clipped on the side with more room, if it doesn't fit on either side
+        let clipped = dropdown_rect(Rect::new(0, 4, 5, 1), (8, 9), screen);
+        assert_eq!(clipped, Rect::new(0, 5, 8, 5));
+    }
+
+    #[test]
+    fn clicked_column_is_mapped_to_a_grapheme_boundary() {
+        // "日" takes the columns 0 and 1, "本" 2 and 3
+        let text = "日本x";
+        assert_eq!(cursor_at_column(text, 0), 0);
+        assert_eq!(cursor_at_column(text, 1), "日".len());
+        assert_eq!(cursor_at_column(text, 2), "日".len());
+        assert_eq!(cursor_at_column(text, 4), "日本".len());
+        assert_eq!(cursor_at_column(text, 10), text.len());
+    }
+}
@@ -0,0 +1,214 @@
+use std::borrow::Cow;
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    geometry::{to_ratatui_rect, Size},
+    ToggleStyles,
+};
+
+use super::{
+    core::{IdPath, LifeCycleCx, PaintCx},
+    set_stringn_clipped, BoxConstraints, ChangeFlags, Event, EventCx, Key, KeyEvent, LayoutCx,
+    LifeCycle, Message, MouseButton, MouseKind, RawMouseEvent, Widget,
+};
+
+pub struct Toggle {
+    id_path: IdPath,
+    is_on: bool,
+    on_glyph: Cow<'static, str>,
+    off_glyph: Cow<'static, str>,
+    styles: ToggleStyles,
+}
+
+impl Toggle {
+    pub(crate) fn new(
+        id_path: &IdPath,
+        is_on: bool,
+        on_glyph: Cow<'static, str>,
+        off_glyph: Cow<'static, str>,
+        styles: ToggleStyles,
+    ) -> Self {
+        Toggle {
+            id_path: id_path.clone(),
+            is_on,
+            on_glyph,
+            off_glyph,
+            styles,
+        }
+    }
+
+    pub(crate) fn set_is_on(&mut self, is_on: bool) -> ChangeFlags {
+        if self.is_on != is_on {
+            self.is_on = is_on;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_glyphs(
+        &mut self,
+        on_glyph: &Cow<'static, str>,
+        off_glyph: &Cow<'static, str>,
+    ) -> ChangeFlags {
+        if self.on_glyph != *on_glyph || self.off_glyph != *off_glyph {
+            self.on_glyph = on_glyph.clone();
+            self.off_glyph = off_glyph.clone();
+            ChangeFlags::LAYOUT | ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_styles(&mut self, styles: ToggleStyles) -> ChangeFlags {
+        if self.styles != styles {
+            self.styles = styles;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// The state is owned by the view, so this only asks it to flip the value
+    fn toggle(&self, cx: &mut EventCx) {
+        cx.add_message(Message::new(self.id_path.clone(), !self.is_on));
+    }
+}
+
+impl Widget for Toggle {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let (glyph, mut style) = if self.is_on {
+            (&self.on_glyph, self.styles.on)
+        } else {
+            (&self.off_glyph, self.styles.off)
+        };
+        if cx.is_hot() {
+            style = style.patch(self.styles.hover);
+        }
+        let style = style.patch(cx.override_style());
+
+        let buf = cx.buffer_mut();
+        let area = rect.intersection(buf.area);
+        if area.height > 0 && area.width > 0 {
+            let max_width = (area.right() - rect.x) as usize;
+            set_stringn_clipped(buf, rect.x, area.y, glyph, max_width, style);
+        }
+    }
+
+    fn layout(&mut self, _cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let width = self.on_glyph.width().max(self.off_glyph.width());
+        bc.constrain(Size::new(width as f64, 1.0))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        match event {
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Down(MouseButton::Left),
+                ..
+            }) if cx.is_hot() => {
+                cx.set_active(true);
+                cx.request_focus();
+            }
+            Event::Mouse(RawMouseEvent {
+                kind: MouseKind::Up(MouseButton::Left),
+                ..
+            }) => {
+                if cx.is_hot() && cx.is_active() {
+                    self.toggle(cx);
+                }
+                cx.set_active(false);
+            }
+            Event::Key(KeyEvent {
+                code: Key::Char(' ') | Key::Enter,
+                ..
+            }) if cx.is_focused() => {
+                self.toggle(cx);
+                cx.set_handled(true);
+            }
+            Event::FocusLost => cx.set_active(false),
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        if matches!(event, LifeCycle::HotChanged(_)) {
+            cx.request_paint();
+        }
+    }
+
+    fn accepts_focus(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        app::{test_key, test_mouse},
+        widget::{Event, Key, MouseButton, MouseKind},
+        App, View,
+    };
+
+    /// A press and release of the left mouse button at `column` in the first row
+    fn click(column: i16) -> [Event; 2] {
+        let left = MouseButton::Left;
+        [
+            test_mouse(MouseKind::Down(left), column, 0),
+            test_mouse(MouseKind::Up(left), column, 0),
+        ]
+    }
+
+    async fn toggle_app() -> App<bool, impl View<bool> + 'static> {
+        App::new(false, |is_on: &mut bool| {
+            crate::toggle(*is_on, |is_on: &mut bool, new: bool| *is_on = new)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn clicks_toggle_the_state() {
+        let mut app = toggle_app().await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(0), "[● ]");
+        app.dispatch(click(1)).await;
+        assert_eq!(app.screen_line(0), "[ ●]");
+        app.dispatch(click(3)).await;
+        assert_eq!(app.screen_line(0), "[● ]");
+        // next to the toggle
+        app.dispatch(click(10)).await;
+        assert_eq!(app.screen_line(0), "[● ]");
+    }
+
+    #[tokio::test]
+    async fn space_and_enter_toggle_the_focused_toggle() {
+        let mut app = toggle_app().await;
+        app.render_once().await.unwrap();
+        // nothing is focused yet
+        app.dispatch([test_key(Key::Char(' '))]).await;
+        assert_eq!(app.screen_line(0), "[● ]");
+        app.dispatch([test_key(Key::Tab), test_key(Key::Char(' '))])
+            .await;
+        assert_eq!(app.screen_line(0), "[ ●]");
+        app.dispatch([test_key(Key::Enter)]).await;
+        assert_eq!(app.screen_line(0), "[● ]");
+    }
+
+    #[tokio::test]
+    async fn the_glyph_of_the_state_is_painted() {
+        let mut app = App::new(true, |is_on: &mut bool| {
+            crate::h_stack((
+                crate::toggle(*is_on, |is_on: &mut bool, new: bool| *is_on = new)
+                    .glyphs("on", "off"),
+                "|",
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        // the toggle is as wide as the widest glyph
+        assert_eq!(app.screen_line(0), "on |");
+        app.dispatch(click(0)).await;
+        assert_eq!(app.screen_line(0), "off|");
+    }
+}
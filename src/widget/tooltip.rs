@@ -0,0 +1,206 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+use unicode_width::UnicodeWidthStr;
+
+use crate::geometry::Size;
+
+use super::{
+    core::{EventCx, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, LayoutCx, LifeCycle, MouseKind, Pod, RawMouseEvent, Widget,
+};
+
+/// Shows a label near the mouse cursor, after it rested on the content for a while.
+pub struct Tooltip {
+    content: Pod,
+    text: String,
+    delay: Duration,
+    style: Style,
+    /// The last position of the mouse cursor relative to the origin of the widget, while it's hot
+    pointer: Option<(i16, i16)>,
+    /// When the tooltip is shown, while the mouse cursor rests on the content
+    deadline: Option<Instant>,
+    shown: bool,
+}
+
+impl Tooltip {
+    pub(crate) fn new(content: impl Widget, text: String, delay: Duration, style: Style) -> Self {
+        Tooltip {
+            content: Pod::new(content),
+            text,
+            delay,
+            style,
+            pointer: None,
+            deadline: None,
+            shown: false,
+        }
+    }
+
+    pub(crate) fn content(&mut self) -> &mut Pod {
+        &mut self.content
+    }
+
+    pub(crate) fn set_text(&mut self, text: &str) -> ChangeFlags {
+        if self.text != text {
+            self.text = text.to_string();
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_delay(&mut self, delay: Duration) -> ChangeFlags {
+        self.delay = delay;
+        ChangeFlags::empty()
+    }
+
+    pub(crate) fn set_style(&mut self, style: Style) -> ChangeFlags {
+        if self.style != style {
+            self.style = style;
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    /// Hides the tooltip and cancels a pending one, returns whether a repaint is necessary.
+    fn hide(&mut self) -> bool {
+        self.pointer = None;
+        self.deadline = None;
+        std::mem::take(&mut self.shown)
+    }
+}
+
+/// The area of a tooltip of `size`, which is placed below the `pointer` (or above it, if there's
+/// not enough space below) and moved to the left to stay within `screen`.
+fn tooltip_rect(pointer: (u16, u16), (width, height): (u16, u16), screen: Rect) -> Rect {
+    let width = width.min(screen.width);
+    let height = height.min(screen.height);
+    let x = pointer.0.min(screen.right() - width).max(screen.left());
+    let y = if pointer.1 + 1 + height <= screen.bottom() {
+        pointer.1 + 1
+    } else if pointer.1 >= screen.top() + height {
+        pointer.1 - height
+    } else {
+        screen.bottom() - height
+    };
+    Rect::new(x, y, width, height)
+}
+
+impl Widget for Tooltip {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.content.paint(cx);
+
+        let Some((column, row)) = self.pointer.filter(|_| self.shown) else {
+            return;
+        };
+        let origin = cx.rect().origin();
+        let pointer = (
+            (origin.x + column as f64).max(0.0) as u16,
+            (origin.y + row as f64).max(0.0) as u16,
+        );
+        let width = self.text.lines().map(|l| l.width()).max().unwrap_or(0) + 2;
+        let height = self.text.lines().count().max(1);
+        let screen = cx.buffer_mut().area;
+        let rect = tooltip_rect(pointer, (width as u16, height as u16), screen);
+        if rect.area() == 0 {
+            return;
+        }
+
+        let mut overlay = Buffer::empty(rect);
+        overlay.set_style(rect, self.style);
+        for (y, line) in (rect.top()..rect.bottom()).zip(self.text.lines()) {
+            let max_width = rect.width.saturating_sub(2) as usize;
+            overlay.set_stringn(rect.x + 1, y, line, max_width, self.style);
+        }
+        cx.paint_overlay(overlay);
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        self.content.layout(cx, bc)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        self.content.event(cx, event);
+
+        let Event::Mouse(RawMouseEvent {
+            kind, column, row, ..
+        }) = event
+        else {
+            return;
+        };
+        if !cx.is_hot() || !matches!(kind, MouseKind::Moved) {
+            // Leaving the content, clicking or scrolling hides the tooltip
+            if self.hide() {
+                cx.request_paint();
+            }
+            return;
+        }
+        let pointer = Some((*column, *row));
+        if self.shown {
+            // The tooltip follows the mouse cursor
+            if self.pointer != pointer {
+                self.pointer = pointer;
+                cx.request_paint();
+            }
+        } else {
+            // The delay starts again until the mouse cursor rests
+            self.pointer = pointer;
+            self.deadline = Some(Instant::now() + self.delay);
+            cx.request_animation_update();
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        self.content.lifecycle(cx, event);
+
+        match event {
+            LifeCycle::HotChanged(false) => {
+                if self.hide() {
+                    cx.request_paint();
+                }
+            }
+            // The deadline is checked in every frame while the tooltip is pending
+            LifeCycle::Animate => {
+                if let Some(deadline) = self.deadline {
+                    if Instant::now() < deadline {
+                        cx.request_animation_update();
+                    } else {
+                        self.deadline = None;
+                        self.shown = true;
+                        cx.request_paint();
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tooltips_stay_on_screen() {
+        let screen = Rect::new(0, 0, 20, 10);
+        // below the pointer
+        assert_eq!(tooltip_rect((2, 3), (5, 1), screen), Rect::new(2, 4, 5, 1));
+        // moved to the left at the right edge
+        assert_eq!(
+            tooltip_rect((18, 3), (5, 1), screen),
+            Rect::new(15, 4, 5, 1)
+        );
+        // above the pointer at the bottom edge
+        assert_eq!(tooltip_rect((2, 9), (5, 2), screen), Rect::new(2, 7, 5, 2));
+        // shrunk to the screen
+        assert_eq!(
+            tooltip_rect((2, 3), (30, 12), screen),
+            Rect::new(0, 0, 20, 10)
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use std::{ops::Range, sync::Arc};
+
+use ratatui::buffer::Buffer;
+
+use crate::geometry::{to_ratatui_rect, Point, Size, Vec2};
+
+use super::{
+    core::{EventCx, IdPath, LifeCycleCx, PaintCx},
+    BoxConstraints, ChangeFlags, Event, Key, KeyEvent, LayoutCx, LifeCycle, Message, MouseKind,
+    Pod, RawMouseEvent, ScrollRequest, Widget,
+};
+
+/// Lines that are scrolled with the mouse wheel
+const SCROLL_STEP: usize = 3;
+
+/// The heights of the items of a [`VirtualList`], which are needed to find the visible items
+/// without laying out all of them.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ItemHeights {
+    Fixed(u16),
+    /// The heights of each item, items without a height are one line high
+    Variable(Arc<[u16]>),
+}
+
+/// The positions of the items of a [`VirtualList`] in lines from the top of the list.
+#[derive(Debug, Clone, PartialEq)]
+struct Layout {
+    count: usize,
+    heights: ItemHeights,
+    /// The top of each item and the total height at the end, only for variable heights
+    tops: Vec<usize>,
+}
+
+impl Layout {
+    fn new(count: usize, heights: ItemHeights) -> Self {
+        let tops = match &heights {
+            ItemHeights::Fixed(_) => Vec::new(),
+            ItemHeights::Variable(heights) => {
+                let mut top = 0;
+                let mut tops = Vec::with_capacity(count + 1);
+                tops.push(0);
+                for index in 0..count {
+                    top += heights.get(index).copied().unwrap_or(1) as usize;
+                    tops.push(top);
+                }
+                tops
+            }
+        };
+        Layout {
+            count,
+            heights,
+            tops,
+        }
+    }
+
+    fn top(&self, index: usize) -> usize {
+        match self.heights {
+            ItemHeights::Fixed(height) => index.min(self.count) * height as usize,
+            ItemHeights::Variable(_) => self.tops[index.min(self.count)],
+        }
+    }
+
+    fn height(&self, index: usize) -> usize {
+        self.top(index + 1) - self.top(index)
+    }
+
+    fn total_height(&self) -> usize {
+        self.top(self.count)
+    }
+
+    /// The index of the item, which covers `line`, or the last item if the line is below the list.
+    fn index_at(&self, line: usize) -> usize {
+        let index = match self.heights {
+            ItemHeights::Fixed(0) => 0,
+            ItemHeights::Fixed(height) => line / height as usize,
+            // The first item, which ends below the line (items without height are skipped)
+            ItemHeights::Variable(_) => self.tops[1..].partition_point(|bottom| *bottom <= line),
+        };
+        index.min(self.count.saturating_sub(1))
+    }
+
+    /// The indices of the items, which are visible within `offset..offset + viewport`,
+    /// extended by `overscan` items in both directions.
+    fn visible_range(&self, offset: usize, viewport: usize, overscan: usize) -> Range<usize> {
+        if self.count == 0 || viewport == 0 {
+            return 0..0;
+        }
+        let first = self.index_at(offset);
+        let last = self.index_at(offset + viewport - 1);
+        first.saturating_sub(overscan)..(last + 1 + overscan).min(self.count)
+    }
+}
+
+/// A vertical list of `count` items, of which only the visible ones are built as children,
+/// see [`virtual_list`](crate::virtual_list).
+///
+/// The list scrolls itself and sends the range of indices, which should be built, to its view
+/// when it changes after a layout pass.
+pub struct VirtualList {
+    id_path: IdPath,
+    layout: Layout,
+    overscan: usize,
+    /// The index of the first child
+    first: usize,
+    children: Vec<Pod>,
+    /// The line of the list at the top of the viewport
+    offset: usize,
+    /// The range of items, which was last sent to the view
+    requested: Option<Range<usize>>,
+    /// Requests of the app logic, which are applied in the next layout pass
+    requests: Vec<ScrollRequest>,
+}
+
+impl VirtualList {
+    pub(crate) fn new(
+        id_path: &IdPath,
+        count: usize,
+        heights: ItemHeights,
+        overscan: usize,
+    ) -> Self {
+        VirtualList {
+            id_path: id_path.clone(),
+            layout: Layout::new(count, heights),
+            overscan,
+            first: 0,
+            children: Vec::new(),
+            offset: 0,
+            requested: None,
+            requests: Vec::new(),
+        }
+    }
+
+    pub(crate) fn set_items(&mut self, count: usize, heights: &ItemHeights) -> ChangeFlags {
+        if self.layout.count != count || self.layout.heights != *heights {
+            self.layout = Layout::new(count, heights.clone());
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn set_overscan(&mut self, overscan: usize) -> ChangeFlags {
+        if self.overscan != overscan {
+            self.overscan = overscan;
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    pub(crate) fn request_scroll(&mut self, requests: Vec<ScrollRequest>) -> ChangeFlags {
+        if requests.is_empty() {
+            return ChangeFlags::empty();
+        }
+        self.requests.extend(requests);
+        ChangeFlags::LAYOUT
+    }
+
+    /// Removes the children, the first one is the item with the returned index.
+    pub(crate) fn take_children(&mut self) -> (usize, Vec<Pod>) {
+        (self.first, std::mem::take(&mut self.children))
+    }
+
+    /// The children are the items from the index `first` on.
+    pub(crate) fn set_children(&mut self, first: usize, children: Vec<Pod>) -> ChangeFlags {
+        let changed = self.first != first || self.children.len() != children.len();
+        self.first = first;
+        self.children = children;
+        if changed {
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn max_offset(&self, viewport: usize) -> usize {
+        self.layout.total_height().saturating_sub(viewport)
+    }
+
+    /// Scrolls by `delta` lines, returns whether the offset changed.
+    fn scroll_by(&mut self, delta: isize, viewport: usize) -> bool {
+        let offset = self
+            .offset
+            .saturating_add_signed(delta)
+            .min(self.max_offset(viewport));
+        let changed = offset != self.offset;
+        self.offset = offset;
+        changed
+    }
+
+    /// The offset after `request`, `None` if it refers to a widget which isn't built.
+    fn requested_offset(&self, request: ScrollRequest, viewport: usize) -> Option<usize> {
+        let (top, bottom) = match request {
+            ScrollRequest::Fraction { y, .. } => {
+                return Some(y.map_or(self.offset, |y| {
+                    (y.clamp(0.0, 1.0) * self.max_offset(viewport) as f64).round() as usize
+                }))
+            }
+            ScrollRequest::RevealChild(index) => {
+                (self.layout.top(index), self.layout.top(index + 1))
+            }
+            ScrollRequest::Reveal(id) => {
+                let index = self.children.iter().position(|child| child.id() == id)?;
+                let index = self.first + index;
+                (self.layout.top(index), self.layout.top(index + 1))
+            }
+        };
+        Some(if top < self.offset {
+            top
+        } else if bottom > self.offset + viewport {
+            (bottom - viewport).min(top)
+        } else {
+            self.offset
+        })
+    }
+}
+
+impl Widget for VirtualList {
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let rect = to_ratatui_rect(cx.rect());
+        let viewport = rect.intersection(cx.buffer.area);
+        if viewport.area() == 0 {
+            return;
+        }
+        // The first visible item may start above the viewport, the children are painted into a
+        // buffer which starts at its top, as widgets expect to be painted in non-negative coordinates
+        let visible = self
+            .layout
+            .visible_range(self.offset, rect.height as usize, 0);
+        let overhang = self.offset.saturating_sub(self.layout.top(visible.start)) as u16;
+        let mut offscreen = Buffer::empty(ratatui::layout::Rect::new(
+            0,
+            0,
+            rect.width,
+            overhang.saturating_add(rect.height),
+        ));
+        let to_offscreen = |x: u16, y: u16| (x - rect.x, y - rect.y + overhang);
+        // Keep what's painted below the list, e.g. a background
+        for y in viewport.top()..viewport.bottom() {
+            for x in viewport.left()..viewport.right() {
+                let (ox, oy) = to_offscreen(x, y);
+                *offscreen.get_mut(ox, oy) = cx.buffer.get(x, y).clone();
+            }
+        }
+
+        let translation = Vec2::new(-(rect.x as f64), overhang as f64 - rect.y as f64);
+        let first = self.first;
+        for (index, child) in (first..).zip(&mut self.children) {
+            if visible.contains(&index) {
+                cx.paint_offscreen(child, &mut offscreen, translation, viewport);
+            }
+        }
+
+        for y in viewport.top()..viewport.bottom() {
+            for x in viewport.left()..viewport.right() {
+                let (ox, oy) = to_offscreen(x, y);
+                *cx.buffer.get_mut(x, y) = offscreen.get(ox, oy).clone();
+            }
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
+        let width = bc.max().width;
+        let mut content_width: f64 = 0.0;
+        for (index, child) in (self.first..).zip(&mut self.children) {
+            let height = self.layout.height(index) as f64;
+            let child_bc = BoxConstraints::new(Size::new(0.0, height), Size::new(width, height));
+            content_width = content_width.max(child.layout(cx, &child_bc).width);
+        }
+        let size = bc.constrain(Size::new(content_width, self.layout.total_height() as f64));
+
+        let viewport = size.height as usize;
+        for request in std::mem::take(&mut self.requests) {
+            if let Some(offset) = self.requested_offset(request, viewport) {
+                self.offset = offset;
+            }
+        }
+        self.offset = self.offset.min(self.max_offset(viewport));
+        for (index, child) in (self.first..).zip(&mut self.children) {
+            let y = self.layout.top(index) as f64 - self.offset as f64;
+            child.set_origin(cx, Point::new(0.0, y));
+        }
+
+        let range = self
+            .layout
+            .visible_range(self.offset, viewport, self.overscan);
+        if self.requested.as_ref() != Some(&range) {
+            self.requested = Some(range.clone());
+            cx.add_message(Message::new(self.id_path.clone(), range));
+        }
+        size
+    }
+
+    fn event(&mut self, cx: &mut EventCx, event: &Event) {
+        for child in &mut self.children {
+            child.event(cx, event);
+        }
+        if cx.is_handled() {
+            return;
+        }
+
+        let viewport = cx.rect().height() as usize;
+        let page = viewport.max(1) as isize;
+        let delta = match event {
+            Event::Mouse(RawMouseEvent { kind, .. }) if cx.is_hot() => match kind {
+                MouseKind::ScrollDown => Some(SCROLL_STEP as isize),
+                MouseKind::ScrollUp => Some(-(SCROLL_STEP as isize)),
+                _ => None,
+            },
+            // Keys which aren't handled by a focused item
+            Event::Key(KeyEvent { code, .. }) if cx.has_focus() => match code {
+                Key::Up => Some(-1),
+                Key::Down => Some(1),
+                Key::PageUp => Some(-page),
+                Key::PageDown => Some(page),
+                Key::Home => Some(isize::MIN),
+                Key::End => Some(isize::MAX),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(delta) = delta {
+            if self.scroll_by(delta, viewport) {
+                cx.request_layout();
+                cx.set_handled(true);
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, cx: &mut LifeCycleCx, event: &LifeCycle) {
+        for child in &mut self.children {
+            child.lifecycle(cx, event);
+        }
+    }
+
+    fn accepts_focus(&self) -> bool {
+        // So that the list can be scrolled with the keyboard, even without focusable items
+        true
+    }
+
+    fn children(&self) -> &[Pod] {
+        &self.children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visible_range_of_fixed_heights() {
+        let layout = Layout::new(100_000, ItemHeights::Fixed(2));
+        assert_eq!(layout.total_height(), 200_000);
+        assert_eq!(layout.visible_range(0, 5, 0), 0..3);
+        // the first item is partly scrolled out of view
+        assert_eq!(layout.visible_range(3, 5, 0), 1..4);
+        assert_eq!(layout.visible_range(3, 5, 2), 0..6);
+        assert_eq!(layout.visible_range(199_998, 5, 2), 99_997..100_000);
+        assert_eq!(
+            Layout::new(0, ItemHeights::Fixed(1)).visible_range(0, 5, 2),
+            0..0
+        );
+    }
+
+    #[test]
+    fn visible_range_of_variable_heights() {
+        let heights: Arc<[u16]> = Arc::from([1, 3, 0, 2]);
+        // the fifth item has no height and defaults to a single line
+        let layout = Layout::new(5, ItemHeights::Variable(heights));
+        assert_eq!(layout.total_height(), 7);
+        assert_eq!((layout.top(1), layout.top(3), layout.top(4)), (1, 4, 4));
+        assert_eq!(layout.index_at(0), 0);
+        assert_eq!(layout.index_at(3), 1);
+        // the item without height is skipped
+        assert_eq!(layout.index_at(4), 3);
+        assert_eq!(layout.index_at(100), 4);
+        assert_eq!(layout.visible_range(2, 3, 0), 1..4);
+        assert_eq!(layout.visible_range(5, 3, 1), 2..5);
+    }
+}
@@ -3,7 +3,7 @@ use crate::geometry::{Axis, Size};
 use super::{
     animatables::AnimatableElement,
     core::{EventCx, PaintCx},
-    BoxConstraints, LayoutCx, LifeCycle, LifeCycleCx, Pod, Widget,
+    BoxConstraints, ChangeFlags, LayoutCx, LifeCycle, LifeCycleCx, Pod, Widget,
 };
 
 pub struct WeightedLinearLayout {
@@ -16,6 +16,8 @@ pub struct WeightedLayoutElement {
     pub(crate) content: Pod,
     pub(crate) weight_animatable: Box<dyn AnimatableElement<f64>>,
     weight: f64,
+    /// Whether the size of the content is reserved before the space is distributed by weight
+    reserve_min_size: bool,
 }
 
 impl WeightedLayoutElement {
@@ -24,6 +26,16 @@ impl WeightedLayoutElement {
             content: Pod::new(content),
             weight_animatable: Box::new(weight_element),
             weight: 1.0,
+            reserve_min_size: false,
+        }
+    }
+
+    pub(crate) fn set_reserve_min_size(&mut self, reserve: bool) -> ChangeFlags {
+        if self.reserve_min_size != reserve {
+            self.reserve_min_size = reserve;
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
         }
     }
 }
@@ -48,21 +60,132 @@ impl Widget for WeightedLayoutElement {
     fn event(&mut self, cx: &mut EventCx, event: &super::Event) {
         self.content.event(cx, event)
     }
+
+    fn children(&self) -> &[Pod] {
+        std::slice::from_ref(&self.content)
+    }
 }
 
-fn get_weights(children: &[Pod], weights: &mut Vec<f64>) -> f64 {
+/// Whether `child` is a [`WeightedLayoutElement`] which reserves the size of its content.
+fn reserves_min_size(child: &Pod) -> bool {
+    child
+        .downcast_ref::<WeightedLayoutElement>()
+        .is_some_and(|weighted_el| weighted_el.reserve_min_size)
+}
+
+fn get_weights(children: &[Pod], weights: &mut Vec<f64>) {
     weights.clear();
-    let mut sum = 0.0;
     for child in children {
         let weight = if let Some(weighted_el) = child.downcast_ref::<WeightedLayoutElement>() {
             weighted_el.weight
         } else {
             1.0
         };
-        sum += weight;
         weights.push(weight);
     }
-    sum
+}
+
+/// Distributes `space` (whole cells) proportionally to `weights`, so that the sizes add up exactly
+/// to `space`.
+///
+/// The cells which are left over after rounding down go to the children with the largest remainders
+/// (the earlier child on ties). Zero, negative and NaN weights (e.g. while a weight animation
+/// overshoots) get no space, if no weight is positive all sizes are zero.
+fn distribute(space: f64, weights: &[f64]) -> Vec<f64> {
+    let weights: Vec<f64> = weights
+        .iter()
+        .map(|w| if *w > 0.0 { *w } else { 0.0 })
+        .collect();
+    let total_weight: f64 = weights.iter().sum();
+    let space = space.max(0.0).floor();
+    if total_weight <= 0.0 || !total_weight.is_finite() {
+        return vec![0.0; weights.len()];
+    }
+    let exact: Vec<f64> = weights.iter().map(|w| space * w / total_weight).collect();
+    let mut sizes: Vec<f64> = exact.iter().map(|size| size.floor()).collect();
+    let leftover = (space - sizes.iter().sum::<f64>()).max(0.0) as usize;
+    let mut by_remainder: Vec<usize> = (0..weights.len()).filter(|i| weights[*i] > 0.0).collect();
+    // stable, so that earlier children win ties
+    by_remainder.sort_by(|a, b| {
+        (exact[*b] - sizes[*b])
+            .partial_cmp(&(exact[*a] - sizes[*a]))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    for i in by_remainder.into_iter().take(leftover) {
+        sizes[i] += 1.0;
+    }
+    sizes
+}
+
+/// Like [`distribute`], but a child never gets less than its minimum (in `mins`), as long as the space
+/// suffices.
+///
+/// Children which would get less than their minimum by weight get their minimum, the rest of the space
+/// is distributed by weight among the other children, so the sizes are purely proportional to the
+/// weights when all minimums are satisfied anyway. If the minimums exceed the space, the children get
+/// their minimum in the order of their weight (highest first, the earlier child on ties) until the space
+/// is used up, i.e. the children with the lowest weights are cut off.
+/// Children without a positive weight get no space, regardless of their minimum.
+fn distribute_with_minimums(space: f64, weights: &[f64], mins: &[f64]) -> Vec<f64> {
+    let space = space.max(0.0).floor();
+    let mins: Vec<f64> = mins
+        .iter()
+        .zip(weights)
+        .map(|(min, weight)| {
+            if *weight > 0.0 && min.is_finite() {
+                min.max(0.0).ceil()
+            } else {
+                0.0
+            }
+        })
+        .collect();
+
+    if mins.iter().sum::<f64>() > space {
+        let mut by_weight: Vec<usize> = (0..weights.len()).collect();
+        // stable, so that earlier children win ties
+        by_weight.sort_by(|a, b| {
+            weights[*b]
+                .partial_cmp(&weights[*a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut sizes = vec![0.0; weights.len()];
+        let mut remaining = space;
+        for i in by_weight {
+            sizes[i] = mins[i].min(remaining);
+            remaining -= sizes[i];
+        }
+        return sizes;
+    }
+
+    let mut pinned = vec![false; weights.len()];
+    loop {
+        let free = space
+            - (0..mins.len())
+                .filter(|i| pinned[*i])
+                .map(|i| mins[i])
+                .sum::<f64>();
+        let unpinned_weights: Vec<f64> = weights
+            .iter()
+            .zip(&pinned)
+            .map(|(weight, pinned)| if *pinned { 0.0 } else { *weight })
+            .collect();
+        let sizes = distribute(free, &unpinned_weights);
+        let mut newly_pinned = false;
+        for i in 0..sizes.len() {
+            if !pinned[i] && sizes[i] < mins[i] {
+                pinned[i] = true;
+                newly_pinned = true;
+            }
+        }
+        if !newly_pinned {
+            return sizes
+                .into_iter()
+                .zip(&pinned)
+                .enumerate()
+                .map(|(i, (size, pinned))| if *pinned { mins[i] } else { size })
+                .collect();
+        }
+    }
 }
 
 impl WeightedLinearLayout {
@@ -86,18 +209,39 @@ impl Widget for WeightedLinearLayout {
     fn layout(&mut self, cx: &mut LayoutCx, bc: &BoxConstraints) -> Size {
         let mut major_used: f64 = 0.0;
         let mut max_minor: f64 = 0.0;
-        let major_max = self.axis.major(*bc).end;
 
-        let total_weight_inv = 1.0 / get_weights(&self.children, &mut self.weights);
+        get_weights(&self.children, &mut self.weights);
         let space_available = self.axis.major(*bc).end;
+        let sizes = if space_available.is_finite() {
+            // The minimum of a child which reserves it is its size when it's not limited along the
+            // axis, e.g. the amount of lines of a wrapped text in a vertical layout. This is opt-in,
+            // as it lays out the child twice, and content which takes any space it's given (like a
+            // scroll view) would be as large as its whole content.
+            let measure_bc = self.axis.with_major(bc.loosen(), 0.0..f64::INFINITY);
+            let mins: Vec<f64> = self
+                .children
+                .iter_mut()
+                .map(|child| {
+                    if reserves_min_size(child) {
+                        self.axis.major(child.layout(cx, &measure_bc))
+                    } else {
+                        0.0
+                    }
+                })
+                .collect();
+            Some(distribute_with_minimums(
+                space_available,
+                &self.weights,
+                &mins,
+            ))
+        } else {
+            None
+        };
 
         for (index, child) in self.children.iter_mut().enumerate() {
-            let constraint = if space_available != f64::INFINITY {
-                let size = (space_available * (self.weights[index] * total_weight_inv))
-                    .min(major_max - major_used);
-                size..size // TODO loosen the minimum size (to 0)?
-            } else {
-                0.0..f64::INFINITY
+            let constraint = match &sizes {
+                Some(sizes) => sizes[index]..sizes[index], // TODO loosen the minimum size (to 0)?
+                None => 0.0..f64::INFINITY,
             };
             let child_bc = self.axis.with_major(bc.loosen(), constraint);
             let size = child.layout(cx, &child_bc);
@@ -120,4 +264,161 @@ impl Widget for WeightedLinearLayout {
             child.lifecycle(cx, event);
         }
     }
+
+    fn children(&self) -> &[Pod] {
+        &self.children
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::{
+        app::test_mouse, virtual_list, weighted_v_stack, widget::MouseKind, App, ScrollAxes,
+        ToWrappedText, ViewExt,
+    };
+
+    #[test]
+    fn distribute_fills_the_space_exactly() {
+        assert_eq!(distribute(7.0, &[1.0, 2.0, 3.0]), vec![1.0, 2.0, 4.0]);
+        assert_eq!(distribute(11.0, &[1.0, 2.0, 3.0]), vec![2.0, 4.0, 5.0]);
+        assert_eq!(distribute(10.0, &[1.0, 1.0, 1.0]), vec![4.0, 3.0, 3.0]);
+        assert_eq!(distribute(10.0, &[0.3, 0.3, 0.4]), vec![3.0, 3.0, 4.0]);
+        // fractional space is rounded down to whole cells
+        assert_eq!(distribute(5.5, &[1.0, 1.0]), vec![3.0, 2.0]);
+    }
+
+    #[test]
+    fn minimums_are_reserved_before_distributing_by_weight() {
+        // satisfied minimums don't change the distribution
+        assert_eq!(
+            distribute_with_minimums(12.0, &[1.0, 2.0, 3.0], &[1.0, 1.0, 1.0]),
+            vec![2.0, 4.0, 6.0]
+        );
+        // the first child needs 5 cells, the rest is distributed 2:3
+        assert_eq!(
+            distribute_with_minimums(12.0, &[1.0, 2.0, 3.0], &[5.0, 0.0, 0.0]),
+            vec![5.0, 3.0, 4.0]
+        );
+        // pinning the first child pushes the second below its minimum as well
+        assert_eq!(
+            distribute_with_minimums(10.0, &[1.0, 3.0, 6.0], &[4.0, 3.0, 0.0]),
+            vec![4.0, 3.0, 3.0]
+        );
+        // fractional minimums are rounded up to whole cells
+        assert_eq!(
+            distribute_with_minimums(9.0, &[1.0, 1.0], &[2.5, 0.0]),
+            vec![5.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn minimums_exceeding_the_space_overflow_in_weight_order() {
+        assert_eq!(
+            distribute_with_minimums(7.0, &[1.0, 3.0, 2.0], &[4.0, 4.0, 4.0]),
+            vec![0.0, 4.0, 3.0]
+        );
+        // ties go to the earlier child, zero weights get nothing
+        assert_eq!(
+            distribute_with_minimums(5.0, &[1.0, 1.0, 0.0], &[3.0, 3.0, 3.0]),
+            vec![3.0, 2.0, 0.0]
+        );
+        // unbounded minimums are ignored
+        assert_eq!(
+            distribute_with_minimums(6.0, &[1.0, 2.0], &[f64::INFINITY, 0.0]),
+            vec![2.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn distribute_ignores_non_positive_weights() {
+        assert_eq!(
+            distribute(9.0, &[1.0, 0.0, -1.0, 2.0]),
+            vec![3.0, 0.0, 0.0, 6.0]
+        );
+        assert_eq!(distribute(9.0, &[f64::NAN, 1.0]), vec![0.0, 9.0]);
+        assert_eq!(distribute(9.0, &[0.0, 0.0]), vec![0.0, 0.0]);
+        assert_eq!(distribute(9.0, &[]), Vec::<f64>::new());
+    }
+
+    fn lines(count: usize) -> String {
+        (0..count)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[tokio::test]
+    async fn weighted_scroll_views_get_their_share_and_keep_their_offset() {
+        let mut app = App::new((), |_: &mut ()| {
+            weighted_v_stack((
+                "header".weight(1.0),
+                lines(100).scroll(ScrollAxes::VERTICAL).weight(3.0),
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        // 40 lines are split 1:3
+        assert_eq!(app.screen_line(0), "header");
+        assert_eq!(app.screen_line(10), "0");
+
+        app.dispatch([test_mouse(MouseKind::ScrollDown, 0, 20)])
+            .await;
+        assert_eq!(app.screen_line(10), "3");
+        // laid out again without scrolling
+        app.config.terminal.backend_mut().resize(81, 40);
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(10), "3");
+    }
+
+    #[tokio::test]
+    async fn weighted_virtual_lists_only_build_the_visible_items() {
+        // how often an item was built and the highest index of the built items
+        let built = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+        let app_built = built.clone();
+        let mut app = App::new((), move |_: &mut ()| {
+            let built = app_built.clone();
+            weighted_v_stack((
+                "header".weight(1.0),
+                virtual_list(1000, 1, move |index| {
+                    built.0.fetch_add(1, Ordering::Relaxed);
+                    built.1.fetch_max(index, Ordering::Relaxed);
+                    index.to_string()
+                })
+                .weight(3.0),
+            ))
+        })
+        .await;
+        for _ in 0..3 {
+            app.render_once().await.unwrap();
+        }
+        assert_eq!(app.screen_line(10), "0");
+        assert_eq!(app.screen_line(39), "29");
+        assert!(built.1.load(Ordering::Relaxed) < 40);
+        let settled = built.0.load(Ordering::Relaxed);
+        // the list doesn't request new items on every render
+        for _ in 0..3 {
+            app.render_once().await.unwrap();
+        }
+        assert_eq!(built.0.load(Ordering::Relaxed), settled);
+    }
+
+    #[tokio::test]
+    async fn reserved_min_sizes_are_satisfied_before_the_weights() {
+        let mut app = App::new((), |_: &mut ()| {
+            weighted_v_stack((
+                lines(15).wrapped().weight(1.0).reserve_min_size(),
+                "rest".weight(3.0),
+            ))
+        })
+        .await;
+        app.render_once().await.unwrap();
+        assert_eq!(app.screen_line(14), "14");
+        assert_eq!(app.screen_line(15), "rest");
+    }
 }